@@ -12,13 +12,17 @@ fn accept_transaction(criterion: &mut Criterion) {
     };
     let account_name = "account";
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, [0; 32]),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, [0; 32])
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let transaction = RequestedTransaction::new(
         vec![
@@ -26,7 +30,7 @@ fn accept_transaction(criterion: &mut Criterion) {
             create_account.into(),
             create_asset.into(),
         ],
-        AccountId::new("account", "domain"),
+        AccountId::try_new("account", "domain").expect("Failed to create account id."),
     );
     let mut success_count = 0;
     let mut failures_count = 0;
@@ -53,13 +57,17 @@ fn sign_transaction(criterion: &mut Criterion) {
     };
     let account_name = "account";
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, [0; 32]),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, [0; 32])
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let transaction = RequestedTransaction::new(
         vec![
@@ -67,7 +75,7 @@ fn sign_transaction(criterion: &mut Criterion) {
             create_account.into(),
             create_asset.into(),
         ],
-        AccountId::new("account", "domain"),
+        AccountId::try_new("account", "domain").expect("Failed to create account id."),
     )
     .accept()
     .expect("Failed to accept transaction.");
@@ -102,13 +110,17 @@ fn validate_transaction(criterion: &mut Criterion) {
     };
     let account_name = "account";
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, public_key),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, public_key)
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let transaction = RequestedTransaction::new(
         vec![
@@ -116,7 +128,7 @@ fn validate_transaction(criterion: &mut Criterion) {
             create_account.into(),
             create_asset.into(),
         ],
-        AccountId::new("account", "domain"),
+        AccountId::try_new("account", "domain").expect("Failed to create account id."),
     )
     .accept()
     .expect("Failed to accept transaction.")
@@ -158,13 +170,17 @@ fn chain_blocks(criterion: &mut Criterion) {
     };
     let account_name = "account";
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, public_key),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, public_key)
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let transaction = RequestedTransaction::new(
         vec![
@@ -172,7 +188,7 @@ fn chain_blocks(criterion: &mut Criterion) {
             create_account.into(),
             create_asset.into(),
         ],
-        AccountId::new("account", "domain"),
+        AccountId::try_new("account", "domain").expect("Failed to create account id."),
     )
     .accept()
     .expect("Failed to accept transaction.");
@@ -202,13 +218,17 @@ fn sign_blocks(criterion: &mut Criterion) {
     };
     let account_name = "account";
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, public_key),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, public_key)
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let transaction = RequestedTransaction::new(
         vec![
@@ -216,7 +236,7 @@ fn sign_blocks(criterion: &mut Criterion) {
             create_account.into(),
             create_asset.into(),
         ],
-        AccountId::new("account", "domain"),
+        AccountId::try_new("account", "domain").expect("Failed to create account id."),
     )
     .accept()
     .expect("Failed to accept transaction.");
@@ -248,13 +268,17 @@ fn validate_blocks(criterion: &mut Criterion) {
     };
     let account_name = "account";
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, public_key),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, public_key)
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let transaction = RequestedTransaction::new(
         vec![
@@ -262,7 +286,7 @@ fn validate_blocks(criterion: &mut Criterion) {
             create_account.into(),
             create_asset.into(),
         ],
-        AccountId::new("account", "domain"),
+        AccountId::try_new("account", "domain").expect("Failed to create account id."),
     )
     .accept()
     .expect("Failed to accept transaction.");
@@ -3,29 +3,107 @@
 
 use crate::{isi::prelude::*, prelude::*};
 use parity_scale_codec::{Decode, Encode};
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// Account entity is an authority which is used to execute `Iroha Special Insturctions`.
-#[derive(Debug, Clone, Encode, Decode)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct Account {
     /// An Identification of the `Account`.
     pub id: Id,
     /// Asset's in this `Account`.
     pub assets: BTreeMap<<Asset as Identifiable>::Id, Asset>,
     signatories: Vec<PublicKey>,
+    /// Minimal number of signatures of the `Account`'s signatories a transaction should have.
+    /// At least one signature of a signatory is always required, so zero acts as one.
+    pub quorum: u32,
+    /// Key-value structured data associated with the `Account`.
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl Account {
+    /// Constructor of the detached `Account` entity, names are not checked - use
+    /// `Account::try_new` for names which are not known to be valid.
+    pub(crate) fn new(account_name: &str, domain_name: &str, public_key: PublicKey) -> Self {
+        Account {
+            id: Id::new(account_name, domain_name),
+            assets: BTreeMap::new(),
+            signatories: vec![public_key],
+            quorum: 1,
+            metadata: BTreeMap::new(),
+        }
+    }
+
     /// Constructor of the detached `Account` entity.
     ///
     /// This method can be used to create an `Account` which should be registered in the domain.
     /// This method should not be used to create an `Account` to work with as a part of the Iroha
     /// State.
-    pub fn new(account_name: &str, domain_name: &str, public_key: PublicKey) -> Self {
-        Account {
-            id: Id::new(account_name, domain_name),
+    ///
+    /// Returns `Err(String)` if the account's or the domain's name contains characters other
+    /// than the allowed ones, see `Id::try_new`.
+    pub fn try_new(
+        account_name: &str,
+        domain_name: &str,
+        public_key: PublicKey,
+    ) -> Result<Self, String> {
+        Account::new_with_metadata(account_name, domain_name, public_key, BTreeMap::new())
+    }
+
+    /// Constructor of the detached `Account` entity with the initial `metadata`.
+    ///
+    /// Returns `Err(String)` if the account's or the domain's name contains characters other
+    /// than the allowed ones, see `Id::try_new`.
+    pub fn new_with_metadata(
+        account_name: &str,
+        domain_name: &str,
+        public_key: PublicKey,
+        metadata: BTreeMap<String, String>,
+    ) -> Result<Self, String> {
+        Ok(Account {
+            id: Id::try_new(account_name, domain_name)?,
             assets: BTreeMap::new(),
             signatories: vec![public_key],
+            quorum: 1,
+            metadata,
+        })
+    }
+
+    /// Public keys which can sign transactions on behalf of the `Account`.
+    pub fn signatories(&self) -> &[PublicKey] {
+        &self.signatories
+    }
+
+    /// Check that `signatures` contain at least `quorum` signatures of the `Account`'s
+    /// signatories, and at least one even if `quorum` is zero. Signatures should be verified
+    /// before.
+    ///
+    /// Returns `Err(String)` with error message if quorum is not reached.
+    pub fn check_quorum<'a>(
+        &self,
+        signatures: impl IntoIterator<Item = &'a Signature>,
+    ) -> Result<(), String> {
+        let mut signed_by: Vec<&PublicKey> = signatures
+            .into_iter()
+            .map(|signature| &signature.public_key)
+            .filter(|public_key| self.signatories.contains(public_key))
+            .collect();
+        signed_by.sort();
+        signed_by.dedup();
+        let quorum = self.quorum.max(1);
+        if signed_by.len() < quorum as usize {
+            Err(format!(
+                "Account {} requires {} signatures of its signatories, but got {}.",
+                self.id,
+                quorum,
+                signed_by.len()
+            ))
+        } else {
+            Ok(())
         }
     }
 
@@ -35,11 +113,7 @@ impl Account {
         object: Asset,
         destination_id: Id,
     ) -> Transfer<Account, Asset, Account> {
-        Transfer {
-            source_id: self.id.clone(),
-            object,
-            destination_id,
-        }
+        Transfer::new(self.id.clone(), object, destination_id)
     }
 }
 
@@ -50,34 +124,88 @@ impl Account {
 /// ```
 /// use iroha::account::Id;
 ///
-/// let id = Id::new("user", "company");
+/// let id = Id::try_new("user", "company").expect("Names should be valid.");
 /// ```
 #[derive(Clone, Debug, PartialEq, PartialOrd, Ord, Eq, std::hash::Hash, Encode, Decode)]
 pub struct Id {
     /// Account's name.
-    pub name: String,
+    pub name: Name,
     /// Domain's name.
-    pub domain_name: String,
+    pub domain_name: Name,
 }
 
 impl Id {
     /// `Id` constructor used to easily create an `Id` from two string slices - one for the
-    /// account's name, another one for the container's name.
-    pub fn new(name: &str, domain_name: &str) -> Self {
+    /// account's name, another one for the container's name. Names are not checked - use
+    /// `Id::try_new` for names which are not known to be valid.
+    pub(crate) fn new(name: &str, domain_name: &str) -> Self {
         Id {
-            name: name.to_string(),
-            domain_name: domain_name.to_string(),
+            name: Name::new(name),
+            domain_name: Name::new(domain_name),
         }
     }
+
+    /// `Id` constructor which checks that names are not empty and contain only ASCII
+    /// alphanumeric characters, `_`, `-` and `.`, so the `name@domain_name` form stays
+    /// unambiguous.
+    ///
+    /// Returns `Err(String)` with error message if names are not valid.
+    pub fn try_new(name: &str, domain_name: &str) -> Result<Self, String> {
+        let id = Id::new(name, domain_name);
+        id.validate()?;
+        Ok(id)
+    }
+
+    /// Check that the account's and the domain's names are valid, see `Id::try_new`.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_name(&self.name).map_err(|e| format!("Invalid account name: {}", e))?;
+        validate_name(&self.domain_name).map_err(|e| format!("Invalid domain name: {}", e))
+    }
 }
 
-impl From<&str> for Id {
-    fn from(string: &str) -> Id {
+pub(crate) fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Name should not be empty.".to_string());
+    }
+    match name
+        .chars()
+        .find(|character| !(character.is_ascii_alphanumeric() || "_-.".contains(*character)))
+    {
+        Some(character) => Err(format!(
+            "Name {:?} contains forbidden character {:?}.",
+            name, character
+        )),
+        None => Ok(()),
+    }
+}
+
+impl TryFrom<&str> for Id {
+    type Error = String;
+
+    fn try_from(string: &str) -> Result<Id, String> {
+        string.parse()
+    }
+}
+
+impl Display for Id {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name, self.domain_name)
+    }
+}
+
+/// Account Identification is represented by `name@domain_name` string.
+impl FromStr for Id {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
         let vector: Vec<&str> = string.split('@').collect();
-        Id {
-            name: String::from(vector[0]),
-            domain_name: String::from(vector[1]),
+        if vector.len() != 2 {
+            return Err(format!(
+                "Account id {:?} should have the form `name@domain_name`.",
+                string
+            ));
         }
+        Id::try_new(vector[0], vector[1])
     }
 }
 
@@ -94,15 +222,26 @@ pub mod isi {
     use iroha_derive::*;
     use std::ops::{Add, Sub};
 
+    /// Maximum length of the transfer memo in bytes.
+    pub const MAX_MEMO_LENGTH: usize = 256;
+
     /// Enumeration of all legal Account related Instructions.
-    #[derive(Clone, Debug, Io, Encode, Decode)]
+    #[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
+    #[allow(clippy::large_enum_variant)]
     pub enum AccountInstruction {
         /// Variant of the generic `Transfer` instruction for `Account` --`Asset`--> `Account`.
         TransferAsset(
             <Account as Identifiable>::Id,
             <Account as Identifiable>::Id,
             Asset,
+            Option<String>,
         ),
+        /// Instruction to add a signatory to the `Account`.
+        AddSignatory(<Account as Identifiable>::Id, PublicKey),
+        /// Instruction to remove a signatory from the `Account`.
+        RemoveSignatory(<Account as Identifiable>::Id, PublicKey),
+        /// Instruction to set the `Account`'s quorum.
+        SetQuorum(<Account as Identifiable>::Id, u32),
     }
 
     impl AccountInstruction {
@@ -118,16 +257,49 @@ pub mod isi {
                     source_account_id,
                     destination_account_id,
                     component,
-                ) => Transfer::new(
-                    source_account_id.clone(),
-                    component.clone(),
-                    destination_account_id.clone(),
-                )
+                    memo,
+                ) => Transfer {
+                    source_id: source_account_id.clone(),
+                    object: component.clone(),
+                    destination_id: destination_account_id.clone(),
+                    memo: memo.clone(),
+                }
                 .execute(authority, world_state_view),
+                AccountInstruction::AddSignatory(account_id, public_key) => {
+                    let account = account_to_modify(account_id, authority, world_state_view)?;
+                    if !account.signatories.contains(public_key) {
+                        account.signatories.push(*public_key);
+                    }
+                    Ok(())
+                }
+                AccountInstruction::RemoveSignatory(account_id, public_key) => {
+                    let account = account_to_modify(account_id, authority, world_state_view)?;
+                    account.signatories.retain(|key| key != public_key);
+                    Ok(())
+                }
+                AccountInstruction::SetQuorum(account_id, quorum) => {
+                    account_to_modify(account_id, authority, world_state_view)?.quorum = *quorum;
+                    Ok(())
+                }
             }
         }
     }
 
+    /// Signatories and quorum of the `Account` can be changed by the `Account` itself or by an
+    /// authority with permission to do anything.
+    fn account_to_modify<'a>(
+        account_id: &<Account as Identifiable>::Id,
+        authority: <Account as Identifiable>::Id,
+        world_state_view: &'a mut WorldStateView,
+    ) -> Result<&'a mut Account, String> {
+        if account_id != &authority {
+            PermissionInstruction::CanAnything(authority).execute(world_state_view)?;
+        }
+        world_state_view
+            .account(account_id)
+            .ok_or_else(|| format!("Failed to find account {}.", account_id))
+    }
+
     /// The purpose of add signatory command is to add an identifier to the account. Such
     /// identifier is a public key of another device or a public key of another user.
     impl Add<PublicKey> for Account {
@@ -156,6 +328,15 @@ pub mod isi {
             authority: <Account as Identifiable>::Id,
             world_state_view: &mut WorldStateView,
         ) -> Result<(), String> {
+            if let Some(memo) = &self.memo {
+                if memo.len() > MAX_MEMO_LENGTH {
+                    return Err(format!(
+                        "Transfer memo is {} bytes long, but at most {} are allowed.",
+                        memo.len(),
+                        MAX_MEMO_LENGTH
+                    ));
+                }
+            }
             PermissionInstruction::CanTransferAsset(
                 authority,
                 self.object.id.definition_id.clone(),
@@ -164,10 +345,10 @@ pub mod isi {
             .execute(world_state_view)?;
             let source = world_state_view
                 .account(&self.source_id)
-                .ok_or("Failed to find accounts.")?
+                .ok_or_else(|| format!("Failed to find account {}.", self.source_id))?
                 .assets
                 .get_mut(&self.object.id)
-                .ok_or("Asset's component was not found.")?;
+                .ok_or_else(|| format!("Failed to find asset {}.", self.object.id))?;
             let quantity_to_transfer = self.object.quantity;
             if source.quantity < quantity_to_transfer {
                 return Err(format!(
@@ -178,7 +359,7 @@ pub mod isi {
             source.quantity -= quantity_to_transfer;
             match world_state_view
                 .account(&self.destination_id)
-                .ok_or("Failed to find destination account.")?
+                .ok_or_else(|| format!("Failed to find account {}.", self.destination_id))?
                 .assets
                 .get_mut(&self.object.id)
             {
@@ -188,7 +369,7 @@ pub mod isi {
                 None => {
                     world_state_view
                         .account(&self.destination_id)
-                        .ok_or("Failed to find destination account.")?
+                        .ok_or_else(|| format!("Failed to find account {}.", self.destination_id))?
                         .assets
                         .insert(self.object.id.clone(), self.object.clone());
                 }
@@ -203,7 +384,321 @@ pub mod isi {
                 instruction.source_id,
                 instruction.destination_id,
                 instruction.object,
+                instruction.memo,
+            ))
+        }
+    }
+}
+
+/// Query module provides `IrohaQuery` Account related implementations.
+pub mod query {
+    use super::*;
+    use crate::{account::isi::AccountInstruction, crypto::Hash, permission, query::IrohaQuery};
+    use iroha_derive::{log, IntoQuery, Io};
+    use std::time::SystemTime;
+
+    /// To get the history of asset transfers from or to an account with their memos,
+    /// GetAccountTransfers query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetAccountTransfers {
+        account_id: <Account as Identifiable>::Id,
+    }
+
+    /// Asset transfer committed in a block.
+    #[derive(Clone, Debug, PartialEq, Encode, Decode)]
+    pub struct TransferRecord {
+        /// Height of the block with the transfer.
+        pub height: u64,
+        /// Hash of the transaction with the transfer.
+        pub transaction_hash: Hash,
+        /// Account the asset was transferred from.
+        pub source_id: <Account as Identifiable>::Id,
+        /// Account the asset was transferred to.
+        pub destination_id: <Account as Identifiable>::Id,
+        /// Transferred asset.
+        pub asset: Asset,
+        /// Memo attached to the transfer.
+        pub memo: Option<String>,
+    }
+
+    /// Result of the `GetAccountTransfers` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetAccountTransfersResult {
+        /// Transfers in the order they were committed.
+        pub transfers: Vec<TransferRecord>,
+    }
+
+    impl GetAccountTransfers {
+        /// Build a `GetAccountTransfers` query in the form of a `QueryRequest`.
+        pub fn build_request(account_id: <Account as Identifiable>::Id) -> QueryRequest {
+            let query = GetAccountTransfers { account_id };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+
+        /// Transfers can be read only by the account itself or by accounts with the
+        /// `read_account` permission, the same as its assets.
+        pub fn authorize(
+            &self,
+            authority: Option<&AccountId>,
+            world_state_view: &WorldStateView,
+        ) -> Result<(), String> {
+            let authority = authority.ok_or(format!(
+                "Query of the account {} transfers should be signed.",
+                self.account_id
+            ))?;
+            if *authority == self.account_id {
+                return Ok(());
+            }
+            permission::check_can_read_account(authority, &self.account_id, world_state_view)
+        }
+    }
+
+    impl Query for GetAccountTransfers {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let mut transfers = Vec::new();
+            for height in 0..world_state_view.height() {
+                let block = world_state_view
+                    .read_block(height)
+                    .ok_or(format!("Failed to find block at height {}.", height))?;
+                for transaction in &block.transactions {
+                    for instruction in transaction.instructions() {
+                        collect_transfers(
+                            instruction,
+                            &mut |source_id, destination_id, asset, memo| {
+                                if *source_id == self.account_id
+                                    || *destination_id == self.account_id
+                                {
+                                    transfers.push(TransferRecord {
+                                        height,
+                                        transaction_hash: transaction.hash(),
+                                        source_id: source_id.clone(),
+                                        destination_id: destination_id.clone(),
+                                        asset: asset.clone(),
+                                        memo: memo.clone(),
+                                    });
+                                }
+                            },
+                        );
+                    }
+                }
+            }
+            Ok(QueryResult::GetAccountTransfers(
+                GetAccountTransfersResult { transfers },
             ))
         }
     }
+
+    /// Visit asset transfers of the instruction including ones nested in composite instructions.
+    fn collect_transfers<F>(instruction: &Instruction, visit: &mut F)
+    where
+        F: FnMut(&AccountId, &AccountId, &Asset, &Option<String>),
+    {
+        match instruction {
+            Instruction::Account(AccountInstruction::TransferAsset(
+                source_id,
+                destination_id,
+                asset,
+                memo,
+            )) => visit(source_id, destination_id, asset, memo),
+            Instruction::Compose(left, right) => {
+                collect_transfers(left, visit);
+                collect_transfers(right, visit);
+            }
+            Instruction::If(condition, then, otherwise) => {
+                collect_transfers(condition, visit);
+                collect_transfers(then, visit);
+                if let Some(otherwise) = otherwise {
+                    collect_transfers(otherwise, visit);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{peer::PeerId, permission::permission_asset_definition_id};
+    use std::collections::HashMap;
+
+    #[async_std::test]
+    async fn transfer_memo_is_returned_by_history_query() {
+        let definition_id = AssetDefinitionId::new("xor", "wonderland");
+        let alice_id = Id::new("alice", "wonderland");
+        let bob_id = Id::new("bob", "wonderland");
+        let mut domain = Domain::new("wonderland".to_string());
+        domain.asset_definitions.insert(
+            definition_id.clone(),
+            AssetDefinition::new(definition_id.clone(), alice_id.clone()),
+        );
+        let (public_key, private_key) =
+            crate::crypto::generate_key_pair().expect("Failed to generate key pair.");
+        for id in &[&alice_id, &bob_id] {
+            domain.accounts.insert(
+                (*id).clone(),
+                Account::new(&id.name, &id.domain_name, public_key),
+            );
+        }
+        let asset_id = AssetId {
+            definition_id,
+            account_id: alice_id.clone(),
+        };
+        let permission_asset_id = AssetId {
+            definition_id: permission_asset_definition_id(),
+            account_id: alice_id.clone(),
+        };
+        let alice = domain
+            .accounts
+            .get_mut(&alice_id)
+            .expect("Failed to find account.");
+        alice.assets.insert(
+            asset_id.clone(),
+            Asset::with_quantity(asset_id.clone(), 100),
+        );
+        alice.assets.insert(
+            permission_asset_id.clone(),
+            Asset::with_permission(
+                permission_asset_id,
+                ("anything".to_string(), "".to_string()),
+            ),
+        );
+        let mut domains = HashMap::new();
+        domains.insert(Name::new("wonderland"), domain);
+        let mut world_state_view = WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+            domains,
+        ));
+        let transfer = Transfer::<Account, Asset, Account>::new(
+            alice_id.clone(),
+            Asset::with_quantity(asset_id, 20),
+            bob_id.clone(),
+        )
+        .with_memo("invoice 42");
+        let transaction = RequestedTransaction::new(vec![transfer.into()], alice_id.clone())
+            .accept()
+            .expect("Failed to accept transaction.");
+        let block = PendingBlock::new(vec![transaction])
+            .chain_first()
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign block.")
+            .validate(&world_state_view)
+            .expect("Failed to validate block.")
+            .commit();
+        world_state_view.put(&block).await;
+        for account_id in &[alice_id.clone(), bob_id.clone()] {
+            match query::GetAccountTransfers::build_request(account_id.clone())
+                .query
+                .execute(&world_state_view)
+                .expect("Failed to execute query.")
+            {
+                QueryResult::GetAccountTransfers(result) => {
+                    assert_eq!(1, result.transfers.len());
+                    let record = &result.transfers[0];
+                    assert_eq!(alice_id, record.source_id);
+                    assert_eq!(bob_id, record.destination_id);
+                    assert_eq!(20, record.asset.quantity);
+                    assert_eq!(Some("invoice 42".to_string()), record.memo);
+                }
+                _ => panic!("Wrong Query Result Type."),
+            }
+        }
+    }
+
+    #[test]
+    fn too_long_transfer_memo_is_rejected() {
+        let alice_id = Id::new("alice", "wonderland");
+        let transfer = Transfer::<Account, Asset, Account>::new(
+            alice_id.clone(),
+            Asset::with_quantity(
+                AssetId {
+                    definition_id: AssetDefinitionId::new("xor", "wonderland"),
+                    account_id: alice_id.clone(),
+                },
+                1,
+            ),
+            alice_id,
+        )
+        .with_memo(&"x".repeat(isi::MAX_MEMO_LENGTH + 1));
+        let mut world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        ));
+        let authority = world_state_view.read_peer().authority();
+        let result = Instruction::from(transfer).execute(authority, &mut world_state_view);
+        assert!(result
+            .expect_err("Long memo was accepted.")
+            .contains("memo"));
+    }
+
+    #[test]
+    fn valid_account_id() {
+        assert!(Id::try_new("account_1-test.x", "domain").is_ok());
+        assert!(Account::new_with_metadata(
+            "account",
+            "domain",
+            [0; 32],
+            vec![("key".to_string(), "value".to_string())]
+                .into_iter()
+                .collect(),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn account_id_with_separator_is_rejected() {
+        assert!(Id::try_new("acc@ount", "domain").is_err());
+        assert!(Id::try_new("account", "dom ain").is_err());
+        assert!(
+            Account::new_with_metadata("acc@ount", "domain", [0; 32], BTreeMap::new()).is_err()
+        );
+    }
+
+    #[test]
+    fn account_id_display_from_str_round_trip() {
+        for id in &[
+            Id::new("account", "domain"),
+            Id::new("account_1-test.x", "my-domain.io"),
+        ] {
+            assert_eq!(Ok(id.clone()), id.to_string().parse::<Id>());
+        }
+        assert_eq!("account@domain", Id::new("account", "domain").to_string());
+        assert!("account".parse::<Id>().is_err());
+        assert!("account@domain@other".parse::<Id>().is_err());
+        assert!("@domain".parse::<Id>().is_err());
+    }
+
+    #[test]
+    fn empty_account_name_is_rejected() {
+        assert!(Id::try_new("", "domain").is_err());
+    }
+
+    #[test]
+    fn account_id_without_separator_is_rejected_instead_of_panicking() {
+        assert!(Id::try_from("account").is_err());
+        assert!(Id::try_from("acc ount@domain").is_err());
+        assert_eq!(
+            Ok(Id::new("account", "domain")),
+            Id::try_from("account@domain")
+        );
+        assert!(Account::try_new("account", "dom ain", [0; 32]).is_err());
+    }
 }
@@ -1,20 +1,25 @@
 //! This module contains `Asset` structure, it's implementation and related traits and
 //! instructions implementations.
 
-use crate::{isi::prelude::*, permission::Permissions, prelude::*};
+use crate::{account::validate_name, isi::prelude::*, permission::Permissions, prelude::*};
 use iroha_derive::log;
 use parity_scale_codec::{Decode, Encode};
 use std::{
     collections::BTreeMap,
     fmt::{self, Display, Formatter},
     hash::Hash,
+    str::FromStr,
 };
 
 /// Asset entity represents some sort of commodity or value.
-#[derive(Clone, Debug, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
 pub struct AssetDefinition {
     /// An Identification of the `Asset`.
     pub id: <AssetDefinition as Identifiable>::Id,
+    /// Account which owns the `AssetDefinition` and can mint it without additional permissions.
+    pub owned_by: <Account as Identifiable>::Id,
+    /// Sum of `quantity` and `big_quantity` of all holders, changed by mints and burns.
+    pub total_supply: u128,
 }
 
 impl AssetDefinition {
@@ -23,13 +28,28 @@ impl AssetDefinition {
     /// This method can be used to create an `AssetDefinition` which should be registered in the domain.
     /// This method should not be used to create an `AssetDefinition` to work with as a part of the Iroha
     /// State.
-    pub fn new(id: <AssetDefinition as Identifiable>::Id) -> Self {
-        AssetDefinition { id }
+    pub fn new(
+        id: <AssetDefinition as Identifiable>::Id,
+        owned_by: <Account as Identifiable>::Id,
+    ) -> Self {
+        AssetDefinition {
+            id,
+            owned_by,
+            total_supply: 0,
+        }
+    }
+
+    /// Constructor of the `TransferAssetDefinitionOwnership` Iroha Special Instruction.
+    pub fn transfer_ownership(&self, new_owner: <Account as Identifiable>::Id) -> Instruction {
+        Instruction::Asset(isi::AssetInstruction::TransferAssetDefinitionOwnership(
+            self.id.clone(),
+            new_owner,
+        ))
     }
 }
 
 /// All possible variants of `Asset` entity's components.
-#[derive(Clone, Debug, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
 pub struct Asset {
     /// Component Identification.
     pub id: <Asset as Identifiable>::Id,
@@ -93,6 +113,14 @@ impl Asset {
             destination_id: self.id.clone(),
         }
     }
+
+    /// Constructor of the `Burn<Asset, u32>` Iroha Special Instruction.
+    pub fn burn(&self, object: u32) -> Burn<Asset, u32> {
+        Burn {
+            object,
+            destination_id: self.id.clone(),
+        }
+    }
 }
 
 /// Identification of an Asset Definition. Consists of Asset's name and Domain's name.
@@ -107,9 +135,9 @@ impl Asset {
 #[derive(Clone, Debug, PartialEq, PartialOrd, Ord, Eq, std::hash::Hash, Encode, Decode)]
 pub struct AssetDefinitionId {
     /// Asset's name.
-    pub name: String,
+    pub name: Name,
     /// Domain's name.
-    pub domain_name: String,
+    pub domain_name: Name,
 }
 
 impl AssetDefinitionId {
@@ -117,8 +145,8 @@ impl AssetDefinitionId {
     /// asset's name, another one for the domain's name.
     pub fn new(name: &str, domain_name: &str) -> Self {
         AssetDefinitionId {
-            name: name.to_string(),
-            domain_name: domain_name.to_string(),
+            name: Name::new(name),
+            domain_name: Name::new(domain_name),
         }
     }
 }
@@ -127,10 +155,7 @@ impl AssetDefinitionId {
 impl From<&str> for AssetDefinitionId {
     fn from(string: &str) -> AssetDefinitionId {
         let vector: Vec<&str> = string.split('#').collect();
-        AssetDefinitionId {
-            name: String::from(vector[0]),
-            domain_name: String::from(vector[1]),
-        }
+        AssetDefinitionId::new(vector[0], vector[1])
     }
 }
 
@@ -140,6 +165,24 @@ impl Display for AssetDefinitionId {
     }
 }
 
+/// Asset Definition Identification is represented by `name#domain_name` string.
+impl FromStr for AssetDefinitionId {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let vector: Vec<&str> = string.split('#').collect();
+        if vector.len() != 2 {
+            return Err(format!(
+                "Asset definition id {:?} should have the form `name#domain_name`.",
+                string
+            ));
+        }
+        validate_name(vector[0]).map_err(|e| format!("Invalid asset name: {}", e))?;
+        validate_name(vector[1]).map_err(|e| format!("Invalid domain name: {}", e))?;
+        Ok(AssetDefinitionId::new(vector[0], vector[1]))
+    }
+}
+
 impl Identifiable for AssetDefinition {
     type Id = AssetDefinitionId;
 }
@@ -153,6 +196,29 @@ pub struct AssetId {
     pub account_id: <Account as Identifiable>::Id,
 }
 
+/// Asset Identification is represented by `name#domain_name@account_name@account_domain_name`
+/// string - the asset definition id followed by the owner account id.
+impl Display for AssetId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.definition_id, self.account_id)
+    }
+}
+
+impl FromStr for AssetId {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let separator = string.find('@').ok_or(format!(
+            "Asset id {:?} should have the form `name#domain_name@account_id`.",
+            string
+        ))?;
+        Ok(AssetId {
+            definition_id: string[..separator].parse()?,
+            account_id: string[separator + 1..].parse()?,
+        })
+    }
+}
+
 impl Identifiable for Asset {
     type Id = AssetId;
 }
@@ -166,12 +232,24 @@ pub mod isi {
     use iroha_derive::*;
 
     /// Enumeration of all legal Asset related Instructions.
-    #[derive(Clone, Debug, Io, Encode, Decode)]
+    #[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
     pub enum AssetInstruction {
         /// Variant of the generic `Mint` instruction for `u32` --> `Asset`.
         MintAsset(u32, <Asset as Identifiable>::Id),
         /// Variant of the generic `Mint` instruction for `u128` --> `Asset`.
         MintBigAsset(u128, <Asset as Identifiable>::Id),
+        /// Variant of the `MintMany` instruction.
+        MintAssetToMany(
+            <AssetDefinition as Identifiable>::Id,
+            Vec<(<Account as Identifiable>::Id, u32)>,
+        ),
+        /// Variant of the generic `Burn` instruction for `u32` --> `Asset`.
+        BurnAsset(u32, <Asset as Identifiable>::Id),
+        /// Instruction to pass the ownership of the `AssetDefinition` to another `Account`.
+        TransferAssetDefinitionOwnership(
+            <AssetDefinition as Identifiable>::Id,
+            <Account as Identifiable>::Id,
+        ),
     }
 
     impl AssetInstruction {
@@ -189,33 +267,129 @@ pub mod isi {
                 AssetInstruction::MintBigAsset(big_quantity, asset_id) => {
                     Mint::new(*big_quantity, asset_id.clone()).execute(authority, world_state_view)
                 }
+                AssetInstruction::MintAssetToMany(definition_id, recipients) => {
+                    MintMany::new(definition_id.clone(), recipients.clone())
+                        .execute(authority, world_state_view)
+                }
+                AssetInstruction::BurnAsset(quantity, asset_id) => {
+                    Burn::new(*quantity, asset_id.clone()).execute(authority, world_state_view)
+                }
+                AssetInstruction::TransferAssetDefinitionOwnership(definition_id, new_owner) => {
+                    transfer_asset_definition_ownership(
+                        definition_id,
+                        new_owner,
+                        authority,
+                        world_state_view,
+                    )
+                }
             }
         }
     }
 
+    /// Owner of the `AssetDefinition` can mint it, other accounts need a `mint_asset` permission.
+    fn check_can_mint(
+        definition_id: &<AssetDefinition as Identifiable>::Id,
+        authority: <Account as Identifiable>::Id,
+        world_state_view: &mut WorldStateView,
+    ) -> Result<(), String> {
+        let asset_definition = world_state_view
+            .read_asset_definition(definition_id)
+            .ok_or_else(|| format!("Failed to find asset definition {}.", definition_id))?;
+        if asset_definition.owned_by == authority {
+            return Ok(());
+        }
+        PermissionInstruction::CanMintAsset(authority, definition_id.clone(), None)
+            .execute(world_state_view)
+    }
+
+    /// Balances are changed only together with the `total_supply` of their definition, so it
+    /// always equals the sum of the balances.
+    pub(crate) fn increase_total_supply(
+        definition_id: &<AssetDefinition as Identifiable>::Id,
+        quantity: u128,
+        world_state_view: &mut WorldStateView,
+    ) -> Result<(), String> {
+        let asset_definition = world_state_view
+            .asset_definition(definition_id)
+            .ok_or_else(|| format!("Failed to find asset definition {}.", definition_id))?;
+        asset_definition.total_supply = asset_definition
+            .total_supply
+            .checked_add(quantity)
+            .ok_or(format!("Total supply of {} overflows.", definition_id))?;
+        Ok(())
+    }
+
+    /// See `increase_total_supply`.
+    pub(crate) fn decrease_total_supply(
+        definition_id: &<AssetDefinition as Identifiable>::Id,
+        quantity: u128,
+        world_state_view: &mut WorldStateView,
+    ) -> Result<(), String> {
+        let asset_definition = world_state_view
+            .asset_definition(definition_id)
+            .ok_or_else(|| format!("Failed to find asset definition {}.", definition_id))?;
+        asset_definition.total_supply = asset_definition
+            .total_supply
+            .checked_sub(quantity)
+            .ok_or(format!("Total supply of {} underflows.", definition_id))?;
+        Ok(())
+    }
+
+    fn transfer_asset_definition_ownership(
+        definition_id: &<AssetDefinition as Identifiable>::Id,
+        new_owner: &<Account as Identifiable>::Id,
+        authority: <Account as Identifiable>::Id,
+        world_state_view: &mut WorldStateView,
+    ) -> Result<(), String> {
+        let asset_definition = world_state_view
+            .read_asset_definition(definition_id)
+            .ok_or_else(|| format!("Failed to find asset definition {}.", definition_id))?;
+        if asset_definition.owned_by != authority {
+            PermissionInstruction::CanAnything(authority).execute(world_state_view)?;
+        }
+        world_state_view
+            .read_account(new_owner)
+            .ok_or_else(|| format!("Failed to find account {}.", new_owner))?;
+        world_state_view
+            .asset_definition(definition_id)
+            .ok_or_else(|| format!("Failed to find asset definition {}.", definition_id))?
+            .owned_by = new_owner.clone();
+        Ok(())
+    }
+
     impl Mint<Asset, u32> {
         fn execute(
             &self,
             authority: <Account as Identifiable>::Id,
             world_state_view: &mut WorldStateView,
         ) -> Result<(), String> {
-            PermissionInstruction::CanMintAsset(
+            check_can_mint(
+                &self.destination_id.definition_id,
                 authority,
-                self.destination_id.definition_id.clone(),
-                None,
-            )
-            .execute(world_state_view)?;
-            world_state_view
-                .asset_definition(&self.destination_id.definition_id)
-                .ok_or("Failed to find asset.")?;
+                world_state_view,
+            )?;
+            let balance = world_state_view
+                .read_account(&self.destination_id.account_id)
+                .ok_or_else(|| {
+                    format!("Failed to find account {}.", self.destination_id.account_id)
+                })?
+                .assets
+                .get(&self.destination_id)
+                .map_or(0, |asset| asset.quantity)
+                .checked_add(self.object)
+                .ok_or(format!(
+                    "Minting {} to {} overflows.",
+                    self.object, self.destination_id
+                ))?;
+            increase_total_supply(
+                &self.destination_id.definition_id,
+                u128::from(self.object),
+                world_state_view,
+            )?;
             match world_state_view.asset(&self.destination_id) {
-                Some(asset) => {
-                    asset.quantity += self.object;
-                }
-                None => world_state_view.add_asset(Asset::with_quantity(
-                    self.destination_id.clone(),
-                    self.object,
-                )),
+                Some(asset) => asset.quantity = balance,
+                None => world_state_view
+                    .add_asset(Asset::with_quantity(self.destination_id.clone(), balance)),
             }
             Ok(())
         }
@@ -227,28 +401,133 @@ pub mod isi {
             authority: <Account as Identifiable>::Id,
             world_state_view: &mut WorldStateView,
         ) -> Result<(), String> {
-            PermissionInstruction::CanMintAsset(
+            check_can_mint(
+                &self.destination_id.definition_id,
                 authority,
-                self.destination_id.definition_id.clone(),
-                None,
-            )
-            .execute(world_state_view)?;
-            world_state_view
-                .asset_definition(&self.destination_id.definition_id)
-                .ok_or("Failed to find asset.")?;
+                world_state_view,
+            )?;
+            let big_balance = world_state_view
+                .read_account(&self.destination_id.account_id)
+                .ok_or_else(|| {
+                    format!("Failed to find account {}.", self.destination_id.account_id)
+                })?
+                .assets
+                .get(&self.destination_id)
+                .map_or(0, |asset| asset.big_quantity)
+                .checked_add(self.object)
+                .ok_or(format!(
+                    "Minting {} to {} overflows.",
+                    self.object, self.destination_id
+                ))?;
+            increase_total_supply(
+                &self.destination_id.definition_id,
+                self.object,
+                world_state_view,
+            )?;
             match world_state_view.asset(&self.destination_id) {
-                Some(asset) => {
-                    asset.big_quantity += self.object;
-                }
+                Some(asset) => asset.big_quantity = big_balance,
                 None => world_state_view.add_asset(Asset::with_big_quantity(
                     self.destination_id.clone(),
-                    self.object,
+                    big_balance,
                 )),
             }
             Ok(())
         }
     }
 
+    impl Burn<Asset, u32> {
+        /// Holders can burn their assets, other accounts need the right to mint the asset.
+        fn execute(
+            &self,
+            authority: <Account as Identifiable>::Id,
+            world_state_view: &mut WorldStateView,
+        ) -> Result<(), String> {
+            if authority != self.destination_id.account_id {
+                check_can_mint(
+                    &self.destination_id.definition_id,
+                    authority,
+                    world_state_view,
+                )?;
+            }
+            let balance = world_state_view
+                .read_account(&self.destination_id.account_id)
+                .ok_or_else(|| {
+                    format!("Failed to find account {}.", self.destination_id.account_id)
+                })?
+                .assets
+                .get(&self.destination_id)
+                .map_or(0, |asset| asset.quantity)
+                .checked_sub(self.object)
+                .ok_or(format!(
+                    "Insufficient balance of {} to burn {}.",
+                    self.destination_id, self.object
+                ))?;
+            decrease_total_supply(
+                &self.destination_id.definition_id,
+                u128::from(self.object),
+                world_state_view,
+            )?;
+            if let Some(asset) = world_state_view.asset(&self.destination_id) {
+                asset.quantity = balance;
+            }
+            Ok(())
+        }
+    }
+
+    impl MintMany {
+        /// Credits are checked for missing accounts and overflows before any of them is applied,
+        /// so a failed `MintMany` leaves the world state untouched.
+        fn execute(
+            &self,
+            authority: <Account as Identifiable>::Id,
+            world_state_view: &mut WorldStateView,
+        ) -> Result<(), String> {
+            check_can_mint(&self.definition_id, authority, world_state_view)?;
+            let mut credits: Vec<(AssetId, u32)> = Vec::new();
+            for (account_id, quantity) in &self.recipients {
+                let asset_id = AssetId {
+                    definition_id: self.definition_id.clone(),
+                    account_id: account_id.clone(),
+                };
+                let balance = match credits.iter().position(|(id, _)| *id == asset_id) {
+                    Some(index) => credits.remove(index).1,
+                    None => world_state_view
+                        .read_account(account_id)
+                        .ok_or_else(|| format!("Failed to find account {}.", account_id))?
+                        .assets
+                        .get(&asset_id)
+                        .map_or(0, |asset| asset.quantity),
+                };
+                let balance = balance
+                    .checked_add(*quantity)
+                    .ok_or(format!("Minting {} to {} overflows.", quantity, asset_id))?;
+                credits.push((asset_id, balance));
+            }
+            let minted = self
+                .recipients
+                .iter()
+                .map(|(_, quantity)| u128::from(*quantity))
+                .sum();
+            increase_total_supply(&self.definition_id, minted, world_state_view)?;
+            for (asset_id, balance) in credits {
+                match world_state_view.asset(&asset_id) {
+                    Some(asset) => asset.quantity = balance,
+                    None => world_state_view.add_asset(Asset::with_quantity(asset_id, balance)),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl From<MintMany> for Instruction {
+        fn from(instruction: MintMany) -> Self {
+            Instruction::Asset(AssetInstruction::MintAssetToMany(
+                instruction.definition_id,
+                instruction.recipients,
+            ))
+        }
+    }
+
     impl From<Mint<Asset, u32>> for Instruction {
         fn from(instruction: Mint<Asset, u32>) -> Self {
             Instruction::Asset(AssetInstruction::MintAsset(
@@ -257,19 +536,28 @@ pub mod isi {
             ))
         }
     }
+
+    impl From<Burn<Asset, u32>> for Instruction {
+        fn from(instruction: Burn<Asset, u32>) -> Self {
+            Instruction::Asset(AssetInstruction::BurnAsset(
+                instruction.object,
+                instruction.destination_id,
+            ))
+        }
+    }
 }
 
 /// Query module provides `IrohaQuery` Asset related implementations.
 pub mod query {
     use super::*;
-    use crate::query::IrohaQuery;
+    use crate::{permission, query::IrohaQuery};
     use iroha_derive::{IntoQuery, Io};
     use parity_scale_codec::{Decode, Encode};
     use std::time::SystemTime;
 
     /// To get the state of all assets in an account (a balance),
     /// GetAccountAssets query can be used.
-    #[derive(Debug, Io, IntoQuery, Encode, Decode)]
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
     pub struct GetAccountAssets {
         account_id: <Account as Identifiable>::Id,
     }
@@ -293,10 +581,31 @@ pub mod query {
                     .to_string(),
                 signature: Option::None,
                 query: query.into(),
+                at_height: None,
+                account_id: None,
             }
         }
     }
 
+    impl GetAccountAssets {
+        /// Assets include the account's permissions, so they can be read only by the account
+        /// itself or by accounts with the `read_account` permission.
+        pub fn authorize(
+            &self,
+            authority: Option<&AccountId>,
+            world_state_view: &WorldStateView,
+        ) -> Result<(), String> {
+            let authority = authority.ok_or(format!(
+                "Query of the account {} assets should be signed.",
+                self.account_id
+            ))?;
+            if *authority == self.account_id {
+                return Ok(());
+            }
+            permission::check_can_read_account(authority, &self.account_id, world_state_view)
+        }
+    }
+
     impl Query for GetAccountAssets {
         #[log]
         fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
@@ -315,4 +624,320 @@ pub mod query {
             }))
         }
     }
+
+    /// To get the amount of the asset in all accounts, GetAssetSupply query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetAssetSupply {
+        definition_id: <AssetDefinition as Identifiable>::Id,
+    }
+
+    /// Result of the `GetAssetSupply` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetAssetSupplyResult {
+        /// Sum of `quantity` and `big_quantity` of all holders.
+        pub total_supply: u128,
+        /// Number of accounts with a positive balance of the asset.
+        pub holders: u64,
+    }
+
+    impl GetAssetSupply {
+        /// Build a `GetAssetSupply` query in the form of a `QueryRequest`.
+        pub fn build_request(definition_id: <AssetDefinition as Identifiable>::Id) -> QueryRequest {
+            let query = GetAssetSupply { definition_id };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetAssetSupply {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let total_supply = world_state_view
+                .read_asset_definition(&self.definition_id)
+                .ok_or(format!(
+                    "No asset definition with id: {} found in the current world state.",
+                    self.definition_id
+                ))?
+                .total_supply;
+            let holders = world_state_view
+                .assets_iter()
+                .filter(|asset| {
+                    asset.id.definition_id == self.definition_id
+                        && (asset.quantity > 0 || asset.big_quantity > 0)
+                })
+                .count() as u64;
+            Ok(QueryResult::GetAssetSupply(GetAssetSupplyResult {
+                total_supply,
+                holders,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::PeerId;
+    use std::collections::HashMap;
+
+    const DOMAIN_NAME: &str = "Company";
+
+    fn world_state_view_with_owned_asset_definition(
+        owner_id: &AccountId,
+        other_id: &AccountId,
+        asset_definition_id: &AssetDefinitionId,
+    ) -> WorldStateView {
+        let public_key = [0; 32];
+        let mut asset_definitions = HashMap::new();
+        asset_definitions.insert(
+            asset_definition_id.clone(),
+            AssetDefinition::new(asset_definition_id.clone(), owner_id.clone()),
+        );
+        let mut accounts = HashMap::new();
+        for account_id in &[owner_id, other_id] {
+            accounts.insert(
+                (*account_id).clone(),
+                Account::new(&account_id.name, &account_id.domain_name, public_key),
+            );
+        }
+        let mut domains = HashMap::new();
+        domains.insert(
+            Name::new(DOMAIN_NAME),
+            Domain {
+                name: Name::from(DOMAIN_NAME),
+                accounts,
+                asset_definitions,
+            },
+        );
+        WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key,
+            },
+            &Vec::new(),
+            domains,
+        ))
+    }
+
+    fn mint(
+        account_id: &AccountId,
+        asset_definition_id: &AssetDefinitionId,
+        world_state_view: &mut WorldStateView,
+    ) -> Result<(), String> {
+        Instruction::from(Mint::<Asset, u32>::new(
+            10,
+            AssetId {
+                definition_id: asset_definition_id.clone(),
+                account_id: account_id.clone(),
+            },
+        ))
+        .execute(account_id.clone(), world_state_view)
+    }
+
+    #[test]
+    fn asset_ids_display_from_str_round_trip() {
+        for definition_id in &[
+            AssetDefinitionId::new("xor", "soramitsu"),
+            AssetDefinitionId::new("x_o-r.1", "sora-mitsu.co"),
+        ] {
+            assert_eq!(
+                Ok(definition_id.clone()),
+                definition_id.to_string().parse::<AssetDefinitionId>()
+            );
+            let asset_id = AssetId {
+                definition_id: definition_id.clone(),
+                account_id: AccountId::new("alice_1.x", "wonder-land"),
+            };
+            assert_eq!(
+                Ok(asset_id.clone()),
+                asset_id.to_string().parse::<AssetId>()
+            );
+        }
+        assert_eq!(
+            "xor#soramitsu@alice@wonderland",
+            AssetId {
+                definition_id: AssetDefinitionId::new("xor", "soramitsu"),
+                account_id: AccountId::new("alice", "wonderland"),
+            }
+            .to_string()
+        );
+        assert!("xor".parse::<AssetDefinitionId>().is_err());
+        assert!("xor#".parse::<AssetDefinitionId>().is_err());
+        assert!("xor#soramitsu".parse::<AssetId>().is_err());
+        assert!("xor#soramitsu@alice".parse::<AssetId>().is_err());
+    }
+
+    #[test]
+    fn owner_can_mint_and_non_owner_can_not() {
+        let owner_id = AccountId::new("owner", DOMAIN_NAME);
+        let other_id = AccountId::new("other", DOMAIN_NAME);
+        let asset_definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_owned_asset_definition(
+            &owner_id,
+            &other_id,
+            &asset_definition_id,
+        );
+        assert!(mint(&owner_id, &asset_definition_id, &mut world_state_view).is_ok());
+        assert!(mint(&other_id, &asset_definition_id, &mut world_state_view).is_err());
+    }
+
+    #[test]
+    fn new_owner_can_mint_after_ownership_transfer_and_old_owner_can_not() {
+        let owner_id = AccountId::new("owner", DOMAIN_NAME);
+        let other_id = AccountId::new("other", DOMAIN_NAME);
+        let asset_definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_owned_asset_definition(
+            &owner_id,
+            &other_id,
+            &asset_definition_id,
+        );
+        let asset_definition = world_state_view
+            .read_asset_definition(&asset_definition_id)
+            .expect("Failed to find asset definition.")
+            .clone();
+        assert!(asset_definition
+            .transfer_ownership(owner_id.clone())
+            .execute(other_id.clone(), &mut world_state_view)
+            .is_err());
+        asset_definition
+            .transfer_ownership(other_id.clone())
+            .execute(owner_id.clone(), &mut world_state_view)
+            .expect("Failed to transfer ownership.");
+        assert_eq!(
+            other_id,
+            world_state_view
+                .read_asset_definition(&asset_definition_id)
+                .expect("Failed to find asset definition.")
+                .owned_by
+        );
+        assert!(mint(&other_id, &asset_definition_id, &mut world_state_view).is_ok());
+        assert!(mint(&owner_id, &asset_definition_id, &mut world_state_view).is_err());
+    }
+
+    #[test]
+    fn mint_many_with_unknown_recipient_credits_nobody() {
+        let owner_id = AccountId::new("owner", DOMAIN_NAME);
+        let other_id = AccountId::new("other", DOMAIN_NAME);
+        let asset_definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_owned_asset_definition(
+            &owner_id,
+            &other_id,
+            &asset_definition_id,
+        );
+        let balance = |account_id: &AccountId, world_state_view: &WorldStateView| {
+            world_state_view
+                .read_account(account_id)
+                .expect("Failed to find account.")
+                .assets
+                .get(&AssetId {
+                    definition_id: asset_definition_id.clone(),
+                    account_id: account_id.clone(),
+                })
+                .map_or(0, |asset| asset.quantity)
+        };
+        assert!(Instruction::from(MintMany::new(
+            asset_definition_id.clone(),
+            vec![
+                (owner_id.clone(), 10),
+                (AccountId::new("bogus", DOMAIN_NAME), 20),
+                (other_id.clone(), 30),
+            ],
+        ))
+        .execute(owner_id.clone(), &mut world_state_view)
+        .is_err());
+        assert_eq!(0, balance(&owner_id, &world_state_view));
+        assert_eq!(0, balance(&other_id, &world_state_view));
+        Instruction::from(MintMany::new(
+            asset_definition_id.clone(),
+            vec![
+                (owner_id.clone(), 10),
+                (other_id.clone(), 30),
+                (owner_id.clone(), 5),
+            ],
+        ))
+        .execute(owner_id.clone(), &mut world_state_view)
+        .expect("Failed to mint to many accounts.");
+        assert_eq!(15, balance(&owner_id, &world_state_view));
+        assert_eq!(30, balance(&other_id, &world_state_view));
+        assert!(Instruction::from(MintMany::new(
+            asset_definition_id.clone(),
+            vec![(other_id.clone(), 1), (owner_id.clone(), u32::MAX)],
+        ))
+        .execute(owner_id.clone(), &mut world_state_view)
+        .is_err());
+        assert_eq!(30, balance(&other_id, &world_state_view));
+    }
+
+    fn assert_total_supply_is_sum_of_balances(world_state_view: &WorldStateView) {
+        for domain in world_state_view.domains_iter() {
+            for asset_definition in domain.asset_definitions.values() {
+                let balances: u128 = world_state_view
+                    .assets_iter()
+                    .filter(|asset| asset.id.definition_id == asset_definition.id)
+                    .map(|asset| u128::from(asset.quantity) + asset.big_quantity)
+                    .sum();
+                assert_eq!(balances, asset_definition.total_supply);
+            }
+        }
+    }
+
+    #[test]
+    fn total_supply_is_changed_by_mints_and_burns() {
+        let owner_id = AccountId::new("owner", DOMAIN_NAME);
+        let other_id = AccountId::new("other", DOMAIN_NAME);
+        let asset_definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_owned_asset_definition(
+            &owner_id,
+            &other_id,
+            &asset_definition_id,
+        );
+        let asset_id = |account_id: &AccountId| AssetId {
+            definition_id: asset_definition_id.clone(),
+            account_id: account_id.clone(),
+        };
+        Instruction::from(MintMany::new(
+            asset_definition_id.clone(),
+            vec![(owner_id.clone(), 10), (other_id.clone(), 30)],
+        ))
+        .execute(owner_id.clone(), &mut world_state_view)
+        .expect("Failed to mint to many accounts.");
+        Instruction::from(Mint::<Asset, u32>::new(5, asset_id(&owner_id)))
+            .execute(owner_id.clone(), &mut world_state_view)
+            .expect("Failed to mint.");
+        assert_total_supply_is_sum_of_balances(&world_state_view);
+        Instruction::from(Burn::<Asset, u32>::new(12, asset_id(&other_id)))
+            .execute(other_id.clone(), &mut world_state_view)
+            .expect("Failed to burn.");
+        assert!(
+            Instruction::from(Burn::<Asset, u32>::new(19, asset_id(&other_id)))
+                .execute(other_id.clone(), &mut world_state_view)
+                .is_err()
+        );
+        assert!(
+            Instruction::from(Burn::<Asset, u32>::new(1, asset_id(&owner_id)))
+                .execute(other_id.clone(), &mut world_state_view)
+                .is_err()
+        );
+        assert_total_supply_is_sum_of_balances(&world_state_view);
+        match query::GetAssetSupply::build_request(asset_definition_id.clone())
+            .query
+            .execute(&world_state_view)
+            .expect("Failed to execute query.")
+        {
+            QueryResult::GetAssetSupply(result) => {
+                assert_eq!(15 + 18, result.total_supply);
+                assert_eq!(2, result.holders);
+            }
+            result => panic!("Unexpected query result: {:?}", result),
+        }
+    }
 }
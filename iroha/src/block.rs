@@ -1,10 +1,38 @@
 //! This module contains `Block` structures for each state, it's transitions, implementations and related traits
 //! implementations.
 
-use crate::{crypto, prelude::*};
+use crate::{crypto, merkle::MerkleTree, peer::PeerId, prelude::*, scheduler, tx::RejectionReason};
 use iroha_derive::Io;
-use parity_scale_codec::{Decode, Encode};
-use std::time::SystemTime;
+use parity_scale_codec::{Decode, Encode, Error, Input, Output};
+use std::{convert::TryFrom, time::SystemTime};
+
+/// Version of the `ValidBlock` encoding, it is written before the encoded block so that blocks
+/// of an unknown format are rejected instead of being decoded into garbage.
+pub const BLOCK_FORMAT_VERSION: u8 = 1;
+/// Default maximum size of a SCALE encoded `ValidBlock`.
+pub const DEFAULT_MAX_BLOCK_BYTES: usize = 64 * 1024 * 1024;
+/// Size of the SCALE encoded `BlockHeader`: timestamp, height and two hashes.
+const ENCODED_BLOCK_HEADER_LEN: usize = 16 + 8 + 32 + 32;
+/// Maximum size of a SCALE encoded compact length of a vector.
+const MAX_ENCODED_COMPACT_LEN: usize = 5;
+
+/// Parameters the peer builds blocks from the pending transactions with.
+#[derive(Clone, Debug)]
+pub struct BlockSchedule {
+    /// Time interval in milliseconds between attempts to build a block.
+    pub block_build_step_ms: u64,
+    /// Maximum number of pending transactions put into a single block.
+    pub max_transactions_in_block: u64,
+}
+
+impl Default for BlockSchedule {
+    fn default() -> Self {
+        BlockSchedule {
+            block_build_step_ms: crate::TX_RETRIEVAL_INTERVAL.as_millis() as u64,
+            max_transactions_in_block: crate::queue::DEFAULT_MAX_TRANSACTIONS_IN_BLOCK as u64,
+        }
+    }
+}
 
 /// Transaction data is permanently recorded in files called blocks. Blocks are organized into
 /// a linear sequence over time (also known as the block chain).
@@ -29,32 +57,131 @@ impl PendingBlock {
         }
     }
 
+    /// Run `AcceptedTransaction::validate_stateless` over every transaction of the block.
+    ///
+    /// Returns `Err` with the indices of the failed transactions and the reasons.
+    pub fn validate_transactions(&self) -> Result<(), Vec<(usize, RejectionReason)>> {
+        let rejected: Vec<(usize, RejectionReason)> = self
+            .transactions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, transaction)| {
+                transaction
+                    .validate_stateless()
+                    .err()
+                    .map(|reason| (index, reason))
+            })
+            .collect();
+        if rejected.is_empty() {
+            Ok(())
+        } else {
+            Err(rejected)
+        }
+    }
+
+    /// Drop transactions failed `validate_transactions`, reporting them.
+    pub fn without_invalid_transactions(mut self) -> PendingBlock {
+        if let Err(rejected) = self.validate_transactions() {
+            for (index, reason) in rejected.iter().rev() {
+                let transaction = self.transactions.remove(*index);
+                eprintln!(
+                    "Transaction {} was rejected: {}",
+                    HashHex(transaction.hash()),
+                    reason
+                );
+            }
+        }
+        self
+    }
+
+    /// Drop transactions which fail validation against the `world_state_view`, reporting them.
+    ///
+    /// Peers reject a block with an invalid transaction as a whole, so the leader should drop
+    /// them before chaining the block. Transactions are signed by the leader and validated in
+    /// the order of the chained block, the same way `SignedBlock::validate` does it.
+    pub fn without_transactions_invalid_in(
+        mut self,
+        world_state_view: &WorldStateView,
+        public_key: &PublicKey,
+        private_key: &PrivateKey,
+    ) -> Result<PendingBlock, String> {
+        self.sort_transactions();
+        let signed_transactions = self
+            .transactions
+            .iter()
+            .cloned()
+            .map(|transaction| transaction.sign(public_key, private_key))
+            .collect::<Result<Vec<SignedTransaction>, String>>()?;
+        let results =
+            scheduler::execute_in_parallel(signed_transactions, &mut world_state_view.clone());
+        self.transactions = self
+            .transactions
+            .into_iter()
+            .zip(results)
+            .filter_map(|(transaction, result)| match result {
+                Ok(_) => Some(transaction),
+                Err(e) => {
+                    eprintln!(
+                        "Transaction {} was rejected: {}",
+                        HashHex(transaction.hash()),
+                        e
+                    );
+                    None
+                }
+            })
+            .collect();
+        Ok(self)
+    }
+
     /// Chain block with the existing blockchain.
-    pub fn chain(self, height: u64, previous_block_hash: Hash) -> ChainedBlock {
+    pub fn chain(mut self, height: u64, previous_block_hash: Hash) -> ChainedBlock {
+        self.sort_transactions();
         ChainedBlock {
-            transactions: self.transactions,
             header: BlockHeader {
                 timestamp: self.timestamp,
                 height,
                 previous_block_hash,
-                // TODO: get actual merkle tree hash
-                merkle_root_hash: [0u8; 32],
+                merkle_root_hash: self.merkle_root_hash(),
             },
+            transactions: self.transactions,
         }
     }
 
     /// Create a new blockchain with current block as a first block.
-    pub fn chain_first(self) -> ChainedBlock {
+    pub fn chain_first(mut self) -> ChainedBlock {
+        self.sort_transactions();
         ChainedBlock {
-            transactions: self.transactions,
             header: BlockHeader {
                 timestamp: self.timestamp,
                 height: 0,
                 previous_block_hash: [0u8; 32],
-                merkle_root_hash: [0u8; 32],
+                merkle_root_hash: self.merkle_root_hash(),
             },
+            transactions: self.transactions,
         }
     }
+
+    /// Transactions are ordered by their creation time, then by account and hash, so every peer
+    /// builds identical blocks from the same set of transactions regardless of the order they
+    /// were received in, while transactions of an account keep the order they were created in.
+    fn sort_transactions(&mut self) {
+        self.transactions.sort_by_cached_key(|transaction| {
+            (
+                transaction.creation_time_ms(),
+                transaction.account_id().clone(),
+                transaction.hash(),
+            )
+        });
+    }
+
+    fn merkle_root_hash(&self) -> Hash {
+        let hashes: Vec<Hash> = self
+            .transactions
+            .iter()
+            .map(AcceptedTransaction::hash)
+            .collect();
+        merkle_root_hash(&hashes)
+    }
 }
 
 /// When `PendingBlock` chained with a blockchain it becomes `ChainedBlock`
@@ -67,7 +194,7 @@ pub struct ChainedBlock {
 }
 
 /// Header of the block. The hash should be taken from its byte representation.
-#[derive(Clone, Debug, Io, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
 pub struct BlockHeader {
     /// Unix time (in milliseconds) of block forming by a peer.
     pub timestamp: u128,
@@ -102,10 +229,10 @@ impl ChainedBlock {
         Ok(SignedBlock {
             header: self.header,
             transactions,
-            signatures: vec![Signature::new(
-                *public_key,
+            signatures: vec![Signature::new_with_context(
+                &KeyPair::new(*public_key, *private_key),
+                crypto::context::BLOCK,
                 &signature_payload,
-                private_key,
             )?],
         })
     }
@@ -138,10 +265,10 @@ impl SignedBlock {
         private_key: &PrivateKey,
     ) -> Result<SignedBlock, String> {
         let signature_payload: Vec<u8> = self.hash().to_vec();
-        self.signatures.push(Signature::new(
-            *public_key,
+        self.signatures.push(Signature::new_with_context(
+            &KeyPair::new(*public_key, *private_key),
+            crypto::context::BLOCK,
             &signature_payload,
-            private_key,
         )?);
         Ok(SignedBlock {
             header: self.header,
@@ -150,16 +277,54 @@ impl SignedBlock {
         })
     }
 
+    /// Add signatures of other peers, which are not present yet, keeping at most
+    /// `max_signatures` signatures in the block.
+    ///
+    /// Returns `Err(String)` with the number of dropped signatures if the limit was reached.
+    pub fn append_signatures(
+        &mut self,
+        signatures: Vec<Signature>,
+        max_signatures: usize,
+    ) -> Result<(), String> {
+        let mut dropped = 0;
+        for signature in signatures {
+            if self.signatures.contains(&signature) {
+                continue;
+            }
+            if self.signatures.len() >= max_signatures {
+                dropped += 1;
+            } else {
+                self.signatures.push(signature);
+            }
+        }
+        if dropped > 0 {
+            Err(format!(
+                "Block already has {} signatures, {} more signatures were dropped.",
+                self.signatures.len(),
+                dropped
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Validate block transactions against current state of the world.
+    ///
+    /// The header commits to all the transactions and is signed by the peers, so a block with an
+    /// invalid transaction is rejected as a whole.
     pub fn validate(self, world_state_view: &WorldStateView) -> Result<ValidBlock, String> {
         let mut world_state_view = world_state_view.clone();
-        let mut transactions = Vec::new();
-        for transaction in self.transactions {
-            match transaction.validate(&mut world_state_view) {
-                Ok(transaction) => transactions.push(transaction),
-                Err(e) => eprintln!("Transaction validation failed: {}", e),
-            }
-        }
+        let hash = self.hash();
+        let transactions = scheduler::execute_in_parallel(self.transactions, &mut world_state_view)
+            .into_iter()
+            .collect::<Result<Vec<ValidTransaction>, String>>()
+            .map_err(|e| {
+                format!(
+                    "Block {} contains an invalid transaction: {}",
+                    HashHex(hash),
+                    e
+                )
+            })?;
         Ok(ValidBlock {
             header: self.header,
             signatures: self.signatures,
@@ -174,7 +339,7 @@ impl SignedBlock {
 }
 
 /// After full validation `SignedBlock` can transform into `ValidBlock`.
-#[derive(Clone, Debug, Io, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ValidBlock {
     /// Header
     pub header: BlockHeader,
@@ -184,7 +349,136 @@ pub struct ValidBlock {
     pub signatures: Vec<Signature>,
 }
 
+impl Encode for ValidBlock {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        BLOCK_FORMAT_VERSION.encode_to(dest);
+        self.header.encode_to(dest);
+        self.transactions.encode_to(dest);
+        self.signatures.encode_to(dest);
+    }
+}
+
+impl Decode for ValidBlock {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        if u8::decode(input)? != BLOCK_FORMAT_VERSION {
+            return Err("Unsupported block version.".into());
+        }
+        Ok(ValidBlock {
+            header: BlockHeader::decode(input)?,
+            transactions: Vec::decode(input)?,
+            signatures: Vec::decode(input)?,
+        })
+    }
+}
+
+impl From<&ValidBlock> for Vec<u8> {
+    fn from(block: &ValidBlock) -> Self {
+        block.encode()
+    }
+}
+
+impl From<ValidBlock> for Vec<u8> {
+    fn from(block: ValidBlock) -> Self {
+        block.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for ValidBlock {
+    type Error = String;
+
+    /// Decode a block, reporting whether the buffer is truncated, of an unknown version
+    /// or which field of the block failed to decode.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        let mut input = FieldInput::new(&bytes);
+        let version: u8 = input.decode_field("version")?;
+        if version != BLOCK_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported block version {}, expected {}.",
+                version, BLOCK_FORMAT_VERSION
+            ));
+        }
+        let block = ValidBlock {
+            header: input.decode_field("header")?,
+            transactions: input.decode_field("transactions")?,
+            signatures: input.decode_field("signatures")?,
+        };
+        if input.offset != bytes.len() {
+            return Err(format!(
+                "Block has {} trailing bytes after byte {}.",
+                bytes.len() - input.offset,
+                input.offset
+            ));
+        }
+        Ok(block)
+    }
+}
+
+/// `Input` over a byte slice which keeps track of the read position and of reads past the end,
+/// so that decoding errors can point at the field and tell truncation from malformed data.
+struct FieldInput<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    truncated: bool,
+}
+
+impl<'a> FieldInput<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        FieldInput {
+            bytes,
+            offset: 0,
+            truncated: false,
+        }
+    }
+
+    fn decode_field<T: Decode>(&mut self, field: &str) -> Result<T, String> {
+        let start = self.offset;
+        T::decode(self).map_err(|error| {
+            if self.truncated {
+                format!(
+                    "Block is truncated: field `{}` at byte {} does not fit into {} bytes.",
+                    field,
+                    start,
+                    self.bytes.len()
+                )
+            } else {
+                format!(
+                    "Failed to decode block field `{}` at byte {}: {}",
+                    field, start, error
+                )
+            }
+        })
+    }
+}
+
+impl Input for FieldInput<'_> {
+    fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+        // Unknown length makes the codec read instead of failing early, so truncation is noticed.
+        Ok(None)
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+        let end = self.offset + into.len();
+        if end > self.bytes.len() {
+            self.truncated = true;
+            return Err("Not enough data to fill buffer".into());
+        }
+        into.copy_from_slice(&self.bytes[self.offset..end]);
+        self.offset = end;
+        Ok(())
+    }
+}
+
 impl ValidBlock {
+    /// Upper bound of the SCALE encoded size of a block with `transactions_len` bytes of encoded
+    /// transactions and at most `signatures` signatures.
+    pub fn max_encoded_len(transactions_len: usize, signatures: usize) -> usize {
+        1 + ENCODED_BLOCK_HEADER_LEN
+            + MAX_ENCODED_COMPACT_LEN
+            + transactions_len
+            + MAX_ENCODED_COMPACT_LEN
+            + signatures * crypto::MAX_ENCODED_SIGNATURE_LEN
+    }
+
     /// Commit block to the store.
     //TODO: pass block store and block sender as parameters?
     pub fn commit(self) -> CommittedBlock {
@@ -199,6 +493,93 @@ impl ValidBlock {
     pub fn hash(&self) -> Hash {
         self.header.hash()
     }
+
+    /// Header of the block.
+    pub fn header(&self) -> &BlockHeader {
+        &self.header
+    }
+
+    /// Transactions of the block in the order they were applied.
+    pub fn transactions(&self) -> &[ValidTransaction] {
+        &self.transactions
+    }
+
+    /// Signatures of the peers which approved the block.
+    pub fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
+    /// Check that the merkle root hash in the header corresponds to the block's transactions.
+    ///
+    /// Returns `Err(String)` with the block's height if the block's transactions were tampered.
+    pub fn verify_merkle_root_hash(&self) -> Result<(), String> {
+        let hashes: Vec<Hash> = self
+            .transactions
+            .iter()
+            .map(ValidTransaction::hash)
+            .collect();
+        if merkle_root_hash(&hashes) == self.header.merkle_root_hash {
+            Ok(())
+        } else {
+            Err(format!(
+                "Merkle root hash of transactions does not match the header of the block at height {}.",
+                self.header.height
+            ))
+        }
+    }
+
+    /// Check that the block is signed by at least `required_signatures` of the `trusted_peers`,
+    /// signatures of other keys and repeated signatures of the same peer are not counted.
+    ///
+    /// Returns `Err(String)` with the block's height if there are not enough signatures.
+    pub fn verify_signatures(
+        &self,
+        trusted_peers: &[PeerId],
+        required_signatures: usize,
+    ) -> Result<(), String> {
+        let payload = self.hash().to_vec();
+        let mut signed_by: Vec<&PublicKey> = self
+            .signatures
+            .iter()
+            .filter(|signature| {
+                trusted_peers
+                    .iter()
+                    .any(|peer_id| peer_id.public_key == signature.public_key)
+            })
+            .filter(|signature| {
+                signature
+                    .verify_with_context(crypto::context::BLOCK, &payload)
+                    .is_ok()
+            })
+            .map(|signature| &signature.public_key)
+            .collect();
+        signed_by.sort();
+        signed_by.dedup();
+        if signed_by.len() < required_signatures {
+            Err(format!(
+                "Block at height {} is signed by {} trusted peers, but {} signatures are required.",
+                self.header.height,
+                signed_by.len(),
+                required_signatures
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<ValidBlock> for SignedBlock {
+    fn from(block: ValidBlock) -> SignedBlock {
+        SignedBlock {
+            header: block.header,
+            transactions: block
+                .transactions
+                .into_iter()
+                .map(SignedTransaction::from)
+                .collect(),
+            signatures: block.signatures,
+        }
+    }
 }
 
 /// When Kura receives `ValidBlock`, the block is stored and
@@ -221,9 +602,544 @@ impl CommittedBlock {
     }
 }
 
+/// Calculate hash of merkle tree root of the tree of transactions hashes.
+fn merkle_root_hash(transactions_hashes: &[Hash]) -> Hash {
+    let mut merkle_tree = MerkleTree::new();
+    merkle_tree.build_from_hashes(transactions_hashes);
+    merkle_tree.root_hash()
+}
+
+/// Query module provides `IrohaQuery` Block related implementations.
+pub mod query {
+    use super::*;
+    use crate::{block_sync, query::IrohaQuery};
+    use iroha_derive::{log, IntoQuery};
+
+    /// To get the number of blocks committed to the blockchain,
+    /// GetBlockchainHeight query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetBlockchainHeight {}
+
+    /// Result of the `GetBlockchainHeight` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetBlockchainHeightResult {
+        /// Number of committed blocks.
+        pub height: u64,
+    }
+
+    impl GetBlockchainHeight {
+        /// Build a `GetBlockchainHeight` query in the form of a `QueryRequest`.
+        pub fn build_request() -> QueryRequest {
+            let query = GetBlockchainHeight {};
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetBlockchainHeight {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            Ok(QueryResult::GetBlockchainHeight(
+                GetBlockchainHeightResult {
+                    height: world_state_view.height(),
+                },
+            ))
+        }
+    }
+
+    /// To get the committed block by its height,
+    /// GetBlock query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetBlock {
+        height: u64,
+    }
+
+    /// Result of the `GetBlock` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetBlockResult {
+        /// Block committed at the requested height.
+        pub block: CommittedBlock,
+    }
+
+    impl GetBlock {
+        /// Build a `GetBlock` query in the form of a `QueryRequest`.
+        pub fn build_request(height: u64) -> QueryRequest {
+            let query = GetBlock { height };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetBlock {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let block = world_state_view
+                .read_block(self.height)
+                .ok_or(format!("No block with height: {} found.", self.height))?
+                .clone();
+            Ok(QueryResult::GetBlock(GetBlockResult { block }))
+        }
+    }
+
+    /// To get the block building parameters of the peer and the number of transactions waiting
+    /// for a block, GetBlockSchedule query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetBlockSchedule {}
+
+    /// Result of the `GetBlockSchedule` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetBlockScheduleResult {
+        /// Time interval in milliseconds between attempts to build a block.
+        pub block_build_step_ms: u64,
+        /// Maximum number of pending transactions put into a single block.
+        pub max_transactions_in_block: u64,
+        /// Number of transactions waiting in the queue.
+        pub pending_transactions: u64,
+    }
+
+    impl GetBlockScheduleResult {
+        /// Estimate the time until a transaction submitted now is put into a block: the queue is
+        /// drained by `max_transactions_in_block` transactions every `block_build_step_ms`.
+        ///
+        /// The estimate does not include the consensus round and is not a guarantee.
+        pub fn estimate_inclusion_ms(&self) -> u64 {
+            let blocks_ahead = self.pending_transactions / self.max_transactions_in_block.max(1);
+            blocks_ahead
+                .saturating_add(1)
+                .saturating_mul(self.block_build_step_ms)
+        }
+    }
+
+    impl GetBlockSchedule {
+        /// Build a `GetBlockSchedule` query in the form of a `QueryRequest`.
+        pub fn build_request() -> QueryRequest {
+            let query = GetBlockSchedule {};
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetBlockSchedule {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let block_schedule = world_state_view.block_schedule();
+            Ok(QueryResult::GetBlockSchedule(GetBlockScheduleResult {
+                block_build_step_ms: block_schedule.block_build_step_ms,
+                max_transactions_in_block: block_schedule.max_transactions_in_block,
+                pending_transactions: world_state_view.pending_transactions() as u64,
+            }))
+        }
+    }
+
+    /// To check whether the peer is building blocks, GetHealth query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetHealth {}
+
+    /// Result of the `GetHealth` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetHealthResult {
+        /// Number of committed blocks.
+        pub height: u64,
+        /// Number of transactions waiting in the queue.
+        pub pending_transactions: u64,
+        /// Whether block production was paused by the operator.
+        pub block_production_paused: bool,
+    }
+
+    impl GetHealth {
+        /// Build a `GetHealth` query in the form of a `QueryRequest`.
+        pub fn build_request() -> QueryRequest {
+            let query = GetHealth {};
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetHealth {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            Ok(QueryResult::GetHealth(GetHealthResult {
+                height: world_state_view.height(),
+                pending_transactions: world_state_view.pending_transactions() as u64,
+                block_production_paused: world_state_view.block_production_paused(),
+            }))
+        }
+    }
+
+    /// To get up to `count` committed blocks starting from the given height,
+    /// GetBlocks query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetBlocks {
+        from_height: u64,
+        count: u64,
+    }
+
+    /// Result of the `GetBlocks` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetBlocksResult {
+        /// Blocks committed starting from the requested height, ordered by height.
+        pub blocks: Vec<CommittedBlock>,
+    }
+
+    impl GetBlocks {
+        /// Build a `GetBlocks` query in the form of a `QueryRequest`.
+        pub fn build_request(from_height: u64, count: u64) -> QueryRequest {
+            let query = GetBlocks { from_height, count };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetBlocks {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            // At most a chunk of blocks is returned at once, whatever was requested.
+            let to_height = self
+                .from_height
+                .saturating_add(self.count.min(block_sync::CHUNK_SIZE))
+                .min(world_state_view.height());
+            let blocks = (self.from_height..to_height)
+                .filter_map(|height| world_state_view.read_block(height).cloned())
+                .collect();
+            Ok(QueryResult::GetBlocks(GetBlocksResult { blocks }))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::block::{BlockHeader, ValidBlock};
+    use crate::account::isi::AccountInstruction;
+    use crate::block::{
+        query::GetBlocks, BlockHeader, PendingBlock, SignedBlock, ValidBlock, BLOCK_FORMAT_VERSION,
+    };
+    use crate::{block_sync, crypto, peer::PeerId, prelude::*, tx::RejectionReason};
+    use parity_scale_codec::{Decode, Encode};
+    use std::{collections::HashMap, convert::TryFrom};
+
+    /// World state with `root@global`, whose only signatory is the `public_key` of the leader,
+    /// which signs the transactions of the blocks.
+    fn world_state_view_with_root(public_key: PublicKey) -> WorldStateView {
+        let mut world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        ));
+        let mut global = Domain::new("global".to_string());
+        global.accounts.insert(
+            AccountId::new("root", "global"),
+            Account::new("root", "global", public_key),
+        );
+        world_state_view.add_domain(global);
+        world_state_view
+    }
+
+    #[test]
+    fn valid_block_encoding_round_trip() {
+        let transaction = RequestedTransaction::new(
+            vec![Instruction::Notify("message".to_string())],
+            AccountId::new("root", "global"),
+        )
+        .accept()
+        .expect("Failed to accept transaction.");
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let block = PendingBlock::new(vec![transaction])
+            .chain_first()
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign block.")
+            .validate(&world_state_view_with_root(public_key))
+            .expect("Failed to validate block.");
+        let bytes = block.encode();
+        assert_eq!(BLOCK_FORMAT_VERSION, bytes[0]);
+        let decoded = ValidBlock::decode(&mut bytes.as_slice()).expect("Failed to decode block.");
+        assert_eq!(block.hash(), decoded.hash());
+        assert_eq!(1, decoded.transactions.len());
+        assert_eq!(bytes, decoded.encode());
+        let decoded = ValidBlock::try_from(Vec::from(&block)).expect("Failed to decode block.");
+        assert_eq!(bytes, decoded.encode());
+        crate::assert_canonical_encoding(&block);
+    }
+
+    #[test]
+    fn valid_block_accessors_return_its_contents() {
+        let transactions: Vec<AcceptedTransaction> = ["first", "second"]
+            .iter()
+            .map(|message| {
+                RequestedTransaction::new(
+                    vec![Instruction::Notify(message.to_string())],
+                    AccountId::new("root", "global"),
+                )
+                .accept()
+                .expect("Failed to accept transaction.")
+            })
+            .collect();
+        let transaction_hashes: Vec<Hash> =
+            transactions.iter().map(AcceptedTransaction::hash).collect();
+        let key_pairs: Vec<(PublicKey, PrivateKey)> = (0..3)
+            .map(|_| crypto::generate_key_pair().expect("Failed to generate key pair."))
+            .collect();
+        let mut block = PendingBlock::new(transactions)
+            .chain_first()
+            .sign(&key_pairs[0].0, &key_pairs[0].1)
+            .expect("Failed to sign block.");
+        for (public_key, private_key) in &key_pairs[1..] {
+            block = block
+                .sign(public_key, private_key)
+                .expect("Failed to sign block.");
+        }
+        let block = block
+            .validate(&world_state_view_with_root(key_pairs[0].0))
+            .expect("Failed to validate block.");
+        assert_eq!(&block.header, block.header());
+        assert_eq!(0, block.header().height);
+        let mut hashes: Vec<Hash> = block
+            .transactions()
+            .iter()
+            .map(ValidTransaction::hash)
+            .collect();
+        hashes.sort();
+        let mut expected_hashes = transaction_hashes;
+        expected_hashes.sort();
+        assert_eq!(expected_hashes, hashes);
+        let public_keys: Vec<PublicKey> = block
+            .signatures()
+            .iter()
+            .map(|signature| signature.public_key)
+            .collect();
+        let expected_public_keys: Vec<PublicKey> = key_pairs
+            .iter()
+            .map(|(public_key, _)| *public_key)
+            .collect();
+        assert_eq!(expected_public_keys, public_keys);
+    }
+
+    #[test]
+    fn block_of_unknown_version_is_rejected() {
+        let block = ValidBlock {
+            header: BlockHeader {
+                timestamp: 0,
+                height: 0,
+                previous_block_hash: [0u8; 32],
+                merkle_root_hash: [0u8; 32],
+            },
+            transactions: vec![],
+            signatures: vec![],
+        };
+        let mut bytes = block.encode();
+        bytes[0] = BLOCK_FORMAT_VERSION + 1;
+        let error = ValidBlock::decode(&mut bytes.as_slice())
+            .expect_err("Block of unknown version was decoded.");
+        assert_eq!("Unsupported block version.", error.what());
+        assert!(ValidBlock::try_from(bytes)
+            .expect_err("Block of unknown version was decoded.")
+            .contains("Unsupported block version"));
+    }
+
+    fn empty_valid_block() -> ValidBlock {
+        ValidBlock {
+            header: BlockHeader {
+                timestamp: 0,
+                height: 0,
+                previous_block_hash: [0u8; 32],
+                merkle_root_hash: [0u8; 32],
+            },
+            transactions: vec![],
+            signatures: vec![],
+        }
+    }
+
+    #[test]
+    fn block_should_be_signed_by_enough_trusted_peers() {
+        let key_pairs: Vec<(PublicKey, PrivateKey)> = (0..3)
+            .map(|_| crypto::generate_key_pair().expect("Failed to generate key pair."))
+            .collect();
+        let trusted_peers: Vec<PeerId> = key_pairs[..2]
+            .iter()
+            .enumerate()
+            .map(|(i, (public_key, _))| PeerId {
+                address: format!("127.0.0.1:{}", 8080 + i),
+                public_key: *public_key,
+            })
+            .collect();
+        let mut block = empty_valid_block();
+        let hash = block.hash();
+        let sign = |(public_key, private_key): &(PublicKey, PrivateKey), payload: &[u8]| {
+            Signature::new_with_context(
+                &KeyPair::new(*public_key, *private_key),
+                crypto::context::BLOCK,
+                payload,
+            )
+            .expect("Failed to sign block.")
+        };
+        block.signatures = vec![
+            sign(&key_pairs[0], &hash),
+            sign(&key_pairs[0], &hash),
+            sign(&key_pairs[1], b"another block"),
+            sign(&key_pairs[2], &hash),
+        ];
+        assert!(block.verify_signatures(&trusted_peers, 2).is_err());
+        block.signatures.push(sign(&key_pairs[1], &hash));
+        assert_eq!(Ok(()), block.verify_signatures(&trusted_peers, 2));
+    }
+
+    #[async_std::test]
+    async fn get_blocks_returns_at_most_a_chunk_below_the_height() {
+        let mut world_state_view = world_state_view_with_root([0; 32]);
+        for height in 0..=block_sync::CHUNK_SIZE {
+            let mut block = empty_valid_block();
+            block.header.height = height;
+            world_state_view.put(&block.commit()).await;
+        }
+        let blocks = |from_height, count| match GetBlocks::build_request(from_height, count)
+            .execute(&world_state_view)
+        {
+            Ok(QueryResult::GetBlocks(result)) => result.blocks,
+            result => panic!("Unexpected query result: {:?}", result),
+        };
+        assert_eq!(block_sync::CHUNK_SIZE as usize, blocks(0, u64::MAX).len());
+        let top_blocks = blocks(block_sync::CHUNK_SIZE, u64::MAX);
+        assert_eq!(1, top_blocks.len());
+        assert_eq!(block_sync::CHUNK_SIZE, top_blocks[0].header.height);
+        assert!(blocks(u64::MAX, u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn max_encoded_len_covers_the_encoded_block() {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let mut block = empty_valid_block();
+        block.signatures = (0..3)
+            .map(|_| {
+                Signature::new(&KeyPair::new(public_key, private_key), b"block")
+                    .expect("Failed to sign.")
+            })
+            .collect();
+        let encoded_len = Vec::from(&block).len();
+        assert!(encoded_len <= ValidBlock::max_encoded_len(0, 3));
+        assert!(encoded_len > ValidBlock::max_encoded_len(0, 2));
+    }
+
+    #[test]
+    fn truncated_block_is_reported_as_truncated() {
+        let bytes: Vec<u8> = empty_valid_block().into();
+        assert_eq!(
+            empty_valid_block(),
+            ValidBlock::try_from(bytes.clone()).expect("Failed to decode block.")
+        );
+        let error = ValidBlock::try_from(bytes[..bytes.len() - 1].to_vec())
+            .expect_err("Truncated block was decoded.");
+        assert!(error.starts_with("Block is truncated"), "{}", error);
+        assert!(error.contains("`signatures`"), "{}", error);
+        let error = ValidBlock::try_from(Vec::new()).expect_err("Empty block was decoded.");
+        assert!(error.contains("field `version`"), "{}", error);
+    }
+
+    #[test]
+    fn block_with_bad_version_byte_reports_version() {
+        let mut bytes: Vec<u8> = empty_valid_block().into();
+        bytes[0] = 42;
+        assert_eq!(
+            Err(format!(
+                "Unsupported block version 42, expected {}.",
+                BLOCK_FORMAT_VERSION
+            )),
+            ValidBlock::try_from(bytes)
+        );
+    }
+
+    #[test]
+    fn malformed_block_reports_failed_field() {
+        let mut bytes: Vec<u8> = empty_valid_block().into();
+        // Replace the compact length of `transactions` with an invalid compact prefix.
+        let transactions_offset = 1 + empty_valid_block().header.encode().len();
+        bytes[transactions_offset] = 0b1111_1111;
+        let error = ValidBlock::try_from(bytes).expect_err("Malformed block was decoded.");
+        assert!(
+            error.starts_with(&format!(
+                "Failed to decode block field `transactions` at byte {}",
+                transactions_offset
+            )),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn appended_signatures_are_capped() {
+        let mut block = PendingBlock::new(Vec::new())
+            .chain_first()
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign block.");
+        let hash = block.hash();
+        let signatures = |count: usize| -> Vec<Signature> {
+            (0..count)
+                .map(|_| {
+                    let (public_key, private_key) =
+                        crypto::generate_key_pair().expect("Failed to generate key pair.");
+                    Signature::new(&KeyPair::new(public_key, private_key), &hash)
+                        .expect("Failed to create signature.")
+                })
+                .collect()
+        };
+        let first = signatures(3);
+        assert!(block.append_signatures(first.clone(), 5).is_ok());
+        assert!(block.append_signatures(first, 5).is_ok());
+        assert_eq!(4, block.signatures.len());
+        let result = block.append_signatures(signatures(3), 5);
+        assert!(result
+            .expect_err("Overflow was not reported.")
+            .contains("2 more"));
+        assert_eq!(5, block.signatures.len());
+        assert!(block.append_signatures(signatures(1), 5).is_err());
+        assert_eq!(5, block.signatures.len());
+    }
 
     #[test]
     pub fn committed_and_valid_block_hashes_are_equal() {
@@ -240,4 +1156,209 @@ mod tests {
         let commited_block = valid_block.clone().commit();
         assert_eq!(valid_block.hash(), commited_block.hash())
     }
+
+    #[test]
+    pub fn blocks_from_differently_ordered_transactions_are_equal() {
+        let transactions: Vec<AcceptedTransaction> = ["alice", "bob", "carol", "alice"]
+            .iter()
+            .map(|name| {
+                RequestedTransaction::new(Vec::new(), AccountId::new(name, "wonderland"))
+                    .accept()
+                    .expect("Failed to accept transaction.")
+            })
+            .collect();
+        let mut reversed_transactions = transactions.clone();
+        reversed_transactions.reverse();
+        let mut block = PendingBlock::new(transactions);
+        let mut reversed_block = PendingBlock::new(reversed_transactions);
+        block.timestamp = 0;
+        reversed_block.timestamp = 0;
+        let block = block.chain_first();
+        let reversed_block = reversed_block.chain_first();
+        assert_eq!(block.hash(), reversed_block.hash());
+        assert_eq!(Vec::from(&block), Vec::from(&reversed_block));
+    }
+
+    #[test]
+    pub fn chained_block_keeps_creation_order_of_account_transactions() {
+        let first = RequestedTransaction::new(Vec::new(), AccountId::new("alice", "wonderland"))
+            .accept()
+            .expect("Failed to accept transaction.");
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = RequestedTransaction::new(Vec::new(), AccountId::new("alice", "wonderland"))
+            .accept()
+            .expect("Failed to accept transaction.");
+        let hashes = vec![first.hash(), second.hash()];
+        let block = PendingBlock::new(vec![second, first]).chain(1, [1; 32]);
+        assert_eq!(
+            hashes,
+            block
+                .transactions
+                .iter()
+                .map(AcceptedTransaction::hash)
+                .collect::<Vec<Hash>>()
+        );
+    }
+
+    #[test]
+    pub fn transaction_signed_by_removed_signatory_is_rejected() {
+        let (public_key1, private_key1) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let (public_key2, private_key2) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let domain_name = "wonderland";
+        let account_id = AccountId::new("alice", domain_name);
+        let mut account = Account::new("alice", domain_name, public_key1) + public_key2;
+        account.quorum = 1;
+        let mut accounts = HashMap::new();
+        accounts.insert(account_id.clone(), account);
+        let mut domains = HashMap::new();
+        domains.insert(
+            Name::new(domain_name),
+            Domain {
+                name: Name::from(domain_name),
+                accounts,
+                asset_definitions: HashMap::new(),
+            },
+        );
+        let world_state_view = WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+            domains,
+        ));
+        let remove_signatory = RequestedTransaction::new(
+            vec![Instruction::Account(AccountInstruction::RemoveSignatory(
+                account_id.clone(),
+                public_key1,
+            ))],
+            account_id.clone(),
+        )
+        .accept()
+        .expect("Failed to accept transaction.");
+        let remove_signatory_hash = remove_signatory.hash();
+        let remove_signatory = remove_signatory
+            .sign(&public_key2, &private_key2)
+            .expect("Failed to sign transaction.");
+        let signed_by_removed_signatory =
+            RequestedTransaction::new(vec![Instruction::Notify("hi".to_string())], account_id)
+                .accept()
+                .expect("Failed to accept transaction.")
+                .sign(&public_key1, &private_key1)
+                .expect("Failed to sign transaction.");
+        let signed_block = SignedBlock {
+            header: BlockHeader {
+                timestamp: 0,
+                height: 0,
+                previous_block_hash: [0u8; 32],
+                merkle_root_hash: [0u8; 32],
+            },
+            transactions: vec![remove_signatory, signed_by_removed_signatory],
+            signatures: vec![],
+        };
+        let signed_block_hash = signed_block.hash();
+        let error = signed_block
+            .clone()
+            .validate(&world_state_view)
+            .expect_err("Block with an invalid transaction should be rejected.");
+        assert!(error.contains(&HashHex(signed_block_hash).to_string()));
+        let valid_block = SignedBlock {
+            transactions: signed_block.transactions[..1].to_vec(),
+            ..signed_block
+        }
+        .validate(&world_state_view)
+        .expect("Failed to validate block.");
+        assert_eq!(1, valid_block.transactions.len());
+        assert_eq!(remove_signatory_hash, valid_block.transactions[0].hash());
+    }
+
+    #[test]
+    pub fn leader_drops_transactions_invalid_in_world_state_view() {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let transaction = |account_name: &str| {
+            RequestedTransaction::new(
+                vec![Instruction::Notify("hi".to_string())],
+                AccountId::new(account_name, "global"),
+            )
+            .accept()
+            .expect("Failed to accept transaction.")
+        };
+        let valid = transaction("root");
+        let world_state_view = world_state_view_with_root(public_key);
+        let block = PendingBlock::new(vec![transaction("nobody"), valid.clone()])
+            .without_transactions_invalid_in(&world_state_view, &public_key, &private_key)
+            .expect("Failed to validate transactions.");
+        assert_eq!(1, block.transactions.len());
+        assert_eq!(valid.hash(), block.transactions[0].hash());
+        assert!(block
+            .chain_first()
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign block.")
+            .validate(&world_state_view)
+            .is_ok());
+    }
+
+    #[test]
+    pub fn stateless_validation_reports_failed_transactions() {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let account_id = AccountId::new("alice", "wonderland");
+        let signed = |message: &str| {
+            RequestedTransaction::new(
+                vec![Instruction::Notify(message.to_string())],
+                account_id.clone(),
+            )
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.")
+        };
+        // Forwarded transactions are decoded as accepted, so `accept` checks are bypassed.
+        let forwarded = |transaction: SignedTransaction| {
+            AcceptedTransaction::decode(
+                &mut Vec::from(&RequestedTransaction::from(transaction)).as_slice(),
+            )
+            .expect("Failed to decode transaction.")
+        };
+        let (other_public_key, other_private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let bogus_signature = crypto::Signature::new(
+            &KeyPair::new(other_public_key, other_private_key),
+            b"other payload",
+        )
+        .expect("Failed to sign payload.");
+        let twice_signed = RequestedTransaction::from(signed("twice"))
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.");
+        let transactions = vec![
+            forwarded(signed("valid")),
+            forwarded(
+                RequestedTransaction::new(Vec::new(), account_id.clone())
+                    .accept()
+                    .expect("Failed to accept transaction.")
+                    .sign(&public_key, &private_key)
+                    .expect("Failed to sign transaction.")
+                    .sign(vec![bogus_signature])
+                    .expect("Failed to add signature."),
+            ),
+            forwarded(signed("also valid")),
+            forwarded(twice_signed),
+        ];
+        let block = PendingBlock::new(transactions);
+        assert_eq!(
+            Err(vec![
+                (1, RejectionReason::InvalidSignature(other_public_key)),
+                (3, RejectionReason::DuplicateSignature(public_key)),
+            ]),
+            block.validate_transactions()
+        );
+        let block = block.without_invalid_transactions();
+        assert_eq!(2, block.transactions.len());
+        assert_eq!(Ok(()), block.validate_transactions());
+    }
 }
@@ -0,0 +1,164 @@
+//! This module contains the logic of downloading the existing blockchain from other peers.
+//! `BlockSynchronizer` is used by a fresh or lagging peer on startup to catch up with the network.
+
+use crate::{
+    block::{
+        query::{GetBlockchainHeight, GetBlocks},
+        SignedBlock,
+    },
+    kura::Kura,
+    peer::{self, PeerId},
+    prelude::*,
+    torii::uri,
+};
+use async_std::{sync::RwLock, task};
+use iroha_network::{Request, Response};
+use std::{convert::TryFrom, sync::Arc, time::Duration};
+
+/// Number of blocks requested from a peer at once.
+pub const CHUNK_SIZE: u64 = 100;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Downloads blocks missing in `Kura` from the trusted peers.
+#[derive(Debug)]
+pub struct BlockSynchronizer {
+    kura: Arc<RwLock<Kura>>,
+    world_state_view: Arc<RwLock<WorldStateView>>,
+    /// Trusted peers except the current one, blocks are downloaded from them.
+    peers: Vec<PeerId>,
+    trusted_peers: Vec<PeerId>,
+    /// Number of trusted peers' signatures a block should have, the same as `Sumeragi` collects
+    /// before committing a block.
+    required_signatures: usize,
+    /// Key pair of the current peer, block queries are signed with it.
+    key_pair: KeyPair,
+}
+
+impl BlockSynchronizer {
+    /// Default `BlockSynchronizer` constructor.
+    pub fn new(
+        kura: Arc<RwLock<Kura>>,
+        world_state_view: Arc<RwLock<WorldStateView>>,
+        peer_id: &PeerId,
+        trusted_peers: &[PeerId],
+        max_faulty_peers: usize,
+        key_pair: KeyPair,
+    ) -> Self {
+        BlockSynchronizer {
+            kura,
+            world_state_view,
+            peers: trusted_peers
+                .iter()
+                .filter(|peer| *peer != peer_id)
+                .cloned()
+                .collect(),
+            trusted_peers: trusted_peers.to_vec(),
+            required_signatures: 2 * max_faulty_peers + 1,
+            key_pair,
+        }
+    }
+
+    /// Download missing blocks from every peer in turn starting from the local top height.
+    /// Failed peers are retried with an exponential backoff before moving to the next one.
+    pub async fn synchronize(&self) {
+        for peer in &self.peers {
+            let mut backoff = INITIAL_BACKOFF;
+            for attempt in 1..=MAX_ATTEMPTS {
+                match self.synchronize_with(peer).await {
+                    Ok(()) => break,
+                    Err(e) => {
+                        eprintln!(
+                            "Failed to synchronize blocks with peer {} (attempt {} of {}): {}",
+                            peer.address, attempt, MAX_ATTEMPTS, e
+                        );
+                        if attempt < MAX_ATTEMPTS {
+                            task::sleep(backoff).await;
+                            backoff = (backoff * 2).min(MAX_BACKOFF);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn synchronize_with(&self, peer: &PeerId) -> Result<(), String> {
+        let peer_height = match request(peer, GetBlockchainHeight::build_request()).await? {
+            QueryResult::GetBlockchainHeight(result) => result.height,
+            _ => return Err("Wrong Query Result Type.".to_string()),
+        };
+        loop {
+            let height = self.kura.read().await.height();
+            if height >= peer_height {
+                return Ok(());
+            }
+            let count = CHUNK_SIZE.min(peer_height - height);
+            let blocks = match request(
+                peer,
+                GetBlocks::build_request(height, count).sign_as_peer(&self.key_pair)?,
+            )
+            .await?
+            {
+                QueryResult::GetBlocks(result) => result.blocks,
+                _ => return Err("Wrong Query Result Type.".to_string()),
+            };
+            if blocks.is_empty() {
+                return Err(format!("Peer returned no blocks from height {}.", height));
+            }
+            self.store(blocks).await?;
+        }
+    }
+
+    /// Check blocks' integrity and signatures, validate their transactions against the world
+    /// state and store them, `Kura` checks that they continue the local blockchain.
+    async fn store(&self, blocks: Vec<CommittedBlock>) -> Result<(), String> {
+        let height = self.kura.read().await.height();
+        let mut world_state_view = self.world_state_view_at(height).await;
+        let mut valid_blocks = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let block = ValidBlock {
+                header: block.header,
+                transactions: block.transactions,
+                signatures: block.signatures,
+            };
+            block.verify_merkle_root_hash()?;
+            block.verify_signatures(&self.trusted_peers, self.required_signatures)?;
+            let block = SignedBlock::from(block).validate(&world_state_view)?;
+            // Next blocks of the batch are validated against the state after this one.
+            world_state_view.put(&block.clone().commit()).await;
+            valid_blocks.push(block);
+        }
+        self.kura.write().await.store_batch(valid_blocks).await?;
+        Ok(())
+    }
+
+    /// Blocks stored in `Kura` reach the `WorldStateView` a bit later, so the state is awaited
+    /// to catch up with the `height`.
+    async fn world_state_view_at(&self, height: u64) -> WorldStateView {
+        loop {
+            {
+                let world_state_view = self.world_state_view.read().await;
+                if world_state_view.height() >= height {
+                    return world_state_view.clone();
+                }
+            }
+            task::sleep(INITIAL_BACKOFF).await;
+        }
+    }
+}
+
+async fn request(peer: &PeerId, request: QueryRequest) -> Result<QueryResult, String> {
+    match peer::send_request_to(
+        peer,
+        Request::new(uri::QUERY_URI.to_string(), request.into()),
+    )
+    .await?
+    {
+        Response::Ok(payload) => QueryResult::try_from(payload).map_err(|e| e.to_string()),
+        Response::InternalError | Response::Rejected(_) => Err(format!(
+            "Failed to execute query - Internal Error on peer: {}",
+            peer.address
+        )),
+    }
+}
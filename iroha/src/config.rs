@@ -1,10 +1,15 @@
 //! This module contains `Configuration` structure and related implementation.
 use crate::{
-    crypto::{PrivateKey, PublicKey},
-    kura::Mode,
+    asset::AssetDefinitionId,
+    block::DEFAULT_MAX_BLOCK_BYTES,
+    crypto::{self, Algorithm, PrivateKey, PublicKey},
+    kura::{BlockEncryptionKey, BlockStoreLayout, Mode},
     peer::PeerId,
+    queue::{DEFAULT_MAX_PENDING_TRANSACTIONS, DEFAULT_MAX_TRANSACTIONS_IN_BLOCK},
+    sumeragi::DEFAULT_MAX_BLOCK_SIGNATURES,
+    torii::{DEFAULT_MAX_TRANSACTION_BYTES, DEFAULT_TRANSACTION_BURST, DEFAULT_TRANSACTION_RATE},
 };
-use iroha_derive::*;
+use iroha_macro::log::Configuration as LoggerConfiguration;
 use std::{
     collections::HashMap,
     convert::TryInto,
@@ -14,21 +19,60 @@ use std::{
     path::Path,
 };
 
+const CONFIG_VERSION: &str = "CONFIG_VERSION";
 const TORII_URL: &str = "TORII_URL";
+const TORII_URLS: &str = "TORII_URLS";
 const BLOCK_TIME_MS: &str = "BLOCK_TIME_MS";
+const MAX_TRANSACTIONS_IN_BLOCK: &str = "MAX_TRANSACTIONS_IN_BLOCK";
+const MAX_PENDING_TRANSACTIONS: &str = "MAX_PENDING_TRANSACTIONS";
+const BLOCK_TRIGGER_DEPTH: &str = "BLOCK_TRIGGER_DEPTH";
+const PRODUCE_EMPTY_BLOCKS: &str = "PRODUCE_EMPTY_BLOCKS";
 const KURA_INIT_MODE: &str = "KURA_INIT_MODE";
 const KURA_BLOCK_STORE_PATH: &str = "KURA_BLOCK_STORE_PATH";
+const KURA_BLOCK_CACHE_SIZE: &str = "KURA_BLOCK_CACHE_SIZE";
+const KURA_MAX_BLOCKS_IN_MEMORY: &str = "KURA_MAX_BLOCKS_IN_MEMORY";
+const KURA_BLOCK_STORE_LAYOUT: &str = "KURA_BLOCK_STORE_LAYOUT";
+const KURA_BLOCK_STORE_ENCRYPTION_KEY: &str = "KURA_BLOCK_STORE_ENCRYPTION_KEY";
+const KURA_VERIFY_CHAIN_INTERVAL_MS: &str = "KURA_VERIFY_CHAIN_INTERVAL_MS";
+const KURA_VERIFY_CHAIN_WINDOW: &str = "KURA_VERIFY_CHAIN_WINDOW";
 const TRUSTED_PEERS: &str = "IROHA_TRUSTED_PEERS";
 const MAX_FAULTY_PEERS: &str = "MAX_FAULTY_PEERS";
+const MAX_BLOCK_SIGNATURES: &str = "MAX_BLOCK_SIGNATURES";
+const STRICT_SIGNATURE_VERIFICATION: &str = "STRICT_SIGNATURE_VERIFICATION";
+const TLS: &str = "TLS";
 const IROHA_PUBLIC_KEY: &str = "IROHA_PUBLIC_KEY";
 const IROHA_PRIVATE_KEY: &str = "IROHA_PRIVATE_KEY";
+const ROOT_PUBLIC_KEY: &str = "ROOT_PUBLIC_KEY";
+const KEY_ALGORITHM: &str = "KEY_ALGORITHM";
 const COMMIT_TIME_MS: &str = "COMMIT_TIME_MS";
 const TX_RECEIPT_TIME_MS: &str = "TX_RECEIPT_TIME_MS";
+const LOG_LEVEL: &str = "LOG_LEVEL";
+const LOG_FORMAT: &str = "LOG_FORMAT";
+const MAX_TRANSACTION_COST: &str = "MAX_TRANSACTION_COST";
+const TRANSACTION_FEE_ASSET: &str = "TRANSACTION_FEE_ASSET";
+const TORII_TRANSACTION_RATE: &str = "TORII_TRANSACTION_RATE";
+const TORII_TRANSACTION_BURST: &str = "TORII_TRANSACTION_BURST";
+const MAX_TRANSACTION_BYTES: &str = "MAX_TRANSACTION_BYTES";
+const MAX_BLOCK_BYTES: &str = "MAX_BLOCK_BYTES";
 const DEFAULT_TORII_URL: &str = "127.0.0.1:1337";
 const DEFAULT_BLOCK_TIME_MS: u64 = 1000;
 const DEFAULT_KURA_INIT_MODE: Mode = Mode::Strict;
 const DEFAULT_KURA_BLOCK_STORE_PATH: &str = "./blocks";
+const DEFAULT_KURA_BLOCK_CACHE_SIZE: usize = 100;
+const DEFAULT_KURA_BLOCK_STORE_LAYOUT: BlockStoreLayout = BlockStoreLayout::Files;
+const DEFAULT_KURA_VERIFY_CHAIN_WINDOW: u64 = 100;
 const DEFAULT_MAX_FAULTY_PEERS: usize = 0;
+const DEFAULT_STRICT_SIGNATURE_VERIFICATION: bool = true;
+const DEFAULT_TLS: bool = false;
+const DEFAULT_PRODUCE_EMPTY_BLOCKS: bool = false;
+const DEFAULT_KEY_ALGORITHM: Algorithm = Algorithm::Ed25519;
+const DEFAULT_LOG_LEVEL: &str = "info";
+const DEFAULT_LOG_FORMAT: &str = "text";
+const DEFAULT_MAX_TRANSACTION_COST: u64 = 10_000;
+/// Version of the configuration format read by this build, older versions are migrated on load.
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+/// Version of the configuration files written before `CONFIG_VERSION` was introduced.
+const LEGACY_CONFIG_VERSION: u32 = 1;
 /// Amount of time Peer waits for `BlockCommitted` message from the proxy tail.
 pub const DEFAULT_COMMIT_TIME_MS: u64 = 1000;
 /// Amount of time Peer waits for `TransactionReceipt` from the leader.
@@ -36,15 +80,44 @@ pub const DEFAULT_TX_RECEIPT_TIME_MS: u64 = 100;
 
 /// Configuration parameters container.
 pub struct Configuration {
+    /// Version of the configuration format, always `CURRENT_CONFIG_VERSION` after loading.
+    pub config_version: u32,
     /// Current instance `PeerId`.
     pub peer_id: PeerId,
+    /// Torii addresses clients send requests to in the order of preference, failing over to the
+    /// next one if a peer is unreachable. Only the address of `peer_id` is used if empty.
+    pub torii_urls: Vec<String>,
     /// Time interval in milliseconds to wait before an attempt to create a new block.
     /// A new block can be build earlier if the pending transactions queue will be filled.
     pub block_build_step_ms: u64,
-    /// Possible modes: `strict`, `fast`.
+    /// Maximum number of pending transactions put into a single block.
+    pub max_transactions_in_block: usize,
+    /// Maximum number of transactions waiting in the queue, new transactions are dropped above it.
+    pub max_pending_transactions: usize,
+    /// Optional number of pending transactions that seals a block before `block_build_step_ms`
+    /// elapses, `max_transactions_in_block` is used if not set.
+    pub block_trigger_depth: Option<usize>,
+    /// Whether a block is sealed every `block_build_step_ms` even if there are no transactions.
+    pub produce_empty_blocks: bool,
+    /// Possible modes: `strict`, `fast`, `recovery`.
     pub mode: Mode,
     /// Path to the existing block store folder or path to create new folder.
     pub kura_block_store_path: String,
+    /// Number of decoded blocks read from the block store which `Kura` keeps cached.
+    pub kura_block_cache_size: usize,
+    /// Optional number of the most recent blocks `Kura` keeps in memory, older blocks are read
+    /// from the block store. All blocks are kept in memory if not set.
+    pub kura_max_blocks_in_memory: Option<usize>,
+    /// Possible layouts of the block store: `files`, `log`.
+    pub kura_block_store_layout: BlockStoreLayout,
+    /// Optional key of the ChaCha20-Poly1305 encryption of the blocks at rest, blocks are stored
+    /// unencrypted if not set.
+    pub kura_block_store_encryption_key: Option<BlockEncryptionKey>,
+    /// Optional interval in milliseconds of the background integrity check of the stored
+    /// blocks, see `Kura::verify_chain`. Blocks are checked only at the start if not set.
+    pub kura_verify_chain_interval_ms: Option<u64>,
+    /// Number of stored blocks checked by every run of the background integrity check.
+    pub kura_verify_chain_window: u64,
     /// Optional list of predefined trusted peers.
     pub trusted_peers: Vec<PeerId>,
     /// Maximum amount of peers to fail and do not compromise the consensus.
@@ -53,10 +126,42 @@ pub struct Configuration {
     pub public_key: PublicKey,
     /// Private key of this peer.
     pub private_key: PrivateKey,
+    /// Signature scheme of the key pair, clients check it against the algorithms allowed by the
+    /// peer when connecting.
+    pub key_algorithm: Algorithm,
+    /// Signatory of the genesis `root@global` account, `public_key` if not set. All peers should
+    /// use the same value.
+    pub root_public_key: PublicKey,
     /// Amount of time Peer waits for CommitMessage from the proxy tail.
     pub commit_time_ms: u64,
     /// Amount of time Peer waits for TxReceipt from the leader.
     pub tx_receipt_time_ms: u64,
+    /// Maximum number of signatures collected for a block, gossiped signatures above it are
+    /// dropped.
+    pub max_block_signatures: usize,
+    /// Reject Ed25519 signatures and keys which some implementations accept and others do not,
+    /// see `crypto::set_strict_verification`. All peers should use the same value.
+    pub strict_signature_verification: bool,
+    /// Connect to the peers over TLS pinned to their public keys and serve `Torii` over TLS with
+    /// the certificate of `public_key`, see `peer::set_tls`. Requires the `tls` feature, all
+    /// peers and clients of a network should use the same value.
+    pub tls: bool,
+    /// Log levels (e.g. `warn,iroha::kura=debug`) and format (`text` or `json`).
+    pub logger_configuration: LoggerConfiguration,
+    /// Maximum total cost of instructions in a single transaction.
+    pub max_transaction_cost: u64,
+    /// Optional definition of the asset (`name#domain_name`) transaction fees are debited from.
+    /// Transactions are free if not set.
+    pub transaction_fee_asset_definition_id: Option<AssetDefinitionId>,
+    /// Number of transactions per second `Torii` accepts from a single account.
+    pub torii_transaction_rate: u32,
+    /// Number of transactions `Torii` accepts from a single account at once above the rate.
+    pub torii_transaction_burst: u32,
+    /// Maximum size in bytes of an encoded transaction `Torii` reads from a connection.
+    pub max_transaction_bytes: usize,
+    /// Maximum size in bytes of an encoded block including signatures, the leader stops adding
+    /// transactions to a block at it and `Kura` rejects larger blocks.
+    pub max_block_bytes: usize,
 }
 
 impl Configuration {
@@ -65,10 +170,10 @@ impl Configuration {
     /// # Panics
     /// This method will panic if configuration file presented, but has incorrect scheme or format.
     /// # Errors
-    /// This method will return error if system will fail to find a file or read it's content.
-    #[log]
+    /// This method will return error if system will fail to find a file or read it's content, or
+    /// if the file has a configuration version which can not be migrated to the current one.
     pub fn from_path<P: AsRef<Path> + Debug>(path: P) -> Result<Configuration, String> {
-        let mut config_map: HashMap<String, String> = fs::read_to_string(path)
+        let config_map: HashMap<String, String> = fs::read_to_string(path)
             .map_err(|error| format!("Failed to read configuration from path: {}.", error))?
             .lines()
             .filter(|line| line.contains(':'))
@@ -87,13 +192,31 @@ impl Configuration {
                 )
             })
             .collect();
-        Ok(ConfigurationBuilder {
+        let mut config_map = migrate(config_map)?;
+        ConfigurationBuilder {
             torii_url: env::var(TORII_URL)
                 .ok()
                 .or_else(|| config_map.remove(TORII_URL)),
+            torii_urls: parse_torii_urls(
+                env::var(TORII_URLS)
+                    .ok()
+                    .or_else(|| config_map.remove(TORII_URLS)),
+            ),
             block_build_step_ms: env::var(BLOCK_TIME_MS)
                 .ok()
                 .or_else(|| config_map.remove(BLOCK_TIME_MS)),
+            max_transactions_in_block: env::var(MAX_TRANSACTIONS_IN_BLOCK)
+                .ok()
+                .or_else(|| config_map.remove(MAX_TRANSACTIONS_IN_BLOCK)),
+            max_pending_transactions: env::var(MAX_PENDING_TRANSACTIONS)
+                .ok()
+                .or_else(|| config_map.remove(MAX_PENDING_TRANSACTIONS)),
+            block_trigger_depth: env::var(BLOCK_TRIGGER_DEPTH)
+                .ok()
+                .or_else(|| config_map.remove(BLOCK_TRIGGER_DEPTH)),
+            produce_empty_blocks: env::var(PRODUCE_EMPTY_BLOCKS)
+                .ok()
+                .or_else(|| config_map.remove(PRODUCE_EMPTY_BLOCKS)),
             mode: env::var(KURA_INIT_MODE)
                 .ok()
                 .or_else(|| config_map.remove(KURA_INIT_MODE))
@@ -101,6 +224,24 @@ impl Configuration {
             kura_block_store_path: env::var(KURA_BLOCK_STORE_PATH)
                 .ok()
                 .or_else(|| config_map.remove(KURA_BLOCK_STORE_PATH)),
+            kura_block_cache_size: env::var(KURA_BLOCK_CACHE_SIZE)
+                .ok()
+                .or_else(|| config_map.remove(KURA_BLOCK_CACHE_SIZE)),
+            kura_max_blocks_in_memory: env::var(KURA_MAX_BLOCKS_IN_MEMORY)
+                .ok()
+                .or_else(|| config_map.remove(KURA_MAX_BLOCKS_IN_MEMORY)),
+            kura_block_store_layout: env::var(KURA_BLOCK_STORE_LAYOUT)
+                .ok()
+                .or_else(|| config_map.remove(KURA_BLOCK_STORE_LAYOUT)),
+            kura_block_store_encryption_key: env::var(KURA_BLOCK_STORE_ENCRYPTION_KEY)
+                .ok()
+                .or_else(|| config_map.remove(KURA_BLOCK_STORE_ENCRYPTION_KEY)),
+            kura_verify_chain_interval_ms: env::var(KURA_VERIFY_CHAIN_INTERVAL_MS)
+                .ok()
+                .or_else(|| config_map.remove(KURA_VERIFY_CHAIN_INTERVAL_MS)),
+            kura_verify_chain_window: env::var(KURA_VERIFY_CHAIN_WINDOW)
+                .ok()
+                .or_else(|| config_map.remove(KURA_VERIFY_CHAIN_WINDOW)),
             trusted_peers: parse_trusted_peers(
                 env::var(TRUSTED_PEERS)
                     .ok()
@@ -121,14 +262,51 @@ impl Configuration {
                     .or_else(|| config_map.remove(IROHA_PRIVATE_KEY))
                     .ok_or("IROHA_PRIVATE_KEY should be set.")?,
             )?,
+            key_algorithm: env::var(KEY_ALGORITHM)
+                .ok()
+                .or_else(|| config_map.remove(KEY_ALGORITHM)),
+            root_public_key: env::var(ROOT_PUBLIC_KEY)
+                .ok()
+                .or_else(|| config_map.remove(ROOT_PUBLIC_KEY)),
             commit_time_ms: env::var(COMMIT_TIME_MS)
                 .ok()
                 .or_else(|| config_map.remove(COMMIT_TIME_MS)),
             tx_receipt_time_ms: env::var(TX_RECEIPT_TIME_MS)
                 .ok()
                 .or_else(|| config_map.remove(TX_RECEIPT_TIME_MS)),
+            max_block_signatures: env::var(MAX_BLOCK_SIGNATURES)
+                .ok()
+                .or_else(|| config_map.remove(MAX_BLOCK_SIGNATURES)),
+            strict_signature_verification: env::var(STRICT_SIGNATURE_VERIFICATION)
+                .ok()
+                .or_else(|| config_map.remove(STRICT_SIGNATURE_VERIFICATION)),
+            tls: env::var(TLS).ok().or_else(|| config_map.remove(TLS)),
+            log_level: env::var(LOG_LEVEL)
+                .ok()
+                .or_else(|| config_map.remove(LOG_LEVEL)),
+            log_format: env::var(LOG_FORMAT)
+                .ok()
+                .or_else(|| config_map.remove(LOG_FORMAT)),
+            max_transaction_cost: env::var(MAX_TRANSACTION_COST)
+                .ok()
+                .or_else(|| config_map.remove(MAX_TRANSACTION_COST)),
+            transaction_fee_asset: env::var(TRANSACTION_FEE_ASSET)
+                .ok()
+                .or_else(|| config_map.remove(TRANSACTION_FEE_ASSET)),
+            torii_transaction_rate: env::var(TORII_TRANSACTION_RATE)
+                .ok()
+                .or_else(|| config_map.remove(TORII_TRANSACTION_RATE)),
+            torii_transaction_burst: env::var(TORII_TRANSACTION_BURST)
+                .ok()
+                .or_else(|| config_map.remove(TORII_TRANSACTION_BURST)),
+            max_transaction_bytes: env::var(MAX_TRANSACTION_BYTES)
+                .ok()
+                .or_else(|| config_map.remove(MAX_TRANSACTION_BYTES)),
+            max_block_bytes: env::var(MAX_BLOCK_BYTES)
+                .ok()
+                .or_else(|| config_map.remove(MAX_BLOCK_BYTES)),
         }
-        .build()?)
+        .build()
     }
 
     /// Set `peer_id` configuration parameter - will overwrite the existing one.
@@ -175,38 +353,91 @@ impl Display for Configuration {
 
 impl Debug for Configuration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let first_half_of_private_key: [u8; 32] = self.private_key[0..32]
-            .try_into()
-            .expect("Wrong format of private key.");
-        let second_half_of_private_key: [u8; 32] = self.private_key[32..64]
-            .try_into()
-            .expect("Wrong format of private key.");
         f.debug_struct("Configuration")
+            .field("config_version", &self.config_version)
             .field("peer_id", &self.peer_id)
+            .field("torii_urls", &self.torii_urls)
             .field("block_build_step_ms", &self.block_build_step_ms)
+            .field("max_transactions_in_block", &self.max_transactions_in_block)
+            .field("max_pending_transactions", &self.max_pending_transactions)
+            .field("block_trigger_depth", &self.block_trigger_depth)
+            .field("produce_empty_blocks", &self.produce_empty_blocks)
             .field("mode", &self.mode)
             .field("kura_block_store_path", &self.kura_block_store_path)
+            .field("kura_block_cache_size", &self.kura_block_cache_size)
+            .field("kura_max_blocks_in_memory", &self.kura_max_blocks_in_memory)
+            .field("kura_block_store_layout", &self.kura_block_store_layout)
+            .field(
+                "kura_block_store_encryption_key",
+                &self.kura_block_store_encryption_key.map(|_| "[redacted]"),
+            )
+            .field(
+                "kura_verify_chain_interval_ms",
+                &self.kura_verify_chain_interval_ms,
+            )
+            .field("kura_verify_chain_window", &self.kura_verify_chain_window)
             .field("trusted_peers", &self.trusted_peers)
             .field("max_faulty_peers", &self.max_faulty_peers)
             .field("public_key", &self.public_key)
-            .field("private_key[0..32]", &first_half_of_private_key)
-            .field("private_key[32..64]", &second_half_of_private_key)
+            .field("private_key", &"[redacted]")
+            .field("key_algorithm", &self.key_algorithm)
+            .field("root_public_key", &self.root_public_key)
             .field("commit_time_ms", &self.commit_time_ms)
+            .field("max_block_signatures", &self.max_block_signatures)
+            .field(
+                "strict_signature_verification",
+                &self.strict_signature_verification,
+            )
+            .field("tls", &self.tls)
+            .field("logger_configuration", &self.logger_configuration)
+            .field("max_transaction_cost", &self.max_transaction_cost)
+            .field(
+                "transaction_fee_asset_definition_id",
+                &self.transaction_fee_asset_definition_id,
+            )
+            .field("torii_transaction_rate", &self.torii_transaction_rate)
+            .field("torii_transaction_burst", &self.torii_transaction_burst)
+            .field("max_transaction_bytes", &self.max_transaction_bytes)
+            .field("max_block_bytes", &self.max_block_bytes)
             .finish()
     }
 }
 
 struct ConfigurationBuilder {
     torii_url: Option<String>,
+    torii_urls: Vec<String>,
     block_build_step_ms: Option<String>,
+    max_transactions_in_block: Option<String>,
+    max_pending_transactions: Option<String>,
+    block_trigger_depth: Option<String>,
+    produce_empty_blocks: Option<String>,
     mode: Option<Mode>,
     kura_block_store_path: Option<String>,
+    kura_block_cache_size: Option<String>,
+    kura_max_blocks_in_memory: Option<String>,
+    kura_block_store_layout: Option<String>,
+    kura_block_store_encryption_key: Option<String>,
+    kura_verify_chain_interval_ms: Option<String>,
+    kura_verify_chain_window: Option<String>,
     trusted_peers: Vec<PeerId>,
     max_faulty_peers: Option<String>,
     public_key: PublicKey,
     private_key: PrivateKey,
+    key_algorithm: Option<String>,
+    root_public_key: Option<String>,
     commit_time_ms: Option<String>,
     tx_receipt_time_ms: Option<String>,
+    max_block_signatures: Option<String>,
+    strict_signature_verification: Option<String>,
+    tls: Option<String>,
+    log_level: Option<String>,
+    log_format: Option<String>,
+    max_transaction_cost: Option<String>,
+    transaction_fee_asset: Option<String>,
+    torii_transaction_rate: Option<String>,
+    torii_transaction_burst: Option<String>,
+    max_transaction_bytes: Option<String>,
+    max_block_bytes: Option<String>,
 }
 
 impl ConfigurationBuilder {
@@ -217,17 +448,76 @@ impl ConfigurationBuilder {
                 .unwrap_or_else(|| DEFAULT_TORII_URL.to_string()),
             public_key: self.public_key,
         };
+        let tls = self
+            .tls
+            .unwrap_or_else(|| DEFAULT_TLS.to_string())
+            .parse()
+            .map_err(|e| format!("TLS parse failed: {}", e))?;
+        if tls && !cfg!(feature = "tls") {
+            return Err("TLS requires Iroha built with the `tls` feature.".to_string());
+        }
         Ok(Configuration {
+            config_version: CURRENT_CONFIG_VERSION,
             peer_id,
+            torii_urls: self.torii_urls,
             block_build_step_ms: self
                 .block_build_step_ms
                 .unwrap_or_else(|| DEFAULT_BLOCK_TIME_MS.to_string())
                 .parse()
                 .expect("Block build step should be a number."),
+            max_transactions_in_block: self
+                .max_transactions_in_block
+                .unwrap_or_else(|| DEFAULT_MAX_TRANSACTIONS_IN_BLOCK.to_string())
+                .parse()
+                .map_err(|e| format!("Max transactions in block parse failed: {}", e))?,
+            max_pending_transactions: self
+                .max_pending_transactions
+                .unwrap_or_else(|| DEFAULT_MAX_PENDING_TRANSACTIONS.to_string())
+                .parse()
+                .map_err(|e| format!("Max pending transactions parse failed: {}", e))?,
+            block_trigger_depth: self
+                .block_trigger_depth
+                .map(|block_trigger_depth| block_trigger_depth.parse())
+                .transpose()
+                .map_err(|e| format!("Block trigger depth parse failed: {}", e))?,
+            produce_empty_blocks: self
+                .produce_empty_blocks
+                .unwrap_or_else(|| DEFAULT_PRODUCE_EMPTY_BLOCKS.to_string())
+                .parse()
+                .map_err(|e| format!("Produce empty blocks parse failed: {}", e))?,
             mode: self.mode.unwrap_or_else(|| DEFAULT_KURA_INIT_MODE),
             kura_block_store_path: self
                 .kura_block_store_path
                 .unwrap_or_else(|| DEFAULT_KURA_BLOCK_STORE_PATH.to_string()),
+            kura_block_cache_size: self
+                .kura_block_cache_size
+                .unwrap_or_else(|| DEFAULT_KURA_BLOCK_CACHE_SIZE.to_string())
+                .parse()
+                .map_err(|e| format!("Kura block cache size parse failed: {}", e))?,
+            kura_max_blocks_in_memory: self
+                .kura_max_blocks_in_memory
+                .map(|max_blocks_in_memory| max_blocks_in_memory.parse())
+                .transpose()
+                .map_err(|e| format!("Kura max blocks in memory parse failed: {}", e))?,
+            kura_block_store_layout: self
+                .kura_block_store_layout
+                .map(|layout| layout.parse())
+                .transpose()?
+                .unwrap_or(DEFAULT_KURA_BLOCK_STORE_LAYOUT),
+            kura_block_store_encryption_key: self
+                .kura_block_store_encryption_key
+                .map(|key| parse_encryption_key(&key))
+                .transpose()?,
+            kura_verify_chain_interval_ms: self
+                .kura_verify_chain_interval_ms
+                .map(|interval_ms| interval_ms.parse())
+                .transpose()
+                .map_err(|e| format!("Kura verify chain interval parse failed: {}", e))?,
+            kura_verify_chain_window: self
+                .kura_verify_chain_window
+                .unwrap_or_else(|| DEFAULT_KURA_VERIFY_CHAIN_WINDOW.to_string())
+                .parse()
+                .map_err(|e| format!("Kura verify chain window parse failed: {}", e))?,
             trusted_peers: self.trusted_peers,
             max_faulty_peers: self
                 .max_faulty_peers
@@ -236,6 +526,16 @@ impl ConfigurationBuilder {
                 .map_err(|e| format!("Max faulty peers parse failed: {}", e))?,
             public_key: self.public_key,
             private_key: self.private_key,
+            key_algorithm: self
+                .key_algorithm
+                .map(|algorithm| algorithm.parse())
+                .transpose()?
+                .unwrap_or(DEFAULT_KEY_ALGORITHM),
+            root_public_key: self
+                .root_public_key
+                .map(|public_key| parse_public_key(&public_key))
+                .transpose()?
+                .unwrap_or(self.public_key),
             commit_time_ms: self
                 .commit_time_ms
                 .unwrap_or_else(|| DEFAULT_COMMIT_TIME_MS.to_string())
@@ -246,11 +546,131 @@ impl ConfigurationBuilder {
                 .unwrap_or_else(|| DEFAULT_TX_RECEIPT_TIME_MS.to_string())
                 .parse()
                 .expect("Tx receipt time should be a number."),
+            max_block_signatures: self
+                .max_block_signatures
+                .unwrap_or_else(|| DEFAULT_MAX_BLOCK_SIGNATURES.to_string())
+                .parse()
+                .map_err(|e| format!("Max block signatures parse failed: {}", e))?,
+            strict_signature_verification: self
+                .strict_signature_verification
+                .unwrap_or_else(|| DEFAULT_STRICT_SIGNATURE_VERIFICATION.to_string())
+                .parse()
+                .map_err(|e| format!("Strict signature verification parse failed: {}", e))?,
+            tls,
+            logger_configuration: LoggerConfiguration::parse(
+                &self
+                    .log_level
+                    .unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string()),
+                &self
+                    .log_format
+                    .unwrap_or_else(|| DEFAULT_LOG_FORMAT.to_string()),
+            )?,
+            max_transaction_cost: self
+                .max_transaction_cost
+                .unwrap_or_else(|| DEFAULT_MAX_TRANSACTION_COST.to_string())
+                .parse()
+                .map_err(|e| format!("Max transaction cost parse failed: {}", e))?,
+            transaction_fee_asset_definition_id: self
+                .transaction_fee_asset
+                .map(|asset_definition_id| asset_definition_id.parse())
+                .transpose()
+                .map_err(|e| format!("Transaction fee asset parse failed: {}", e))?,
+            torii_transaction_rate: self
+                .torii_transaction_rate
+                .unwrap_or_else(|| DEFAULT_TRANSACTION_RATE.to_string())
+                .parse()
+                .map_err(|e| format!("Torii transaction rate parse failed: {}", e))?,
+            torii_transaction_burst: self
+                .torii_transaction_burst
+                .unwrap_or_else(|| DEFAULT_TRANSACTION_BURST.to_string())
+                .parse()
+                .map_err(|e| format!("Torii transaction burst parse failed: {}", e))?,
+            max_transaction_bytes: self
+                .max_transaction_bytes
+                .unwrap_or_else(|| DEFAULT_MAX_TRANSACTION_BYTES.to_string())
+                .parse()
+                .map_err(|e| format!("Max transaction bytes parse failed: {}", e))?,
+            max_block_bytes: self
+                .max_block_bytes
+                .unwrap_or_else(|| DEFAULT_MAX_BLOCK_BYTES.to_string())
+                .parse()
+                .map_err(|e| format!("Max block bytes parse failed: {}", e))?,
         })
     }
 }
 
+/// Upgrades configuration entries of an older format version to `CURRENT_CONFIG_VERSION`.
+///
+/// Files without `CONFIG_VERSION` are treated as version 1. Parameters added since then get their
+/// default values written explicitly, so the migrated entries describe the same peer as before.
+///
+/// # Errors
+/// Fails if the version is not a number, is newer than the current one or is unknown.
+pub fn migrate(mut config_map: HashMap<String, String>) -> Result<HashMap<String, String>, String> {
+    let version = match config_map.get(CONFIG_VERSION) {
+        Some(version) => version
+            .parse()
+            .map_err(|e| format!("Configuration version parse failed: {}", e))?,
+        None => LEGACY_CONFIG_VERSION,
+    };
+    match version {
+        CURRENT_CONFIG_VERSION => (),
+        LEGACY_CONFIG_VERSION => {
+            for (key, default) in &[
+                (
+                    KURA_BLOCK_CACHE_SIZE,
+                    DEFAULT_KURA_BLOCK_CACHE_SIZE.to_string(),
+                ),
+                (
+                    MAX_TRANSACTION_COST,
+                    DEFAULT_MAX_TRANSACTION_COST.to_string(),
+                ),
+                (TORII_TRANSACTION_RATE, DEFAULT_TRANSACTION_RATE.to_string()),
+                (
+                    TORII_TRANSACTION_BURST,
+                    DEFAULT_TRANSACTION_BURST.to_string(),
+                ),
+            ] {
+                config_map
+                    .entry((*key).to_string())
+                    .or_insert_with(|| default.clone());
+            }
+        }
+        version if version > CURRENT_CONFIG_VERSION => {
+            return Err(format!(
+                "Configuration version {} is newer than the supported version {}, please upgrade Iroha.",
+                version, CURRENT_CONFIG_VERSION
+            ))
+        }
+        version => {
+            return Err(format!(
+                "Configuration version {} is unknown and can not be migrated to version {}.",
+                version, CURRENT_CONFIG_VERSION
+            ))
+        }
+    }
+    config_map.insert(
+        CONFIG_VERSION.to_string(),
+        CURRENT_CONFIG_VERSION.to_string(),
+    );
+    Ok(config_map)
+}
+
 /// Parses string formatted as "[address1, address2, ...]" into `Vec<PeerId>`.
+/// Parses string formatted as `["address1", "address2", ...]` into a list of addresses.
+fn parse_torii_urls(torii_urls_string: Option<String>) -> Vec<String> {
+    match torii_urls_string {
+        None => Vec::new(),
+        Some(torii_urls_string) => torii_urls_string
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|url| url.trim().trim_matches('"').to_string())
+            .filter(|url| !url.is_empty())
+            .collect(),
+    }
+}
+
 fn parse_trusted_peers(trusted_peers_string: Option<String>) -> Result<Vec<PeerId>, String> {
     match trusted_peers_string {
         None => Ok(Vec::new()),
@@ -304,9 +724,21 @@ fn parse_public_key(public_key_string: &str) -> Result<PublicKey, String> {
         .split(',')
         .map(|byte| byte.trim().parse::<u8>().expect("Failed to parse byte."))
         .collect();
+    crypto::public_key_from_bytes(vector)
+}
+
+/// Parses string formatted as "[ byte1, byte2, ... ]" into `BlockEncryptionKey`.
+fn parse_encryption_key(encryption_key_string: &str) -> Result<BlockEncryptionKey, String> {
+    let vector = encryption_key_string
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|byte| byte.trim().parse::<u8>())
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|e| format!("Failed to parse block store encryption key: {}", e))?;
     vector[..]
         .try_into()
-        .map_err(|e| format!("Public key should be 32 bytes long: {}", e))
+        .map_err(|e| format!("Block store encryption key should be 32 bytes long: {}", e))
 }
 
 /// Parses string formatted as "[ byte1, byte2, ... ]" into `crypto::PrivateKey`.
@@ -333,6 +765,7 @@ impl From<&str> for Mode {
         match string_mode {
             "strict" => Mode::Strict,
             "fast" => Mode::Fast,
+            "recovery" => Mode::Recovery,
             other => {
                 eprintln!("Defined unexpected Kura Mode: {}", other);
                 Mode::Strict
@@ -379,6 +812,100 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn private_key_is_redacted_in_debug_output() {
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to read configuration.");
+        let output = format!("{:?}", configuration);
+        assert!(output.contains("private_key: \"[redacted]\""));
+        assert!(!output.contains(&format!("{:?}", &configuration.private_key[..32])));
+    }
+
+    fn write_configuration(directory: &Path, version: Option<u32>) -> std::path::PathBuf {
+        let content = fs::read_to_string(CONFIGURATION_PATH)
+            .expect("Failed to read configuration.")
+            .lines()
+            .filter(|line| !line.contains(CONFIG_VERSION))
+            .map(|line| match (line.trim(), version) {
+                ("{", Some(version)) => format!("{{\n  \"{}\": {},", CONFIG_VERSION, version),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        let path = directory.join("config.json");
+        fs::write(&path, content).expect("Failed to write configuration.");
+        path
+    }
+
+    #[test]
+    fn legacy_configuration_is_migrated_to_current_version() {
+        let directory = tempfile::tempdir().expect("Failed to create temp dir.");
+        let path = write_configuration(directory.path(), None);
+        let configuration =
+            Configuration::from_path(&path).expect("Failed to load legacy configuration.");
+        assert_eq!(CURRENT_CONFIG_VERSION, configuration.config_version);
+        assert_eq!("127.0.0.1:1338", configuration.peer_id.address);
+        assert_eq!(
+            DEFAULT_KURA_BLOCK_CACHE_SIZE,
+            configuration.kura_block_cache_size
+        );
+        assert_eq!(
+            DEFAULT_MAX_TRANSACTION_COST,
+            configuration.max_transaction_cost
+        );
+        let path = write_configuration(directory.path(), Some(LEGACY_CONFIG_VERSION));
+        assert_eq!(
+            CURRENT_CONFIG_VERSION,
+            Configuration::from_path(&path)
+                .expect("Failed to load version 1 configuration.")
+                .config_version
+        );
+    }
+
+    #[test]
+    fn configuration_of_unknown_version_is_rejected() {
+        let directory = tempfile::tempdir().expect("Failed to create temp dir.");
+        for version in &[CURRENT_CONFIG_VERSION + 1, 0] {
+            let path = write_configuration(directory.path(), Some(*version));
+            let error = Configuration::from_path(&path)
+                .expect_err("Configuration of unknown version should be rejected.");
+            assert!(error.contains(&version.to_string()));
+        }
+    }
+
+    #[test]
+    fn tls_is_enabled_only_with_tls_feature() {
+        let directory = tempfile::tempdir().expect("Failed to create temp dir.");
+        let content = fs::read_to_string(CONFIGURATION_PATH)
+            .expect("Failed to read configuration.")
+            .replacen('{', &format!("{{\n  \"{}\": true,", TLS), 1);
+        let path = directory.path().join("config.json");
+        fs::write(&path, content).expect("Failed to write configuration.");
+        let configuration = Configuration::from_path(&path);
+        if cfg!(feature = "tls") {
+            assert!(configuration.expect("Failed to load configuration.").tls);
+        } else {
+            assert!(configuration
+                .expect_err("TLS should be rejected without the feature.")
+                .contains("tls"));
+        }
+        assert!(
+            !Configuration::from_path(CONFIGURATION_PATH)
+                .expect("Failed to load configuration.")
+                .tls
+        );
+    }
+
+    #[test]
+    fn parse_torii_urls_list() {
+        assert_eq!(
+            vec!["127.0.0.1:1337".to_string(), "127.0.0.1:1338".to_string()],
+            parse_torii_urls(Some(r#"["127.0.0.1:1337", "127.0.0.1:1338"]"#.to_string()))
+        );
+        assert!(parse_torii_urls(Some("[]".to_string())).is_empty());
+        assert!(parse_torii_urls(None).is_empty());
+    }
+
     #[test]
     fn parse_public_key_success() {
         let public_key_string = "[101, 170, 80, 164, 103, 38, 73, 61, 223, 133, 83, 139, 247, 77, 176, 84, 117, 15, 22, 28, 155, 125, 80, 226, 40, 26, 61, 248, 40, 159, 58, 53]";
@@ -1,19 +1,26 @@
 //! This module contains structures and implementations related to the cryptographic parts of the
 //! Iroha.
+//!
+//! Cryptographic primitives are provided by `ursa` by default or by pure Rust crates if the
+//! `pure-rust` feature is enabled. Both backends produce identical keys, signatures and hashes.
+//!
+//! Ed25519 implementations disagree on some degenerate inputs, so signatures are verified in the
+//! strict mode by default, see `set_strict_verification`.
+use async_std::task;
+use curve25519_dalek::{edwards::CompressedEdwardsY, scalar::Scalar};
 use parity_scale_codec::{Decode, Encode};
 use std::{
-    convert::TryInto,
-    fmt::{self, Debug, Formatter},
-};
-use ursa::{
-    blake2::{
-        digest::{Input, VariableOutput},
-        VarBlake2b,
-    },
-    keys::{KeyGenOption, PrivateKey as UrsaPrivateKey, PublicKey as UrsaPublicKey},
-    signatures::{ed25519::Ed25519Sha512, SignatureScheme, Signer},
+    borrow::Borrow,
+    collections::BTreeSet,
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
 };
 
+#[cfg(not(any(feature = "ursa", feature = "pure-rust")))]
+compile_error!("Either `ursa` or `pure-rust` feature should be enabled.");
+
 /// Represents hash of Iroha entities like `Block` or `Transaction.
 pub type Hash = [u8; 32];
 /// Public Key used in signatures.
@@ -21,46 +28,485 @@ pub type PublicKey = [u8; 32];
 /// Private Key used in signatures.
 pub type PrivateKey = [u8; 64];
 type Ed25519Signature = [u8; 64];
+const ED25519_SIGNATURE_LENGTH: usize = 64;
+const MAX_HASH_LENGTH: usize = 64;
+/// Key of the HMAC deriving the master key of the ed25519 curve from a seed in SLIP-0010.
+const SLIP10_ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+const HMAC_SHA512_BLOCK_SIZE: usize = 128;
+/// Child key indices starting from this one are hardened, SLIP-0010 defines only hardened
+/// derivation for ed25519.
+pub const HARDENED_INDEX: u32 = 0x8000_0000;
+
+/// Signature schemes this peer accepts for account and peer keys.
+pub const ALLOWED_ALGORITHMS: &[Algorithm] = &[Algorithm::Ed25519];
+
+static STRICT_VERIFICATION: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable the strict verification of Ed25519 signatures in this process, it is
+/// enabled by default.
+///
+/// Strict verification rejects public keys and signature `R` points of small order, public keys
+/// with non-canonical encodings and signatures with `S` not reduced modulo the group order. Such
+/// signatures are forged or malleated and some implementations accept them while others do not.
+/// All peers of a network should use the same mode, otherwise they can disagree on the validity
+/// of a transaction or a block. Disabling it is only needed to accept signatures of clients
+/// which produce non-canonical signatures, peers of such a network will accept everything
+/// the backend accepts.
+pub fn set_strict_verification(strict: bool) {
+    STRICT_VERIFICATION.store(strict, Ordering::Relaxed);
+}
+
+/// Domain separation tags, prepended to the payload before signing so that a signature created
+/// for one kind of data can not be replayed as a signature of another kind.
+pub mod context {
+    /// Context of the transactions signatures.
+    pub const TRANSACTION: &[u8] = b"iroha-tx-v1";
+    /// Context of the blocks signatures.
+    pub const BLOCK: &[u8] = b"iroha-block-v1";
+    /// Context of the query requests signatures.
+    pub const QUERY: &[u8] = b"iroha-query-v1";
+}
+
+/// Recoverable ECDSA signatures over the secp256k1 curve for the interoperability with Ethereum
+/// compatible chains, e.g. by bridges. The public key of the signer is recovered from the
+/// signature and the message, so it does not have to be sent along.
+///
+/// Messages are hashed with Keccak-256 before signing, the same way Ethereum does.
+pub mod secp256k1 {
+    use k256::ecdsa::{recoverable, signature::Signer, SigningKey};
+    use std::convert::TryFrom;
+
+    /// SEC1 compressed public key.
+    pub type PublicKey = [u8; 33];
+    /// Private key scalar.
+    pub type PrivateKey = [u8; 32];
+    /// Recoverable signature, `r` and `s` followed by the recovery id.
+    pub type RecoverableSignature = [u8; 65];
+
+    /// Generates a pair of Public and Private key.
+    /// Returns `Err(String)` with error message if failed.
+    pub fn generate_key_pair() -> Result<(PublicKey, PrivateKey), String> {
+        loop {
+            let private_key: PrivateKey = rand::random();
+            // Almost every random scalar is a valid key, the rest are retried.
+            if let Ok(public_key) = public_key(&private_key) {
+                return Ok((public_key, private_key));
+            }
+        }
+    }
+
+    /// Constructs a `PublicKey` from the raw `payload`.
+    ///
+    /// # Errors
+    /// Fails if the `payload` is not as long as compressed secp256k1 public keys.
+    pub fn public_key_from_bytes(payload: Vec<u8>) -> Result<PublicKey, String> {
+        super::Algorithm::Secp256k1.check_public_key_length(&payload)?;
+        let mut public_key = [0; 33];
+        public_key.copy_from_slice(&payload);
+        Ok(public_key)
+    }
+
+    /// Public key of the `private_key`.
+    pub fn public_key(private_key: &PrivateKey) -> Result<PublicKey, String> {
+        let signing_key = SigningKey::from_bytes(private_key)
+            .map_err(|e| format!("Invalid secp256k1 private key: {}", e))?;
+        compress(&signing_key.verifying_key())
+    }
+
+    /// Sign the Keccak-256 digest of the `message`, the signature is 65 bytes long.
+    ///
+    /// The nonce is derived from the key and the digest as specified by RFC 6979, so signing
+    /// the same message with the same key gives the same signature.
+    pub fn sign_recoverable(
+        message: &[u8],
+        private_key: &PrivateKey,
+    ) -> Result<RecoverableSignature, String> {
+        let signing_key = SigningKey::from_bytes(private_key)
+            .map_err(|e| format!("Invalid secp256k1 private key: {}", e))?;
+        let signature: recoverable::Signature = signing_key
+            .try_sign(message)
+            .map_err(|e| format!("Failed to sign message: {}", e))?;
+        let mut bytes = [0; 65];
+        bytes.copy_from_slice(signature.as_ref());
+        Ok(bytes)
+    }
+
+    /// Recover the public key which signed the `message` with the `signature`.
+    ///
+    /// A signature of another message or a corrupted signature either fails to recover or
+    /// recovers a different key, so the result should be compared with the expected signer.
+    pub fn recover_public_key(
+        signature: &RecoverableSignature,
+        message: &[u8],
+    ) -> Result<PublicKey, String> {
+        let signature = recoverable::Signature::try_from(&signature[..])
+            .map_err(|e| format!("Invalid recoverable signature: {}", e))?;
+        let verifying_key = signature
+            .recover_verify_key(message)
+            .map_err(|e| format!("Failed to recover public key: {}", e))?;
+        compress(&verifying_key)
+    }
+
+    fn compress(verifying_key: &k256::ecdsa::VerifyingKey) -> Result<PublicKey, String> {
+        PublicKey::try_from(&verifying_key.to_bytes()[..])
+            .map_err(|_| "Compressed secp256k1 public key should be 33 bytes.".to_string())
+    }
+}
+
+/// Constructs an Ed25519 `PublicKey` from the raw `payload`.
+///
+/// # Errors
+/// Fails if the `payload` is not as long as Ed25519 public keys.
+pub fn public_key_from_bytes(payload: Vec<u8>) -> Result<PublicKey, String> {
+    Algorithm::Ed25519.check_public_key_length(&payload)?;
+    let mut public_key = [0; 32];
+    public_key.copy_from_slice(&payload);
+    Ok(public_key)
+}
 
 /// Generates a pair of Public and Private key.
 /// Returns `Err(String)` with error message if failed.
 pub fn generate_key_pair() -> Result<(PublicKey, PrivateKey), String> {
-    let (public_key, ursa_private_key) = Ed25519Sha512
-        .keypair(Option::None)
-        .map_err(|e| format!("Failed to generate Ed25519Sha512 key pair: {}", e))?;
-    let public_key = public_key[..]
-        .try_into()
-        .map_err(|e| format!("Public key should be [u8;32]: {}", e))?;
-    let mut private_key = [0; 64];
-    private_key.copy_from_slice(ursa_private_key.as_ref());
-    Ok((public_key, private_key))
+    backend::generate_key_pair(None)
 }
 
 /// Generates a determined pair of Public and Private key from the given seed.
 /// Returns `Err(String)` with error message if failed.
 pub fn generate_key_pair_from_seed(seed: Hash) -> Result<(PublicKey, PrivateKey), String> {
-    let (public_key, ursa_private_key) = Ed25519Sha512
-        .keypair(Some(KeyGenOption::UseSeed(seed.to_vec())))
-        .map_err(|e| format!("Failed to generate Ed25519Sha512 key pair: {}", e))?;
-    let public_key = public_key[..]
-        .try_into()
-        .map_err(|e| format!("Public key should be [u8;32]: {}", e))?;
-    let mut private_key = [0; 64];
-    private_key.copy_from_slice(ursa_private_key.as_ref());
-    Ok((public_key, private_key))
+    backend::generate_key_pair(Some(seed))
+}
+
+/// Ed25519 key pair with a chain code, used for hierarchical deterministic derivation of child
+/// key pairs as defined by SLIP-0010.
+#[derive(Clone)]
+pub struct ExtendedKeyPair {
+    /// Public key of the pair.
+    pub public_key: PublicKey,
+    /// Private key of the pair.
+    pub private_key: PrivateKey,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKeyPair {
+    /// Derive the master key pair from the `seed`.
+    pub fn from_seed(seed: &[u8]) -> Result<ExtendedKeyPair, String> {
+        ExtendedKeyPair::from_hmac(hmac_sha512(SLIP10_ED25519_SEED_KEY, seed))
+    }
+
+    /// Derive the key pair from the `seed` along the `path` like `m/44'/0'/0'`.
+    ///
+    /// Returns `Err(String)` if the path is malformed or has non-hardened indices.
+    pub fn from_seed_path(seed: &[u8], path: &str) -> Result<ExtendedKeyPair, String> {
+        let mut segments = path.split('/');
+        if segments.next() != Some("m") {
+            return Err(format!("Derivation path {} should start with `m`.", path));
+        }
+        segments.try_fold(ExtendedKeyPair::from_seed(seed)?, |key_pair, segment| {
+            let index = segment
+                .strip_suffix('\'')
+                .or_else(|| segment.strip_suffix('H'))
+                .ok_or(format!(
+                    "Index {} of the derivation path is not hardened.",
+                    segment
+                ))?
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < HARDENED_INDEX)
+                .ok_or(format!("Invalid index {} of the derivation path.", segment))?;
+            key_pair.derive_child(index + HARDENED_INDEX)
+        })
+    }
+
+    /// Derive the child key pair at the hardened `index`.
+    ///
+    /// Returns `Err(String)` if the `index` is below `HARDENED_INDEX`.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedKeyPair, String> {
+        if index < HARDENED_INDEX {
+            return Err(format!(
+                "Only hardened child keys can be derived for ed25519, but got index {}.",
+                index
+            ));
+        }
+        let mut data = Vec::with_capacity(37);
+        data.push(0);
+        data.extend_from_slice(&self.private_key[..32]);
+        data.extend_from_slice(&index.to_be_bytes());
+        ExtendedKeyPair::from_hmac(hmac_sha512(&self.chain_code, &data))
+    }
+
+    /// Chain code used to derive child key pairs.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    fn from_hmac(output: [u8; 64]) -> Result<ExtendedKeyPair, String> {
+        let mut secret_key = [0; 32];
+        secret_key.copy_from_slice(&output[..32]);
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&output[32..]);
+        let (public_key, private_key) = backend::key_pair_from_secret_key(secret_key)?;
+        Ok(ExtendedKeyPair {
+            public_key,
+            private_key,
+            chain_code,
+        })
+    }
+}
+
+impl Debug for ExtendedKeyPair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedKeyPair")
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    use backend::{Digest, Sha512};
+    let mut block_key = [0; HMAC_SHA512_BLOCK_SIZE];
+    if key.len() > HMAC_SHA512_BLOCK_SIZE {
+        block_key[..64].copy_from_slice(&Sha512::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| {
+        block_key
+            .iter()
+            .map(|key_byte| key_byte ^ byte)
+            .collect::<Vec<u8>>()
+    };
+    let inner = Sha512::new().chain(pad(0x36)).chain(data).result();
+    let outer = Sha512::new().chain(pad(0x5c)).chain(inner).result();
+    let mut output = [0; 64];
+    output.copy_from_slice(&outer);
+    output
 }
 
 /// Calculates hash of the given bytes.
 pub fn hash(bytes: Vec<u8>) -> Hash {
-    let vec_hash = VarBlake2b::new(32)
-        .expect("Failed to initialize variable size hash")
+    hash_n::<32>(&bytes).expect("Failed to initialize variable size hash")
+}
+
+/// Calculate Blake2b hash of `N` bytes length.
+///
+/// Returns `Err(String)` if `N` is out of the range supported by Blake2b: from 1 to 64 bytes.
+pub fn hash_n<const N: usize>(bytes: &[u8]) -> Result<[u8; N], String> {
+    use backend::{Input, VarBlake2b, VariableOutput};
+    if N == 0 || N > MAX_HASH_LENGTH {
+        return Err(format!(
+            "Blake2b output length should be from 1 to {} bytes, but got {}.",
+            MAX_HASH_LENGTH, N
+        ));
+    }
+    let vec_hash = VarBlake2b::new(N)
+        .map_err(|_| format!("Failed to initialize variable size hash of {} bytes.", N))?
         .chain(bytes)
         .vec_result();
-    let mut hash = [0; 32];
+    let mut hash = [0; N];
     hash.copy_from_slice(&vec_hash);
-    hash
+    Ok(hash)
+}
+
+/// Lowercase hex form of a `Hash` for log lines and file names, so they can be correlated with
+/// each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashHex(pub Hash);
+
+impl Display for HashHex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for HashHex {
+    type Err = String;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        let mut hash = [0; 32];
+        if hex.len() != hash.len() * 2 || !hex.is_ascii() {
+            return Err(format!(
+                "Hash should be {} hex digits, got: {}.",
+                hash.len() * 2,
+                hex
+            ));
+        }
+        for (byte, digits) in hash.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits).map_err(|e| e.to_string())?;
+            *byte = u8::from_str_radix(digits, 16)
+                .map_err(|e| format!("Hash is not a hex string {}: {}", hex, e))?;
+        }
+        Ok(HashHex(hash))
+    }
+}
+
+/// Signature scheme of a key pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum Algorithm {
+    /// Ed25519 (Edwards-curve Digital Signature Algorithm scheme using SHA-512 and Curve25519).
+    Ed25519,
+    /// ECDSA over the secp256k1 curve.
+    Secp256k1,
+    /// ECDSA over the secp256k1 curve with recoverable signatures of Keccak-256 digests, as used
+    /// by Ethereum, see `secp256k1`.
+    Secp256k1Recoverable,
+}
+
+impl Algorithm {
+    /// Checks that the algorithm is one of the `allowed_algorithms`, e.g. reported by a peer.
+    ///
+    /// # Errors
+    /// Fails with a message naming both the algorithm and the allowed ones otherwise.
+    pub fn check_allowed(self, allowed_algorithms: &[Algorithm]) -> Result<(), String> {
+        if allowed_algorithms.contains(&self) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Key algorithm {} is not allowed by the peer, allowed algorithms: {}.",
+                self,
+                allowed_algorithms
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+    }
+
+    /// Length in bytes of the public keys of the algorithm, secp256k1 keys are compressed.
+    pub fn public_key_length(self) -> usize {
+        match self {
+            Algorithm::Ed25519 => 32,
+            Algorithm::Secp256k1 | Algorithm::Secp256k1Recoverable => 33,
+        }
+    }
+
+    /// Checks that the `payload` is as long as the public keys of the algorithm, so keys of
+    /// a mismatched length are rejected before reaching the signature backend.
+    pub fn check_public_key_length(self, payload: &[u8]) -> Result<(), String> {
+        if payload.len() == self.public_key_length() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Public key of {} should be {} bytes long, got {} bytes.",
+                self,
+                self.public_key_length(),
+                payload.len()
+            ))
+        }
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Algorithm::Ed25519 => write!(f, "ed25519"),
+            Algorithm::Secp256k1 => write!(f, "secp256k1"),
+            Algorithm::Secp256k1Recoverable => write!(f, "secp256k1-recoverable"),
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(algorithm: &str) -> Result<Self, Self::Err> {
+        match algorithm {
+            "ed25519" => Ok(Algorithm::Ed25519),
+            "secp256k1" => Ok(Algorithm::Secp256k1),
+            "secp256k1-recoverable" => Ok(Algorithm::Secp256k1Recoverable),
+            other => Err(format!(
+                "Unknown key algorithm: {}, expected `ed25519`, `secp256k1` or \
+                 `secp256k1-recoverable`.",
+                other
+            )),
+        }
+    }
+}
+
+/// Source of Ed25519 signatures made by a single key.
+///
+/// Implement it to keep the private key out of the process, e.g. in an HSM or a remote key
+/// management service.
+pub trait Signer {
+    /// Public key of the signatures.
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign the `payload`, returns the 64 bytes of the Ed25519 signature.
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// Key pair kept in memory.
+///
+/// The private key is redacted in `Debug` output and compared in constant time.
+#[derive(Clone)]
+pub struct KeyPair {
+    /// Public key of the pair.
+    pub public_key: PublicKey,
+    private_key: PrivateKey,
+}
+
+impl KeyPair {
+    /// Default `KeyPair` constructor.
+    pub fn new(public_key: PublicKey, private_key: PrivateKey) -> KeyPair {
+        KeyPair {
+            public_key,
+            private_key,
+        }
+    }
+
+    /// Raw bytes of the private key, available only with the `expose-secret` feature.
+    #[cfg(feature = "expose-secret")]
+    pub fn expose_secret(&self) -> &PrivateKey {
+        &self.private_key
+    }
+}
+
+impl Debug for KeyPair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("public_key", &self.public_key)
+            .field(
+                "private_key",
+                &format_args!("[REDACTED {} bytes]", self.private_key.len()),
+            )
+            .finish()
+    }
+}
+
+impl PartialEq for KeyPair {
+    fn eq(&self, other: &Self) -> bool {
+        // Both comparisons are done to keep the timing independent of which key differs.
+        let public_keys_eq = self.public_key == other.public_key;
+        private_keys_eq(&self.private_key, &other.private_key) & public_keys_eq
+    }
 }
 
+impl Eq for KeyPair {}
+
+/// Compare private keys in constant time, so the time taken does not reveal the matching prefix.
+pub fn private_keys_eq(left: &PrivateKey, right: &PrivateKey) -> bool {
+    left.iter()
+        .zip(right.iter())
+        .fold(0u8, |difference, (left, right)| difference | (left ^ right))
+        == 0
+}
+
+impl Signer for KeyPair {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, String> {
+        backend::sign(payload, &self.private_key).map(|signature| signature.to_vec())
+    }
+}
+
+/// Maximum size of a SCALE encoded `Signature`: the public key and the signature.
+pub const MAX_ENCODED_SIGNATURE_LEN: usize = 32 + 64;
+
 /// Represents signature of the data (`Block` or `Transaction` for example).
 #[derive(Clone, Encode, Decode)]
 pub struct Signature {
@@ -69,40 +515,269 @@ pub struct Signature {
     pub public_key: PublicKey,
     /// Ed25519 signature is placed here.
     signature: Ed25519Signature,
+    /// Hash of the signed payload, set by `Signature::new` and checked by `verify`, so a
+    /// signature passed along with the wrong payload is caught early in the same process.
+    ///
+    /// It is kept in memory only: the field is not encoded, so every decoded signature, e.g.
+    /// received over the network or read from the stored blocks, has `None` here. It can not be
+    /// used to audit stored signatures.
+    #[codec(skip)]
+    pub payload_hash: Option<Hash>,
 }
 
 impl Signature {
-    /// Creates new `Signature` by signing payload via `private_key`.
-    pub fn new(
-        public_key: PublicKey,
-        payload: &[u8],
-        private_key: &PrivateKey,
-    ) -> Result<Signature, String> {
-        let private_key = UrsaPrivateKey(private_key.to_vec());
-        let transaction_signature = Signer::new(&Ed25519Sha512, &private_key)
-            .sign(payload)
-            .map_err(|e| format!("Failed to sign payload: {}", e))?;
-        let mut signature = [0; 64];
-        signature.copy_from_slice(&transaction_signature);
+    /// Creates new `Signature` by signing payload via the `signer`, e.g. a `KeyPair`.
+    pub fn new(signer: &dyn Signer, payload: &[u8]) -> Result<Signature, String> {
+        let signature = signer.sign(payload)?;
         Ok(Signature {
-            public_key,
-            signature,
+            public_key: signer.public_key(),
+            signature: ed25519_signature(&signature).ok_or(format!(
+                "Ed25519 signature should be {} bytes long, but signer returned {}.",
+                ED25519_SIGNATURE_LENGTH,
+                signature.len()
+            ))?,
+            payload_hash: Some(hash(payload.to_vec())),
         })
     }
 
-    /// Verify `message` using signed data and `public_key`.
+    /// Creates new `Signature` by signing payload tagged with the `context` via the `signer`.
+    pub fn new_with_context(
+        signer: &dyn Signer,
+        context: &[u8],
+        payload: &[u8],
+    ) -> Result<Signature, String> {
+        Signature::new(signer, &with_context(context, payload))
+    }
+
+    /// Creates new `Signature` like `new`, but signs on a thread of the blocking pool, so the
+    /// curve operations do not hold up other tasks of the executor.
+    pub async fn new_async<S: Signer + Send + 'static>(
+        signer: S,
+        payload: Vec<u8>,
+    ) -> Result<Signature, String> {
+        task::spawn_blocking(move || Signature::new(&signer, &payload)).await
+    }
+
+    /// Verify `message` like `verify`, but on a thread of the blocking pool, so the curve
+    /// operations do not hold up other tasks of the executor.
+    pub async fn verify_async(self, message: Vec<u8>) -> Result<(), String> {
+        task::spawn_blocking(move || self.verify(&message)).await
+    }
+
+    /// Verify `message` tagged with the `context` using signed data and `public_key`.
+    pub fn verify_with_context(&self, context: &[u8], message: &[u8]) -> Result<(), String> {
+        self.verify(&with_context(context, message))
+    }
+
+    /// Verify `message` using signed data and `public_key`. If the signature carries the payload
+    /// hash, the `message` should match it too.
+    ///
+    /// Signatures created without a context can only be verified this way.
     pub fn verify(&self, message: &[u8]) -> Result<(), String> {
-        Ed25519Sha512::new()
-            .verify(
-                message,
-                &self.signature,
-                &UrsaPublicKey(self.public_key.to_vec()),
-            )
-            .map_err(|e| e.to_string())
-            .map(|_| ())
+        if let Some(payload_hash) = self.payload_hash {
+            if hash(message.to_vec()) != payload_hash {
+                return Err("Message does not match the signed payload hash.".to_string());
+            }
+        }
+        verify_ed25519(message, &self.signature, &self.public_key)
     }
 }
 
+/// Signatures of the `payload` made by the `allowed` keys, e.g. of the current validators.
+///
+/// Signatures which fail verification or are made by other keys are skipped.
+pub fn verified_by(
+    signatures: &[Signature],
+    payload: &[u8],
+    allowed: &BTreeSet<PublicKey>,
+) -> Vec<Signature> {
+    iter_verified(
+        signatures
+            .iter()
+            .filter(|signature| allowed.contains(&signature.public_key)),
+        payload,
+    )
+    .cloned()
+    .collect()
+}
+
+/// Signatures of the `payload` which pass verification, borrowed from `signatures`, so the
+/// signatures of large validator sets are not cloned just to be counted or inspected.
+pub fn iter_verified<'a, S: Borrow<Signature> + 'a>(
+    signatures: impl IntoIterator<Item = &'a S> + 'a,
+    payload: &'a [u8],
+) -> impl Iterator<Item = &'a S> + 'a {
+    signatures
+        .into_iter()
+        .filter(move |signature| (*signature).borrow().verify(payload).is_ok())
+}
+
+/// In-place pruning of the collected signatures, e.g. the votes for a block.
+pub trait RetainVerified {
+    /// Remove every signature which fails verification of the `payload`.
+    ///
+    /// Returns the number of removed signatures.
+    fn retain_verified(&mut self, payload: &[u8]) -> usize;
+
+    /// Remove every signature which fails verification of the `payload` signed with the
+    /// `context`, see `Signature::new_with_context`.
+    ///
+    /// Returns the number of removed signatures.
+    fn retain_verified_with_context(&mut self, context: &[u8], payload: &[u8]) -> usize {
+        self.retain_verified(&with_context(context, payload))
+    }
+}
+
+impl RetainVerified for Vec<Signature> {
+    fn retain_verified(&mut self, payload: &[u8]) -> usize {
+        let len = self.len();
+        self.retain(|signature| signature.verify(payload).is_ok());
+        len - self.len()
+    }
+}
+
+/// Verify independent signatures of different messages made by different keys at once.
+///
+/// Items are `(public_key, signature, message)` tuples. Ed25519 signatures are checked with a
+/// single `ed25519-dalek` batch verification with any backend, items are checked one by one
+/// only if the batch fails, to find the failed ones.
+///
+/// Returns `Err(Vec<usize>)` with indices of the items which failed verification.
+pub fn verify_batch(items: &[(PublicKey, &[u8], &[u8])]) -> Result<(), Vec<usize>> {
+    let strict = STRICT_VERIFICATION.load(Ordering::Relaxed);
+    let signatures = items
+        .iter()
+        .map(|(public_key, signature, _)| {
+            ed25519_signature(signature)
+                .filter(|signature| !strict || check_canonical(signature, public_key).is_ok())
+        })
+        .collect::<Option<Vec<Ed25519Signature>>>();
+    if let Some(signatures) = signatures {
+        let public_keys: Vec<PublicKey> =
+            items.iter().map(|(public_key, _, _)| *public_key).collect();
+        let messages: Vec<&[u8]> = items.iter().map(|(_, _, message)| *message).collect();
+        if verify_ed25519_batch(&messages, &signatures, &public_keys).is_ok() {
+            return Ok(());
+        }
+    }
+    let failed: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(
+            |(_, (public_key, signature, message))| match ed25519_signature(signature) {
+                Some(signature) => verify_ed25519(message, &signature, public_key).is_err(),
+                None => true,
+            },
+        )
+        .map(|(index, _)| index)
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed)
+    }
+}
+
+/// `ursa` has no batch verification, so `ed25519-dalek` it is built upon is used directly.
+fn verify_ed25519_batch(
+    messages: &[&[u8]],
+    signatures: &[Ed25519Signature],
+    public_keys: &[PublicKey],
+) -> Result<(), String> {
+    let signatures = signatures
+        .iter()
+        .map(|signature| ed25519_dalek::Signature::from_bytes(signature).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, String>>()?;
+    let public_keys = public_keys
+        .iter()
+        .map(|public_key| {
+            ed25519_dalek::PublicKey::from_bytes(public_key).map_err(|e| e.to_string())
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    ed25519_dalek::verify_batch(messages, &signatures, &public_keys).map_err(|e| e.to_string())
+}
+
+/// Decode `T` from the untrusted `bytes` only after all the `signatures` are verified over the
+/// `signed_region`, so unauthenticated input never reaches the decoder.
+///
+/// Returns `Err(String)` describing the failed stage - a missing or invalid signature, or a
+/// payload which can not be decoded.
+pub fn verify_and_decode<T: Decode>(
+    bytes: &[u8],
+    signatures: &[Signature],
+    signed_region: &[u8],
+) -> Result<T, String> {
+    if signatures.is_empty() {
+        return Err("Signature verification failed: payload is not signed.".to_string());
+    }
+    for signature in signatures {
+        signature
+            .verify(signed_region)
+            .map_err(|e| format!("Signature verification failed: {}", e))?;
+    }
+    T::decode(&mut &bytes[..]).map_err(|e| format!("Failed to decode verified payload: {}", e))
+}
+
+fn verify_ed25519(
+    message: &[u8],
+    signature: &Ed25519Signature,
+    public_key: &PublicKey,
+) -> Result<(), String> {
+    if STRICT_VERIFICATION.load(Ordering::Relaxed) {
+        check_canonical(signature, public_key)?;
+    }
+    backend::verify(message, signature, public_key)
+}
+
+/// Check that the `public_key` is a canonically encoded point of the curve which is not of
+/// small order, otherwise no private key can make signatures verified in the strict mode.
+pub fn validate_public_key(public_key: &PublicKey) -> Result<(), String> {
+    let point = CompressedEdwardsY(*public_key)
+        .decompress()
+        .ok_or("Public key is not a point of the curve.")?;
+    if point.is_small_order() {
+        return Err("Public key is a point of small order.".to_string());
+    }
+    if point.compress().to_bytes() != *public_key {
+        return Err("Public key is not canonically encoded.".to_string());
+    }
+    Ok(())
+}
+
+/// Checks of the strict verification, which backends do not do themselves.
+fn check_canonical(signature: &Ed25519Signature, public_key: &PublicKey) -> Result<(), String> {
+    validate_public_key(public_key)?;
+    let mut r = [0; 32];
+    r.copy_from_slice(&signature[..32]);
+    if CompressedEdwardsY(r)
+        .decompress()
+        .is_none_or(|point| point.is_small_order())
+    {
+        return Err("Signature R is not a point of the curve or is of small order.".to_string());
+    }
+    let mut s = [0; 32];
+    s.copy_from_slice(&signature[32..]);
+    if Scalar::from_canonical_bytes(s).is_none() {
+        return Err("Signature S is not reduced modulo the group order.".to_string());
+    }
+    Ok(())
+}
+
+/// Signatures of other schemes have different lengths and are not Ed25519 signatures.
+fn ed25519_signature(signature: &[u8]) -> Option<Ed25519Signature> {
+    if signature.len() != 64 {
+        return None;
+    }
+    let mut ed25519_signature = [0; 64];
+    ed25519_signature.copy_from_slice(signature);
+    Some(ed25519_signature)
+}
+
+/// Length prefixes make the tagged payload unambiguous for contexts of different lengths.
+fn with_context(context: &[u8], payload: &[u8]) -> Vec<u8> {
+    (context, payload).encode()
+}
+
 impl PartialEq for Signature {
     fn eq(&self, other: &Self) -> bool {
         self.public_key == other.public_key && self.signature.to_vec() == other.signature.to_vec()
@@ -116,29 +791,499 @@ impl Debug for Signature {
         f.debug_struct("Signature")
             .field("public_key", &self.public_key)
             .field("signature", &self.signature.to_vec())
+            .field("payload_hash", &self.payload_hash)
             .finish()
     }
 }
 
+impl TryFrom<(PublicKey, &[u8])> for Signature {
+    type Error = String;
+
+    /// Construct `Signature` from the raw bytes received over the network. Only the length is
+    /// checked here, the signature should still be verified.
+    fn try_from((public_key, signature): (PublicKey, &[u8])) -> Result<Self, Self::Error> {
+        let signature = Ed25519Signature::try_from(signature).map_err(|_| {
+            format!(
+                "Ed25519 signature should be {} bytes long, but got {}.",
+                ED25519_SIGNATURE_LENGTH,
+                signature.len()
+            )
+        })?;
+        Ok(Signature {
+            public_key,
+            signature,
+            payload_hash: None,
+        })
+    }
+}
+
+#[cfg(not(feature = "pure-rust"))]
+mod backend {
+    use super::{Ed25519Signature, Hash, PrivateKey, PublicKey};
+    use std::convert::TryInto;
+    pub use ursa::blake2::{
+        digest::{Input, VariableOutput},
+        VarBlake2b,
+    };
+    pub use ursa::sha2::{Digest, Sha512};
+    use ursa::{
+        keys::{KeyGenOption, PrivateKey as UrsaPrivateKey, PublicKey as UrsaPublicKey},
+        signatures::{ed25519::Ed25519Sha512, SignatureScheme, Signer},
+    };
+
+    pub fn generate_key_pair(seed: Option<Hash>) -> Result<(PublicKey, PrivateKey), String> {
+        let (public_key, ursa_private_key) = Ed25519Sha512
+            .keypair(seed.map(|seed| KeyGenOption::UseSeed(seed.to_vec())))
+            .map_err(|e| format!("Failed to generate Ed25519Sha512 key pair: {}", e))?;
+        let public_key = public_key[..]
+            .try_into()
+            .map_err(|e| format!("Public key should be [u8;32]: {}", e))?;
+        let mut private_key = [0; 64];
+        private_key.copy_from_slice(ursa_private_key.as_ref());
+        Ok((public_key, private_key))
+    }
+
+    /// `ursa` can not derive the public key from the secret key, so `ed25519-dalek` it is built
+    /// upon is used directly.
+    pub fn key_pair_from_secret_key(
+        secret_key: [u8; 32],
+    ) -> Result<(PublicKey, PrivateKey), String> {
+        use ed25519_dalek::{Keypair, PublicKey as DalekPublicKey, SecretKey};
+        let secret = SecretKey::from_bytes(&secret_key)
+            .map_err(|e| format!("Failed to create Ed25519 key pair: {}", e))?;
+        let public = DalekPublicKey::from(&secret);
+        Ok((public.to_bytes(), Keypair { public, secret }.to_bytes()))
+    }
+
+    pub fn sign(payload: &[u8], private_key: &PrivateKey) -> Result<Ed25519Signature, String> {
+        let private_key = UrsaPrivateKey(private_key.to_vec());
+        let transaction_signature = Signer::new(&Ed25519Sha512, &private_key)
+            .sign(payload)
+            .map_err(|e| format!("Failed to sign payload: {}", e))?;
+        let mut signature = [0; 64];
+        signature.copy_from_slice(&transaction_signature);
+        Ok(signature)
+    }
+
+    pub fn verify(
+        message: &[u8],
+        signature: &Ed25519Signature,
+        public_key: &PublicKey,
+    ) -> Result<(), String> {
+        Ed25519Sha512::new()
+            .verify(message, signature, &UrsaPublicKey(public_key.to_vec()))
+            .map_err(|e| e.to_string())
+            .map(|_| ())
+    }
+}
+
+#[cfg(feature = "pure-rust")]
+mod backend {
+    use super::{Ed25519Signature, Hash, PrivateKey, PublicKey};
+    pub use blake2::{
+        digest::{Input, VariableOutput},
+        VarBlake2b,
+    };
+    use ed25519_dalek::{Keypair, PublicKey as DalekPublicKey, SecretKey, Signature};
+    use rand::{rngs::OsRng, RngCore as _};
+    use rand_chacha::ChaChaRng;
+    use rand_core::{RngCore as _, SeedableRng};
+    use sha2::Sha256;
+    pub use sha2::{Digest, Sha512};
+
+    pub fn generate_key_pair(seed: Option<Hash>) -> Result<(PublicKey, PrivateKey), String> {
+        let mut secret_key = [0; 32];
+        match seed {
+            // Seeded keys are derived the same way `ursa` does it, so they do not depend on the
+            // backend.
+            Some(seed) => {
+                let mut chacha_seed = [0; 32];
+                chacha_seed.copy_from_slice(&Sha256::digest(&seed));
+                ChaChaRng::from_seed(chacha_seed).fill_bytes(&mut secret_key);
+            }
+            None => OsRng.fill_bytes(&mut secret_key),
+        }
+        let secret = SecretKey::from_bytes(&secret_key)
+            .map_err(|e| format!("Failed to generate Ed25519 key pair: {}", e))?;
+        let public = DalekPublicKey::from(&secret);
+        Ok((public.to_bytes(), Keypair { public, secret }.to_bytes()))
+    }
+
+    pub fn key_pair_from_secret_key(
+        secret_key: [u8; 32],
+    ) -> Result<(PublicKey, PrivateKey), String> {
+        let secret = SecretKey::from_bytes(&secret_key)
+            .map_err(|e| format!("Failed to create Ed25519 key pair: {}", e))?;
+        let public = DalekPublicKey::from(&secret);
+        Ok((public.to_bytes(), Keypair { public, secret }.to_bytes()))
+    }
+
+    pub fn sign(payload: &[u8], private_key: &PrivateKey) -> Result<Ed25519Signature, String> {
+        Ok(Keypair::from_bytes(private_key)
+            .map_err(|e| format!("Failed to sign payload: {}", e))?
+            .sign(payload)
+            .to_bytes())
+    }
+
+    pub fn verify(
+        message: &[u8],
+        signature: &Ed25519Signature,
+        public_key: &PublicKey,
+    ) -> Result<(), String> {
+        DalekPublicKey::from_bytes(public_key)
+            .map_err(|e| e.to_string())?
+            .verify(
+                message,
+                &Signature::from_bytes(signature).map_err(|e| e.to_string())?,
+            )
+            .map_err(|e| e.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use hex_literal::hex;
+    use std::sync::atomic::AtomicUsize;
     use ursa::blake2::{
         digest::{Input, VariableOutput},
         VarBlake2b,
     };
 
+    #[test]
+    fn keys_and_signatures_are_encoded_canonically() {
+        let signatures: Vec<Signature> = (0..3)
+            .map(|_| {
+                let (public_key, private_key) =
+                    generate_key_pair().expect("Failed to generate key pair.");
+                crate::assert_canonical_encoding(&public_key);
+                Signature::new_with_context(
+                    &KeyPair::new(public_key, private_key),
+                    context::TRANSACTION,
+                    b"payload",
+                )
+                .expect("Failed to sign payload.")
+            })
+            .collect();
+        crate::assert_canonical_encoding(&signatures);
+    }
+
+    /// Payload which records whether decoding was attempted.
+    #[derive(Debug, PartialEq, Encode)]
+    struct Probe(u64);
+
+    static PROBE_DECODED: AtomicBool = AtomicBool::new(false);
+
+    impl Decode for Probe {
+        fn decode<I: parity_scale_codec::Input>(
+            input: &mut I,
+        ) -> Result<Self, parity_scale_codec::Error> {
+            PROBE_DECODED.store(true, Ordering::SeqCst);
+            Ok(Probe(u64::decode(input)?))
+        }
+    }
+
+    #[test]
+    fn payload_is_decoded_only_after_signatures_are_verified() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let key_pair = KeyPair::new(public_key, private_key);
+        let bytes = Probe(42).encode();
+        let signatures = vec![Signature::new(&key_pair, &bytes).expect("Failed to sign payload.")];
+        assert_eq!(
+            Ok(Probe(42)),
+            verify_and_decode::<Probe>(&bytes, &signatures, &bytes)
+        );
+        PROBE_DECODED.store(false, Ordering::SeqCst);
+        let tampered = Probe(43).encode();
+        let error = verify_and_decode::<Probe>(&tampered, &signatures, &tampered)
+            .expect_err("Tampered payload should not be accepted.");
+        assert!(error.starts_with("Signature verification failed"));
+        assert!(verify_and_decode::<Probe>(&bytes, &[], &bytes).is_err());
+        assert!(!PROBE_DECODED.load(Ordering::SeqCst));
+        let corrupt = bytes[..4].to_vec();
+        let signatures =
+            vec![Signature::new(&key_pair, &corrupt).expect("Failed to sign payload.")];
+        let error = verify_and_decode::<Probe>(&corrupt, &signatures, &corrupt)
+            .expect_err("Corrupt payload should not be decoded.");
+        assert!(error.starts_with("Failed to decode verified payload"));
+        assert!(PROBE_DECODED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn signature_from_raw_bytes() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let payload = b"raw signature";
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), payload)
+            .expect("Failed to sign payload.");
+        let parsed = Signature::try_from((public_key, &signature.signature[..]))
+            .expect("Failed to parse signature.");
+        assert_eq!(signature, parsed);
+        assert!(parsed.verify(payload).is_ok());
+    }
+
+    #[test]
+    fn signature_of_wrong_length_is_rejected() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), b"raw signature")
+            .expect("Failed to sign.");
+        let truncated = &signature.signature[..ED25519_SIGNATURE_LENGTH - 1];
+        assert!(Signature::try_from((public_key, truncated)).is_err());
+        let mut extended = signature.signature.to_vec();
+        extended.push(0);
+        assert!(Signature::try_from((public_key, &extended[..])).is_err());
+        assert!(Signature::try_from((public_key, &[][..])).is_err());
+    }
+
     #[test]
     fn create_signature() {
         let (public_key, private_key) =
             super::generate_key_pair().expect("Failed to generate key pair.");
-        let result = Signature::new(public_key, b"Test message to sign.", &private_key)
-            .expect("Failed to create signature.");
+        let result = Signature::new(
+            &KeyPair::new(public_key, private_key),
+            b"Test message to sign.",
+        )
+        .expect("Failed to create signature.");
         assert_eq!(result.public_key, public_key[..]);
     }
 
+    #[test]
+    fn only_verified_signatures_are_retained() {
+        let key_pairs: Vec<KeyPair> = (0..3)
+            .map(|_| {
+                let (public_key, private_key) =
+                    super::generate_key_pair().expect("Failed to generate key pair.");
+                KeyPair::new(public_key, private_key)
+            })
+            .collect();
+        let payload = b"Test message to sign.";
+        let valid: Vec<Signature> = key_pairs
+            .iter()
+            .map(|key_pair| {
+                Signature::new_with_context(key_pair, context::BLOCK, payload)
+                    .expect("Failed to create signature.")
+            })
+            .collect();
+        let mut forged = valid[1].clone();
+        forged.public_key = key_pairs[2].public_key;
+        let mut signatures = vec![
+            valid[0].clone(),
+            Signature::new(&key_pairs[1], payload).expect("Failed to create signature."),
+            forged,
+            valid[2].clone(),
+            Signature::new_with_context(&key_pairs[0], context::BLOCK, b"Other message.")
+                .expect("Failed to create signature."),
+        ];
+        assert_eq!(
+            3,
+            signatures.retain_verified_with_context(context::BLOCK, payload)
+        );
+        assert_eq!(vec![valid[0].clone(), valid[2].clone()], signatures);
+        assert_eq!(
+            0,
+            signatures.retain_verified_with_context(context::BLOCK, payload)
+        );
+        assert_eq!(2, signatures.retain_verified(payload));
+        assert!(signatures.is_empty());
+    }
+
+    #[test]
+    fn transaction_context_signature_fails_block_context_verification() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let payload = b"Test message to sign.";
+        let signature = Signature::new_with_context(
+            &KeyPair::new(public_key, private_key),
+            context::TRANSACTION,
+            payload,
+        )
+        .expect("Failed to create signature.");
+        assert!(signature
+            .verify_with_context(context::TRANSACTION, payload)
+            .is_ok());
+        assert!(signature
+            .verify_with_context(context::BLOCK, payload)
+            .is_err());
+        assert!(signature.verify(payload).is_err());
+    }
+
+    #[test]
+    fn signature_without_context_verifies_via_legacy_path() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let payload = b"Test message to sign.";
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), payload)
+            .expect("Failed to create signature.");
+        assert!(signature.verify(payload).is_ok());
+        assert!(signature
+            .verify_with_context(context::TRANSACTION, payload)
+            .is_err());
+    }
+
+    #[test]
+    fn verified_by_returns_only_signatures_of_allowed_keys() {
+        let key_pairs: Vec<KeyPair> = (0..4u8)
+            .map(|seed| {
+                let (public_key, private_key) = super::generate_key_pair_from_seed([seed; 32])
+                    .expect("Failed to generate key pair.");
+                KeyPair::new(public_key, private_key)
+            })
+            .collect();
+        let payload = b"block hash";
+        let mut signatures: Vec<Signature> = key_pairs
+            .iter()
+            .map(|key_pair| Signature::new(key_pair, payload).expect("Failed to sign."))
+            .collect();
+        signatures.push(Signature::new(&key_pairs[1], b"other").expect("Failed to sign."));
+        let allowed: BTreeSet<PublicKey> = key_pairs[..2]
+            .iter()
+            .map(|key_pair| key_pair.public_key)
+            .collect();
+        let verified_keys: Vec<PublicKey> = verified_by(&signatures, payload, &allowed)
+            .iter()
+            .map(|signature| signature.public_key)
+            .collect();
+        assert_eq!(
+            vec![key_pairs[0].public_key, key_pairs[1].public_key],
+            verified_keys
+        );
+        assert!(verified_by(&signatures, payload, &BTreeSet::new()).is_empty());
+    }
+
+    #[test]
+    fn iter_verified_does_not_clone_signatures() {
+        static CLONES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+        struct CountedSignature(Signature);
+
+        impl Clone for CountedSignature {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::SeqCst);
+                CountedSignature(self.0.clone())
+            }
+        }
+
+        impl Borrow<Signature> for CountedSignature {
+            fn borrow(&self) -> &Signature {
+                &self.0
+            }
+        }
+
+        let payload = b"block hash";
+        let signatures: Vec<CountedSignature> = (0..4u8)
+            .map(|seed| {
+                let (public_key, private_key) = super::generate_key_pair_from_seed([seed; 32])
+                    .expect("Failed to generate key pair.");
+                let message: &[u8] = if seed == 2 { b"other" } else { payload };
+                CountedSignature(
+                    Signature::new(&KeyPair::new(public_key, private_key), message)
+                        .expect("Failed to sign."),
+                )
+            })
+            .collect();
+        let verified: Vec<&CountedSignature> = iter_verified(&signatures, payload).collect();
+        assert_eq!(3, verified.len());
+        assert!(verified.iter().all(|signature| signatures
+            .iter()
+            .any(|other| std::ptr::eq(*signature, other))));
+        assert_eq!(0, CLONES.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn verify_batch_reports_failed_items() {
+        let key_pairs: Vec<(PublicKey, PrivateKey)> = (0..3u8)
+            .map(|seed| {
+                super::generate_key_pair_from_seed([seed; 32])
+                    .expect("Failed to generate key pair.")
+            })
+            .collect();
+        let messages: Vec<Vec<u8>> = (0..3u8).map(|index| vec![index; 10]).collect();
+        let signatures: Vec<Signature> = key_pairs
+            .iter()
+            .zip(&messages)
+            .map(|((public_key, private_key), message)| {
+                Signature::new(&KeyPair::new(*public_key, *private_key), message)
+                    .expect("Failed to create signature.")
+            })
+            .collect();
+        let items: Vec<(PublicKey, &[u8], &[u8])> = signatures
+            .iter()
+            .zip(&messages)
+            .map(|(signature, message)| {
+                (
+                    signature.public_key,
+                    &signature.signature[..],
+                    message.as_slice(),
+                )
+            })
+            .collect();
+        assert_eq!(Ok(()), verify_batch(&items));
+        let mut one_invalid = items.clone();
+        one_invalid[1].2 = &messages[2];
+        assert_eq!(Err(vec![1]), verify_batch(&one_invalid));
+        // A signature of another length can not be an Ed25519 signature.
+        let other_scheme_signature = [0u8; 65];
+        let mut mixed = items.clone();
+        mixed[2].1 = &other_scheme_signature;
+        assert_eq!(Err(vec![2]), verify_batch(&mixed));
+    }
+
+    #[test]
+    fn signature_with_payload_hash_rejects_other_message() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let payload = b"Test message to sign.";
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), payload)
+            .expect("Failed to create signature.");
+        assert_eq!(Some(super::hash(payload.to_vec())), signature.payload_hash);
+        assert!(signature.verify(payload).is_ok());
+        assert_eq!(
+            Err("Message does not match the signed payload hash.".to_string()),
+            signature.verify(b"Other message.")
+        );
+    }
+
+    #[test]
+    fn signature_without_payload_hash_verifies_signature_only() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let payload = b"Test message to sign.";
+        let mut signature = Signature::new(&KeyPair::new(public_key, private_key), payload)
+            .expect("Failed to create signature.");
+        signature.payload_hash = None;
+        assert!(signature.verify(payload).is_ok());
+        let result = signature.verify(b"Other message.");
+        assert!(result.is_err());
+        assert_ne!(
+            Err("Message does not match the signed payload hash.".to_string()),
+            result
+        );
+        let decoded = Signature::decode(&mut signature.encode().as_slice())
+            .expect("Failed to decode signature.");
+        assert_eq!(None, decoded.payload_hash);
+    }
+
+    #[test]
+    fn signature_encoding_does_not_include_payload_hash() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let payload = b"Test message to sign.";
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), payload)
+            .expect("Failed to create signature.");
+        assert!(signature.payload_hash.is_some());
+        let mut legacy_encoding = public_key.to_vec();
+        legacy_encoding.extend_from_slice(&signature.signature);
+        assert_eq!(legacy_encoding, signature.encode());
+        assert_eq!(MAX_ENCODED_SIGNATURE_LEN, legacy_encoding.len());
+        let decoded = Signature::decode(&mut legacy_encoding.as_slice())
+            .expect("Failed to decode signature.");
+        assert_eq!(signature, decoded);
+        assert!(decoded.verify(payload).is_ok());
+    }
+
     #[test]
     fn blake2_32b() {
         let mut hasher = VarBlake2b::new(32).unwrap();
@@ -151,6 +1296,76 @@ mod tests {
         })
     }
 
+    #[test]
+    fn hash_of_32_and_64_bytes_matches_known_vectors() {
+        assert_eq!(
+            hex!("bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"),
+            super::hash_n::<32>(b"abc").expect("Failed to calculate hash.")
+        );
+        assert_eq!(
+            super::hash(b"abc".to_vec()),
+            super::hash_n::<32>(b"abc").unwrap()
+        );
+        assert_eq!(
+            hex!("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923")[..],
+            super::hash_n::<64>(b"abc").expect("Failed to calculate hash.")[..]
+        );
+        assert_eq!(
+            hex!("786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce")[..],
+            super::hash_n::<64>(b"").expect("Failed to calculate hash.")[..]
+        );
+    }
+
+    #[test]
+    fn hash_of_unsupported_length_is_rejected() {
+        assert!(super::hash_n::<0>(b"abc").is_err());
+        assert!(super::hash_n::<65>(b"abc").is_err());
+        assert!(super::hash_n::<128>(b"abc").is_err());
+    }
+
+    #[test]
+    fn seeded_signature_matches_known_vector() {
+        let (public_key, private_key) =
+            super::generate_key_pair_from_seed([64u8; 32]).expect("Failed to generate key pair.");
+        let signature = Signature::new(
+            &KeyPair::new(public_key, private_key),
+            b"Test message to sign.",
+        )
+        .expect("Failed to create signature.");
+        assert_eq!(signature.signature[..], hex!("81f163d49ad6fd5966a54072265868ee200b32196fa60c961a42944da6acb8bb468de03a49a6d152e53fa7c470cf68285c888985bd68192c852f2b5b4917df08")[..]);
+    }
+
+    #[cfg(feature = "pure-rust")]
+    #[test]
+    fn pure_rust_backend_is_compatible_with_ursa() {
+        use ursa::{
+            keys::KeyGenOption,
+            signatures::{ed25519::Ed25519Sha512, SignatureScheme, Signer},
+        };
+
+        let seed = [7u8; 32];
+        let payload = b"Test message to sign.";
+        let (public_key, private_key) =
+            super::generate_key_pair_from_seed(seed).expect("Failed to generate key pair.");
+        let (ursa_public_key, ursa_private_key) = Ed25519Sha512
+            .keypair(Some(KeyGenOption::UseSeed(seed.to_vec())))
+            .expect("Failed to generate key pair.");
+        assert_eq!(ursa_public_key[..], public_key[..]);
+        assert_eq!(ursa_private_key.as_ref(), &private_key[..]);
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), payload)
+            .expect("Failed to create signature.");
+        assert!(Ed25519Sha512::new()
+            .verify(payload, &signature.signature, &ursa_public_key)
+            .is_ok());
+        let ursa_signature = Signer::new(&Ed25519Sha512, &ursa_private_key)
+            .sign(payload)
+            .expect("Failed to create signature.");
+        assert_eq!(ursa_signature, signature.signature.to_vec());
+        let mut hasher = VarBlake2b::new(32).unwrap();
+        hasher.input(payload);
+        assert_eq!(hasher.vec_result(), super::hash(payload.to_vec()).to_vec());
+    }
+
     #[test]
     fn create_keypair_from_seed() {
         let seed = [64u8; 32];
@@ -162,4 +1377,370 @@ mod tests {
         );
         assert_eq!(private_key[..], hex!("3c0a1fabf193da9c1325e9dc918e4824c35682875aefd20afda2ff56bf8e7cad2ce0c446f8a2bcd835336f7db9e047e5d391054d7fdcb8cfdb32252445170f7f")[..]);
     }
+
+    /// Test vector 1 for ed25519 from the SLIP-0010 specification.
+    #[test]
+    fn derive_key_pairs_of_slip10_test_vector() {
+        let seed = hex!("000102030405060708090a0b0c0d0e0f");
+        let vectors = [
+            (
+                "m",
+                hex!("90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb"),
+                hex!("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"),
+                hex!("a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"),
+            ),
+            (
+                "m/0'",
+                hex!("8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69"),
+                hex!("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"),
+                hex!("8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"),
+            ),
+            (
+                "m/0'/1'",
+                hex!("a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14"),
+                hex!("b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"),
+                hex!("1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187"),
+            ),
+            (
+                "m/0'/1'/2'",
+                hex!("2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c"),
+                hex!("92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9"),
+                hex!("ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1"),
+            ),
+        ];
+        for (path, chain_code, secret_key, public_key) in &vectors {
+            let key_pair =
+                ExtendedKeyPair::from_seed_path(&seed, path).expect("Failed to derive key pair.");
+            assert_eq!(chain_code, key_pair.chain_code());
+            assert_eq!(secret_key[..], key_pair.private_key[..32]);
+            assert_eq!(public_key, &key_pair.public_key);
+            assert_eq!(public_key[..], key_pair.private_key[32..]);
+        }
+        let child = ExtendedKeyPair::from_seed(&seed)
+            .and_then(|master| master.derive_child(HARDENED_INDEX))
+            .expect("Failed to derive key pair.");
+        assert_eq!(vectors[1].3, child.public_key);
+    }
+
+    #[test]
+    fn non_hardened_derivation_is_rejected() {
+        let master = ExtendedKeyPair::from_seed(&[0; 16]).expect("Failed to derive key pair.");
+        assert!(master.derive_child(0).is_err());
+        assert!(master.derive_child(HARDENED_INDEX - 1).is_err());
+        assert!(ExtendedKeyPair::from_seed_path(&[0; 16], "m/44'/0").is_err());
+        assert!(ExtendedKeyPair::from_seed_path(&[0; 16], "44'/0'").is_err());
+        assert!(ExtendedKeyPair::from_seed_path(&[0; 16], "m/2147483648'").is_err());
+        assert!(ExtendedKeyPair::from_seed_path(&[0; 16], "m/44H/0'").is_ok());
+    }
+
+    #[test]
+    fn signature_is_made_by_pluggable_signer() {
+        struct MockSigner {
+            public_key: PublicKey,
+            payloads: std::cell::RefCell<Vec<Vec<u8>>>,
+        }
+
+        impl Signer for MockSigner {
+            fn public_key(&self) -> PublicKey {
+                self.public_key
+            }
+
+            fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, String> {
+                self.payloads.borrow_mut().push(payload.to_vec());
+                Ok(vec![7; 64])
+            }
+        }
+
+        let signer = MockSigner {
+            public_key: [3; 32],
+            payloads: std::cell::RefCell::new(Vec::new()),
+        };
+        let payload = b"Test message to sign.";
+        let signature = Signature::new(&signer, payload).expect("Failed to create signature.");
+        assert_eq!([3; 32], signature.public_key);
+        assert_eq!([7; 64][..], signature.signature[..]);
+        assert_eq!(Some(super::hash(payload.to_vec())), signature.payload_hash);
+        let signature = Signature::new_with_context(&signer, context::QUERY, payload)
+            .expect("Failed to create signature.");
+        assert_eq!([3; 32], signature.public_key);
+        assert_eq!(
+            vec![payload.to_vec(), with_context(context::QUERY, payload)],
+            *signer.payloads.borrow()
+        );
+    }
+
+    #[test]
+    fn signature_of_wrong_length_from_signer_is_rejected() {
+        struct TruncatingSigner(KeyPair);
+
+        impl Signer for TruncatingSigner {
+            fn public_key(&self) -> PublicKey {
+                self.0.public_key()
+            }
+
+            fn sign(&self, payload: &[u8]) -> Result<Vec<u8>, String> {
+                let mut signature = self.0.sign(payload)?;
+                signature.pop();
+                Ok(signature)
+            }
+        }
+
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let signer = TruncatingSigner(KeyPair::new(public_key, private_key));
+        assert!(Signature::new(&signer, b"Test message to sign.").is_err());
+    }
+
+    #[test]
+    fn small_order_public_keys_are_rejected_in_strict_mode() {
+        // Identity as both `A` and `R` with zero `S` satisfies the verification equation for
+        // any message.
+        let identity = hex!("0100000000000000000000000000000000000000000000000000000000000000");
+        let mut forged_signature = [0; 64];
+        forged_signature[..32].copy_from_slice(&identity);
+        assert!(backend::verify(b"Any message.", &forged_signature, &identity).is_ok());
+        for public_key in &[
+            identity,
+            hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+            hex!("ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"),
+            hex!("26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05"),
+            hex!("c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a"),
+        ] {
+            assert!(check_canonical(&forged_signature, public_key).is_err());
+            assert!(Signature::try_from((*public_key, &forged_signature[..]))
+                .expect("Failed to create signature.")
+                .verify(b"Any message.")
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn signature_with_non_canonical_s_is_rejected_in_strict_mode() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let message = b"Test message to sign.";
+        let signature = backend::sign(message, &private_key).expect("Failed to sign.");
+        // `S + L` is the same scalar, but not reduced modulo the group order `L`.
+        let order = curve25519_dalek::constants::BASEPOINT_ORDER.to_bytes();
+        let mut malleated_signature = signature;
+        let mut carry = 0;
+        for (byte, order_byte) in malleated_signature[32..].iter_mut().zip(order.iter()) {
+            let sum = u16::from(*byte) + u16::from(*order_byte) + carry;
+            *byte = sum as u8;
+            carry = sum >> 8;
+        }
+        assert!(check_canonical(&malleated_signature, &public_key).is_err());
+        assert!(Signature::try_from((public_key, &malleated_signature[..]))
+            .expect("Failed to create signature.")
+            .verify(message)
+            .is_err());
+    }
+
+    #[test]
+    fn canonical_signature_passes_strict_verification() {
+        let (public_key, private_key) =
+            super::generate_key_pair().expect("Failed to generate key pair.");
+        let message = b"Test message to sign.";
+        let signature = Signature::new(&KeyPair::new(public_key, private_key), message)
+            .expect("Failed to create signature.");
+        assert!(check_canonical(&signature.signature, &public_key).is_ok());
+        assert!(signature.verify(message).is_ok());
+        assert!(verify_batch(&[(public_key, &signature.signature[..], &message[..])]).is_ok());
+    }
+
+    #[test]
+    fn hash_hex_round_trip() {
+        let hash = super::hash(b"Test message to hash.".to_vec());
+        let hex = HashHex(hash).to_string();
+        assert_eq!(64, hex.len());
+        assert_eq!(hex, hex.to_lowercase());
+        assert_eq!(Ok(HashHex(hash)), hex.parse());
+        assert_eq!(Ok(HashHex(hash)), hex.to_uppercase().parse());
+        assert!(hex[1..].parse::<HashHex>().is_err());
+        assert!(hex.replacen(&hex[..1], "g", 1).parse::<HashHex>().is_err());
+    }
+
+    #[test]
+    fn public_key_is_recovered_from_recoverable_signature() {
+        let (public_key, private_key) =
+            secp256k1::generate_key_pair().expect("Failed to generate key pair.");
+        let message = b"Test message to sign.";
+        let signature =
+            secp256k1::sign_recoverable(message, &private_key).expect("Failed to sign message.");
+        assert_eq!(65, signature.len());
+        assert_eq!(
+            Ok(public_key),
+            secp256k1::recover_public_key(&signature, message)
+        );
+        assert_eq!(Ok(public_key), secp256k1::public_key(&private_key));
+    }
+
+    #[test]
+    fn corrupted_recoverable_signature_does_not_recover_signer() {
+        let (public_key, private_key) =
+            secp256k1::generate_key_pair().expect("Failed to generate key pair.");
+        let message = b"Test message to sign.";
+        let mut signature =
+            secp256k1::sign_recoverable(message, &private_key).expect("Failed to sign message.");
+        signature[10] ^= 0x01;
+        assert_ne!(
+            Ok(public_key),
+            secp256k1::recover_public_key(&signature, message)
+        );
+        let signature =
+            secp256k1::sign_recoverable(message, &private_key).expect("Failed to sign message.");
+        assert_ne!(
+            Ok(public_key),
+            secp256k1::recover_public_key(&signature, b"Another message.")
+        );
+    }
+
+    #[test]
+    fn signing_same_message_twice_gives_same_signature() {
+        let message = b"Test message to sign.";
+        let (_, private_key) =
+            secp256k1::generate_key_pair().expect("Failed to generate key pair.");
+        let signature =
+            secp256k1::sign_recoverable(message, &private_key).expect("Failed to sign message.");
+        assert_eq!(
+            signature[..],
+            secp256k1::sign_recoverable(message, &private_key).expect("Failed to sign message.")[..]
+        );
+        assert_ne!(
+            signature[..],
+            secp256k1::sign_recoverable(b"Another message.", &private_key)
+                .expect("Failed to sign message.")[..]
+        );
+        let (public_key, private_key) = generate_key_pair().expect("Failed to generate key pair.");
+        let key_pair = KeyPair::new(public_key, private_key);
+        assert_eq!(
+            Signature::new(&key_pair, message).expect("Failed to sign message."),
+            Signature::new(&key_pair, message).expect("Failed to sign message.")
+        );
+    }
+
+    #[test]
+    fn key_pair_debug_redacts_private_key() {
+        let (public_key, private_key) = generate_key_pair().expect("Failed to generate key pair.");
+        let key_pair = KeyPair::new(public_key, private_key);
+        let debug = format!("{:?}", key_pair);
+        let hex: String = private_key
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert!(!debug.to_lowercase().contains(&hex[..16]), "{}", debug);
+        for secret_bytes in &[
+            format!("{:X?}", &private_key[..4]),
+            format!("{:?}", &private_key[..4]),
+        ] {
+            assert!(!debug.contains(secret_bytes.trim_matches(|c| c == '[' || c == ']')));
+        }
+        assert!(debug.contains("[REDACTED 64 bytes]"), "{}", debug);
+    }
+
+    #[test]
+    fn key_pairs_are_equal_only_with_equal_private_keys() {
+        let (public_key, private_key) = generate_key_pair().expect("Failed to generate key pair.");
+        let key_pair = KeyPair::new(public_key, private_key);
+        assert_eq!(key_pair, KeyPair::new(public_key, private_key));
+        let mut other_private_key = private_key;
+        other_private_key[63] ^= 1;
+        assert!(!private_keys_eq(&private_key, &other_private_key));
+        assert_ne!(key_pair, KeyPair::new(public_key, other_private_key));
+    }
+
+    #[test]
+    fn async_verification_keeps_executor_responsive() {
+        let (public_key, private_key) = generate_key_pair().expect("Failed to generate key pair.");
+        let message = b"Message to verify concurrently.".to_vec();
+        let signature = task::block_on(Signature::new_async(
+            KeyPair::new(public_key, private_key),
+            message.clone(),
+        ))
+        .expect("Failed to sign message.");
+        let ticks = AtomicUsize::new(0);
+        let done = AtomicBool::new(false);
+        // Both futures run on this single thread, so the ticker makes progress during the
+        // verifications only if they do not block the thread.
+        let (ticks_during_verification, _) = task::block_on(futures::future::join(
+            async {
+                let results = futures::future::join_all(
+                    (0..500).map(|_| signature.clone().verify_async(message.clone())),
+                )
+                .await;
+                done.store(true, Ordering::SeqCst);
+                assert!(results.iter().all(Result::is_ok));
+                ticks.load(Ordering::SeqCst)
+            },
+            async {
+                while !done.load(Ordering::SeqCst) {
+                    task::sleep(std::time::Duration::from_millis(1)).await;
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        ));
+        assert!(ticks_during_verification > 0);
+        assert!(task::block_on(signature.verify_async(b"Other message.".to_vec())).is_err());
+    }
+
+    #[test]
+    fn only_allowed_algorithms_pass_the_check() {
+        for algorithm in &[
+            Algorithm::Ed25519,
+            Algorithm::Secp256k1,
+            Algorithm::Secp256k1Recoverable,
+        ] {
+            assert_eq!(
+                *algorithm,
+                algorithm
+                    .to_string()
+                    .parse()
+                    .expect("Failed to parse algorithm.")
+            );
+        }
+        assert!("rsa".parse::<Algorithm>().is_err());
+        assert!(Algorithm::Ed25519.check_allowed(ALLOWED_ALGORITHMS).is_ok());
+        assert!(Algorithm::Secp256k1
+            .check_allowed(ALLOWED_ALGORITHMS)
+            .is_err());
+    }
+
+    #[test]
+    fn public_keys_of_correct_length_are_constructed() {
+        let (ed25519_public_key, _) = generate_key_pair().expect("Failed to generate key pair.");
+        assert_eq!(
+            ed25519_public_key,
+            public_key_from_bytes(ed25519_public_key.to_vec())
+                .expect("Failed to construct ed25519 public key.")
+        );
+        let (secp256k1_public_key, _) =
+            secp256k1::generate_key_pair().expect("Failed to generate key pair.");
+        assert_eq!(
+            secp256k1_public_key,
+            secp256k1::public_key_from_bytes(secp256k1_public_key.to_vec())
+                .expect("Failed to construct secp256k1 public key.")
+        );
+    }
+
+    #[test]
+    fn public_keys_of_incorrect_length_are_rejected() {
+        for length in &[0, 31, 33] {
+            assert_eq!(
+                Err(format!(
+                    "Public key of ed25519 should be 32 bytes long, got {} bytes.",
+                    length
+                )),
+                public_key_from_bytes(vec![1; *length])
+            );
+        }
+        for length in &[0, 32, 65] {
+            assert_eq!(
+                Err(format!(
+                    "Public key of secp256k1 should be 33 bytes long, got {} bytes.",
+                    length
+                )),
+                secp256k1::public_key_from_bytes(vec![1; *length])
+            );
+        }
+    }
 }
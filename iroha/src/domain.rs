@@ -1,11 +1,10 @@
 //! This module contains `Domain` structure and related implementations and trait implementations.
 use crate::{isi::prelude::*, prelude::*};
+use parity_scale_codec::{Decode, Encode, Input, Output};
 use std::collections::HashMap;
 
-type Name = String;
-
 /// Named group of `Account` and `Asset` entities.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Domain {
     /// Domain name, for example company name.
     pub name: Name,
@@ -19,9 +18,9 @@ impl Domain {
     /// Creates new detached `Domain`.
     ///
     /// Should be used for creation of a new `Domain` or while making queries.
-    pub fn new(name: Name) -> Self {
+    pub fn new(name: impl Into<Name>) -> Self {
         Domain {
-            name,
+            name: name.into(),
             accounts: HashMap::new(),
             asset_definitions: HashMap::new(),
         }
@@ -48,22 +47,56 @@ impl Identifiable for Domain {
     type Id = Name;
 }
 
+/// Maps are encoded as vectors of entries ordered by key, so equal domains have equal encodings.
+impl Encode for Domain {
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        let mut accounts: Vec<_> = self.accounts.iter().collect();
+        accounts.sort_by_key(|(id, _)| *id);
+        let mut asset_definitions: Vec<_> = self.asset_definitions.iter().collect();
+        asset_definitions.sort_by_key(|(id, _)| *id);
+        (&self.name, accounts, asset_definitions).encode_to(dest)
+    }
+}
+
+impl Decode for Domain {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+        #[allow(clippy::type_complexity)]
+        let (name, accounts, asset_definitions): (
+            Name,
+            Vec<(<Account as Identifiable>::Id, Account)>,
+            Vec<(<AssetDefinition as Identifiable>::Id, AssetDefinition)>,
+        ) = Decode::decode(input)?;
+        Ok(Domain {
+            name,
+            accounts: accounts.into_iter().collect(),
+            asset_definitions: asset_definitions.into_iter().collect(),
+        })
+    }
+}
+
 /// Iroha Special Instructions module provides `DomainInstruction` enum with all legal types of
 /// Domain related instructions as variants, implementations of generic Iroha Special Instructions
 /// and the `From/Into` implementations to convert `DomainInstruction` variants into generic ISI.
 pub mod isi {
     use super::*;
-    use crate::{isi::Register, permission::isi::PermissionInstruction};
+    use crate::{
+        account::validate_name,
+        isi::{Register, RenameDomain},
+        permission::{isi::PermissionInstruction, permission_asset_definition_id},
+    };
     use iroha_derive::*;
     use parity_scale_codec::{Decode, Encode};
+    use std::mem;
 
     /// Enumeration of all legal Domain related Instructions.
-    #[derive(Clone, Debug, Io, Encode, Decode)]
+    #[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
     pub enum DomainInstruction {
         /// Variant of the generic `Register` instruction for `Account` --> `Domain`.
         RegisterAccount(Name, Account),
         /// Variant of the generic `Register` instruction for `AssetDefinition` --> `Domain`.
         RegisterAsset(Name, AssetDefinition),
+        /// Variant of the `RenameDomain` instruction.
+        RenameDomain(Name, Name),
     }
 
     impl DomainInstruction {
@@ -83,6 +116,104 @@ pub mod isi {
                     Register::new(asset.clone(), domain_name.clone())
                         .execute(authority, world_state_view)
                 }
+                DomainInstruction::RenameDomain(from, to) => {
+                    RenameDomain::new(from, to).execute(authority, world_state_view)
+                }
+            }
+        }
+    }
+
+    impl From<RenameDomain> for Instruction {
+        fn from(instruction: RenameDomain) -> Self {
+            Instruction::Domain(DomainInstruction::RenameDomain(
+                instruction.from,
+                instruction.to,
+            ))
+        }
+    }
+
+    impl RenameDomain {
+        /// Move the domain under the new name and update ids referencing it in all domains.
+        ///
+        /// Permissions are opaque strings, so objects of permissions are left intact. The domains of
+        /// the permission and fee asset definitions can not be renamed, their ids are fixed.
+        fn execute(
+            &self,
+            authority: <Account as Identifiable>::Id,
+            world_state_view: &mut WorldStateView,
+        ) -> Result<(), String> {
+            PermissionInstruction::CanAddDomain(authority).execute(world_state_view)?;
+            validate_name(&self.to).map_err(|e| format!("Invalid domain name: {}", e))?;
+            if permission_asset_definition_id().domain_name == self.from {
+                return Err(format!(
+                    "Domain {} holds the permissions and can not be renamed.",
+                    self.from
+                ));
+            }
+            if let Some(fee_asset_definition_id) = &world_state_view
+                .transaction_limits()
+                .fee_asset_definition_id
+            {
+                if fee_asset_definition_id.domain_name == self.from {
+                    return Err(format!(
+                        "Domain {} holds the fee asset definition and can not be renamed.",
+                        self.from
+                    ));
+                }
+            }
+            let domains = &mut world_state_view.peer().domains;
+            if domains.contains_key(&self.to) {
+                return Err(format!("Domain {} already exists.", self.to));
+            }
+            let mut domain = domains
+                .remove(&self.from)
+                .ok_or(format!("Failed to find domain {}.", self.from))?;
+            domain.name = self.to.clone();
+            let _ = domains.insert(self.to.clone(), domain);
+            for domain in domains.values_mut() {
+                domain.accounts = mem::take(&mut domain.accounts)
+                    .into_values()
+                    .map(|mut account| {
+                        self.rename_account(&mut account);
+                        (account.id.clone(), account)
+                    })
+                    .collect();
+                domain.asset_definitions = mem::take(&mut domain.asset_definitions)
+                    .into_values()
+                    .map(|mut asset_definition| {
+                        self.rename_asset_definition_id(&mut asset_definition.id);
+                        self.rename_account_id(&mut asset_definition.owned_by);
+                        (asset_definition.id.clone(), asset_definition)
+                    })
+                    .collect();
+            }
+            Ok(())
+        }
+
+        fn rename_account(&self, account: &mut Account) {
+            self.rename_account_id(&mut account.id);
+            account.assets = mem::take(&mut account.assets)
+                .into_values()
+                .map(|mut asset| {
+                    self.rename_asset_definition_id(&mut asset.id.definition_id);
+                    self.rename_account_id(&mut asset.id.account_id);
+                    (asset.id.clone(), asset)
+                })
+                .collect();
+        }
+
+        fn rename_account_id(&self, account_id: &mut <Account as Identifiable>::Id) {
+            if account_id.domain_name == self.from {
+                account_id.domain_name = self.to.as_str().into();
+            }
+        }
+
+        fn rename_asset_definition_id(
+            &self,
+            definition_id: &mut <AssetDefinition as Identifiable>::Id,
+        ) {
+            if definition_id.domain_name == self.from {
+                definition_id.domain_name = self.to.as_str().into();
             }
         }
     }
@@ -104,6 +235,7 @@ pub mod isi {
         ) -> Result<(), String> {
             PermissionInstruction::CanRegisterAccount(authority, None).execute(world_state_view)?;
             let account = self.object.clone();
+            account.id.validate()?;
             let domain = world_state_view
                 .domain(&self.destination_id)
                 .ok_or("Failed to find domain.")?;
@@ -136,13 +268,601 @@ pub mod isi {
         ) -> Result<(), String> {
             PermissionInstruction::CanRegisterAssetDefinition(authority, None)
                 .execute(world_state_view)?;
-            let asset = self.object.clone();
-            world_state_view
+            let mut asset = self.object.clone();
+            asset.total_supply = 0;
+            let domain = world_state_view
                 .domain(&self.destination_id)
-                .ok_or("Failed to find domain.")?
+                .ok_or("Failed to find domain.")?;
+            if domain.asset_definitions.contains_key(&asset.id) {
+                Err(format!(
+                    "Asset definition already exists in the domain: {:?}",
+                    &asset.id
+                ))
+            } else {
+                domain.asset_definitions.insert(asset.id.clone(), asset);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Query module provides `IrohaQuery` Domain related implementations.
+pub mod query {
+    use super::*;
+    use crate::query::IrohaQuery;
+    use iroha_derive::{log, IntoQuery, Io};
+    use std::time::SystemTime;
+
+    /// To get a page of all domains ordered by their names, GetAllDomains query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetAllDomains {
+        offset: u64,
+        limit: u64,
+    }
+
+    /// Result of the `GetAllDomains` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetAllDomainsResult {
+        /// Domains of the requested page.
+        pub domains: Vec<Domain>,
+        /// Number of all domains.
+        pub total: u64,
+    }
+
+    impl GetAllDomains {
+        /// Build a `GetAllDomains` query in the form of a `QueryRequest`.
+        pub fn build_request(offset: u64, limit: u64) -> QueryRequest {
+            let query = GetAllDomains { offset, limit };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetAllDomains {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            Ok(QueryResult::GetAllDomains(GetAllDomainsResult {
+                domains: page(world_state_view, self.offset, self.limit)
+                    .cloned()
+                    .collect(),
+                total: world_state_view.domains_iter().count() as u64,
+            }))
+        }
+    }
+
+    /// To get a page of the asset definitions of a domain ordered by their ids,
+    /// GetDomainAssetDefinitions query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetDomainAssetDefinitions {
+        domain_name: Name,
+        offset: u64,
+        limit: u64,
+    }
+
+    /// Result of the `GetDomainAssetDefinitions` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetDomainAssetDefinitionsResult {
+        /// Asset definitions of the requested page.
+        pub asset_definitions: Vec<AssetDefinition>,
+        /// Number of all asset definitions of the domain.
+        pub total: u64,
+    }
+
+    impl GetDomainAssetDefinitions {
+        /// Build a `GetDomainAssetDefinitions` query in the form of a `QueryRequest`.
+        pub fn build_request(domain_name: Name, offset: u64, limit: u64) -> QueryRequest {
+            let query = GetDomainAssetDefinitions {
+                domain_name,
+                offset,
+                limit,
+            };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetDomainAssetDefinitions {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let domain = world_state_view
+                .read_domain(&self.domain_name)
+                .ok_or(format!("Failed to find domain {}.", self.domain_name))?;
+            let mut asset_definitions: Vec<&AssetDefinition> =
+                domain.asset_definitions.values().collect();
+            asset_definitions.sort_by(|a, b| a.id.cmp(&b.id));
+            Ok(QueryResult::GetDomainAssetDefinitions(
+                GetDomainAssetDefinitionsResult {
+                    asset_definitions: asset_definitions
+                        .into_iter()
+                        .skip(self.offset as usize)
+                        .take(self.limit as usize)
+                        .cloned()
+                        .collect(),
+                    total: domain.asset_definitions.len() as u64,
+                },
+            ))
+        }
+    }
+
+    /// To get the number of accounts and asset definitions of a domain without fetching them,
+    /// GetDomainStats query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetDomainStats {
+        domain_name: Name,
+    }
+
+    /// Result of the `GetDomainStats` execution.
+    #[derive(Debug, PartialEq, Eq, Encode, Decode)]
+    pub struct GetDomainStatsResult {
+        /// Number of accounts of the domain.
+        pub account_count: u64,
+        /// Number of asset definitions of the domain.
+        pub asset_definition_count: u64,
+        /// Sum of the total supplies of all asset definitions of the domain.
+        pub total_supply: u128,
+    }
+
+    impl GetDomainStats {
+        /// Build a `GetDomainStats` query in the form of a `QueryRequest`.
+        pub fn build_request(domain_name: Name) -> QueryRequest {
+            let query = GetDomainStats { domain_name };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetDomainStats {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let domain = world_state_view
+                .read_domain(&self.domain_name)
+                .ok_or(format!("Failed to find domain {}.", self.domain_name))?;
+            Ok(QueryResult::GetDomainStats(GetDomainStatsResult {
+                account_count: domain.accounts.len() as u64,
+                asset_definition_count: domain.asset_definitions.len() as u64,
+                total_supply: domain.asset_definitions.values().fold(
+                    0u128,
+                    |total_supply, asset_definition| {
+                        total_supply.saturating_add(asset_definition.total_supply)
+                    },
+                ),
+            }))
+        }
+    }
+
+    /// Domains are sorted by reference, so only the domains of the page are cloned.
+    pub(crate) fn page(
+        world_state_view: &WorldStateView,
+        offset: u64,
+        limit: u64,
+    ) -> impl Iterator<Item = &Domain> {
+        let mut domains: Vec<&Domain> = world_state_view.domains_iter().collect();
+        domains.sort_by(|a, b| a.name.cmp(&b.name));
+        domains
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{query::*, *};
+    use crate::{peer::PeerId, permission::isi::PermissionInstruction, tx::TransactionLimits};
+    use std::convert::TryFrom;
+
+    fn world_state_view_with_domains(n_domains: usize) -> WorldStateView {
+        let mut world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        ));
+        for index in 0..n_domains {
+            world_state_view.add_domain(Domain::new(format!("domain{:04}", index)));
+        }
+        world_state_view
+    }
+
+    #[test]
+    fn registering_existing_asset_definition_fails() {
+        let mut world_state_view = world_state_view_with_domains(0);
+        let root_id = AccountId::new("root", "global");
+        let permission_asset_id = AssetId {
+            definition_id: crate::permission::permission_asset_definition_id(),
+            account_id: root_id.clone(),
+        };
+        let mut root = Account::new("root", "global", [0; 32]);
+        root.assets.insert(
+            permission_asset_id.clone(),
+            Asset::with_permission(
+                permission_asset_id,
+                ("anything".to_string(), "".to_string()),
+            ),
+        );
+        let mut global = Domain::new("global".to_string());
+        global.accounts.insert(root_id.clone(), root);
+        world_state_view.add_domain(global);
+        world_state_view.add_domain(Domain::new("wonderland".to_string()));
+        let xor_id = AssetDefinitionId::new("xor", "wonderland");
+        let alice_id = AccountId::new("alice", "wonderland");
+        let bob_id = AccountId::new("bob", "wonderland");
+        let register = |owned_by: &AccountId| {
+            Instruction::from(Register::<Domain, AssetDefinition>::new(
+                AssetDefinition::new(xor_id.clone(), owned_by.clone()),
+                Name::from("wonderland"),
+            ))
+        };
+        register(&alice_id)
+            .execute(root_id.clone(), &mut world_state_view)
+            .expect("Failed to register asset definition.");
+        world_state_view
+            .asset_definition(&xor_id)
+            .expect("Failed to find asset definition.")
+            .total_supply = 100;
+        let error = register(&bob_id)
+            .execute(root_id, &mut world_state_view)
+            .expect_err("Asset definition is already registered.");
+        assert!(error.contains("already exists"));
+        let asset_definition = world_state_view
+            .read_asset_definition(&xor_id)
+            .expect("Failed to find asset definition.");
+        assert_eq!(alice_id, asset_definition.owned_by);
+        assert_eq!(100, asset_definition.total_supply);
+    }
+
+    #[test]
+    fn renamed_domain_is_reachable_under_new_name() {
+        let mut world_state_view = world_state_view_with_domains(0);
+        let root_id = AccountId::new("root", "global");
+        let permission_asset_id = AssetId {
+            definition_id: crate::permission::permission_asset_definition_id(),
+            account_id: root_id.clone(),
+        };
+        let mut root = Account::new("root", "global", [0; 32]);
+        root.assets.insert(
+            permission_asset_id.clone(),
+            Asset::with_permission(
+                permission_asset_id,
+                ("anything".to_string(), "".to_string()),
+            ),
+        );
+        let mut global = Domain::new("global".to_string());
+        global.accounts.insert(root_id.clone(), root);
+        world_state_view.add_domain(global);
+        let xor_id = AssetDefinitionId::new("xor", "wonderland");
+        let rose_id = AssetDefinitionId::new("rose", "garden");
+        let alice_id = AccountId::new("alice", "wonderland");
+        let bob_id = AccountId::new("bob", "wonderland");
+        let carol_id = AccountId::new("carol", "garden");
+        let mut wonderland = Domain::new("wonderland".to_string());
+        wonderland.asset_definitions.insert(
+            xor_id.clone(),
+            AssetDefinition::new(xor_id.clone(), alice_id.clone()),
+        );
+        let mut garden = Domain::new("garden".to_string());
+        garden.asset_definitions.insert(
+            rose_id.clone(),
+            AssetDefinition::new(rose_id.clone(), bob_id.clone()),
+        );
+        for account_id in &[&alice_id, &bob_id, &carol_id] {
+            let mut account = Account::new(&account_id.name, &account_id.domain_name, [0; 32]);
+            let asset_id = AssetId {
+                definition_id: xor_id.clone(),
+                account_id: (*account_id).clone(),
+            };
+            account
+                .assets
+                .insert(asset_id.clone(), Asset::with_quantity(asset_id, 10));
+            if account_id.domain_name == "wonderland" {
+                wonderland.accounts.insert((*account_id).clone(), account);
+            } else {
+                garden.accounts.insert((*account_id).clone(), account);
+            }
+        }
+        world_state_view.add_domain(wonderland);
+        world_state_view.add_domain(garden);
+        Instruction::from(RenameDomain::new("wonderland", "garden"))
+            .execute(root_id.clone(), &mut world_state_view)
+            .expect_err("Target domain is taken.");
+        Instruction::from(RenameDomain::new("atlantis", "looking-glass"))
+            .execute(root_id.clone(), &mut world_state_view)
+            .expect_err("Source domain does not exist.");
+        Instruction::from(RenameDomain::new("wonderland", "looking-glass"))
+            .execute(carol_id.clone(), &mut world_state_view)
+            .expect_err("Authority has no permission.");
+        Instruction::from(RenameDomain::new("wonderland", "looking-glass"))
+            .execute(root_id, &mut world_state_view)
+            .expect("Failed to rename domain.");
+        assert!(world_state_view.read_domain("wonderland").is_none());
+        assert!(world_state_view.read_account(&alice_id).is_none());
+        assert!(world_state_view.read_asset_definition(&xor_id).is_none());
+        let renamed_xor_id = AssetDefinitionId::new("xor", "looking-glass");
+        let renamed_alice_id = AccountId::new("alice", "looking-glass");
+        let renamed_bob_id = AccountId::new("bob", "looking-glass");
+        assert_eq!(
+            "looking-glass",
+            world_state_view
+                .read_domain("looking-glass")
+                .expect("Failed to find domain.")
+                .name
+        );
+        assert_eq!(
+            renamed_alice_id,
+            world_state_view
+                .read_asset_definition(&renamed_xor_id)
+                .expect("Failed to find asset definition.")
+                .owned_by
+        );
+        assert_eq!(
+            renamed_bob_id,
+            world_state_view
+                .read_asset_definition(&rose_id)
+                .expect("Failed to find asset definition.")
+                .owned_by
+        );
+        for account_id in &[renamed_alice_id, renamed_bob_id, carol_id] {
+            let account = world_state_view
+                .read_account(account_id)
+                .expect("Failed to find account.");
+            assert_eq!(account_id, &account.id);
+            let asset_id = AssetId {
+                definition_id: renamed_xor_id.clone(),
+                account_id: account_id.clone(),
+            };
+            assert_eq!(
+                10,
+                account
+                    .assets
+                    .get(&asset_id)
+                    .expect("Failed to find asset.")
+                    .quantity
+            );
+            assert_eq!(1, account.assets.len());
+        }
+    }
+
+    #[test]
+    fn domains_of_permission_and_fee_assets_are_not_renamed() {
+        let mut world_state_view = world_state_view_with_domains(0);
+        let root_id = AccountId::new("root", "global");
+        let permission_asset_id = AssetId {
+            definition_id: crate::permission::permission_asset_definition_id(),
+            account_id: root_id.clone(),
+        };
+        let mut root = Account::new("root", "global", [0; 32]);
+        root.assets.insert(
+            permission_asset_id.clone(),
+            Asset::with_permission(
+                permission_asset_id,
+                ("anything".to_string(), "".to_string()),
+            ),
+        );
+        let mut global = Domain::new("global".to_string());
+        global.accounts.insert(root_id.clone(), root);
+        world_state_view.add_domain(global);
+        world_state_view.add_domain(Domain::new("bank".to_string()));
+        world_state_view.set_transaction_limits(TransactionLimits {
+            fee_asset_definition_id: Some(AssetDefinitionId::new("fee", "bank")),
+            ..TransactionLimits::default()
+        });
+        let error = Instruction::from(RenameDomain::new("global", "world"))
+            .execute(root_id.clone(), &mut world_state_view)
+            .expect_err("Domain of the permissions should not be renamed.");
+        assert!(error.contains("holds the permissions"));
+        assert!(world_state_view.read_domain("global").is_some());
+        PermissionInstruction::CanAddDomain(root_id.clone())
+            .execute(&mut world_state_view)
+            .expect("Root should keep its permissions.");
+        let error = Instruction::from(RenameDomain::new("bank", "vault"))
+            .execute(root_id, &mut world_state_view)
+            .expect_err("Domain of the fee asset definition should not be renamed.");
+        assert!(error.contains("holds the fee asset definition"));
+        assert!(world_state_view.read_domain("bank").is_some());
+        assert_eq!(
+            Some(AssetDefinitionId::new("fee", "bank")),
+            world_state_view
+                .transaction_limits()
+                .fee_asset_definition_id
+                .clone()
+        );
+    }
+
+    #[test]
+    fn paginated_domains_query_clones_only_requested_page() {
+        let world_state_view = world_state_view_with_domains(1000);
+        let page_refs: Vec<&Domain> = page(&world_state_view, 990, 20).collect();
+        assert_eq!(10, page_refs.len());
+        // The page borrows domains from the world state, cloning is left to the caller.
+        for domain in &page_refs {
+            assert!(std::ptr::eq(
+                *domain,
+                world_state_view
+                    .read_domain(&domain.name)
+                    .expect("Failed to find domain.")
+            ));
+        }
+        let result = match GetAllDomains::build_request(100, 10)
+            .query
+            .execute(&world_state_view)
+            .expect("Failed to execute query.")
+        {
+            QueryResult::GetAllDomains(result) => result,
+            _ => panic!("Wrong Query Result Type."),
+        };
+        assert_eq!(1000, result.total);
+        assert_eq!(
+            (100..110)
+                .map(|index| format!("domain{:04}", index))
+                .collect::<Vec<String>>(),
+            result
+                .domains
+                .iter()
+                .map(|domain| domain.name.to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn asset_definitions_of_domain_are_paged() {
+        let mut world_state_view = world_state_view_with_domains(0);
+        let mut domain = Domain::new("wonderland".to_string());
+        for index in (0..25).rev() {
+            let asset_definition_id =
+                AssetDefinitionId::new(&format!("coin{:02}", index), "wonderland");
+            domain.asset_definitions.insert(
+                asset_definition_id.clone(),
+                AssetDefinition::new(asset_definition_id, AccountId::new("alice", "wonderland")),
+            );
+        }
+        world_state_view.add_domain(domain);
+        let page = |offset: u64| -> GetDomainAssetDefinitionsResult {
+            match GetDomainAssetDefinitions::build_request(Name::from("wonderland"), offset, 10)
+                .query
+                .execute(&world_state_view)
+                .expect("Failed to execute query.")
+            {
+                QueryResult::GetDomainAssetDefinitions(result) => result,
+                _ => panic!("Wrong Query Result Type."),
+            }
+        };
+        let mut names = Vec::new();
+        for offset in &[0, 10, 20] {
+            let result = page(*offset);
+            assert_eq!(25, result.total);
+            names.extend(
+                result
+                    .asset_definitions
+                    .into_iter()
+                    .map(|asset_definition| asset_definition.id.name),
+            );
+        }
+        assert_eq!(
+            (0..25)
+                .map(|index| format!("coin{:02}", index))
+                .collect::<Vec<String>>(),
+            names
+        );
+        assert!(page(30).asset_definitions.is_empty());
+        let error = GetDomainAssetDefinitions::build_request(Name::from("looking_glass"), 0, 10)
+            .query
+            .execute(&world_state_view)
+            .expect_err("Asset definitions of unknown domain were found.");
+        assert_eq!("Failed to find domain looking_glass.", error);
+    }
+
+    #[test]
+    fn domain_stats_count_accounts_and_asset_definitions() {
+        let mut world_state_view = world_state_view_with_domains(0);
+        let mut domain = Domain::new("wonderland".to_string());
+        for name in &["alice", "bob", "carol"] {
+            domain.accounts.insert(
+                AccountId::new(name, "wonderland"),
+                Account::new(name, "wonderland", [0; 32]),
+            );
+        }
+        for (name, total_supply) in &[("xor", 100), ("val", 20)] {
+            let asset_definition_id = AssetDefinitionId::new(name, "wonderland");
+            let mut asset_definition = AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::new("alice", "wonderland"),
+            );
+            asset_definition.total_supply = *total_supply;
+            domain
                 .asset_definitions
-                .insert(asset.id.clone(), asset);
-            Ok(())
+                .insert(asset_definition_id, asset_definition);
+        }
+        world_state_view.add_domain(domain);
+        world_state_view.add_domain(Domain::new("looking_glass".to_string()));
+        let stats = |domain_name: &str| -> Result<GetDomainStatsResult, String> {
+            match GetDomainStats::build_request(Name::from(domain_name))
+                .query
+                .execute(&world_state_view)?
+            {
+                QueryResult::GetDomainStats(result) => Ok(result),
+                _ => panic!("Wrong Query Result Type."),
+            }
+        };
+        assert_eq!(
+            Ok(GetDomainStatsResult {
+                account_count: 3,
+                asset_definition_count: 2,
+                total_supply: 120,
+            }),
+            stats("wonderland")
+        );
+        assert_eq!(
+            Ok(GetDomainStatsResult {
+                account_count: 0,
+                asset_definition_count: 0,
+                total_supply: 0,
+            }),
+            stats("looking_glass")
+        );
+        assert_eq!(
+            Err("Failed to find domain narnia.".to_string()),
+            stats("narnia")
+        );
+    }
+
+    #[test]
+    fn domain_encoding_round_trip() {
+        let mut domain = Domain::new("wonderland".to_string());
+        for name in &["alice", "bob"] {
+            domain.accounts.insert(
+                AccountId::new(name, "wonderland"),
+                Account::new(name, "wonderland", [0; 32]),
+            );
+        }
+        let asset_definition_id = AssetDefinitionId::new("xor", "wonderland");
+        domain.asset_definitions.insert(
+            asset_definition_id.clone(),
+            AssetDefinition::new(asset_definition_id, AccountId::new("alice", "wonderland")),
+        );
+        let decoded = Domain::decode(&mut domain.encode().as_slice()).expect("Failed to decode.");
+        assert_eq!(domain.name, decoded.name);
+        assert_eq!(domain.accounts, decoded.accounts);
+        assert_eq!(domain.asset_definitions, decoded.asset_definitions);
+        assert_eq!(domain.encode(), decoded.encode());
+        crate::assert_canonical_encoding(&domain);
+        match QueryResult::try_from(Vec::from(&QueryResult::GetAllDomains(
+            GetAllDomainsResult {
+                domains: vec![domain],
+                total: 1,
+            },
+        )))
+        .expect("Failed to decode query result.")
+        {
+            QueryResult::GetAllDomains(result) => assert_eq!(1, result.domains.len()),
+            _ => panic!("Wrong Query Result Type."),
         }
     }
 }
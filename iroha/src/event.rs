@@ -0,0 +1,300 @@
+//! This module contains `DataEvent` - typed notifications about the state changes made by the
+//! committed blocks, and `EventFilter` which is used by subscribers to select them.
+
+use crate::{isi::prelude::*, prelude::*};
+use parity_scale_codec::{Decode, Encode};
+
+/// Change of the world state made by a committed transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum DataEvent {
+    /// `Domain` with the name was created.
+    DomainCreated(String),
+    /// `Account` with the id was registered.
+    AccountRegistered(AccountId),
+    /// `AssetDefinition` with the id was registered.
+    AssetDefinitionRegistered(AssetDefinitionId),
+    /// Quantity of the asset was increased.
+    AssetMinted {
+        /// Asset which quantity was increased.
+        asset_id: AssetId,
+        /// Minted quantity.
+        quantity: u32,
+    },
+    /// Quantity of the asset was decreased without a transfer to another account.
+    AssetBurned {
+        /// Asset which quantity was decreased.
+        asset_id: AssetId,
+        /// Burned quantity.
+        quantity: u32,
+    },
+    /// Quantity of the asset was moved from one account to another.
+    AssetTransferred {
+        /// Account which sent the asset.
+        source_id: AccountId,
+        /// Account which received the asset.
+        destination_id: AccountId,
+        /// Definition of the transferred asset.
+        asset_definition_id: AssetDefinitionId,
+        /// Transferred quantity.
+        quantity: u32,
+    },
+}
+
+/// Kinds of `DataEvent` without the event's data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum EventKind {
+    /// See `DataEvent::DomainCreated`.
+    DomainCreated,
+    /// See `DataEvent::AccountRegistered`.
+    AccountRegistered,
+    /// See `DataEvent::AssetDefinitionRegistered`.
+    AssetDefinitionRegistered,
+    /// See `DataEvent::AssetMinted`.
+    AssetMinted,
+    /// See `DataEvent::AssetBurned`.
+    AssetBurned,
+    /// See `DataEvent::AssetTransferred`.
+    AssetTransferred,
+}
+
+impl DataEvent {
+    /// Kind of the event.
+    pub fn kind(&self) -> EventKind {
+        match self {
+            DataEvent::DomainCreated(_) => EventKind::DomainCreated,
+            DataEvent::AccountRegistered(_) => EventKind::AccountRegistered,
+            DataEvent::AssetDefinitionRegistered(_) => EventKind::AssetDefinitionRegistered,
+            DataEvent::AssetMinted { .. } => EventKind::AssetMinted,
+            DataEvent::AssetBurned { .. } => EventKind::AssetBurned,
+            DataEvent::AssetTransferred { .. } => EventKind::AssetTransferred,
+        }
+    }
+
+    fn account_ids(&self) -> Vec<&AccountId> {
+        match self {
+            DataEvent::DomainCreated(_) | DataEvent::AssetDefinitionRegistered(_) => Vec::new(),
+            DataEvent::AccountRegistered(account_id) => vec![account_id],
+            DataEvent::AssetMinted { asset_id, .. } | DataEvent::AssetBurned { asset_id, .. } => {
+                vec![&asset_id.account_id]
+            }
+            DataEvent::AssetTransferred {
+                source_id,
+                destination_id,
+                ..
+            } => vec![source_id, destination_id],
+        }
+    }
+
+    fn domain_names(&self) -> Vec<&str> {
+        match self {
+            DataEvent::DomainCreated(domain_name) => vec![domain_name],
+            DataEvent::AssetDefinitionRegistered(asset_definition_id) => {
+                vec![&asset_definition_id.domain_name]
+            }
+            DataEvent::AssetMinted { asset_id, .. } | DataEvent::AssetBurned { asset_id, .. } => {
+                vec![
+                    &asset_id.definition_id.domain_name,
+                    &asset_id.account_id.domain_name,
+                ]
+            }
+            DataEvent::AssetTransferred {
+                asset_definition_id,
+                ..
+            } => {
+                let mut domain_names = vec![asset_definition_id.domain_name.as_str()];
+                domain_names.extend(
+                    self.account_ids()
+                        .into_iter()
+                        .map(|account_id| account_id.domain_name.as_str()),
+                );
+                domain_names
+            }
+            DataEvent::AccountRegistered(account_id) => vec![&account_id.domain_name],
+        }
+    }
+
+    /// Build events from the successfully executed `instruction`.
+    ///
+    /// Composite instructions emit no events themselves, their parts do when they are executed.
+    pub fn from_instruction(instruction: &Instruction) -> Vec<DataEvent> {
+        match instruction {
+            Instruction::Peer(PeerInstruction::AddDomain(domain_name, _)) => {
+                vec![DataEvent::DomainCreated(domain_name.to_string())]
+            }
+            Instruction::Domain(DomainInstruction::RegisterAccount(_, account)) => {
+                vec![DataEvent::AccountRegistered(account.id.clone())]
+            }
+            Instruction::Domain(DomainInstruction::RegisterAsset(_, asset_definition)) => {
+                vec![DataEvent::AssetDefinitionRegistered(
+                    asset_definition.id.clone(),
+                )]
+            }
+            Instruction::Asset(AssetInstruction::MintAsset(quantity, asset_id)) => {
+                vec![DataEvent::AssetMinted {
+                    asset_id: asset_id.clone(),
+                    quantity: *quantity,
+                }]
+            }
+            Instruction::Asset(AssetInstruction::MintAssetToMany(definition_id, recipients)) => {
+                recipients
+                    .iter()
+                    .map(|(account_id, quantity)| DataEvent::AssetMinted {
+                        asset_id: AssetId {
+                            definition_id: definition_id.clone(),
+                            account_id: account_id.clone(),
+                        },
+                        quantity: *quantity,
+                    })
+                    .collect()
+            }
+            Instruction::Asset(AssetInstruction::BurnAsset(quantity, asset_id)) => {
+                vec![DataEvent::AssetBurned {
+                    asset_id: asset_id.clone(),
+                    quantity: *quantity,
+                }]
+            }
+            Instruction::Account(AccountInstruction::TransferAsset(
+                source_id,
+                destination_id,
+                asset,
+                _,
+            )) => vec![DataEvent::AssetTransferred {
+                source_id: source_id.clone(),
+                destination_id: destination_id.clone(),
+                asset_definition_id: asset.id.definition_id.clone(),
+                quantity: asset.quantity,
+            }],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Selects events a subscriber is interested in. Empty filter matches all events.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub struct EventFilter {
+    /// Match only events involving entities of the domain.
+    pub domain_name: Option<String>,
+    /// Match only events involving the account.
+    pub account_id: Option<AccountId>,
+    /// Match only events of the kind.
+    pub kind: Option<EventKind>,
+}
+
+impl EventFilter {
+    /// Narrow the filter to events involving entities of the domain.
+    pub fn domain_name(mut self, domain_name: &str) -> Self {
+        self.domain_name = Some(domain_name.to_string());
+        self
+    }
+
+    /// Narrow the filter to events involving the account.
+    pub fn account_id(mut self, account_id: AccountId) -> Self {
+        self.account_id = Some(account_id);
+        self
+    }
+
+    /// Narrow the filter to events of the kind.
+    pub fn kind(mut self, kind: EventKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Check whether the event satisfies all conditions of the filter.
+    pub fn matches(&self, event: &DataEvent) -> bool {
+        self.kind.iter().all(|kind| *kind == event.kind())
+            && self
+                .account_id
+                .iter()
+                .all(|account_id| event.account_ids().contains(&account_id))
+            && self
+                .domain_name
+                .iter()
+                .all(|domain_name| event.domain_names().contains(&domain_name.as_str()))
+    }
+}
+
+/// Query module provides `IrohaQuery` Event related implementations.
+pub mod query {
+    use super::*;
+    use crate::query::IrohaQuery;
+    use iroha_derive::{log, IntoQuery, Io};
+    use std::time::SystemTime;
+
+    /// To get events emitted starting from the given index and matching the filter,
+    /// GetEvents query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetEvents {
+        from_index: u64,
+        filter: EventFilter,
+    }
+
+    /// Result of the `GetEvents` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetEventsResult {
+        /// Events matching the filter ordered by their emission.
+        pub events: Vec<DataEvent>,
+        /// Index to request the following events from.
+        pub next_index: u64,
+    }
+
+    impl GetEvents {
+        /// Build a `GetEvents` query in the form of a `QueryRequest`.
+        pub fn build_request(from_index: u64, filter: EventFilter) -> QueryRequest {
+            let query = GetEvents { from_index, filter };
+            QueryRequest {
+                timestamp: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("Failed to get System Time.")
+                    .as_millis()
+                    .to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetEvents {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            let (events, next_index) = world_state_view.read_events(self.from_index);
+            Ok(QueryResult::GetEvents(GetEventsResult {
+                events: events
+                    .filter(|event| self.filter.matches(event))
+                    .cloned()
+                    .collect(),
+                next_index,
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_by_kind_and_account() {
+        let alice_id = AccountId::new("alice", "wonderland");
+        let bob_id = AccountId::new("bob", "wonderland");
+        let transfer = DataEvent::AssetTransferred {
+            source_id: alice_id.clone(),
+            destination_id: bob_id.clone(),
+            asset_definition_id: AssetDefinitionId::new("xor", "wonderland"),
+            quantity: 10,
+        };
+        let registration = DataEvent::AccountRegistered(alice_id);
+        let filter = EventFilter::default()
+            .kind(EventKind::AssetTransferred)
+            .account_id(bob_id.clone());
+        assert!(filter.matches(&transfer));
+        assert!(!filter.matches(&registration));
+        assert!(EventFilter::default()
+            .domain_name("wonderland")
+            .matches(&registration));
+        assert!(!EventFilter::default()
+            .domain_name("looking_glass")
+            .matches(&transfer));
+    }
+}
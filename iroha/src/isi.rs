@@ -1,6 +1,6 @@
 //! This module contains enumeration of all legal Iroha Special Instructions `Instruction`,
 //! generic instruction types and related implementations.
-use crate::prelude::*;
+use crate::{event::DataEvent, prelude::*};
 use iroha_derive::Io;
 use parity_scale_codec::{Decode, Encode};
 
@@ -10,7 +10,7 @@ pub mod prelude {
 }
 
 /// Enumeration of all legal Iroha Special Instructions.
-#[derive(Clone, Debug, Io, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
 #[allow(clippy::large_enum_variant)]
 pub enum Instruction {
     /// Variant of instructions related to `Peer`.
@@ -31,6 +31,8 @@ pub enum Instruction {
     If(Box<Instruction>, Box<Instruction>, Option<Box<Instruction>>),
     /// This variant of Iroha Special Instructions sends notifications.
     Notify(String),
+    /// Variant of instructions checking preconditions without changing the state.
+    Assert(AssertInstruction),
 }
 
 impl Instruction {
@@ -41,36 +43,203 @@ impl Instruction {
         world_state_view: &mut WorldStateView,
     ) -> Result<(), String> {
         match self {
-            Instruction::Peer(origin) => Ok(origin.execute(authority, world_state_view)?),
-            Instruction::Domain(origin) => Ok(origin.execute(authority, world_state_view)?),
-            Instruction::Asset(origin) => Ok(origin.execute(authority, world_state_view)?),
-            Instruction::Account(origin) => Ok(origin.execute(authority, world_state_view)?),
-            Instruction::Permission(origin) => Ok(origin.execute(world_state_view)?),
+            Instruction::Peer(origin) => origin.execute(authority, world_state_view)?,
+            Instruction::Domain(origin) => origin.execute(authority, world_state_view)?,
+            Instruction::Asset(origin) => origin.execute(authority, world_state_view)?,
+            Instruction::Account(origin) => origin.execute(authority, world_state_view)?,
+            Instruction::Permission(origin) => origin.execute(world_state_view)?,
             Instruction::Compose(left, right) => {
                 left.execute(authority.clone(), world_state_view)?;
                 right.execute(authority, world_state_view)?;
-                Ok(())
             }
             Instruction::If(condition, then, otherwise) => {
                 match condition.execute(authority.clone(), world_state_view) {
-                    Ok(_) => then.execute(authority, world_state_view),
+                    Ok(_) => then.execute(authority, world_state_view)?,
                     Err(_) => {
                         if let Some(otherwise) = otherwise {
-                            otherwise.execute(authority, world_state_view)
-                        } else {
-                            Ok(())
+                            otherwise.execute(authority, world_state_view)?
                         }
                     }
                 }
             }
-            Instruction::Notify(message) => {
-                println!("Notification: {}", message);
+            Instruction::Notify(message) => println!("Notification: {}", message),
+            Instruction::Assert(origin) => origin.execute(world_state_view)?,
+        }
+        for event in DataEvent::from_instruction(self) {
+            world_state_view.push_event(event);
+        }
+        Ok(())
+    }
+
+    /// Stateless checks of the instruction, e.g. empty names, zero quantities or transfers to
+    /// the source account, which reject it before it is executed on `WorldStateView`.
+    pub fn validate(&self) -> Result<(), String> {
+        use crate::{
+            account::isi::AccountInstruction, asset::isi::AssetInstruction,
+            domain::isi::DomainInstruction, peer::isi::PeerInstruction,
+        };
+        match self {
+            Instruction::Peer(PeerInstruction::AddDomain(domain_name, _)) => {
+                check_not_empty("Domain name", domain_name)
+            }
+            Instruction::Peer(PeerInstruction::AddListener(listener, _)) => listener.validate(),
+            Instruction::Peer(PeerInstruction::AddPeer(peer_id, _)) => peer_id.validate(),
+            Instruction::Domain(DomainInstruction::RegisterAccount(domain_name, account)) => {
+                check_not_empty("Domain name", domain_name)?;
+                account.id.validate()
+            }
+            Instruction::Domain(DomainInstruction::RegisterAsset(
+                domain_name,
+                asset_definition,
+            )) => {
+                check_not_empty("Domain name", domain_name)?;
+                check_not_empty("Asset definition name", &asset_definition.id.name)
+            }
+            Instruction::Domain(DomainInstruction::RenameDomain(from, to)) => {
+                check_not_empty("Domain name", from)?;
+                check_not_empty("New domain name", to)
+            }
+            Instruction::Asset(AssetInstruction::MintAsset(quantity, _))
+            | Instruction::Asset(AssetInstruction::BurnAsset(quantity, _)) => {
+                check_not_zero(u128::from(*quantity))
+            }
+            Instruction::Asset(AssetInstruction::MintBigAsset(big_quantity, _)) => {
+                check_not_zero(*big_quantity)
+            }
+            Instruction::Asset(crate::asset::isi::AssetInstruction::MintAssetToMany(
+                _,
+                recipients,
+            )) => recipients
+                .iter()
+                .try_for_each(|(_, quantity)| check_not_zero(u128::from(*quantity))),
+            Instruction::Account(AccountInstruction::TransferAsset(
+                source_account_id,
+                destination_account_id,
+                asset,
+                _,
+            )) => {
+                if source_account_id == destination_account_id {
+                    return Err(format!(
+                        "Transfer source and destination are the same account {}.",
+                        source_account_id
+                    ));
+                }
+                check_not_zero(u128::from(asset.quantity))
+            }
+            Instruction::Compose(left, right) => {
+                left.validate()?;
+                right.validate()
+            }
+            Instruction::If(condition, then, otherwise) => {
+                condition.validate()?;
+                then.validate()?;
+                otherwise
+                    .as_ref()
+                    .map_or(Ok(()), |otherwise| otherwise.validate())
+            }
+            Instruction::Peer(_)
+            | Instruction::Asset(_)
+            | Instruction::Account(_)
+            | Instruction::Permission(_)
+            | Instruction::Notify(_)
+            | Instruction::Assert(_) => Ok(()),
+        }
+    }
+
+    /// Total cost of the `instructions` of a transaction, the same on the peers and the clients
+    /// estimating it before a submission.
+    pub fn total_cost(instructions: &[Instruction]) -> u64 {
+        instructions.iter().fold(0, |cost, instruction| {
+            cost.saturating_add(instruction.cost())
+        })
+    }
+
+    /// Static weight of the instruction used to limit resources consumed by a transaction.
+    ///
+    /// Composite instructions cost the sum of their parts, `If` takes the most expensive branch.
+    /// Minting to many accounts costs as much as minting to each of them.
+    pub fn cost(&self) -> u64 {
+        match self {
+            Instruction::Peer(_) => 10,
+            Instruction::Domain(_) => 5,
+            Instruction::Asset(crate::asset::isi::AssetInstruction::MintAssetToMany(
+                _,
+                recipients,
+            )) => 2u64.saturating_mul(recipients.len().max(1) as u64),
+            Instruction::Asset(_) | Instruction::Account(_) => 2,
+            Instruction::Permission(_) | Instruction::Notify(_) | Instruction::Assert(_) => 1,
+            Instruction::Compose(left, right) => left.cost().saturating_add(right.cost()),
+            Instruction::If(condition, then, otherwise) => condition.cost().saturating_add(
+                then.cost()
+                    .max(otherwise.as_ref().map_or(0, |otherwise| otherwise.cost())),
+            ),
+        }
+    }
+}
+
+fn check_not_empty(what: &str, name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        Err(format!("{} should not be empty.", what))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_not_zero(quantity: u128) -> Result<(), String> {
+    if quantity == 0 {
+        Err("Quantity should not be zero.".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Iroha special instructions checking preconditions, e.g. before a transfer.
+///
+/// An assertion does not change the state, if its condition is false it fails and the whole
+/// transaction containing it is rejected, so instructions following it are not applied.
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
+pub enum AssertInstruction {
+    /// Check that the account exists.
+    AssertAccountExists(<Account as Identifiable>::Id),
+    /// Check that the asset exists and its quantity is not less than the given one.
+    AssertAssetBalanceAtLeast(<Asset as Identifiable>::Id, u32),
+}
+
+impl AssertInstruction {
+    /// Checks the condition on `WorldStateView`.
+    ///
+    /// Returns `Ok(())` if the condition is true and `Err(String)` otherwise.
+    pub fn execute(&self, world_state_view: &WorldStateView) -> Result<(), String> {
+        match self {
+            AssertInstruction::AssertAccountExists(account_id) => world_state_view
+                .read_account(account_id)
+                .map(|_| ())
+                .ok_or(format!(
+                    "Assertion failed: account {} not found.",
+                    account_id
+                )),
+            AssertInstruction::AssertAssetBalanceAtLeast(asset_id, quantity) => {
+                let balance = world_state_view
+                    .read_asset(asset_id)
+                    .map_or(0, |asset| asset.quantity);
+                if balance < *quantity {
+                    return Err(format!(
+                        "Assertion failed: balance of {} is {}, expected at least {}.",
+                        asset_id, balance, quantity
+                    ));
+                }
                 Ok(())
             }
         }
     }
 }
 
+impl From<AssertInstruction> for Instruction {
+    fn from(instruction: AssertInstruction) -> Instruction {
+        Instruction::Assert(instruction)
+    }
+}
+
 /// Generic instruction for an addition of an object to the identifiable destination.
 pub struct Add<D, O>
 where
@@ -143,6 +312,69 @@ where
     }
 }
 
+/// Generic instruction for a burn of an object from the identifiable destination.
+pub struct Burn<D, O>
+where
+    D: Identifiable,
+{
+    /// Object which should be burned.
+    pub object: O,
+    /// Destination object `Id`.
+    pub destination_id: D::Id,
+}
+
+impl<D, O> Burn<D, O>
+where
+    D: Identifiable,
+{
+    /// Default `Burn` constructor.
+    pub fn new(object: O, destination_id: D::Id) -> Self {
+        Burn {
+            object,
+            destination_id,
+        }
+    }
+}
+
+/// Instruction for a mint of the asset to several accounts at once.
+///
+/// Either all recipients are credited or none of them.
+pub struct MintMany {
+    /// Definition of the minted asset.
+    pub definition_id: AssetDefinitionId,
+    /// Accounts which should be credited and the quantities minted to them.
+    pub recipients: Vec<(AccountId, u32)>,
+}
+
+impl MintMany {
+    /// Default `MintMany` constructor.
+    pub fn new(definition_id: AssetDefinitionId, recipients: Vec<(AccountId, u32)>) -> Self {
+        MintMany {
+            definition_id,
+            recipients,
+        }
+    }
+}
+
+/// Instruction for a renaming of the domain, ids of its accounts and asset definitions are
+/// updated everywhere in the world state.
+pub struct RenameDomain {
+    /// Current name of the domain.
+    pub from: Name,
+    /// New name of the domain, should not be taken.
+    pub to: Name,
+}
+
+impl RenameDomain {
+    /// Default `RenameDomain` constructor.
+    pub fn new(from: &str, to: &str) -> Self {
+        RenameDomain {
+            from: Name::new(from),
+            to: Name::new(to),
+        }
+    }
+}
+
 /// Generic instruction for a transfer of an object from the identifiable source to the identifiable destination.
 pub struct Transfer<Src: Identifiable, Obj, Dst: Identifiable> {
     /// Source object `Id`.
@@ -151,6 +383,8 @@ pub struct Transfer<Src: Identifiable, Obj, Dst: Identifiable> {
     pub object: Obj,
     /// Destination object `Id`.
     pub destination_id: Dst::Id,
+    /// Optional reference attached to the transfer, covered by the transaction signature.
+    pub memo: Option<String>,
 }
 
 impl<Src: Identifiable, Obj, Dst: Identifiable> Transfer<Src, Obj, Dst> {
@@ -160,6 +394,13 @@ impl<Src: Identifiable, Obj, Dst: Identifiable> Transfer<Src, Obj, Dst> {
             source_id,
             object,
             destination_id,
+            memo: None,
         }
     }
+
+    /// Attach the `memo` to the transfer.
+    pub fn with_memo(mut self, memo: &str) -> Self {
+        self.memo = Some(memo.to_string());
+        self
+    }
 }
@@ -1,26 +1,84 @@
 //! This module contains persistence related Iroha logic.
 //! `Kura` is the main entity which should be used to store new `Block`s on the blockchain.
 
-use crate::{merkle::MerkleTree, prelude::*};
+use crate::{block::DEFAULT_MAX_BLOCK_BYTES, merkle::MerkleTree, prelude::*};
 use async_std::{
-    fs::{metadata, File},
+    fs::{metadata, remove_file, File, OpenOptions},
+    io::SeekFrom,
     prelude::*,
+    task,
 };
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use futures::future;
 use iroha_derive::log;
+use iroha_macro::log::{self as logger, Level};
 use std::{
-    convert::TryFrom,
+    collections::{BTreeMap, VecDeque},
+    convert::{TryFrom, TryInto},
     fs,
     path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
+const DEFAULT_BLOCK_CACHE_SIZE: usize = 100;
+/// Number of block files `Kura::store_batch` flushes to the disk at once.
+const BATCH_SYNC_CHUNK_SIZE: usize = 100;
+const BLOCK_LOG_FILE_NAME: &str = "blocks.log";
+const BLOCK_INDEX_FILE_NAME: &str = "blocks.idx";
+/// Size of the little endian length written before every block in the log.
+const BLOCK_LENGTH_PREFIX_SIZE: u64 = 8;
+/// Size of the little endian `(height, offset, length)` record of the index.
+const BLOCK_INDEX_RECORD_SIZE: usize = 24;
+/// Size of the random nonce written before every encrypted block.
+const BLOCK_NONCE_SIZE: usize = 12;
+/// Version of the format of the encrypted blocks.
+const BLOCK_ENCRYPTION_VERSION: u8 = 1;
+/// Size of the version and the little endian height written before the nonce of every encrypted
+/// block.
+const BLOCK_ENCRYPTION_HEADER_SIZE: usize = 9;
+
+/// Key of the ChaCha20-Poly1305 encryption of the blocks at rest.
+pub type BlockEncryptionKey = [u8; 32];
+
 /// High level data storage representation.
 /// Provides all necessary methods to read and write data, hides implementation details.
+///
+/// `Kura` has no locks of its own: it is `Send` and `Sync` and is shared between tasks, e.g. the
+/// block synchronizer and the task storing the committed blocks, as `Arc<RwLock<Kura>>`. Blocks
+/// are stored under the write lock one at a time and readers never see a partially stored block.
+/// Only the block store is shared outside of the lock, with the `verify_chain` scan, which reads
+/// it through its own counters and index locks.
 #[derive(Debug)]
 pub struct Kura {
     mode: Mode,
     blocks: Vec<ValidBlock>,
-    block_store: BlockStore,
+    /// Height of the first block in `blocks`, blocks below it are read from the `block_store`.
+    blocks_offset: u64,
+    max_blocks_in_memory: Option<usize>,
+    /// Blocks with a larger SCALE encoding are rejected.
+    max_block_bytes: usize,
+    /// Blocks below this height are covered by a verified state snapshot.
+    snapshot_height: Option<u64>,
+    /// Blocks starting from this height are being sent to a syncing peer.
+    sync_lock_height: Option<u64>,
+    /// Height and reason of the first broken block skipped by the `Mode::Recovery` init.
+    broken_block: Option<(u64, String)>,
+    /// Shared with the background `verify_chain` scan.
+    block_store: Arc<BlockStore>,
+    block_cache: BlockCache,
     block_sender: CommittedBlockSender,
+    /// Hashes of all stored blocks starting from the first one, including the blocks evicted
+    /// from memory.
+    block_hashes: Vec<Hash>,
+    /// Built over `block_hashes`.
     merkle_tree: MerkleTree,
 }
 
@@ -30,45 +88,443 @@ impl Kura {
     pub fn new(mode: Mode, block_store_path: &Path, block_sender: CommittedBlockSender) -> Self {
         Kura {
             mode,
-            block_store: BlockStore::new(block_store_path),
+            block_store: Arc::new(BlockStore::new(block_store_path)),
+            block_cache: BlockCache::new(DEFAULT_BLOCK_CACHE_SIZE),
             block_sender,
+            block_hashes: Vec::new(),
             merkle_tree: MerkleTree::new(),
             blocks: Vec::new(),
+            blocks_offset: 0,
+            max_blocks_in_memory: None,
+            max_block_bytes: DEFAULT_MAX_BLOCK_BYTES,
+            snapshot_height: None,
+            sync_lock_height: None,
+            broken_block: None,
         }
     }
 
+    /// Set the layout of the block store, should be called before `init`.
+    pub fn block_store_layout(&mut self, layout: BlockStoreLayout) {
+        let mut block_store = BlockStore::with_layout(&self.block_store.path, layout);
+        block_store.cipher = self.block_store_mut().cipher.take();
+        self.block_store = Arc::new(block_store);
+    }
+
+    /// Encrypt blocks written to the block store with the `key` and decrypt them on read,
+    /// should be called before `init`.
+    pub fn block_store_encryption_key(&mut self, key: BlockEncryptionKey) {
+        self.block_store_mut().encryption_key(key);
+    }
+
+    fn block_store_mut(&mut self) -> &mut BlockStore {
+        Arc::get_mut(&mut self.block_store)
+            .expect("Block store should be configured before `verify_chain` is started.")
+    }
+
+    /// Set the number of decoded blocks read from the block store to keep cached.
+    pub fn block_cache_size(&mut self, block_cache_size: usize) {
+        self.block_cache = BlockCache::new(block_cache_size);
+    }
+
+    /// Set the maximum size of the SCALE encoded blocks, including signatures, larger blocks are
+    /// rejected by `store` and `store_batch`.
+    pub fn max_block_bytes(&mut self, max_block_bytes: usize) {
+        self.max_block_bytes = max_block_bytes;
+    }
+
+    /// Keep only the most recent `max_blocks_in_memory` blocks in memory, older blocks will be
+    /// read from the block store. The top block is always kept in memory.
+    pub fn max_blocks_in_memory(&mut self, max_blocks_in_memory: usize) {
+        self.max_blocks_in_memory = Some(max_blocks_in_memory.max(1));
+        self.evict_blocks_from_memory();
+    }
+
     /// After constructing `Kura` it should be initialized to be ready to work with it.
     pub async fn init(&mut self) -> Result<(), String> {
-        let blocks = self.block_store.read_all().await;
-        let blocks_refs = blocks.iter().collect::<Vec<&ValidBlock>>();
-        self.merkle_tree.build(&blocks_refs);
+        self.block_store.load().await?;
+        let first_block_height = self.block_store.first_block_height().unwrap_or(0);
+        let blocks = if let Mode::Recovery = self.mode {
+            let (blocks, broken_block) = self
+                .block_store
+                .read_consistent_from(first_block_height)
+                .await;
+            if let Some((height, reason)) = &broken_block {
+                eprintln!(
+                    "Kura stopped initialization at the broken block at height {}: {}",
+                    height, reason
+                );
+                self.block_store.set_aside_from(*height).await?;
+            }
+            self.broken_block = broken_block;
+            blocks
+        } else {
+            self.block_store.read_all_from(first_block_height).await
+        };
+        if let Mode::Strict = self.mode {
+            for block in &blocks {
+                block.verify_merkle_root_hash()?;
+            }
+        }
+        self.block_hashes = blocks.iter().map(ValidBlock::hash).collect();
+        self.merkle_tree.build_from_hashes(&self.block_hashes);
         self.blocks = blocks;
+        self.blocks_offset = first_block_height;
+        self.evict_blocks_from_memory();
+        self.block_store.measure();
+        Ok(())
+    }
+
+    /// Height and reason of the first broken block if `Mode::Recovery` init stopped at it.
+    pub fn broken_block(&self) -> Option<(u64, &str)> {
+        self.broken_block
+            .as_ref()
+            .map(|(height, reason)| (*height, reason.as_str()))
+    }
+
+    /// Current storage metrics of the block store.
+    pub fn metrics(&self) -> KuraMetrics {
+        self.block_store.metrics()
+    }
+
+    /// Start a background scan checking up to `window` stored blocks every `interval` the way
+    /// `BlockStore::integrity_check` does. Every scan continues from the block the previous one
+    /// stopped at and wraps around after the top block, so the whole chain is covered over time.
+    ///
+    /// The scan reads the block store without locking `Kura`, so `store` is not blocked by it.
+    /// Corrupted blocks are logged and counted in `KuraMetrics`, the peer keeps running.
+    pub fn verify_chain(&self, interval: Duration, window: u64) -> task::JoinHandle<()> {
+        let block_store = Arc::clone(&self.block_store);
+        let window = window.max(1);
+        task::spawn(async move {
+            let mut next_height = 0;
+            loop {
+                task::sleep(interval).await;
+                next_height = block_store.verify_window(next_height, window).await;
+            }
+        })
+    }
+
+    /// Mark blocks below `height` as covered by a verified state snapshot, so they can be pruned.
+    pub fn snapshot_height(&mut self, height: u64) {
+        self.snapshot_height = Some(height);
+    }
+
+    /// Protect blocks starting from `height` from pruning while they are sent to a syncing peer.
+    pub fn lock_for_sync(&mut self, height: u64) {
+        self.sync_lock_height = Some(height);
+    }
+
+    /// Allow pruning of the blocks protected by `lock_for_sync`.
+    pub fn unlock_for_sync(&mut self) {
+        self.sync_lock_height = None;
+    }
+
+    /// Delete blocks below `keep_from_height` from the block store.
+    ///
+    /// Returns `Err(String)` if the blocks are not covered by a snapshot, are needed by an
+    /// in-progress sync or if the top block would be deleted.
+    pub async fn prune(&mut self, keep_from_height: u64) -> Result<(), String> {
+        let first_block_height = self.block_store.first_block_height().unwrap_or(0);
+        if keep_from_height <= first_block_height {
+            return Ok(());
+        }
+        let snapshot_height = self.snapshot_height.unwrap_or(0);
+        if keep_from_height > snapshot_height {
+            return Err(format!(
+                "Blocks below height {} are not covered by a snapshot at height {}.",
+                keep_from_height, snapshot_height
+            ));
+        }
+        if let Some(sync_lock_height) = self.sync_lock_height {
+            if keep_from_height > sync_lock_height {
+                return Err(format!(
+                    "Blocks from height {} are needed to serve a sync.",
+                    sync_lock_height
+                ));
+            }
+        }
+        let top_block_height = self.height();
+        if keep_from_height >= top_block_height {
+            return Err(format!(
+                "Top block at height {} can not be pruned.",
+                top_block_height.saturating_sub(1)
+            ));
+        }
+        for height in first_block_height..keep_from_height {
+            self.block_store.delete(height).await?;
+        }
+        self.block_cache.remove_below(keep_from_height);
+        self.block_hashes
+            .drain(..(keep_from_height - first_block_height) as usize);
+        self.merkle_tree.build_from_hashes(&self.block_hashes);
+        if keep_from_height > self.blocks_offset {
+            self.blocks
+                .drain(..(keep_from_height - self.blocks_offset) as usize);
+            self.blocks_offset = keep_from_height;
+        }
         Ok(())
     }
 
     /// Methods consumes new validated block and atomically stores and caches it.
+    ///
+    /// The block should continue the local blockchain, blocks of other forks or out of order
+    /// are rejected.
     #[log]
-    pub async fn store(&mut self, mut block: ValidBlock) -> Result<Hash, String> {
-        if !self.blocks.is_empty() {
-            let last_block_index = self.blocks.len() - 1;
-            block.header.height = last_block_index as u64 + 1;
-            block.header.previous_block_hash = self.blocks.as_mut_slice()[last_block_index].hash();
-        }
+    pub async fn store(&mut self, block: ValidBlock) -> Result<Hash, String> {
+        let (height, previous_block_hash) = self.next_block_position()?;
+        Kura::check_continues_chain(&block, height, previous_block_hash)?;
+        self.check_block_size(&block)?;
         let block_store_result = self.block_store.write(&block).await;
         match block_store_result {
             Ok(hash) => {
+                logger::log(
+                    Level::Info,
+                    module_path!(),
+                    &format!(
+                        "Stored block {} at height {}.",
+                        HashHex(hash),
+                        block.header.height
+                    ),
+                );
                 self.block_sender.send(block.clone().commit()).await;
                 self.blocks.push(block);
+                self.evict_blocks_from_memory();
+                self.block_hashes.push(hash);
+                self.merkle_tree.build_from_hashes(&self.block_hashes);
                 Ok(hash)
             }
             Err(error) => {
-                let blocks = self.block_store.read_all().await;
-                let blocks_refs = blocks.iter().collect::<Vec<&ValidBlock>>();
-                self.merkle_tree.build(&blocks_refs);
+                let first_block_height = self.block_store.first_block_height().unwrap_or(0);
+                let blocks = self.block_store.read_all_from(first_block_height).await;
+                self.block_hashes = blocks.iter().map(ValidBlock::hash).collect();
+                self.merkle_tree.build_from_hashes(&self.block_hashes);
+                Err(error)
+            }
+        }
+    }
+
+    /// Store a chain of blocks continuing the local blockchain with a single flush to the disk.
+    ///
+    /// Nothing is kept in memory or sent to `WorldStateView` unless all blocks were written.
+    #[log]
+    pub async fn store_batch(&mut self, blocks: Vec<ValidBlock>) -> Result<Vec<Hash>, String> {
+        let (height, mut previous_block_hash) = self.next_block_position()?;
+        let mut next_height = Some(height);
+        for block in &blocks {
+            let height = next_height.ok_or_else(|| Kura::max_height_error(u64::MAX))?;
+            Kura::check_continues_chain(block, height, previous_block_hash)?;
+            self.check_block_size(block)?;
+            previous_block_hash = Some(block.hash());
+            next_height = height.checked_add(1);
+        }
+        match self.block_store.write_batch(&blocks).await {
+            Ok(hashes) => {
+                for (block, hash) in blocks.into_iter().zip(&hashes) {
+                    logger::log(
+                        Level::Info,
+                        module_path!(),
+                        &format!(
+                            "Stored block {} at height {}.",
+                            HashHex(*hash),
+                            block.header.height
+                        ),
+                    );
+                    self.block_sender.send(block.clone().commit()).await;
+                    self.blocks.push(block);
+                }
+                self.evict_blocks_from_memory();
+                self.block_hashes.extend_from_slice(&hashes);
+                self.merkle_tree.build_from_hashes(&self.block_hashes);
+                Ok(hashes)
+            }
+            Err(error) => {
+                let first_block_height = self.block_store.first_block_height().unwrap_or(0);
+                let blocks = self.block_store.read_all_from(first_block_height).await;
+                self.block_hashes = blocks.iter().map(ValidBlock::hash).collect();
+                self.merkle_tree.build_from_hashes(&self.block_hashes);
                 Err(error)
             }
         }
     }
+
+    /// Height of the next block and hash of the top block, which is `None` for an empty
+    /// blockchain.
+    ///
+    /// Returns `Err(String)` if the top block is not in memory or the blockchain has reached the
+    /// maximum height.
+    fn next_block_position(&self) -> Result<(u64, Option<Hash>), String> {
+        match self.blocks.last() {
+            Some(top_block) => top_block
+                .header
+                .height
+                .checked_add(1)
+                .map(|height| (height, Some(top_block.hash())))
+                .ok_or_else(|| Kura::max_height_error(top_block.header.height)),
+            None if self.blocks_offset == 0 => Ok((0, None)),
+            None => Err(format!(
+                "Top block at height {} is not in memory.",
+                self.blocks_offset - 1
+            )),
+        }
+    }
+
+    fn max_height_error(top_block_height: u64) -> String {
+        format!(
+            "Blockchain has reached the maximum height {}, no more blocks can be stored.",
+            top_block_height
+        )
+    }
+
+    /// Check that the encoded `block` is not larger than `max_block_bytes`.
+    fn check_block_size(&self, block: &ValidBlock) -> Result<(), String> {
+        let block_bytes = Vec::from(block).len();
+        if block_bytes > self.max_block_bytes {
+            return Err(format!(
+                "Block at height {} is {} bytes, larger than the maximum of {} bytes.",
+                block.header.height, block_bytes, self.max_block_bytes
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that the `block` is the next block at `height` after the top block with
+    /// `previous_block_hash`, which is `None` for an empty blockchain.
+    fn check_continues_chain(
+        block: &ValidBlock,
+        height: u64,
+        previous_block_hash: Option<Hash>,
+    ) -> Result<(), String> {
+        if block.header.height != height {
+            return Err(format!(
+                "Non contiguous block: block at height {} was expected, but got height {}.",
+                height, block.header.height
+            ));
+        }
+        if let Some(previous_block_hash) = previous_block_hash {
+            if block.header.previous_block_hash != previous_block_hash {
+                return Err(format!(
+                    "Fork detected: block at height {} does not reference the top block.",
+                    height
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of blocks stored in the blockchain, which is also the height of the next block.
+    ///
+    /// Saturates at `u64::MAX`, the block at the maximum height is the last one `store` accepts.
+    pub fn height(&self) -> u64 {
+        self.blocks_offset.saturating_add(self.blocks.len() as u64)
+    }
+
+    /// Hash of the top block or `None` if there are no blocks yet.
+    pub fn latest_block_hash(&self) -> Option<Hash> {
+        self.blocks.last().map(ValidBlock::hash)
+    }
+
+    /// Get the block at the given height either from memory or from the block store.
+    /// Pruned blocks can not be read.
+    pub async fn read_block(&mut self, height: u64) -> Result<ValidBlock, String> {
+        if height >= self.blocks_offset {
+            return self
+                .blocks
+                .get((height - self.blocks_offset) as usize)
+                .cloned()
+                .ok_or(format!("No block with height: {} found.", height));
+        }
+        if let Some(block) = self.block_cache.get(height) {
+            return Ok(block);
+        }
+        let block = self.block_store.read(height).await?;
+        self.block_cache.insert(height, block.clone());
+        Ok(block)
+    }
+
+    /// Rebuild the state by replaying all the stored blocks in order against a fresh
+    /// `WorldStateView` of the `peer` the blockchain started from. Used to recover from a
+    /// corrupted state or to verify the live one, as both should be equal.
+    ///
+    /// Returns `Err(String)` if blocks were pruned or a stored block can not be read.
+    pub async fn rebuild_world_state(&self, peer: Peer) -> Result<WorldStateView, String> {
+        // Blocks evicted from memory are still stored, only the store tells whether blocks were
+        // pruned.
+        if let Some(first_block_height) = self.block_store.first_block_height() {
+            if first_block_height > 0 {
+                return Err(format!(
+                    "Blocks below height {} were pruned, the state can not be rebuilt.",
+                    first_block_height
+                ));
+            }
+        }
+        let mut world_state_view = WorldStateView::new(peer);
+        for height in 0..self.height() {
+            let block = self
+                .block_store
+                .read(height)
+                .await
+                .map_err(|e| format!("Failed to read block at height {}: {}", height, e))?;
+            world_state_view.put(&block.commit()).await;
+        }
+        Ok(world_state_view)
+    }
+
+    fn evict_blocks_from_memory(&mut self) {
+        if let Some(max_blocks_in_memory) = self.max_blocks_in_memory {
+            if self.blocks.len() > max_blocks_in_memory {
+                let evicted = self.blocks.len() - max_blocks_in_memory;
+                self.blocks.drain(..evicted);
+                self.blocks_offset += evicted as u64;
+            }
+        }
+    }
+}
+
+/// Least recently used cache of decoded blocks read from the `BlockStore`.
+#[derive(Debug)]
+struct BlockCache {
+    capacity: usize,
+    blocks: VecDeque<(u64, ValidBlock)>,
+}
+
+impl BlockCache {
+    fn new(capacity: usize) -> BlockCache {
+        BlockCache {
+            capacity,
+            blocks: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, height: u64) -> Option<ValidBlock> {
+        let index = self
+            .blocks
+            .iter()
+            .position(|(block_height, _)| *block_height == height)?;
+        let entry = self.blocks.remove(index)?;
+        let block = entry.1.clone();
+        self.blocks.push_back(entry);
+        Some(block)
+    }
+
+    fn insert(&mut self, height: u64, block: ValidBlock) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.blocks.len() >= self.capacity {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back((height, block));
+    }
+
+    fn remove_below(&mut self, height: u64) {
+        self.blocks
+            .retain(|(block_height, _)| *block_height >= height);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.blocks.len()
+    }
 }
 
 /// Kura work mode.
@@ -78,24 +534,216 @@ pub enum Mode {
     Strict,
     /// Fast initialization with basic checks.
     Fast,
+    /// Initialization up to the first block which can not be decoded or does not continue the
+    /// chain, this and the following blocks are set aside for investigation.
+    Recovery,
+}
+
+/// Layout of the blocks in the block store directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockStoreLayout {
+    /// Every block in a separate file named by its height.
+    Files,
+    /// All blocks appended to a single log file with an index of their positions.
+    Log,
+}
+
+impl FromStr for BlockStoreLayout {
+    type Err = String;
+
+    fn from_str(layout: &str) -> Result<Self, Self::Err> {
+        match layout {
+            "files" => Ok(BlockStoreLayout::Files),
+            "log" => Ok(BlockStoreLayout::Log),
+            other => Err(format!(
+                "Unknown block store layout: {}, expected `files` or `log`.",
+                other
+            )),
+        }
+    }
 }
 
 /// Representation of a consistent storage.
 #[derive(Debug)]
-struct BlockStore {
+pub struct BlockStore {
     path: PathBuf,
+    /// Log of the blocks if the store has `BlockStoreLayout::Log`.
+    log: Option<BlockLog>,
+    /// Cipher of the blocks if the encryption at rest is enabled.
+    cipher: Option<BlockCipher>,
+    disk_reads: AtomicU64,
+    disk_syncs: AtomicU64,
+    block_files: AtomicU64,
+    size_bytes: AtomicU64,
+    bytes_written: AtomicU64,
+    last_write_latency_us: AtomicU64,
+    integrity_failures: AtomicU64,
+    corrupted_block_height: Mutex<Option<u64>>,
+}
+
+/// Snapshot of the block store counters, see `Kura::metrics`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KuraMetrics {
+    /// Number of blocks in the block store.
+    pub total_blocks: u64,
+    /// Size of the block files on the disk.
+    pub size_bytes: u64,
+    /// Bytes written to the block store since the start.
+    pub bytes_written: u64,
+    /// Duration of the last `write` including the flush to the disk, for a batch - of the whole
+    /// batch.
+    pub last_write_latency: Duration,
+    /// Number of corrupted blocks found by `Kura::verify_chain` since the start.
+    pub integrity_failures: u64,
+    /// Height of the last corrupted block found by `Kura::verify_chain`.
+    pub corrupted_block_height: Option<u64>,
+}
+
+/// Result of `BlockStore::integrity_check`.
+#[derive(Debug)]
+pub struct IntegrityReport {
+    /// Number of blocks which passed the checks before the first failure.
+    pub total_blocks: u64,
+    /// Height of the first missing or corrupted block and the reason, `None` if the whole
+    /// store is consistent.
+    pub first_failure: Option<(u64, String)>,
+    /// Merkle root hash over the hashes of the blocks which passed the checks.
+    pub root_hash: Hash,
+}
+
+impl IntegrityReport {
+    /// Check whether all stored blocks passed the checks.
+    pub fn is_ok(&self) -> bool {
+        self.first_failure.is_none()
+    }
 }
 
 impl BlockStore {
-    fn new(path: &Path) -> BlockStore {
+    /// Open the block store at the given path, the folder is created if it does not exist.
+    pub fn new(path: &Path) -> BlockStore {
+        BlockStore::with_layout(path, BlockStoreLayout::Files)
+    }
+
+    /// Open the block store with the given layout, `load` should be called before using the
+    /// `BlockStoreLayout::Log` store.
+    pub fn with_layout(path: &Path, layout: BlockStoreLayout) -> BlockStore {
         if fs::read_dir(path).is_err() {
             fs::create_dir_all(path).expect("Failed to create Block Store directory.");
         }
         BlockStore {
             path: path.to_path_buf(),
+            log: match layout {
+                BlockStoreLayout::Files => None,
+                BlockStoreLayout::Log => Some(BlockLog::new(path)),
+            },
+            cipher: None,
+            disk_reads: AtomicU64::new(0),
+            disk_syncs: AtomicU64::new(0),
+            block_files: AtomicU64::new(0),
+            size_bytes: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            last_write_latency_us: AtomicU64::new(0),
+            integrity_failures: AtomicU64::new(0),
+            corrupted_block_height: Mutex::new(None),
+        }
+    }
+
+    /// Encrypt blocks with the `key` on write and decrypt them on read.
+    ///
+    /// Blocks are encrypted after the serialization, every block gets a random nonce written
+    /// before it, so the store can not be read without the key. The height of the block and the
+    /// layout of the store are authenticated with it, so blocks can not be swapped between
+    /// heights.
+    pub fn encryption_key(&mut self, key: BlockEncryptionKey) {
+        self.cipher = Some(BlockCipher::new(&key));
+    }
+
+    /// Load the index of the log, a no-op for the other layouts.
+    pub async fn load(&self) -> Result<(), String> {
+        match &self.log {
+            Some(log) => log.load(self.cipher.as_ref()).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Serialize the block the way it is stored on the disk.
+    fn serialize(&self, block: &ValidBlock) -> Result<Vec<u8>, String> {
+        let serialized_block: Vec<u8> = block.into();
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt(self.layout(), block.header.height, &serialized_block),
+            None => Ok(serialized_block),
+        }
+    }
+
+    /// Decode the block stored on the disk at `height`.
+    fn deserialize(&self, height: u64, bytes: Vec<u8>) -> Result<ValidBlock, String> {
+        decode_block(bytes, Some(height), self.cipher.as_ref(), self.layout())
+    }
+
+    fn layout(&self) -> BlockStoreLayout {
+        match self.log {
+            Some(_) => BlockStoreLayout::Log,
+            None => BlockStoreLayout::Files,
+        }
+    }
+
+    /// Count block files and sum their sizes to initialize the gauges.
+    fn measure(&self) {
+        if let Some(log) = &self.log {
+            self.block_files
+                .store(log.heights().len() as u64, Ordering::Relaxed);
+            self.size_bytes.store(log.size(), Ordering::Relaxed);
+            return;
+        }
+        let sizes: Vec<u64> = fs::read_dir(&self.path)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| {
+                        let entry = entry.ok()?;
+                        entry.file_name().to_str()?.parse::<u64>().ok()?;
+                        Some(entry.metadata().ok()?.len())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.block_files
+            .store(sizes.len() as u64, Ordering::Relaxed);
+        self.size_bytes.store(sizes.iter().sum(), Ordering::Relaxed);
+    }
+
+    fn metrics(&self) -> KuraMetrics {
+        KuraMetrics {
+            total_blocks: self.block_files.load(Ordering::Relaxed),
+            size_bytes: self.size_bytes.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            last_write_latency: Duration::from_micros(
+                self.last_write_latency_us.load(Ordering::Relaxed),
+            ),
+            integrity_failures: self.integrity_failures.load(Ordering::Relaxed),
+            corrupted_block_height: *self
+                .corrupted_block_height
+                .lock()
+                .expect("Failed to lock corrupted block height."),
         }
     }
 
+    fn record_write_latency(&self, start: Instant) {
+        self.last_write_latency_us
+            .store(start.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Number of blocks read from the disk.
+    #[cfg(test)]
+    fn disk_reads(&self) -> u64 {
+        self.disk_reads.load(Ordering::Relaxed)
+    }
+
+    /// Number of times written files were flushed to the disk.
+    #[cfg(test)]
+    fn disk_syncs(&self) -> u64 {
+        self.disk_syncs.load(Ordering::Relaxed)
+    }
+
     fn get_block_filename(block_height: u64) -> String {
         format!("{}", block_height)
     }
@@ -104,25 +752,137 @@ impl BlockStore {
         self.path.join(BlockStore::get_block_filename(block_height))
     }
 
+    /// Writing the same block again is a no-op, so `Kura::store` can be retried after a crash.
+    ///
+    /// Returns `Err(String)` if a different block is already stored at the same height.
     async fn write(&self, block: &ValidBlock) -> Result<Hash, String> {
+        let start = Instant::now();
+        let (hash, files) = self.write_unsynced(block).await?;
+        self.sync(files).await?;
+        self.record_write_latency(start);
+        Ok(hash)
+    }
+
+    /// Write blocks the same way as `write`, but flush files to the disk together once per
+    /// `BATCH_SYNC_CHUNK_SIZE` blocks.
+    async fn write_batch(&self, blocks: &[ValidBlock]) -> Result<Vec<Hash>, String> {
+        let start = Instant::now();
+        let mut hashes = Vec::with_capacity(blocks.len());
+        for chunk in blocks.chunks(BATCH_SYNC_CHUNK_SIZE) {
+            let mut files = Vec::with_capacity(chunk.len());
+            for block in chunk {
+                let (hash, written_files) = self.write_unsynced(block).await?;
+                hashes.push(hash);
+                files.extend(written_files);
+            }
+            self.sync(files).await?;
+        }
+        self.record_write_latency(start);
+        Ok(hashes)
+    }
+
+    /// Returns the block hash and the written file or `None` if the block was already stored.
+    async fn write_unsynced(&self, block: &ValidBlock) -> Result<(Hash, Vec<File>), String> {
+        if let Some(log) = &self.log {
+            return self.append_unsynced(log, block).await;
+        }
         //filename is its height
         let path = self.get_block_path(block.header.height);
+        let hash = block.hash();
+        // Unreadable file could be left by an interrupted write, so it is overwritten.
+        let mut overwritten_bytes = None;
+        if let Ok(bytes) = async_std::fs::read(&path).await {
+            overwritten_bytes = Some(bytes.len() as u64);
+            if let Ok(stored_block) = self.deserialize(block.header.height, bytes) {
+                return if stored_block.hash() == hash {
+                    Ok((hash, Vec::new()))
+                } else {
+                    Err(format!(
+                        "A different block is already stored at height {}.",
+                        block.header.height
+                    ))
+                };
+            }
+        }
+        let serialized_block = self.serialize(block)?;
         match File::create(path).await {
             Ok(mut file) => {
-                let hash = block.hash();
-                let serialized_block: Vec<u8> = block.into();
+                match overwritten_bytes {
+                    Some(bytes) => {
+                        self.size_bytes.fetch_sub(bytes, Ordering::Relaxed);
+                    }
+                    None => {
+                        self.block_files.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
                 if let Err(error) = file.write_all(&serialized_block).await {
                     return Err(format!("Failed to write to storage file {}.", error));
                 }
-                Ok(hash)
+                let len = serialized_block.len() as u64;
+                self.size_bytes.fetch_add(len, Ordering::Relaxed);
+                self.bytes_written.fetch_add(len, Ordering::Relaxed);
+                Ok((hash, vec![file]))
             }
             Err(error) => Result::Err(format!("Failed to open storage file {}.", error)),
         }
     }
 
+    async fn append_unsynced(
+        &self,
+        log: &BlockLog,
+        block: &ValidBlock,
+    ) -> Result<(Hash, Vec<File>), String> {
+        let hash = block.hash();
+        if log.contains(block.header.height) {
+            let stored_block = self.deserialize(
+                block.header.height,
+                log.read_bytes(block.header.height).await?,
+            )?;
+            return if stored_block.hash() == hash {
+                Ok((hash, Vec::new()))
+            } else {
+                Err(format!(
+                    "A different block is already stored at height {}.",
+                    block.header.height
+                ))
+            };
+        }
+        let serialized_block = self.serialize(block)?;
+        let files = log.append(block.header.height, &serialized_block).await?;
+        let len = serialized_block.len() as u64 + BLOCK_LENGTH_PREFIX_SIZE;
+        self.block_files.fetch_add(1, Ordering::Relaxed);
+        self.size_bytes
+            .fetch_add(len + BLOCK_INDEX_RECORD_SIZE as u64, Ordering::Relaxed);
+        self.bytes_written.fetch_add(len, Ordering::Relaxed);
+        Ok((hash, files))
+    }
+
+    /// Flush written files to the disk concurrently.
+    async fn sync(&self, files: Vec<File>) -> Result<(), String> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        self.disk_syncs.fetch_add(1, Ordering::Relaxed);
+        future::try_join_all(files.iter().map(File::sync_all))
+            .await
+            .map(|_| ())
+            .map_err(|error| format!("Failed to sync storage file {}.", error))
+    }
+
     async fn read(&self, height: u64) -> Result<ValidBlock, String> {
+        let bytes = self.read_bytes(height).await?;
+        self.deserialize(height, bytes)
+    }
+
+    async fn read_bytes(&self, height: u64) -> Result<Vec<u8>, String> {
+        if let Some(log) = &self.log {
+            let bytes = log.read_bytes(height).await?;
+            self.disk_reads.fetch_add(1, Ordering::Relaxed);
+            return Ok(bytes);
+        }
         let path = self.get_block_path(height);
         let mut file = File::open(&path).await.map_err(|_| "No file found.")?;
+        self.disk_reads.fetch_add(1, Ordering::Relaxed);
         let metadata = metadata(&path)
             .await
             .map_err(|_| "Unable to read metadata.")?;
@@ -130,12 +890,231 @@ impl BlockStore {
         file.read(&mut buffer)
             .await
             .map_err(|_| "Buffer overflow.")?;
-        Ok(ValidBlock::try_from(buffer).expect("Failed to read block from store."))
+        Ok(buffer)
+    }
+
+    async fn delete(&self, height: u64) -> Result<(), String> {
+        if let Some(log) = &self.log {
+            log.remove(height)
+                .await
+                .map_err(|e| format!("Failed to delete block at height {}: {}", height, e))?;
+            self.block_files.fetch_sub(1, Ordering::Relaxed);
+            return Ok(());
+        }
+        let path = self.get_block_path(height);
+        let len = metadata(&path).await.map(|metadata| metadata.len());
+        remove_file(&path)
+            .await
+            .map_err(|e| format!("Failed to delete block at height {}: {}", height, e))?;
+        self.block_files.fetch_sub(1, Ordering::Relaxed);
+        self.size_bytes
+            .fetch_sub(len.unwrap_or(0), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Read every stored block and check that blocks' heights are contiguous, every block
+    /// references the previous one and its transactions match the merkle root hash.
+    ///
+    /// Unlike `Kura::init` in strict mode, the check does not stop on a failure with an error,
+    /// but reports it. Returns `Err(String)` only if the block store can not be listed.
+    pub async fn integrity_check(&self) -> Result<IntegrityReport, String> {
+        let heights = self.heights()?;
+        let mut hashes = Vec::new();
+        let mut first_failure = None;
+        if let (Some(first_height), Some(top_height)) =
+            (heights.iter().min().copied(), heights.iter().max().copied())
+        {
+            let mut previous_block_hash = None;
+            for height in first_height..=top_height {
+                match self.check_block(height, previous_block_hash).await {
+                    Ok(hash) => {
+                        hashes.push(hash);
+                        previous_block_hash = Some(hash);
+                    }
+                    Err(reason) => {
+                        first_failure = Some((height, reason));
+                        break;
+                    }
+                }
+            }
+        }
+        let mut merkle_tree = MerkleTree::new();
+        merkle_tree.build_from_hashes(&hashes);
+        Ok(IntegrityReport {
+            total_blocks: hashes.len() as u64,
+            first_failure,
+            root_hash: merkle_tree.root_hash(),
+        })
+    }
+
+    /// Check up to `window` blocks starting from `height`, or from the first stored block if
+    /// there is no block at `height`, the way `integrity_check` does, wrapping around after the
+    /// top block. The first of them is checked to reference the block before it. Corrupted
+    /// blocks are logged and counted in the metrics.
+    ///
+    /// Returns the height the next check should start from.
+    async fn verify_window(&self, height: u64, window: u64) -> u64 {
+        let heights = match self.heights() {
+            Ok(heights) => heights,
+            Err(reason) => {
+                logger::log(Level::Error, module_path!(), &reason);
+                return height;
+            }
+        };
+        let (first_height, top_height) =
+            match (heights.iter().min().copied(), heights.iter().max().copied()) {
+                (Some(first_height), Some(top_height)) => (first_height, top_height),
+                _ => return height,
+            };
+        let from = if height < first_height || height > top_height {
+            first_height
+        } else {
+            height
+        };
+        let mut previous_block_hash = None;
+        if from > first_height {
+            previous_block_hash = self.check_block(from - 1, None).await.ok();
+        }
+        let count = (top_height - first_height).saturating_add(1).min(window) as usize;
+        let mut next_height = from;
+        for height in (from..=top_height).chain(first_height..from).take(count) {
+            if height == first_height {
+                previous_block_hash = None;
+            }
+            match self.check_block(height, previous_block_hash).await {
+                Ok(hash) => previous_block_hash = Some(hash),
+                Err(reason) => {
+                    previous_block_hash = None;
+                    self.integrity_failures.fetch_add(1, Ordering::Relaxed);
+                    *self
+                        .corrupted_block_height
+                        .lock()
+                        .expect("Failed to lock corrupted block height.") = Some(height);
+                    logger::log(
+                        Level::Error,
+                        module_path!(),
+                        &format!("Corrupted block at height {}: {}", height, reason),
+                    );
+                }
+            }
+            next_height = height.saturating_add(1);
+        }
+        next_height
+    }
+
+    async fn check_block(
+        &self,
+        height: u64,
+        previous_block_hash: Option<Hash>,
+    ) -> Result<Hash, String> {
+        let bytes = match &self.log {
+            Some(log) => log.read_bytes(height).await?,
+            None => async_std::fs::read(self.get_block_path(height))
+                .await
+                .map_err(|e| format!("Failed to read block file: {}", e))?,
+        };
+        let block = self.deserialize(height, bytes)?;
+        if block.header.height != height {
+            return Err(format!(
+                "Block file contains a block at height {}.",
+                block.header.height
+            ));
+        }
+        if let Some(previous_block_hash) = previous_block_hash {
+            if block.header.previous_block_hash != previous_block_hash {
+                return Err("Block does not reference the previous block.".to_string());
+            }
+        }
+        block.verify_merkle_root_hash()?;
+        Ok(block.hash())
+    }
+
+    /// Height of the first stored block, blocks below it could be pruned.
+    fn first_block_height(&self) -> Option<u64> {
+        self.heights().ok()?.into_iter().min()
+    }
+
+    /// Heights of all stored blocks in no particular order.
+    fn heights(&self) -> Result<Vec<u64>, String> {
+        if let Some(log) = &self.log {
+            return Ok(log.heights());
+        }
+        Ok(fs::read_dir(&self.path)
+            .map_err(|e| format!("Failed to read block store directory: {}", e))?
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u64>().ok())
+            .collect())
+    }
+
+    /// Returns a sorted vector of blocks starting from `height` up to the first block which can
+    /// not be read or decoded or does not continue the chain, with its height and the reason.
+    async fn read_consistent_from(&self, height: u64) -> (Vec<ValidBlock>, Option<(u64, String)>) {
+        let top_height = match self.heights().map(|heights| heights.into_iter().max()) {
+            Ok(Some(top_height)) => top_height,
+            Ok(None) => return (Vec::new(), None),
+            Err(error) => return (Vec::new(), Some((height, error))),
+        };
+        let mut blocks: Vec<ValidBlock> = Vec::new();
+        for height in height..=top_height {
+            let block = match self
+                .read_bytes(height)
+                .await
+                .and_then(|bytes| self.deserialize(height, bytes))
+            {
+                Ok(block) => block,
+                Err(error) => return (blocks, Some((height, error))),
+            };
+            if block.header.height != height {
+                return (
+                    blocks,
+                    Some((
+                        height,
+                        format!("Block has height {} in its header.", block.header.height),
+                    )),
+                );
+            }
+            if let Some(previous_block) = blocks.last() {
+                if block.header.previous_block_hash != previous_block.hash() {
+                    return (
+                        blocks,
+                        Some((
+                            height,
+                            "Block does not reference the previous block.".to_string(),
+                        )),
+                    );
+                }
+            }
+            if let Err(error) = block.verify_merkle_root_hash() {
+                return (blocks, Some((height, error)));
+            }
+            blocks.push(block);
+        }
+        (blocks, None)
+    }
+
+    /// Move blocks starting from `height` out of the block store, block files are renamed with
+    /// a `.broken` extension and blocks in the log are removed from its index only.
+    async fn set_aside_from(&self, height: u64) -> Result<(), String> {
+        for broken_height in self.heights()? {
+            if broken_height < height {
+                continue;
+            }
+            if let Some(log) = &self.log {
+                log.remove(broken_height).await?;
+                continue;
+            }
+            let path = self.get_block_path(broken_height);
+            fs::rename(&path, path.with_extension("broken")).map_err(|e| {
+                format!(
+                    "Failed to set aside block at height {}: {}",
+                    broken_height, e
+                )
+            })?;
+        }
+        Ok(())
     }
 
-    /// Returns a sorted vector of blocks starting from 0 height to the top block.
-    async fn read_all(&self) -> Vec<ValidBlock> {
-        let mut height = 0;
+    /// Returns a sorted vector of blocks starting from `height` to the top block.
+    async fn read_all_from(&self, mut height: u64) -> Vec<ValidBlock> {
         let mut blocks = Vec::new();
         while let Ok(block) = self.read(height).await {
             blocks.push(block);
@@ -145,51 +1124,430 @@ impl BlockStore {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::peer::PeerId;
-    use async_std::sync;
-    use tempfile::TempDir;
+/// Decrypt the block stored on the disk if the `cipher` is set and decode it.
+///
+/// An encrypted block should have been encrypted for the `height` it is stored at, if it is
+/// known, and for the `layout` of the store.
+fn decode_block(
+    bytes: Vec<u8>,
+    height: Option<u64>,
+    cipher: Option<&BlockCipher>,
+    layout: BlockStoreLayout,
+) -> Result<ValidBlock, String> {
+    let bytes = match cipher {
+        Some(cipher) => {
+            let (encrypted_height, bytes) = cipher.decrypt(layout, &bytes)?;
+            match height {
+                Some(height) if height != encrypted_height => {
+                    return Err(format!(
+                        "Block decryption/authentication failed: block of height {} is stored at height {}.",
+                        encrypted_height, height
+                    ))
+                }
+                _ => bytes,
+            }
+        }
+        None => bytes,
+    };
+    ValidBlock::try_from(bytes).map_err(|e| format!("Failed to decode block: {}", e))
+}
 
-    #[async_std::test]
-    async fn strict_init_kura() {
-        let temp_dir = TempDir::new().expect("Failed to create temp dir.");
-        let (tx, _rx) = sync::channel(100);
-        assert!(Kura::new(Mode::Strict, temp_dir.path(), tx)
-            .init()
-            .await
-            .is_ok());
+/// ChaCha20-Poly1305 authenticated encryption of the stored blocks.
+struct BlockCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl BlockCipher {
+    fn new(key: &BlockEncryptionKey) -> BlockCipher {
+        BlockCipher {
+            cipher: ChaCha20Poly1305::new(&Key::from(*key)),
+        }
     }
 
-    #[async_std::test]
-    async fn write_block_to_block_store() {
-        let dir = tempfile::tempdir().unwrap();
-        let block = PendingBlock::new(Vec::new())
-            .chain_first()
-            .sign(&[0; 32], &[0; 64])
-            .expect("Failed to sign blocks.")
-            .validate(&WorldStateView::new(Peer::new(
-                PeerId {
-                    address: "127.0.0.1:8080".to_string(),
-                    public_key: [0; 32],
+    /// Returns the format version, the `height`, a random nonce, the ciphertext and the
+    /// authentication tag. The version, the `layout` and the `height` are authenticated as the
+    /// associated data.
+    fn encrypt(
+        &self,
+        layout: BlockStoreLayout,
+        height: u64,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let nonce: [u8; BLOCK_NONCE_SIZE] = rand::random();
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &BlockCipher::associated_data(layout, height),
                 },
-                &Vec::new(),
-            )))
-            .expect("Failed to validate block.");
-        assert!(BlockStore::new(dir.path()).write(&block).await.is_ok());
+            )
+            .map_err(|_| "Block encryption failed.".to_string())?;
+        let mut bytes =
+            Vec::with_capacity(BLOCK_ENCRYPTION_HEADER_SIZE + BLOCK_NONCE_SIZE + ciphertext.len());
+        bytes.push(BLOCK_ENCRYPTION_VERSION);
+        bytes.extend_from_slice(&height.to_le_bytes());
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        Ok(bytes)
     }
 
-    #[async_std::test]
-    async fn read_block_from_block_store() {
-        let dir = tempfile::tempdir().unwrap();
-        let block = PendingBlock::new(Vec::new())
-            .chain_first()
-            .sign(&[0; 32], &[0; 64])
-            .expect("Failed to sign blocks.")
-            .validate(&WorldStateView::new(Peer::new(
-                PeerId {
-                    address: "127.0.0.1:8080".to_string(),
+    /// Returns the height the block was encrypted for and the plaintext.
+    fn decrypt(&self, layout: BlockStoreLayout, bytes: &[u8]) -> Result<(u64, Vec<u8>), String> {
+        if bytes.len() < BLOCK_ENCRYPTION_HEADER_SIZE + BLOCK_NONCE_SIZE {
+            return Err("Block decryption/authentication failed: block is truncated.".to_string());
+        }
+        let (header, bytes) = bytes.split_at(BLOCK_ENCRYPTION_HEADER_SIZE);
+        if header[0] != BLOCK_ENCRYPTION_VERSION {
+            return Err(format!(
+                "Block decryption/authentication failed: unknown format version {}.",
+                header[0]
+            ));
+        }
+        let height = u64::from_le_bytes(header[1..].try_into().expect("Height has 8 bytes."));
+        let (nonce, ciphertext) = bytes.split_at(BLOCK_NONCE_SIZE);
+        let nonce: [u8; BLOCK_NONCE_SIZE] = nonce.try_into().expect("Nonce has 12 bytes.");
+        let plaintext = self
+            .cipher
+            .decrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &BlockCipher::associated_data(layout, height),
+                },
+            )
+            .map_err(|_| "Block decryption/authentication failed.".to_string())?;
+        Ok((height, plaintext))
+    }
+
+    fn associated_data(layout: BlockStoreLayout, height: u64) -> [u8; 10] {
+        let mut associated_data = [0; 10];
+        associated_data[0] = BLOCK_ENCRYPTION_VERSION;
+        associated_data[1] = match layout {
+            BlockStoreLayout::Files => 0,
+            BlockStoreLayout::Log => 1,
+        };
+        associated_data[2..].copy_from_slice(&height.to_le_bytes());
+        associated_data
+    }
+}
+
+impl std::fmt::Debug for BlockCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BlockCipher")
+    }
+}
+
+/// Append-only log of length prefixed blocks and an index of their positions in it.
+///
+/// The index is a sequence of `(height, offset, length)` records, it is rebuilt by scanning the
+/// log if it is missing or does not match the log.
+#[derive(Debug)]
+struct BlockLog {
+    log_path: PathBuf,
+    index_path: PathBuf,
+    index: Mutex<BTreeMap<u64, (u64, u64)>>,
+}
+
+impl BlockLog {
+    fn new(path: &Path) -> BlockLog {
+        BlockLog {
+            log_path: path.join(BLOCK_LOG_FILE_NAME),
+            index_path: path.join(BLOCK_INDEX_FILE_NAME),
+            index: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Load the index from the disk, rebuild it from the log if it is missing or corrupted.
+    async fn load(&self, cipher: Option<&BlockCipher>) -> Result<(), String> {
+        let log = async_std::fs::read(&self.log_path)
+            .await
+            .unwrap_or_default();
+        let index = match async_std::fs::read(&self.index_path).await {
+            Ok(bytes) => BlockLog::decode_index(&bytes, log.len() as u64),
+            Err(_) => None,
+        };
+        let index = match index {
+            Some(index) => index,
+            None => {
+                let index = BlockLog::scan(&log, cipher);
+                BlockLog::write_index(&self.index_path, &index).await?;
+                index
+            }
+        };
+        *self.index.lock().expect("Failed to lock block log index.") = index;
+        Ok(())
+    }
+
+    fn decode_index(bytes: &[u8], log_len: u64) -> Option<BTreeMap<u64, (u64, u64)>> {
+        let records = bytes.chunks_exact(BLOCK_INDEX_RECORD_SIZE);
+        if !records.remainder().is_empty() {
+            return None;
+        }
+        let mut index = BTreeMap::new();
+        for record in records {
+            let height = u64::from_le_bytes(record[..8].try_into().ok()?);
+            let offset = u64::from_le_bytes(record[8..16].try_into().ok()?);
+            let len = u64::from_le_bytes(record[16..].try_into().ok()?);
+            let end = offset
+                .checked_add(BLOCK_LENGTH_PREFIX_SIZE)?
+                .checked_add(len)?;
+            if end > log_len {
+                return None;
+            }
+            let _ = index.insert(height, (offset, len));
+        }
+        Some(index)
+    }
+
+    /// Read blocks from the log one by one, stops at the first torn or undecodable entry.
+    fn scan(log: &[u8], cipher: Option<&BlockCipher>) -> BTreeMap<u64, (u64, u64)> {
+        let mut index = BTreeMap::new();
+        let mut offset = 0;
+        while offset + BLOCK_LENGTH_PREFIX_SIZE as usize <= log.len() {
+            let start = offset + BLOCK_LENGTH_PREFIX_SIZE as usize;
+            let len = u64::from_le_bytes(
+                log[offset..start]
+                    .try_into()
+                    .expect("Length prefix has 8 bytes."),
+            ) as usize;
+            let end = match start.checked_add(len) {
+                Some(end) if end <= log.len() => end,
+                _ => break,
+            };
+            match decode_block(
+                log[start..end].to_vec(),
+                None,
+                cipher,
+                BlockStoreLayout::Log,
+            ) {
+                Ok(block) => {
+                    let _ = index.insert(block.header.height, (offset as u64, len as u64));
+                }
+                Err(_) => break,
+            }
+            offset = end;
+        }
+        index
+    }
+
+    async fn write_index(path: &Path, index: &BTreeMap<u64, (u64, u64)>) -> Result<(), String> {
+        let bytes: Vec<u8> = index
+            .iter()
+            .flat_map(|(height, (offset, len))| BlockLog::index_record(*height, *offset, *len))
+            .collect();
+        async_std::fs::write(path, bytes)
+            .await
+            .map_err(|e| format!("Failed to write block index: {}", e))
+    }
+
+    fn index_record(height: u64, offset: u64, len: u64) -> Vec<u8> {
+        let mut record = Vec::with_capacity(BLOCK_INDEX_RECORD_SIZE);
+        record.extend_from_slice(&height.to_le_bytes());
+        record.extend_from_slice(&offset.to_le_bytes());
+        record.extend_from_slice(&len.to_le_bytes());
+        record
+    }
+
+    fn position(&self, height: u64) -> Option<(u64, u64)> {
+        self.index
+            .lock()
+            .expect("Failed to lock block log index.")
+            .get(&height)
+            .copied()
+    }
+
+    fn contains(&self, height: u64) -> bool {
+        self.position(height).is_some()
+    }
+
+    fn heights(&self) -> Vec<u64> {
+        self.index
+            .lock()
+            .expect("Failed to lock block log index.")
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// Size of the log and the index on the disk.
+    fn size(&self) -> u64 {
+        [&self.log_path, &self.index_path]
+            .iter()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+
+    async fn read_bytes(&self, height: u64) -> Result<Vec<u8>, String> {
+        let (offset, len) = self.position(height).ok_or("No block found.")?;
+        let mut file = File::open(&self.log_path)
+            .await
+            .map_err(|e| format!("Failed to open block log: {}", e))?;
+        let _ = file
+            .seek(SeekFrom::Start(offset + BLOCK_LENGTH_PREFIX_SIZE))
+            .await
+            .map_err(|e| format!("Failed to seek block log: {}", e))?;
+        let mut buffer = vec![0; len as usize];
+        file.read_exact(&mut buffer)
+            .await
+            .map_err(|e| format!("Failed to read block log: {}", e))?;
+        Ok(buffer)
+    }
+
+    /// Append the block to the log and its position to the index, returns files to sync.
+    async fn append(&self, height: u64, block: &[u8]) -> Result<Vec<File>, String> {
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .await
+            .map_err(|e| format!("Failed to open block log: {}", e))?;
+        let offset = log
+            .metadata()
+            .await
+            .map_err(|e| format!("Failed to read block log metadata: {}", e))?
+            .len();
+        let len = block.len() as u64;
+        let mut entry = Vec::with_capacity(block.len() + BLOCK_LENGTH_PREFIX_SIZE as usize);
+        entry.extend_from_slice(&len.to_le_bytes());
+        entry.extend_from_slice(block);
+        log.write_all(&entry)
+            .await
+            .map_err(|e| format!("Failed to write to block log: {}", e))?;
+        let mut index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.index_path)
+            .await
+            .map_err(|e| format!("Failed to open block index: {}", e))?;
+        index
+            .write_all(&BlockLog::index_record(height, offset, len))
+            .await
+            .map_err(|e| format!("Failed to write to block index: {}", e))?;
+        let _ = self
+            .index
+            .lock()
+            .expect("Failed to lock block log index.")
+            .insert(height, (offset, len));
+        Ok(vec![log, index])
+    }
+
+    /// Remove the block from the index, its bytes stay in the log.
+    async fn remove(&self, height: u64) -> Result<(), String> {
+        let index = {
+            let mut index = self.index.lock().expect("Failed to lock block log index.");
+            if index.remove(&height).is_none() {
+                return Err("No block found.".to_string());
+            }
+            index.clone()
+        };
+        BlockLog::write_index(&self.index_path, &index).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{crypto, isi::Mint, peer::PeerId};
+    use async_std::sync;
+    use std::{collections::HashMap, sync::Arc};
+    use tempfile::TempDir;
+
+    #[async_std::test]
+    async fn strict_init_kura() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir.");
+        let (tx, _rx) = sync::channel(100);
+        assert!(Kura::new(Mode::Strict, temp_dir.path(), tx)
+            .init()
+            .await
+            .is_ok());
+    }
+
+    #[async_std::test]
+    async fn write_block_to_block_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let block = PendingBlock::new(Vec::new())
+            .chain_first()
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign blocks.")
+            .validate(&WorldStateView::new(Peer::new(
+                PeerId {
+                    address: "127.0.0.1:8080".to_string(),
+                    public_key: [0; 32],
+                },
+                &Vec::new(),
+            )))
+            .expect("Failed to validate block.");
+        assert!(BlockStore::new(dir.path()).write(&block).await.is_ok());
+    }
+
+    #[async_std::test]
+    async fn rewrite_same_block_to_block_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let block = PendingBlock::new(Vec::new())
+            .chain_first()
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign blocks.")
+            .validate(&WorldStateView::new(Peer::new(
+                PeerId {
+                    address: "127.0.0.1:8080".to_string(),
+                    public_key: [0; 32],
+                },
+                &Vec::new(),
+            )))
+            .expect("Failed to validate block.");
+        let block_store = BlockStore::new(dir.path());
+        let hash = block_store
+            .write(&block)
+            .await
+            .expect("Failed to write block to file.");
+        assert_eq!(Ok(hash), block_store.write(&block).await);
+    }
+
+    #[async_std::test]
+    async fn write_different_block_at_the_same_height_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block = PendingBlock::new(Vec::new())
+            .chain_first()
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign blocks.")
+            .validate(&WorldStateView::new(Peer::new(
+                PeerId {
+                    address: "127.0.0.1:8080".to_string(),
+                    public_key: [0; 32],
+                },
+                &Vec::new(),
+            )))
+            .expect("Failed to validate block.");
+        let block_store = BlockStore::new(dir.path());
+        block_store
+            .write(&block)
+            .await
+            .expect("Failed to write block to file.");
+        block.header.timestamp += 1;
+        assert!(block_store.write(&block).await.is_err());
+        assert_ne!(
+            block.hash(),
+            block_store
+                .read(0)
+                .await
+                .expect("Failed to read block.")
+                .hash()
+        );
+    }
+
+    #[async_std::test]
+    async fn read_block_from_block_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let block = PendingBlock::new(Vec::new())
+            .chain_first()
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign blocks.")
+            .validate(&WorldStateView::new(Peer::new(
+                PeerId {
+                    address: "127.0.0.1:8080".to_string(),
                     public_key: [0; 32],
                 },
                 &Vec::new(),
@@ -238,10 +1596,371 @@ mod tests {
                 )))
                 .expect("Failed to validate block.");
         }
-        let blocks = block_store.read_all().await;
+        let blocks = block_store.read_all_from(0).await;
         assert_eq!(blocks.len(), n as usize)
     }
 
+    #[async_std::test]
+    async fn strict_init_kura_rejects_tampered_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let mut world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        ));
+        let mut global = Domain::new("global".to_string());
+        for name in &["root", "other"] {
+            global.accounts.insert(
+                AccountId::new(name, "global"),
+                Account::new(name, "global", public_key),
+            );
+        }
+        world_state_view.add_domain(global);
+        let transaction = RequestedTransaction::new(Vec::new(), AccountId::new("root", "global"))
+            .accept()
+            .expect("Failed to accept transaction.");
+        let other_block = PendingBlock::new(vec![RequestedTransaction::new(
+            Vec::new(),
+            AccountId::new("other", "global"),
+        )
+        .accept()
+        .expect("Failed to accept transaction.")])
+        .chain_first()
+        .sign(&public_key, &private_key)
+        .expect("Failed to sign blocks.")
+        .validate(&world_state_view)
+        .expect("Failed to validate block.");
+        let mut block = PendingBlock::new(vec![transaction])
+            .chain_first()
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign blocks.")
+            .validate(&world_state_view)
+            .expect("Failed to validate block.");
+        block.transactions = other_block.transactions;
+        BlockStore::new(dir.path())
+            .write(&block)
+            .await
+            .expect("Failed to write block to file.");
+        let (tx, _rx) = sync::channel(100);
+        let result = Kura::new(Mode::Strict, dir.path(), tx).init().await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("height 0"));
+        let (tx, _rx) = sync::channel(100);
+        assert!(Kura::new(Mode::Fast, dir.path(), tx).init().await.is_ok());
+    }
+
+    #[async_std::test]
+    async fn recovery_init_stops_at_broken_block() {
+        let dir = TempDir::new().expect("Failed to create temp dir.");
+        let (tx, _rx) = sync::channel(100);
+        drop(kura_with_stored_blocks(dir.path(), 10, tx).await);
+        fs::write(dir.path().join("7"), [0xff; 16]).expect("Failed to corrupt block file.");
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Recovery, dir.path(), tx);
+        kura.init()
+            .await
+            .expect("Failed to init Kura in recovery mode.");
+        // The last consistent block is at height 6.
+        assert_eq!(7, kura.height());
+        let (broken_height, reason) = kura.broken_block().expect("Broken block was not logged.");
+        assert_eq!(7, broken_height);
+        assert!(reason.contains("decode"));
+        assert!(dir.path().join("7.broken").exists());
+        let block = PendingBlock::new(Vec::new())
+            .chain(
+                kura.height(),
+                kura.latest_block_hash()
+                    .expect("Failed to get the top block hash."),
+            )
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign blocks.")
+            .validate(&WorldStateView::new(Peer::new(
+                PeerId {
+                    address: "127.0.0.1:8080".to_string(),
+                    public_key: [0; 32],
+                },
+                &Vec::new(),
+            )))
+            .expect("Failed to validate block.");
+        kura.store(block)
+            .await
+            .expect("Failed to store block into Kura.");
+        assert_eq!(8, kura.height());
+        let (tx, _rx) = sync::channel(100);
+        let mut restarted_kura = Kura::new(Mode::Strict, dir.path(), tx);
+        restarted_kura.init().await.expect("Failed to init Kura.");
+        assert_eq!(8, restarted_kura.height());
+    }
+
+    async fn kura_with_stored_blocks(
+        dir: &Path,
+        n_blocks: usize,
+        tx: CommittedBlockSender,
+    ) -> Kura {
+        let mut kura = Kura::new(Mode::Strict, dir, tx);
+        kura.init().await.expect("Failed to init Kura.");
+        for block in chain_of_blocks(n_blocks as u64) {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        kura
+    }
+
+    #[async_std::test]
+    async fn read_cached_block_does_not_read_disk_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = kura_with_stored_blocks(dir.path(), 3, tx).await;
+        kura.max_blocks_in_memory(1);
+        let disk_reads = kura.block_store.disk_reads();
+        let block = kura.read_block(0).await.expect("Failed to read block.");
+        assert_eq!(0, block.header.height);
+        assert_eq!(disk_reads + 1, kura.block_store.disk_reads());
+        let cached_block = kura.read_block(0).await.expect("Failed to read block.");
+        assert_eq!(block.hash(), cached_block.hash());
+        assert_eq!(disk_reads + 1, kura.block_store.disk_reads());
+        kura.read_block(2).await.expect("Failed to read block.");
+        assert_eq!(disk_reads + 1, kura.block_store.disk_reads());
+    }
+
+    #[async_std::test]
+    async fn block_cache_eviction_respects_bound() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = kura_with_stored_blocks(dir.path(), 4, tx).await;
+        kura.max_blocks_in_memory(1);
+        kura.block_cache_size(2);
+        for height in 0..3 {
+            kura.read_block(height)
+                .await
+                .expect("Failed to read block.");
+            assert!(kura.block_cache.len() <= 2);
+        }
+        let disk_reads = kura.block_store.disk_reads();
+        kura.read_block(2).await.expect("Failed to read block.");
+        assert_eq!(disk_reads, kura.block_store.disk_reads());
+        kura.read_block(0).await.expect("Failed to read block.");
+        assert_eq!(disk_reads + 1, kura.block_store.disk_reads());
+    }
+
+    #[async_std::test]
+    async fn metrics_track_stored_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let kura = kura_with_stored_blocks(dir.path(), 5, tx).await;
+        let metrics = kura.metrics();
+        assert_eq!(5, metrics.total_blocks);
+        assert!(metrics.bytes_written > 0);
+        assert_eq!(metrics.bytes_written, metrics.size_bytes);
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let restarted_metrics = kura.metrics();
+        assert_eq!(5, restarted_metrics.total_blocks);
+        assert_eq!(metrics.size_bytes, restarted_metrics.size_bytes);
+        assert_eq!(0, restarted_metrics.bytes_written);
+    }
+
+    async fn log_kura_with_stored_blocks(
+        dir: &Path,
+        n_blocks: usize,
+        tx: CommittedBlockSender,
+    ) -> Kura {
+        let mut kura = Kura::new(Mode::Strict, dir, tx);
+        kura.block_store_layout(BlockStoreLayout::Log);
+        kura.init().await.expect("Failed to init Kura.");
+        for block in chain_of_blocks(n_blocks as u64) {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        kura
+    }
+
+    #[async_std::test]
+    async fn write_and_read_blocks_from_log_block_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let kura = log_kura_with_stored_blocks(dir.path(), 5, tx).await;
+        assert!(dir.path().join(BLOCK_LOG_FILE_NAME).exists());
+        assert!(!kura.block_store.get_block_path(0).exists());
+        let block_store = BlockStore::with_layout(dir.path(), BlockStoreLayout::Log);
+        block_store
+            .load()
+            .await
+            .expect("Failed to load block store.");
+        for height in 0..5 {
+            let block = block_store
+                .read(height)
+                .await
+                .expect("Failed to read block.");
+            assert_eq!(height, block.header.height);
+            assert_eq!(kura.blocks[height as usize].hash(), block.hash());
+        }
+        assert!(block_store.read(5).await.is_err());
+        assert_eq!(5, kura.metrics().total_blocks);
+    }
+
+    #[async_std::test]
+    async fn log_block_store_index_is_rebuilt_from_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let kura = log_kura_with_stored_blocks(dir.path(), 5, tx).await;
+        let index_path = dir.path().join(BLOCK_INDEX_FILE_NAME);
+        let index = fs::read(&index_path).expect("Failed to read index.");
+        fs::remove_file(&index_path).expect("Failed to delete index.");
+        let (tx, _rx) = sync::channel(100);
+        let mut restarted_kura = Kura::new(Mode::Strict, dir.path(), tx);
+        restarted_kura.block_store_layout(BlockStoreLayout::Log);
+        restarted_kura.init().await.expect("Failed to init Kura.");
+        assert_eq!(index, fs::read(&index_path).expect("Failed to read index."));
+        assert_eq!(5, restarted_kura.metrics().total_blocks);
+        for height in 0..5 {
+            assert_eq!(
+                kura.blocks[height].hash(),
+                restarted_kura
+                    .block_store
+                    .read(height as u64)
+                    .await
+                    .expect("Failed to read block.")
+                    .hash()
+            );
+        }
+    }
+
+    const ENCRYPTION_KEY: BlockEncryptionKey = [7; 32];
+
+    #[async_std::test]
+    async fn encrypted_blocks_are_read_back() {
+        for layout in &[BlockStoreLayout::Files, BlockStoreLayout::Log] {
+            let dir = tempfile::tempdir().unwrap();
+            let (tx, _rx) = sync::channel(100);
+            let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+            kura.block_store_encryption_key(ENCRYPTION_KEY);
+            kura.block_store_layout(*layout);
+            kura.init().await.expect("Failed to init Kura.");
+            for block in chain_of_blocks(3) {
+                kura.store(block)
+                    .await
+                    .expect("Failed to store block into Kura.");
+            }
+            let mut block_store = BlockStore::with_layout(dir.path(), *layout);
+            block_store
+                .load()
+                .await
+                .expect("Failed to load block store.");
+            assert!(block_store.read(1).await.is_err());
+            block_store.encryption_key(ENCRYPTION_KEY);
+            block_store
+                .load()
+                .await
+                .expect("Failed to load block store.");
+            for block in &kura.blocks {
+                assert_eq!(
+                    block.hash(),
+                    block_store
+                        .read(block.header.height)
+                        .await
+                        .expect("Failed to read block.")
+                        .hash()
+                );
+            }
+            assert!(block_store.integrity_check().await.unwrap().is_ok());
+        }
+    }
+
+    #[async_std::test]
+    async fn tampered_encrypted_block_fails_authentication() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block_store = BlockStore::new(dir.path());
+        block_store.encryption_key(ENCRYPTION_KEY);
+        let block = chain_of_blocks(1).remove(0);
+        block_store
+            .write(&block)
+            .await
+            .expect("Failed to write block.");
+        let path = block_store.get_block_path(0);
+        let mut bytes = fs::read(&path).expect("Failed to read block file.");
+        let ciphertext_start = BLOCK_ENCRYPTION_HEADER_SIZE + BLOCK_NONCE_SIZE;
+        assert_ne!(Vec::from(&block), bytes[ciphertext_start..].to_vec());
+        bytes[ciphertext_start] ^= 1;
+        fs::write(&path, bytes).expect("Failed to write block file.");
+        let error = block_store
+            .read(0)
+            .await
+            .expect_err("Tampered block should not be read.");
+        assert!(error.contains("decryption/authentication failed"));
+    }
+
+    #[async_std::test]
+    async fn encrypted_blocks_swapped_between_heights_fail_authentication() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut block_store = BlockStore::new(dir.path());
+        block_store.encryption_key(ENCRYPTION_KEY);
+        for block in chain_of_blocks(2) {
+            block_store
+                .write(&block)
+                .await
+                .expect("Failed to write block.");
+        }
+        let (path0, path1) = (block_store.get_block_path(0), block_store.get_block_path(1));
+        let bytes0 = fs::read(&path0).expect("Failed to read block file.");
+        let bytes1 = fs::read(&path1).expect("Failed to read block file.");
+        fs::write(&path0, &bytes1).expect("Failed to write block file.");
+        fs::write(&path1, &bytes0).expect("Failed to write block file.");
+        for height in 0..2 {
+            let error = block_store
+                .read(height)
+                .await
+                .expect_err("Swapped block should not be read.");
+            assert!(error.contains("decryption/authentication failed"));
+        }
+        let mut forged_bytes = bytes1.clone();
+        forged_bytes[1..BLOCK_ENCRYPTION_HEADER_SIZE].copy_from_slice(&0u64.to_le_bytes());
+        fs::write(&path0, &forged_bytes).expect("Failed to write block file.");
+        assert!(block_store.read(0).await.is_err());
+        let cipher = BlockCipher::new(&ENCRYPTION_KEY);
+        assert!(cipher.decrypt(BlockStoreLayout::Log, &bytes0).is_err());
+        assert!(cipher.decrypt(BlockStoreLayout::Files, &bytes0).is_ok());
+    }
+
+    #[async_std::test]
+    async fn prune_blocks_covered_by_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(300);
+        let mut kura = kura_with_stored_blocks(dir.path(), 200, tx).await;
+        assert!(kura.prune(150).await.is_err());
+        kura.snapshot_height(150);
+        kura.lock_for_sync(100);
+        assert!(kura.prune(150).await.is_err());
+        kura.unlock_for_sync();
+        kura.prune(150).await.expect("Failed to prune blocks.");
+        for height in 0..150 {
+            assert!(kura.read_block(height).await.is_err());
+            assert!(!kura.block_store.get_block_path(height).exists());
+        }
+        for height in 150..200 {
+            assert_eq!(
+                height,
+                kura.read_block(height)
+                    .await
+                    .expect("Failed to read block.")
+                    .header
+                    .height
+            );
+        }
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        assert!(kura.read_block(149).await.is_err());
+        assert!(kura.read_block(199).await.is_ok());
+    }
+
     ///Kura takes as input blocks, which comprise multiple transactions. Kura is meant to take only
     ///blocks as input that have passed stateless and stateful validation, and have been finalized
     ///by consensus. For finalized blocks, Kura simply commits the block to the block storage on
@@ -270,4 +1989,540 @@ mod tests {
             .await
             .expect("Failed to store block into Kura.");
     }
+
+    fn chain_of_blocks(n_blocks: u64) -> Vec<ValidBlock> {
+        let world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        ));
+        let mut previous_block_hash = [0u8; 32];
+        (0..n_blocks)
+            .map(|height| {
+                let block = PendingBlock::new(Vec::new())
+                    .chain(height, previous_block_hash)
+                    .sign(&[0; 32], &[0; 64])
+                    .expect("Failed to sign blocks.")
+                    .validate(&world_state_view)
+                    .expect("Failed to validate block.");
+                previous_block_hash = block.hash();
+                block
+            })
+            .collect()
+    }
+
+    #[async_std::test]
+    async fn world_state_is_rebuilt_from_stored_blocks() {
+        let (public_key, private_key) =
+            crate::crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let alice_id = AccountId::new("alice", "wonderland");
+        let asset_definition_id = AssetDefinitionId::new("xor", "wonderland");
+        let asset_id = AssetId {
+            definition_id: asset_definition_id.clone(),
+            account_id: alice_id.clone(),
+        };
+        let mut domain = Domain::new("wonderland".to_string());
+        domain.accounts.insert(
+            alice_id.clone(),
+            Account::new(&alice_id.name, &alice_id.domain_name, public_key),
+        );
+        domain.asset_definitions.insert(
+            asset_definition_id.clone(),
+            AssetDefinition::new(asset_definition_id, alice_id.clone()),
+        );
+        let mut domains = HashMap::new();
+        domains.insert(domain.name.clone(), domain);
+        let peer = Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key,
+            },
+            &Vec::new(),
+            domains,
+        );
+        let mut world_state_view = WorldStateView::new(peer.clone());
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let mut previous_block_hash = [0u8; 32];
+        for height in 0..5 {
+            let transactions = (0..3)
+                .map(|_| {
+                    RequestedTransaction::new(
+                        vec![Mint::<Asset, u32>::new(10, asset_id.clone()).into()],
+                        alice_id.clone(),
+                    )
+                    .accept()
+                    .expect("Failed to accept transaction.")
+                })
+                .collect();
+            let block = PendingBlock::new(transactions)
+                .chain(height, previous_block_hash)
+                .sign(&public_key, &private_key)
+                .expect("Failed to sign block.")
+                .validate(&world_state_view)
+                .expect("Failed to validate block.");
+            previous_block_hash = kura
+                .store(block.clone())
+                .await
+                .expect("Failed to store block.");
+            world_state_view.put(&block.commit()).await;
+        }
+        let mut rebuilt_world_state_view = kura
+            .rebuild_world_state(peer)
+            .await
+            .expect("Failed to rebuild world state.");
+        assert_eq!(world_state_view.height(), rebuilt_world_state_view.height());
+        assert!(world_state_view.diff(&rebuilt_world_state_view).is_empty());
+        assert_eq!(
+            Some(150),
+            rebuilt_world_state_view
+                .asset(&asset_id)
+                .map(|asset| asset.quantity)
+        );
+    }
+
+    #[async_std::test]
+    async fn world_state_is_rebuilt_with_blocks_evicted_from_memory() {
+        let peer = Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.max_blocks_in_memory(2);
+        kura.init().await.expect("Failed to init Kura.");
+        for block in chain_of_blocks(5) {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        assert_eq!(3, kura.blocks_offset);
+        let world_state_view = kura
+            .rebuild_world_state(peer.clone())
+            .await
+            .expect("Failed to rebuild world state.");
+        assert_eq!(5, world_state_view.height());
+        kura.snapshot_height(3);
+        kura.prune(3).await.expect("Failed to prune blocks.");
+        let error = kura
+            .rebuild_world_state(peer)
+            .await
+            .expect_err("State should not be rebuilt from pruned blocks.");
+        assert!(error.contains("were pruned"));
+    }
+
+    #[async_std::test]
+    async fn merkle_tree_covers_all_stored_blocks_regardless_of_how_they_were_stored() {
+        let blocks = chain_of_blocks(6);
+        let mut expected_merkle_tree = MerkleTree::new();
+        expected_merkle_tree.build(&blocks.iter().collect::<Vec<&ValidBlock>>());
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.max_blocks_in_memory(2);
+        kura.init().await.expect("Failed to init Kura.");
+        for block in blocks[..2].iter().cloned() {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        kura.store_batch(blocks[2..4].to_vec())
+            .await
+            .expect("Failed to store blocks into Kura.");
+        for block in blocks[4..].iter().cloned() {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        assert_eq!(
+            expected_merkle_tree.root_hash(),
+            kura.merkle_tree.root_hash()
+        );
+        let (tx, _rx) = sync::channel(100);
+        let mut reloaded_kura = Kura::new(Mode::Strict, dir.path(), tx);
+        reloaded_kura.init().await.expect("Failed to init Kura.");
+        assert_eq!(
+            expected_merkle_tree.root_hash(),
+            reloaded_kura.merkle_tree.root_hash()
+        );
+    }
+
+    #[async_std::test]
+    async fn store_batch_flushes_to_disk_once_per_chunk() {
+        let blocks = chain_of_blocks(500);
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(500);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        for block in blocks.clone() {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(500);
+        let mut batch_kura = Kura::new(Mode::Strict, dir.path(), tx);
+        batch_kura.init().await.expect("Failed to init Kura.");
+        let hashes = batch_kura
+            .store_batch(blocks.clone())
+            .await
+            .expect("Failed to store blocks into Kura.");
+        assert_eq!(500, batch_kura.height());
+        assert_eq!(
+            blocks.iter().map(ValidBlock::hash).collect::<Vec<Hash>>(),
+            hashes
+        );
+        assert_eq!(kura.latest_block_hash(), batch_kura.latest_block_hash());
+        assert_eq!(500, kura.block_store.disk_syncs());
+        let chunks = (500 / BATCH_SYNC_CHUNK_SIZE) as u64;
+        assert_eq!(chunks, batch_kura.block_store.disk_syncs());
+        // Blocks which are already stored are neither written nor flushed again.
+        batch_kura
+            .block_store
+            .write_batch(&blocks)
+            .await
+            .expect("Failed to write stored blocks again.");
+        assert_eq!(chunks, batch_kura.block_store.disk_syncs());
+    }
+
+    #[async_std::test]
+    async fn store_rejects_forked_and_non_contiguous_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let mut blocks = chain_of_blocks(4);
+        let next_block = blocks.pop().expect("Failed to get block.");
+        for block in blocks.clone() {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        let top_block_hash = kura.latest_block_hash();
+        let mut forked_block = next_block.clone();
+        forked_block.header.previous_block_hash = [1; 32];
+        let error = kura
+            .store(forked_block)
+            .await
+            .expect_err("Block of another fork was stored.");
+        assert!(error.contains("Fork detected"));
+        let mut non_contiguous_block = next_block.clone();
+        non_contiguous_block.header.height = 4;
+        let error = kura
+            .store(non_contiguous_block)
+            .await
+            .expect_err("Block out of order was stored.");
+        assert!(error.contains("Non contiguous"));
+        let error = kura
+            .store(blocks[2].clone())
+            .await
+            .expect_err("Stored block was stored again.");
+        assert!(error.contains("Non contiguous"));
+        assert_eq!(3, kura.height());
+        assert_eq!(top_block_hash, kura.latest_block_hash());
+        kura.store(next_block)
+            .await
+            .expect("Failed to store block into Kura.");
+        assert_eq!(4, kura.height());
+    }
+
+    #[async_std::test]
+    async fn store_rejects_blocks_past_maximum_height() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let mut top_block = chain_of_blocks(1).pop().expect("Failed to get block.");
+        top_block.header.height = u64::MAX;
+        // Mocks a blockchain with all blocks below the maximum height pruned.
+        kura.blocks_offset = u64::MAX;
+        kura.blocks.push(top_block.clone());
+        assert_eq!(u64::MAX, kura.height());
+        let mut next_block = chain_of_blocks(1).pop().expect("Failed to get block.");
+        next_block.header.height = 0;
+        next_block.header.previous_block_hash = top_block.hash();
+        let error = kura
+            .store(next_block.clone())
+            .await
+            .expect_err("Block above the maximum height was stored.");
+        assert!(error.contains("maximum height"), "{}", error);
+        let error = kura
+            .store_batch(vec![next_block])
+            .await
+            .expect_err("Block above the maximum height was stored.");
+        assert!(error.contains("maximum height"), "{}", error);
+    }
+
+    #[async_std::test]
+    async fn store_on_chain_without_top_block_in_memory_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        // Mocks a broken invariant: blocks are counted, but none of them are in memory.
+        kura.blocks_offset = 3;
+        let mut block = chain_of_blocks(1).pop().expect("Failed to get block.");
+        block.header.height = 3;
+        let error = kura
+            .store(block)
+            .await
+            .expect_err("Block was stored without checking the top block.");
+        assert_eq!("Top block at height 2 is not in memory.", error);
+        kura.blocks_offset = 0;
+        kura.store(chain_of_blocks(1).pop().expect("Failed to get block."))
+            .await
+            .expect("Failed to store the first block into empty Kura.");
+        assert_eq!(1, kura.height());
+    }
+
+    #[async_std::test]
+    async fn store_rejects_oversized_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let mut block = chain_of_blocks(1).pop().expect("Failed to get block.");
+        let max_block_bytes = Vec::from(&block).len();
+        kura.max_block_bytes(max_block_bytes);
+        block.signatures.push(
+            Signature::new(&KeyPair::new(public_key, private_key), &block.hash())
+                .expect("Failed to sign block."),
+        );
+        let error = kura
+            .store(block.clone())
+            .await
+            .expect_err("Oversized block was stored.");
+        assert!(
+            error.contains(&format!("larger than the maximum of {}", max_block_bytes)),
+            "{}",
+            error
+        );
+        assert_eq!(0, kura.height());
+        kura.max_block_bytes(Vec::from(&block).len());
+        kura.store(block)
+            .await
+            .expect("Failed to store block into Kura.");
+        assert_eq!(1, kura.height());
+    }
+
+    #[async_std::test]
+    async fn store_batch_with_broken_chain_changes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let mut blocks = chain_of_blocks(10);
+        blocks[5].header.previous_block_hash = [1; 32];
+        assert!(kura.store_batch(blocks).await.is_err());
+        assert_eq!(0, kura.height());
+        assert!(BlockStore::new(dir.path()).first_block_height().is_none());
+    }
+
+    #[async_std::test]
+    async fn integrity_check_of_clean_block_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let blocks = chain_of_blocks(5);
+        kura.store_batch(blocks.clone())
+            .await
+            .expect("Failed to store blocks into Kura.");
+        let report = BlockStore::new(dir.path())
+            .integrity_check()
+            .await
+            .expect("Failed to check block store.");
+        let mut merkle_tree = MerkleTree::new();
+        merkle_tree.build(&blocks.iter().collect::<Vec<&ValidBlock>>());
+        assert!(report.is_ok());
+        assert_eq!(5, report.total_blocks);
+        assert_eq!(merkle_tree.root_hash(), report.root_hash);
+    }
+
+    #[async_std::test]
+    async fn integrity_check_reports_corrupted_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        kura.store_batch(chain_of_blocks(5))
+            .await
+            .expect("Failed to store blocks into Kura.");
+        let block_store = BlockStore::new(dir.path());
+        fs::write(block_store.get_block_path(3), b"corrupted")
+            .expect("Failed to corrupt block file.");
+        let report = block_store
+            .integrity_check()
+            .await
+            .expect("Failed to check block store.");
+        assert_eq!(3, report.total_blocks);
+        assert_eq!(Some(3), report.first_failure.map(|(height, _)| height));
+        fs::remove_file(block_store.get_block_path(1)).expect("Failed to delete block file.");
+        let report = block_store
+            .integrity_check()
+            .await
+            .expect("Failed to check block store.");
+        assert_eq!(1, report.total_blocks);
+        assert_eq!(Some(1), report.first_failure.map(|(height, _)| height));
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0
+                .lock()
+                .expect("Failed to lock output.")
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_std::test]
+    async fn logged_block_hash_matches_stored_block() {
+        let output = SharedOutput::default();
+        logger::set_output(Box::new(output.clone()));
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        for block in chain_of_blocks(2) {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        let output = String::from_utf8(output.0.lock().expect("Failed to lock output.").clone())
+            .expect("Failed to read output.");
+        let block_store = BlockStore::new(dir.path());
+        let stored_block = block_store.read(1).await.expect("Failed to read block.");
+        // Other tests may log blocks stored at the same height concurrently.
+        let logged_hashes: Vec<HashHex> = output
+            .lines()
+            .filter_map(|line| line.split("Stored block ").nth(1))
+            .filter(|message| message.ends_with("at height 1."))
+            .filter_map(|message| message.split(' ').next())
+            .map(|hex| hex.parse().expect("Failed to parse logged hash."))
+            .collect();
+        assert!(logged_hashes.contains(&HashHex(stored_block.hash())));
+    }
+
+    #[async_std::test]
+    async fn verify_chain_reports_corrupted_block_within_interval() {
+        let interval = Duration::from_millis(200);
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let mut blocks = chain_of_blocks(4);
+        let top_block = blocks.pop().expect("Chain has blocks.");
+        for block in blocks {
+            kura.store(block)
+                .await
+                .expect("Failed to store block into Kura.");
+        }
+        let _verification = kura.verify_chain(interval, 10);
+        task::sleep(interval * 3 / 2).await;
+        assert_eq!(0, kura.metrics().integrity_failures);
+        let path = kura.block_store.get_block_path(1);
+        let mut bytes = fs::read(&path).expect("Failed to read block file.");
+        bytes.truncate(bytes.len() / 2);
+        fs::write(&path, bytes).expect("Failed to corrupt block file.");
+        kura.store(top_block)
+            .await
+            .expect("Failed to store block into Kura.");
+        task::sleep(interval).await;
+        let metrics = kura.metrics();
+        assert!(metrics.integrity_failures > 0);
+        assert_eq!(Some(1), metrics.corrupted_block_height);
+    }
+
+    #[test]
+    fn kura_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Kura>();
+    }
+
+    #[async_std::test]
+    async fn concurrent_stores_and_reads_keep_chain_consistent() {
+        const N_BLOCKS: u64 = 20;
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, _rx) = sync::channel(100);
+        let mut kura = Kura::new(Mode::Strict, dir.path(), tx);
+        kura.init().await.expect("Failed to init Kura.");
+        let kura = Arc::new(sync::RwLock::new(kura));
+        let blocks = Arc::new(chain_of_blocks(N_BLOCKS));
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let kura = Arc::clone(&kura);
+                let blocks = Arc::clone(&blocks);
+                task::spawn(async move {
+                    loop {
+                        let mut kura = kura.write().await;
+                        let height = kura.height();
+                        if height == N_BLOCKS {
+                            break;
+                        }
+                        kura.store(blocks[height as usize].clone())
+                            .await
+                            .expect("Failed to store block into Kura.");
+                        drop(kura);
+                        task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+        let reader = {
+            let kura = Arc::clone(&kura);
+            let blocks = Arc::clone(&blocks);
+            task::spawn(async move {
+                let mut last_height = 0;
+                while last_height < N_BLOCKS {
+                    let mut kura = kura.write().await;
+                    let height = kura.height();
+                    assert!(height >= last_height, "Height decreased.");
+                    if height > 0 {
+                        let top_block = kura
+                            .read_block(height - 1)
+                            .await
+                            .expect("Failed to read block.");
+                        assert_eq!(blocks[(height - 1) as usize].hash(), top_block.hash());
+                    }
+                    last_height = height;
+                    drop(kura);
+                    task::yield_now().await;
+                }
+            })
+        };
+        for writer in writers {
+            writer.await;
+        }
+        reader.await;
+        let kura = kura.read().await;
+        assert_eq!(N_BLOCKS, kura.height());
+        assert_eq!(
+            blocks.last().map(ValidBlock::hash),
+            kura.latest_block_hash()
+        );
+        assert!(kura
+            .block_store
+            .integrity_check()
+            .await
+            .expect("Failed to check block store.")
+            .is_ok());
+    }
 }
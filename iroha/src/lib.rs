@@ -5,6 +5,7 @@
 pub mod account;
 pub mod asset;
 pub mod block;
+pub mod block_sync;
 #[cfg(feature = "bridge")]
 pub mod bridge;
 pub mod config;
@@ -12,36 +13,52 @@ pub mod crypto;
 #[cfg(feature = "dex")]
 pub mod dex;
 pub mod domain;
+pub mod event;
 pub mod isi;
-mod kura;
+pub mod kura;
 mod merkle;
+pub mod name;
 pub mod peer;
 mod permission;
 pub mod query;
 mod queue;
+pub mod scheduler;
 pub mod sumeragi;
 pub mod torii;
 pub mod tx;
 pub mod wsv;
 
 use crate::{
+    block::BlockSchedule,
+    block_sync::BlockSynchronizer,
     config::Configuration,
     kura::Kura,
-    peer::Peer,
+    peer::{Peer, PeerStatus},
     prelude::*,
     queue::Queue,
     sumeragi::{Message, Sumeragi},
     torii::Torii,
+    tx::TransactionLimits,
 };
 use async_std::{
     prelude::*,
     sync::{self, Receiver, RwLock, Sender},
     task,
 };
-use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 /// The interval at which sumeragi checks if there are tx in the `queue`.
 pub const TX_RETRIEVAL_INTERVAL: Duration = Duration::from_millis(100);
+/// The interval at which the heights of the other known peers are polled.
+pub const NETWORK_STATUS_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Type of `Sender<ValidBlock>` which should be used for channels of `ValidBlock` messages.
 pub type ValidBlockSender = Sender<ValidBlock>;
@@ -68,27 +85,37 @@ pub struct Iroha {
     queue: Arc<RwLock<Queue>>,
     sumeragi: Arc<RwLock<Sumeragi>>,
     kura: Arc<RwLock<Kura>>,
+    block_synchronizer: BlockSynchronizer,
     transactions_receiver: Arc<RwLock<TransactionReceiver>>,
     wsv_blocks_receiver: Arc<RwLock<CommittedBlockReceiver>>,
     kura_blocks_receiver: Arc<RwLock<ValidBlockReceiver>>,
     message_receiver: Arc<RwLock<MessageReceiver>>,
     world_state_view: Arc<RwLock<WorldStateView>>,
+    block_production_paused: Arc<AtomicBool>,
+    /// Interval and window of the background integrity check of the stored blocks.
+    kura_verify_chain: Option<(Duration, u64)>,
     _block_build_step_ms: u64,
 }
 
 impl Iroha {
     /// Default `Iroha` constructor used to build it based on the provided `Configuration`.
     pub fn new(config: Configuration) -> Self {
+        iroha_macro::log::init(config.logger_configuration.clone());
+        crypto::set_strict_verification(config.strict_signature_verification);
+        peer::set_tls(config.tls);
         let (transactions_sender, transactions_receiver) = sync::channel(100);
         let (wsv_blocks_sender, wsv_blocks_receiver) = sync::channel(100);
         let (kura_blocks_sender, kura_blocks_receiver) = sync::channel(100);
         let (message_sender, message_receiver) = sync::channel(100);
-        let domain_name = "global".to_string();
+        let domain_name = Name::new("global");
         let mut asset_definitions = HashMap::new();
         let asset_definition_id = permission::permission_asset_definition_id();
         asset_definitions.insert(
             asset_definition_id.clone(),
-            AssetDefinition::new(asset_definition_id.clone()),
+            AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::new("root", "global"),
+            ),
         );
         let account_id = AccountId::new("root", &domain_name);
         let asset_id = AssetId {
@@ -97,8 +124,11 @@ impl Iroha {
         };
         let asset =
             Asset::with_permission(asset_id.clone(), ("anything".to_string(), "".to_string()));
-        let mut account =
-            Account::new(&account_id.name, &account_id.domain_name, config.public_key);
+        let mut account = Account::new(
+            &account_id.name,
+            &account_id.domain_name,
+            config.root_public_key,
+        );
         account.assets.insert(asset_id, asset);
         let mut accounts = HashMap::new();
         accounts.insert(account_id, account);
@@ -109,90 +139,198 @@ impl Iroha {
         };
         let mut domains = HashMap::new();
         domains.insert(domain_name, domain);
-        let world_state_view = Arc::new(RwLock::new(WorldStateView::new(Peer::with_domains(
+        let mut world_state_view = WorldStateView::try_new(Peer::with_domains(
             config.peer_id.clone(),
             &config.trusted_peers,
             domains,
-        ))));
-        let torii = Torii::new(
+        ))
+        .expect("Failed to initialize World State View.");
+        world_state_view.set_transaction_limits(TransactionLimits {
+            max_cost: Some(config.max_transaction_cost),
+            fee_asset_definition_id: config.transaction_fee_asset_definition_id.clone(),
+        });
+        world_state_view.set_block_schedule(BlockSchedule {
+            block_build_step_ms: config.block_build_step_ms,
+            max_transactions_in_block: config.max_transactions_in_block as u64,
+        });
+        let mut queue = Queue::new(
+            config.max_transactions_in_block,
+            world_state_view.pending_transactions_counter(),
+        );
+        queue.max_pending_transactions(config.max_pending_transactions);
+        // The proxy tail signs the block on top of the collected signatures.
+        queue.max_block_bytes(config.max_block_bytes, config.max_block_signatures + 1);
+        queue.block_time(Duration::from_millis(config.block_build_step_ms));
+        if let Some(block_trigger_depth) = config.block_trigger_depth {
+            queue.block_trigger_depth(block_trigger_depth);
+        }
+        let block_production_paused = world_state_view.block_production_paused_flag();
+        let world_state_view = Arc::new(RwLock::new(world_state_view));
+        let mut torii = Torii::new(
             &config.peer_id.address.clone(),
             Arc::clone(&world_state_view),
             transactions_sender.clone(),
             message_sender,
         );
+        torii.transaction_rate_limit(
+            config.torii_transaction_rate,
+            config.torii_transaction_burst,
+        );
+        torii.max_transaction_bytes(config.max_transaction_bytes);
+        torii.max_pending_transactions(config.max_pending_transactions);
         let (_public_key, private_key) = config.key_pair();
-        let kura = Arc::new(RwLock::new(Kura::new(
+        #[cfg(feature = "tls")]
+        {
+            if config.tls {
+                torii
+                    .enable_tls(&private_key)
+                    .expect("Failed to enable TLS.");
+            }
+        }
+        let mut kura = Kura::new(
             config.mode,
             Path::new(&config.kura_block_store_path),
             wsv_blocks_sender,
-        )));
-        let sumeragi = Arc::new(RwLock::new(
-            Sumeragi::new(
-                private_key,
-                &config.trusted_peers,
-                config.peer_id,
-                config.max_faulty_peers,
-                Arc::new(RwLock::new(kura_blocks_sender)),
-                world_state_view.clone(),
-                transactions_sender,
-                config.commit_time_ms,
-                config.tx_receipt_time_ms,
-            )
-            .expect("Failed to initialize Sumeragi."),
-        ));
-        let queue = Arc::new(RwLock::new(Queue::default()));
+        );
+        kura.block_store_layout(config.kura_block_store_layout);
+        if let Some(key) = config.kura_block_store_encryption_key {
+            kura.block_store_encryption_key(key);
+        }
+        kura.block_cache_size(config.kura_block_cache_size);
+        kura.max_block_bytes(config.max_block_bytes);
+        if let Some(max_blocks_in_memory) = config.kura_max_blocks_in_memory {
+            kura.max_blocks_in_memory(max_blocks_in_memory);
+        }
+        let kura_verify_chain_window = config.kura_verify_chain_window;
+        let kura_verify_chain = config
+            .kura_verify_chain_interval_ms
+            .map(|interval_ms| (Duration::from_millis(interval_ms), kura_verify_chain_window));
+        let kura = Arc::new(RwLock::new(kura));
+        let block_synchronizer = BlockSynchronizer::new(
+            Arc::clone(&kura),
+            Arc::clone(&world_state_view),
+            &config.peer_id,
+            &config.trusted_peers,
+            config.max_faulty_peers,
+            KeyPair::new(config.public_key, private_key),
+        );
+        let mut sumeragi = Sumeragi::new(
+            private_key,
+            &config.trusted_peers,
+            config.peer_id,
+            config.max_faulty_peers,
+            Arc::new(RwLock::new(kura_blocks_sender)),
+            world_state_view.clone(),
+            transactions_sender,
+            config.commit_time_ms,
+            config.tx_receipt_time_ms,
+        )
+        .expect("Failed to initialize Sumeragi.");
+        sumeragi.max_block_signatures(config.max_block_signatures);
+        sumeragi.produce_empty_blocks(config.produce_empty_blocks);
+        let sumeragi = Arc::new(RwLock::new(sumeragi));
+        let queue = Arc::new(RwLock::new(queue));
         Iroha {
             queue,
             torii: Arc::new(RwLock::new(torii)),
             sumeragi,
             kura,
+            block_synchronizer,
             world_state_view,
             transactions_receiver: Arc::new(RwLock::new(transactions_receiver)),
             wsv_blocks_receiver: Arc::new(RwLock::new(wsv_blocks_receiver)),
             message_receiver: Arc::new(RwLock::new(message_receiver)),
             _block_build_step_ms: config.block_build_step_ms,
             kura_blocks_receiver: Arc::new(RwLock::new(kura_blocks_receiver)),
+            block_production_paused,
+            kura_verify_chain,
         }
     }
 
+    /// Stop building new blocks, e.g. for the maintenance. Transactions are still accepted into
+    /// the queue until it is full.
+    pub fn pause_block_production(&self) {
+        self.block_production_paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Continue building blocks from the transactions queued so far.
+    pub fn resume_block_production(&self) {
+        self.block_production_paused.store(false, Ordering::Relaxed);
+    }
+
     /// To make `Iroha` peer work it should be started first. After that moment it will listen for
     /// incoming requests and messages.
     pub async fn start(&self) -> Result<(), String> {
         let kura = Arc::clone(&self.kura);
         kura.write().await.init().await?;
+        if let Some((interval, window)) = self.kura_verify_chain {
+            let _verification = kura.read().await.verify_chain(interval, window);
+        }
         let torii = Arc::clone(&self.torii);
         let torii_handle = task::spawn(async move {
             if let Err(e) = torii.write().await.start().await {
                 eprintln!("Failed to start Torii: {}", e);
             }
         });
+        let wsv_blocks_receiver = Arc::clone(&self.wsv_blocks_receiver);
+        let world_state_view = Arc::clone(&self.world_state_view);
+        let wsv_handle = task::spawn(async move {
+            while let Some(block) = wsv_blocks_receiver.write().await.next().await {
+                world_state_view.write().await.put(&block).await;
+            }
+        });
+        // Blocks are applied to the world state by the task above, so it should be started
+        // before the synchronization.
+        self.block_synchronizer.synchronize().await;
         let transactions_receiver = Arc::clone(&self.transactions_receiver);
         let queue = Arc::clone(&self.queue);
         let tx_handle = task::spawn(async move {
             while let Some(transaction) = transactions_receiver.write().await.next().await {
-                queue.write().await.push_pending_transaction(transaction);
+                if let Err(e) = queue.write().await.push_pending_transaction(transaction) {
+                    eprintln!("Failed to queue transaction: {}", e);
+                }
             }
         });
         let queue = Arc::clone(&self.queue);
         let sumeragi = Arc::clone(&self.sumeragi);
+        let block_production_paused = Arc::clone(&self.block_production_paused);
         let voting_handle = task::spawn(async move {
             loop {
-                if !sumeragi.write().await.voting_in_progress().await {
-                    sumeragi
-                        .write()
-                        .await
-                        .round(queue.write().await.pop_pending_transactions())
-                        .await
-                        .expect("Round failed.");
+                if !block_production_paused.load(Ordering::Relaxed)
+                    && !sumeragi.write().await.voting_in_progress().await
+                {
+                    let transactions = queue.write().await.pop_block_transactions();
+                    if let Some(transactions) = transactions {
+                        sumeragi
+                            .write()
+                            .await
+                            .round(transactions)
+                            .await
+                            .expect("Round failed.");
+                    }
                 }
                 task::sleep(TX_RETRIEVAL_INTERVAL).await;
             }
         });
-        let wsv_blocks_receiver = Arc::clone(&self.wsv_blocks_receiver);
         let world_state_view = Arc::clone(&self.world_state_view);
-        let wsv_handle = task::spawn(async move {
-            while let Some(block) = wsv_blocks_receiver.write().await.next().await {
-                world_state_view.write().await.put(&block).await;
+        let network_status_handle = task::spawn(async move {
+            let peer_statuses = world_state_view.read().await.peer_statuses();
+            loop {
+                let peers = world_state_view.read().await.read_peer().peers.clone();
+                for peer_id in peers {
+                    let mut status = peer_statuses
+                        .lock()
+                        .expect("Failed to lock peer statuses.")
+                        .get(&peer_id)
+                        .cloned()
+                        .unwrap_or_else(|| PeerStatus::unknown(peer_id.clone()));
+                    status.poll(NETWORK_STATUS_POLL_INTERVAL).await;
+                    let _ = peer_statuses
+                        .lock()
+                        .expect("Failed to lock peer statuses.")
+                        .insert(peer_id, status);
+                }
+                task::sleep(NETWORK_STATUS_POLL_INTERVAL).await;
             }
         });
         let message_receiver = Arc::clone(&self.message_receiver);
@@ -208,12 +346,9 @@ impl Iroha {
         let kura = Arc::clone(&self.kura);
         let kura_handle = task::spawn(async move {
             while let Some(block) = kura_blocks_receiver.write().await.next().await {
-                let _hash = kura
-                    .write()
-                    .await
-                    .store(block)
-                    .await
-                    .expect("Failed to write block.");
+                if let Err(e) = kura.write().await.store(block).await {
+                    eprintln!("Failed to write block: {}", e);
+                }
             }
         });
         futures::join!(
@@ -222,7 +357,8 @@ impl Iroha {
             voting_handle,
             wsv_handle,
             sumeragi_message_handle,
-            tx_handle
+            tx_handle,
+            network_status_handle
         );
         Ok(())
     }
@@ -234,6 +370,29 @@ pub trait Identifiable {
     type Id;
 }
 
+/// Encode the `value`, decode and encode it again, and assert that the decoded value is equal to
+/// the original one and both encodings are byte-equal.
+///
+/// Peers agree on hashes only if logically equal values have equal encodings, e.g. maps should
+/// be encoded in the order of the keys regardless of the insertion order.
+#[cfg(test)]
+pub(crate) fn assert_canonical_encoding<T>(value: &T)
+where
+    T: parity_scale_codec::Encode + parity_scale_codec::Decode + PartialEq + std::fmt::Debug,
+{
+    let bytes = value.encode();
+    let decoded = T::decode(&mut bytes.as_slice()).expect("Failed to decode encoded value.");
+    assert_eq!(
+        value, &decoded,
+        "Decoded value differs from the original one."
+    );
+    assert_eq!(
+        bytes,
+        decoded.encode(),
+        "Encoding of the decoded value differs from the original one."
+    );
+}
+
 pub mod prelude {
     //! Re-exports important traits and types. Meant to be glob imported when using `Iroha`.
 
@@ -243,9 +402,11 @@ pub mod prelude {
         asset::{Asset, AssetDefinition, AssetDefinitionId, AssetId},
         block::{CommittedBlock, PendingBlock, ValidBlock},
         config::Configuration,
-        crypto::{Hash, PrivateKey, PublicKey, Signature},
+        crypto::{Hash, HashHex, KeyPair, PrivateKey, PublicKey, Signature, Signer},
         domain::Domain,
         isi::Instruction,
+        merkle::{SparseMerkleProof, SparseMerkleTree},
+        name::Name,
         peer::Peer,
         query::{Query, QueryRequest, QueryResult},
         tx::{AcceptedTransaction, RequestedTransaction, SignedTransaction, ValidTransaction},
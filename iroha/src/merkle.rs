@@ -1,4 +1,4 @@
-use crate::prelude::*;
+use crate::{crypto, prelude::*};
 
 /// [Merkle Tree](https://en.wikipedia.org/wiki/Merkle_tree) used to validate and prove data at
 /// each block height.
@@ -18,10 +18,15 @@ impl MerkleTree {
     /// Builds a Merkle Tree from sorted array of `ValidBlocks`.
     //TODO: should we check or sort blocks here?
     pub fn build(&mut self, blocks: &[&ValidBlock]) {
-        //hm, can we write map(ValidBlock::hash) in Rust?
-        let mut nodes: std::collections::VecDeque<Node> = blocks
+        let hashes: Vec<Hash> = blocks.iter().map(|block| block.hash()).collect();
+        self.build_from_hashes(&hashes);
+    }
+
+    /// Builds a Merkle Tree from sorted array of hashes (e.g. hashes of the block's transactions).
+    pub fn build_from_hashes(&mut self, hashes: &[Hash]) {
+        let mut nodes: std::collections::VecDeque<Node> = hashes
             .iter()
-            .map(|block| Node::Leaf { hash: block.hash() })
+            .map(|hash| Node::Leaf { hash: *hash })
             .collect();
         if nodes.len() % 2 != 0 {
             nodes.push_back(Node::Empty);
@@ -37,6 +42,11 @@ impl MerkleTree {
         }
         self.root_node = nodes.pop_front().unwrap_or(Node::Empty);
     }
+
+    /// Return the `Hash` of the root node, or an array of zeros for an empty tree.
+    pub fn root_hash(&self) -> Hash {
+        self.root_node.hash()
+    }
 }
 
 impl Default for MerkleTree {
@@ -86,10 +96,6 @@ impl Node {
     }
 
     fn nodes_pair_hash(left: &Self, right: &Self) -> Hash {
-        use ursa::blake2::{
-            digest::{Input, VariableOutput},
-            VarBlake2b,
-        };
         let left_hash = left.hash();
         let right_hash = right.hash();
         let sum: Vec<_> = left_hash
@@ -98,16 +104,147 @@ impl Node {
             .map(|(left, right)| left.saturating_add(*right))
             .take(32)
             .collect();
-        let vector = VarBlake2b::new(32)
-            .expect("Failed to initialize VarBlake2b.")
-            .chain(sum)
-            .vec_result();
-        let mut hash = [0; 32];
-        hash.copy_from_slice(&vector);
-        hash
+        crypto::hash(sum)
+    }
+}
+
+/// Number of levels below the root of `SparseMerkleTree`, one per bit of the key.
+const SPARSE_TREE_DEPTH: usize = 256;
+
+/// Key and value hash of a present entry of `SparseMerkleTree`.
+type Entry<'a> = (&'a Hash, &'a Hash);
+
+/// Sparse [Merkle Tree](https://en.wikipedia.org/wiki/Merkle_tree) with a leaf for every
+/// possible 256-bit key, used to commit to mutable state like accounts and assets.
+///
+/// Unlike `MerkleTree`, entries can be updated and removed, and the root does not depend on
+/// the order of insertions. Keys are usually hashes of the entities ids and values are hashes of
+/// the entities. Empty subtrees hash to zeros, so only the present entries are stored.
+#[derive(Clone, Debug, Default)]
+pub struct SparseMerkleTree {
+    leaves: std::collections::BTreeMap<Hash, Hash>,
+}
+
+impl SparseMerkleTree {
+    /// Default `SparseMerkleTree` constructor, creates an empty tree.
+    pub fn new() -> Self {
+        SparseMerkleTree::default()
+    }
+
+    /// Sets the value hash of the `key`.
+    ///
+    /// Returns the previous value hash if the key was present.
+    pub fn insert(&mut self, key: Hash, value_hash: Hash) -> Option<Hash> {
+        self.leaves.insert(key, value_hash)
+    }
+
+    /// Removes the `key` from the tree.
+    ///
+    /// Returns the value hash of the key if it was present.
+    pub fn remove(&mut self, key: &Hash) -> Option<Hash> {
+        self.leaves.remove(key)
+    }
+
+    /// Value hash of the `key` if it is present.
+    pub fn get(&self, key: &Hash) -> Option<&Hash> {
+        self.leaves.get(key)
+    }
+
+    /// Return the `Hash` of the root node, or an array of zeros for an empty tree.
+    pub fn root(&self) -> Hash {
+        let leaves: Vec<Entry<'_>> = self.leaves.iter().collect();
+        Self::subtree_hash(&leaves, 0)
+    }
+
+    /// Builds a proof of the value of the `key`, or of its absence if the key is not present.
+    pub fn prove(&self, key: &Hash) -> SparseMerkleProof {
+        let leaves: Vec<Entry<'_>> = self.leaves.iter().collect();
+        let mut subtree = &leaves[..];
+        let mut siblings = Vec::with_capacity(SPARSE_TREE_DEPTH);
+        for depth in 0..SPARSE_TREE_DEPTH {
+            let (left, right) = Self::split(subtree, depth);
+            if bit(key, depth) {
+                siblings.push(Self::subtree_hash(left, depth + 1));
+                subtree = right;
+            } else {
+                siblings.push(Self::subtree_hash(right, depth + 1));
+                subtree = left;
+            }
+        }
+        SparseMerkleProof { siblings }
+    }
+
+    /// Splits sorted leaves of the subtree at `depth` into the leaves of its left and right
+    /// children.
+    fn split<'a, 'b>(leaves: &'a [Entry<'b>], depth: usize) -> (&'a [Entry<'b>], &'a [Entry<'b>]) {
+        leaves.split_at(leaves.partition_point(|(key, _)| !bit(key, depth)))
+    }
+
+    fn subtree_hash(leaves: &[Entry<'_>], depth: usize) -> Hash {
+        match leaves {
+            [] => [0; 32],
+            [(key, value_hash)] if depth == SPARSE_TREE_DEPTH => leaf_hash(key, value_hash),
+            _ => {
+                let (left, right) = Self::split(leaves, depth);
+                sparse_nodes_pair_hash(
+                    &Self::subtree_hash(left, depth + 1),
+                    &Self::subtree_hash(right, depth + 1),
+                )
+            }
+        }
     }
 }
 
+/// Proof of the value of a key in `SparseMerkleTree`, or of the key absence.
+#[derive(Clone, Debug)]
+pub struct SparseMerkleProof {
+    /// Hashes of the siblings of the nodes on the path from the root to the leaf.
+    siblings: Vec<Hash>,
+}
+
+impl SparseMerkleProof {
+    /// Checks that the tree with the `root` contains the `key` with `value_hash`, or does not
+    /// contain the `key` if `value_hash` is `None`.
+    pub fn verify(&self, root: &Hash, key: &Hash, value_hash: Option<&Hash>) -> bool {
+        if self.siblings.len() != SPARSE_TREE_DEPTH {
+            return false;
+        }
+        let leaf = match value_hash {
+            Some(value_hash) => leaf_hash(key, value_hash),
+            None => [0; 32],
+        };
+        let computed_root =
+            self.siblings
+                .iter()
+                .enumerate()
+                .rev()
+                .fold(leaf, |hash, (depth, sibling)| {
+                    if bit(key, depth) {
+                        sparse_nodes_pair_hash(sibling, &hash)
+                    } else {
+                        sparse_nodes_pair_hash(&hash, sibling)
+                    }
+                });
+        computed_root == *root
+    }
+}
+
+/// Bit of the `key` selecting the child at `depth`, `true` for the right one.
+fn bit(key: &Hash, depth: usize) -> bool {
+    key[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+fn leaf_hash(key: &Hash, value_hash: &Hash) -> Hash {
+    crypto::hash(key.iter().chain(value_hash.iter()).cloned().collect())
+}
+
+fn sparse_nodes_pair_hash(left: &Hash, right: &Hash) -> Hash {
+    if *left == [0; 32] && *right == [0; 32] {
+        return [0; 32];
+    }
+    crypto::hash(left.iter().chain(right.iter()).cloned().collect())
+}
+
 pub struct BreadthFirstIter<'a> {
     queue: Vec<&'a Node>,
 }
@@ -155,6 +292,49 @@ mod tests {
     use super::*;
     use crate::peer::PeerId;
 
+    #[test]
+    fn sparse_tree_proves_membership_and_non_membership() {
+        let key = |name: &str| crypto::hash(name.as_bytes().to_vec());
+        let mut tree = SparseMerkleTree::new();
+        assert_eq!([0; 32], tree.root());
+        for name in &["alice", "bob", "carol", "dave"] {
+            assert_eq!(
+                None,
+                tree.insert(key(name), key(&format!("{}_state", name)))
+            );
+        }
+        let root_before_update = tree.root();
+        assert_eq!(
+            Some(key("bob_state")),
+            tree.insert(key("bob"), key("bob_state_updated"))
+        );
+        assert_eq!(Some(key("carol_state")), tree.remove(&key("carol")));
+        let root = tree.root();
+        assert_ne!(root_before_update, root);
+        let proof = tree.prove(&key("alice"));
+        assert!(proof.verify(&root, &key("alice"), Some(&key("alice_state"))));
+        assert!(!proof.verify(&root, &key("alice"), Some(&key("bob_state"))));
+        assert!(!proof.verify(&root, &key("alice"), None));
+        let proof = tree.prove(&key("bob"));
+        assert!(proof.verify(&root, &key("bob"), Some(&key("bob_state_updated"))));
+        assert!(!proof.verify(&root, &key("bob"), Some(&key("bob_state"))));
+        assert!(!proof.verify(
+            &root_before_update,
+            &key("bob"),
+            Some(&key("bob_state_updated"))
+        ));
+        let proof = tree.prove(&key("carol"));
+        assert!(proof.verify(&root, &key("carol"), None));
+        assert!(!proof.verify(&root, &key("carol"), Some(&key("carol_state"))));
+        let proof = tree.prove(&key("eve"));
+        assert!(proof.verify(&root, &key("eve"), None));
+        let mut same_entries = SparseMerkleTree::new();
+        same_entries.insert(key("dave"), key("dave_state"));
+        same_entries.insert(key("bob"), key("bob_state_updated"));
+        same_entries.insert(key("alice"), key("alice_state"));
+        assert_eq!(root, same_entries.root());
+    }
+
     #[test]
     fn tree_with_two_layers_should_reach_all_nodes() {
         let tree = MerkleTree {
@@ -0,0 +1,316 @@
+//! This module contains `Name` - the shared string used in the identifications of accounts,
+//! assets and domains.
+
+use parity_scale_codec::{Decode, Encode, Input, Output};
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::DefaultHasher, BTreeSet},
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+/// Number of names in a shard after which names not used anymore are dropped for the first time.
+const INITIAL_PRUNE_THRESHOLD: usize = 1024;
+/// Number of independently locked parts of the interner, so threads creating different names
+/// rarely wait for each other.
+const SHARDS: usize = 16;
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SHARD: Mutex<Interner> = Mutex::new(Interner {
+    names: BTreeSet::new(),
+    prune_threshold: INITIAL_PRUNE_THRESHOLD,
+});
+
+static INTERNER: [Mutex<Interner>; SHARDS] = [EMPTY_SHARD; SHARDS];
+
+/// Names created so far in a shard, every name is stored once and shared by all the `Name`s equal
+/// to it.
+struct Interner {
+    names: BTreeSet<Arc<str>>,
+    prune_threshold: usize,
+}
+
+impl Interner {
+    fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return Arc::clone(interned);
+        }
+        if self.names.len() >= self.prune_threshold {
+            self.names.retain(|name| Arc::strong_count(name) > 1);
+            self.prune_threshold = (self.names.len() * 2).max(INITIAL_PRUNE_THRESHOLD);
+        }
+        let interned: Arc<str> = Arc::from(name);
+        self.names.insert(Arc::clone(&interned));
+        interned
+    }
+}
+
+/// Immutable name of an account, an asset or a domain.
+///
+/// Names are interned, so a domain name repeated in the ids of thousands of accounts and assets
+/// is stored once and cloning an id does not allocate. Encoded the same way as `String`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Name(Arc<str>);
+
+impl Name {
+    /// Returns the interned name equal to `name`.
+    pub fn new(name: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        Name(
+            INTERNER[hasher.finish() as usize % SHARDS]
+                .lock()
+                .expect("Failed to lock names interner.")
+                .intern(name),
+        )
+    }
+
+    /// The name as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `self` and `other` share the same storage.
+    pub fn ptr_eq(&self, other: &Name) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Name {
+    fn from(name: &str) -> Self {
+        Name::new(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Self {
+        Name::new(&name)
+    }
+}
+
+impl From<&String> for Name {
+    fn from(name: &String) -> Self {
+        Name::new(name)
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        name.as_str().to_string()
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Name> for String {
+    fn eq(&self, other: &Name) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Name> for &str {
+    fn eq(&self, other: &Name) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Debug for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Encode for Name {
+    fn size_hint(&self) -> usize {
+        self.as_str().size_hint()
+    }
+
+    fn encode_to<T: Output>(&self, dest: &mut T) {
+        self.as_str().encode_to(dest)
+    }
+}
+
+impl Decode for Name {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+        Ok(Name::from(String::decode(input)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{account::Account, asset::Asset, domain::Domain, prelude::*};
+    use std::{
+        collections::{HashMap, HashSet},
+        mem, thread,
+    };
+
+    const ACCOUNTS: usize = 10_000;
+    const DOMAINS: usize = 5;
+
+    #[test]
+    fn equal_names_share_storage() {
+        let name = Name::from("company");
+        let other = Name::from(String::from("company"));
+        assert!(name.ptr_eq(&other));
+        assert_eq!(name, other);
+        assert_eq!("company", name.to_string());
+        assert_eq!(format!("{:?}", "company"), format!("{:?}", name));
+        assert_eq!(
+            "company".to_string().encode(),
+            name.encode(),
+            "Name should be encoded as String."
+        );
+        assert!(name
+            .ptr_eq(&Name::decode(&mut name.encode().as_slice()).expect("Failed to decode name.")));
+    }
+
+    #[test]
+    fn names_created_concurrently_share_storage() {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                thread::spawn(|| {
+                    (0..1000)
+                        .map(|index| Name::new(&format!("concurrent_{}", index)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let names: Vec<Vec<Name>> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Failed to join thread."))
+            .collect();
+        for other in &names[1..] {
+            assert!(names[0]
+                .iter()
+                .zip(other)
+                .all(|(name, other)| name.ptr_eq(other)));
+        }
+    }
+
+    #[test]
+    fn domain_keys_share_storage_with_account_ids() {
+        let domain = Domain::new("keyed_domain");
+        let mut domains = HashMap::new();
+        domains.insert(domain.name.clone(), domain);
+        let account_id = AccountId::new("alice", "keyed_domain");
+        let (key, domain) = domains
+            .get_key_value("keyed_domain")
+            .expect("Failed to find domain by name.");
+        assert!(key.ptr_eq(&account_id.domain_name));
+        assert!(domain.name.ptr_eq(&account_id.domain_name));
+    }
+
+    /// Heap bytes taken by the distinct names and the same names stored as a `String` per id.
+    #[derive(Default)]
+    struct SizeProbe {
+        names: HashSet<*const u8>,
+        interned_bytes: usize,
+        string_bytes: usize,
+    }
+
+    impl SizeProbe {
+        fn visit(&mut self, name: &Name) {
+            self.string_bytes += name.len();
+            if self.names.insert(name.as_ptr()) {
+                self.interned_bytes += name.len() + 2 * mem::size_of::<usize>();
+            }
+        }
+
+        fn visit_account_id(&mut self, id: &AccountId) {
+            self.visit(&id.name);
+            self.visit(&id.domain_name);
+        }
+
+        fn visit_asset_id(&mut self, id: &AssetId) {
+            self.visit(&id.definition_id.name);
+            self.visit(&id.definition_id.domain_name);
+            self.visit_account_id(&id.account_id);
+        }
+    }
+
+    #[test]
+    fn interned_ids_take_less_memory_than_strings() {
+        let definition_id = AssetDefinitionId::new("xor", "global");
+        let mut domains = Vec::new();
+        for domain_index in 0..DOMAINS {
+            let domain_name = format!("domain_{}", domain_index);
+            let mut domain = Domain::new(domain_name.clone());
+            for account_index in 0..ACCOUNTS / DOMAINS {
+                let account_id =
+                    AccountId::new(&format!("account_{}", account_index), &domain_name);
+                let asset_id = AssetId {
+                    definition_id: definition_id.clone(),
+                    account_id: account_id.clone(),
+                };
+                let mut account = Account::new(&account_id.name, &domain_name, [0; 32]);
+                account
+                    .assets
+                    .insert(asset_id.clone(), Asset::with_quantity(asset_id, 1));
+                domain.accounts.insert(account_id, account);
+            }
+            domains.push(domain);
+        }
+        let mut probe = SizeProbe::default();
+        for domain in &domains {
+            for (account_id, account) in &domain.accounts {
+                probe.visit_account_id(account_id);
+                probe.visit_account_id(&account.id);
+                for (asset_id, asset) in &account.assets {
+                    probe.visit_asset_id(asset_id);
+                    probe.visit_asset_id(&asset.id);
+                }
+            }
+        }
+        assert!(
+            probe.interned_bytes * 4 < probe.string_bytes,
+            "Interned names take {} bytes, strings take {} bytes.",
+            probe.interned_bytes,
+            probe.string_bytes
+        );
+    }
+}
@@ -1,12 +1,53 @@
 //! This module contains `Peer` structure and related implementations and traits implementations.
 
-use crate::{isi::prelude::*, prelude::*};
+use crate::{
+    block::query::GetHealth,
+    crypto::{self, Algorithm},
+    isi::prelude::*,
+    prelude::*,
+    torii::uri,
+};
+use async_std::future;
 use iroha_derive::*;
+use iroha_network::{Network, Request, Response};
 use parity_scale_codec::{Decode, Encode};
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    net::SocketAddr,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, SystemTime},
+};
 
 type PublicKey = [u8; 32];
 
+static TLS: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable TLS for the requests this process sends to other peers, it is disabled by
+/// default.
+///
+/// Every peer presents a certificate of its own key, so a request reaches only the peer whose
+/// `PeerId::public_key` it was sent to. All peers of a network should use the same mode.
+pub fn set_tls(enabled: bool) {
+    TLS.store(enabled, Ordering::Relaxed);
+}
+
+/// Sends the `request` to the peer, over TLS pinned to the peer's public key if enabled by
+/// `set_tls`.
+pub(crate) async fn send_request_to(
+    peer_id: &PeerId,
+    request: Request,
+) -> Result<Response, String> {
+    if TLS.load(Ordering::Relaxed) {
+        #[cfg(feature = "tls")]
+        return Network::send_request_over_tls(&peer_id.address, &peer_id.public_key, request)
+            .await;
+        #[cfg(not(feature = "tls"))]
+        return Err("TLS requires Iroha built with the `tls` feature.".to_string());
+    }
+    Network::send_request_to(&peer_id.address, request).await
+}
+
 /// Peer's identification.
 #[derive(Encode, Decode, PartialEq, Eq, Debug, Clone, Hash, Io, Default)]
 pub struct PeerId {
@@ -16,6 +57,44 @@ pub struct PeerId {
     pub public_key: PublicKey,
 }
 
+impl PeerId {
+    /// Construct `PeerId` from the raw public key bytes, checking the key length and the address.
+    pub fn try_new(address: &str, public_key: &[u8]) -> Result<PeerId, String> {
+        let peer_id = PeerId {
+            address: address.to_string(),
+            public_key: PublicKey::try_from(public_key).map_err(|_| {
+                format!(
+                    "Public key should be {} bytes long, but got {}.",
+                    std::mem::size_of::<PublicKey>(),
+                    public_key.len()
+                )
+            })?,
+        };
+        peer_id.validate()?;
+        Ok(peer_id)
+    }
+
+    /// Check that the address is a socket address or a host name with a port.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.address.parse::<SocketAddr>().is_ok() {
+            return Ok(());
+        }
+        match self.address.rsplit_once(':') {
+            Some((host, port))
+                if !host.is_empty()
+                    && !host.contains(char::is_whitespace)
+                    && port.parse::<u16>().is_ok() =>
+            {
+                Ok(())
+            }
+            _ => Err(format!(
+                "Peer address {} is not a socket address.",
+                self.address
+            )),
+        }
+    }
+}
+
 /// Peer represents currently running Iroha instance.
 #[derive(Debug, Clone, Default)]
 pub struct Peer {
@@ -26,7 +105,7 @@ pub struct Peer {
     /// Address to listen to.
     pub listen_address: String,
     /// Registered domains.
-    pub domains: HashMap<String, Domain>,
+    pub domains: HashMap<<Domain as Identifiable>::Id, Domain>,
     /// Events Listeners.
     pub listeners: Vec<Instruction>,
     #[cfg(feature = "bridge")]
@@ -125,6 +204,72 @@ impl Identifiable for Peer {
     type Id = PeerId;
 }
 
+/// Status of a known peer as seen by the current peer, see `GetNetworkStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct PeerStatus {
+    /// Id of the peer.
+    pub peer_id: PeerId,
+    /// Number of blocks the peer reported to be committed, `None` if it never responded.
+    pub height: Option<u64>,
+    /// Unix time (in milliseconds) of the last response of the peer.
+    pub last_seen_ms: Option<u128>,
+    /// Whether the peer responded to the last poll.
+    pub reachable: bool,
+}
+
+impl PeerStatus {
+    /// Status of the peer which was not polled yet.
+    pub fn unknown(peer_id: PeerId) -> PeerStatus {
+        PeerStatus {
+            peer_id,
+            height: None,
+            last_seen_ms: None,
+            reachable: false,
+        }
+    }
+
+    /// Ask the peer for its height with the `GetHealth` query and update the status, the last
+    /// known height is kept if the peer does not respond in `timeout`.
+    pub async fn poll(&mut self, timeout: Duration) {
+        match future::timeout(timeout, request_height(&self.peer_id)).await {
+            Ok(Ok(height)) => {
+                self.height = Some(height);
+                self.last_seen_ms = Some(now_ms());
+                self.reachable = true;
+            }
+            Ok(Err(_)) | Err(_) => self.reachable = false,
+        }
+    }
+}
+
+async fn request_height(peer_id: &PeerId) -> Result<u64, String> {
+    match send_request_to(
+        peer_id,
+        Request::new(
+            uri::QUERY_URI.to_string(),
+            GetHealth::build_request().into(),
+        ),
+    )
+    .await?
+    {
+        Response::Ok(payload) => match QueryResult::try_from(payload)? {
+            QueryResult::GetHealth(result) => Ok(result.height),
+            _ => Err("Wrong Query Result Type.".to_string()),
+        },
+        Response::InternalError | Response::Rejected(_) => Err(format!(
+            "Failed to execute query - Internal Error on peer: {}",
+            peer_id.address
+        )),
+    }
+}
+
+pub(crate) fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Failed to get System Time.")
+        .as_millis()
+}
+
 /// Iroha Special Instructions module provides `PeerInstruction` enum with all legal types of
 /// Peer related instructions as variants, implementations of generic Iroha Special Instructions
 /// and the `From/Into` implementations to convert `PeerInstruction` variants into generic ISI.
@@ -134,15 +279,17 @@ pub mod isi {
     use std::ops::{AddAssign, Sub};
 
     /// Enumeration of all legal Peer related Instructions.
-    #[derive(Clone, Debug, Io, Encode, Decode)]
+    #[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
     pub enum PeerInstruction {
         /// Variant of the generic `Add` instruction for `Domain` --> `Peer`.
-        AddDomain(String, PeerId),
+        AddDomain(<Domain as Identifiable>::Id, PeerId),
         /// Variant of the generic `Add` instruction for `Instruction` --> `Peer`.
         AddListener(Box<Instruction>, PeerId),
         #[cfg(feature = "bridge")]
         /// Variant of the generic `Register` instruction for `BridgeDefinition` --> `Peer`.
         RegisterBridge(BridgeDefinition, PeerId),
+        /// Variant of the generic `Add` instruction for `PeerId` --> `Peer`.
+        AddPeer(PeerId, PeerId),
     }
 
     impl PeerInstruction {
@@ -166,6 +313,10 @@ pub mod isi {
                 PeerInstruction::RegisterBridge(bridge_def, peer_id) => {
                     Register::new(bridge_def.clone(), peer_id.clone()).execute(world_state_view)
                 }
+                PeerInstruction::AddPeer(new_peer_id, peer_id) => {
+                    Add::new(new_peer_id.clone(), peer_id.clone())
+                        .execute(authority, world_state_view)
+                }
             }
         }
     }
@@ -217,4 +368,108 @@ pub mod isi {
             Ok(())
         }
     }
+
+    impl Add<Peer, PeerId> {
+        /// Known peers are polled for their status and can read the blocks to synchronize them,
+        /// the consensus topology is not changed.
+        fn execute(
+            self,
+            authority: <Account as Identifiable>::Id,
+            world_state_view: &mut WorldStateView,
+        ) -> Result<(), String> {
+            PermissionInstruction::CanAnything(authority).execute(world_state_view)?;
+            let peer = world_state_view.peer();
+            if peer.id.address != self.object.address {
+                peer.peers.insert(self.object);
+            }
+            Ok(())
+        }
+    }
+
+    impl From<Add<Peer, PeerId>> for Instruction {
+        fn from(add_instruction: Add<Peer, PeerId>) -> Self {
+            Instruction::Peer(PeerInstruction::AddPeer(
+                add_instruction.object,
+                add_instruction.destination_id,
+            ))
+        }
+    }
+}
+
+/// Query module provides `IrohaQuery` Peer related implementations.
+pub mod query {
+    use super::*;
+    use crate::query::IrohaQuery;
+    use iroha_derive::{log, IntoQuery};
+
+    /// To get the heights of the known peers and whether they are reachable,
+    /// GetNetworkStatus query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetNetworkStatus {}
+
+    /// Result of the `GetNetworkStatus` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetNetworkStatusResult {
+        /// Status of the peer which executed the query followed by the statuses of the other
+        /// known peers ordered by address, unreachable peers included.
+        pub peers: Vec<PeerStatus>,
+    }
+
+    impl GetNetworkStatus {
+        /// Build a `GetNetworkStatus` query in the form of a `QueryRequest`.
+        pub fn build_request() -> QueryRequest {
+            let query = GetNetworkStatus {};
+            QueryRequest {
+                timestamp: now_ms().to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetNetworkStatus {
+        #[log]
+        fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            Ok(QueryResult::GetNetworkStatus(GetNetworkStatusResult {
+                peers: world_state_view.network_status(),
+            }))
+        }
+    }
+
+    /// To get the signature schemes the peer accepts for account keys before submitting
+    /// anything, GetCryptoPolicy query can be used.
+    #[derive(Clone, Debug, Io, IntoQuery, Encode, Decode)]
+    pub struct GetCryptoPolicy {}
+
+    /// Result of the `GetCryptoPolicy` execution.
+    #[derive(Debug, Encode, Decode)]
+    pub struct GetCryptoPolicyResult {
+        /// Signature schemes of the keys the peer accepts.
+        pub allowed_algorithms: Vec<Algorithm>,
+    }
+
+    impl GetCryptoPolicy {
+        /// Build a `GetCryptoPolicy` query in the form of a `QueryRequest`.
+        pub fn build_request() -> QueryRequest {
+            let query = GetCryptoPolicy {};
+            QueryRequest {
+                timestamp: now_ms().to_string(),
+                signature: Option::None,
+                query: query.into(),
+                at_height: None,
+                account_id: None,
+            }
+        }
+    }
+
+    impl Query for GetCryptoPolicy {
+        #[log]
+        fn execute(&self, _world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+            Ok(QueryResult::GetCryptoPolicy(GetCryptoPolicyResult {
+                allowed_algorithms: crypto::ALLOWED_ALGORITHMS.to_vec(),
+            }))
+        }
+    }
 }
@@ -8,7 +8,7 @@ pub fn permission_asset_definition_id() -> AssetDefinitionId {
     AssetDefinitionId::new("permissions", "global")
 }
 
-#[derive(Clone, Debug, Default, Encode, Decode)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
 pub struct Permissions {
     origin: BTreeMap<String, String>,
 }
@@ -42,13 +42,13 @@ impl Permissions {
         }
     }
 
-    fn check_register_account(&self, domain: &Option<String>) -> Result<(), String> {
+    fn check_register_account(&self, domain: &Option<Name>) -> Result<(), String> {
         if self.check_anything().is_ok() {
             Ok(())
         } else {
             match self.origin.get("register_account") {
                 Some(object) => {
-                    if domain.as_ref().unwrap_or(&"any".to_string()) == object {
+                    if domain.as_deref().unwrap_or("any") == object {
                         Ok(())
                     } else {
                         Err(format!("{}: {}", PERMISSION_OBJECT_NOT_SATISFIED, object))
@@ -59,13 +59,13 @@ impl Permissions {
         }
     }
 
-    fn check_register_asset(&self, domain: &Option<String>) -> Result<(), String> {
+    fn check_register_asset(&self, domain: &Option<Name>) -> Result<(), String> {
         if self.check_anything().is_ok() {
             Ok(())
         } else {
             match self.origin.get("register_asset_definition") {
                 Some(object) => {
-                    if domain.as_ref().unwrap_or(&"any".to_string()) == object {
+                    if domain.as_deref().unwrap_or("any") == object {
                         Ok(())
                     } else {
                         Err(format!("{}: {}", PERMISSION_OBJECT_NOT_SATISFIED, object))
@@ -76,10 +76,35 @@ impl Permissions {
         }
     }
 
+    fn check_read_account(&self, account_id: &AccountId) -> Result<(), String> {
+        if self.check_anything().is_ok() {
+            Ok(())
+        } else {
+            match self.origin.get("read_account") {
+                Some(object) => {
+                    if *object == account_id.to_string() {
+                        Ok(())
+                    } else {
+                        Err(format!("{}: {}", PERMISSION_OBJECT_NOT_SATISFIED, object))
+                    }
+                }
+                None => Err(format!("Error: {}, {:?}", PERMISSION_NOT_FOUND, self)),
+            }
+        }
+    }
+
+    fn check_read_blocks(&self) -> Result<(), String> {
+        if self.check_anything().is_ok() || self.origin.contains_key("read_blocks") {
+            Ok(())
+        } else {
+            Err(format!("Error: {}, {:?}", PERMISSION_NOT_FOUND, self))
+        }
+    }
+
     fn check_transfer_asset(
         &self,
         asset_definition_id: &AssetDefinitionId,
-        domain: &Option<String>,
+        domain: &Option<Name>,
     ) -> Result<(), String> {
         if self.check_anything().is_ok() {
             Ok(())
@@ -87,8 +112,7 @@ impl Permissions {
             match self.origin.get("transfer_asset") {
                 Some(object) => {
                     if object
-                        == &(asset_definition_id.to_string()
-                            + domain.as_ref().unwrap_or(&"any".to_string()))
+                        == &(asset_definition_id.to_string() + domain.as_deref().unwrap_or("any"))
                     {
                         Ok(())
                     } else {
@@ -103,7 +127,7 @@ impl Permissions {
     fn check_mint_asset(
         &self,
         asset_definition_id: &AssetDefinitionId,
-        domain: &Option<String>,
+        domain: &Option<Name>,
     ) -> Result<(), String> {
         if self.check_anything().is_ok() {
             Ok(())
@@ -111,8 +135,7 @@ impl Permissions {
             match self.origin.get("mint_asset") {
                 Some(object) => {
                     if object
-                        == &(asset_definition_id.to_string()
-                            + domain.as_ref().unwrap_or(&"any".to_string()))
+                        == &(asset_definition_id.to_string() + domain.as_deref().unwrap_or("any"))
                     {
                         Ok(())
                     } else {
@@ -125,6 +148,47 @@ impl Permissions {
     }
 }
 
+/// Check that the `authority` has a permission to read the state of the account.
+///
+/// Unlike `PermissionInstruction`, works with a read only `WorldStateView`, so it can be used by
+/// queries.
+pub fn check_can_read_account(
+    authority: &AccountId,
+    account_id: &AccountId,
+    world_state_view: &WorldStateView,
+) -> Result<(), String> {
+    let permission_asset_id = AssetId {
+        definition_id: permission_asset_definition_id(),
+        account_id: authority.clone(),
+    };
+    match world_state_view.read_asset(&permission_asset_id) {
+        Some(asset) => asset.permissions.check_read_account(account_id),
+        None => Err(format!(
+            "Error: {}, {} can not read account {}.",
+            PERMISSION_NOT_FOUND, authority, account_id
+        )),
+    }
+}
+
+/// Check that the `authority` has a permission to read the committed blocks and their events,
+/// which contain transactions of all accounts, and all domains with their accounts.
+pub fn check_can_read_blocks(
+    authority: &AccountId,
+    world_state_view: &WorldStateView,
+) -> Result<(), String> {
+    let permission_asset_id = AssetId {
+        definition_id: permission_asset_definition_id(),
+        account_id: authority.clone(),
+    };
+    match world_state_view.read_asset(&permission_asset_id) {
+        Some(asset) => asset.permissions.check_read_blocks(),
+        None => Err(format!(
+            "Error: {}, {} can not read blocks.",
+            PERMISSION_NOT_FOUND, authority
+        )),
+    }
+}
+
 impl From<(String, String)> for Permissions {
     fn from(permission: (String, String)) -> Self {
         let mut origin = BTreeMap::new();
@@ -139,7 +203,7 @@ pub mod isi {
     use parity_scale_codec::{Decode, Encode};
 
     /// Iroha special instructions related to `Permission`.
-    #[derive(Clone, Debug, Io, Encode, Decode)]
+    #[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
     pub enum PermissionInstruction {
         CanAnything(<Account as Identifiable>::Id),
         CanAddListener(<Account as Identifiable>::Id),
@@ -257,13 +321,16 @@ pub mod isi {
 
         #[test]
         fn test_can_anything_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("root", &domain_name);
             let asset_id = AssetId {
@@ -304,13 +371,16 @@ pub mod isi {
 
         #[test]
         fn test_can_anything_without_permission_should_fail_with_permission_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("NOT_ROOT", &domain_name);
             let account = Account::new(
@@ -360,13 +430,16 @@ pub mod isi {
 
         #[test]
         fn test_can_add_domain_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -409,13 +482,16 @@ pub mod isi {
 
         #[test]
         fn test_can_add_domain_without_permission_should_fail_with_permission_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("NOT_ROOT", &domain_name);
             let account = Account::new(
@@ -465,13 +541,16 @@ pub mod isi {
 
         #[test]
         fn test_can_add_listener_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -514,13 +593,16 @@ pub mod isi {
 
         #[test]
         fn test_can_add_listener_without_permission_should_fail_with_permission_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("NOT_ROOT", &domain_name);
             let account = Account::new(
@@ -570,13 +652,16 @@ pub mod isi {
 
         #[test]
         fn test_can_register_account_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -620,13 +705,16 @@ pub mod isi {
 
         #[test]
         fn test_can_register_account_in_domain_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -635,7 +723,7 @@ pub mod isi {
             };
             let asset = Asset::with_permission(
                 asset_id.clone(),
-                ("register_account".to_string(), domain_name.clone()),
+                ("register_account".to_string(), domain_name.to_string()),
             );
             let mut account = Account::new(
                 &account_id.name,
@@ -670,13 +758,16 @@ pub mod isi {
 
         #[test]
         fn test_can_register_account_in_domain_should_fail_with_permission_object_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -721,13 +812,16 @@ pub mod isi {
 
         #[test]
         fn test_can_register_account_without_permission_should_fail_with_permission_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let account = Account::new(
@@ -778,13 +872,16 @@ pub mod isi {
 
         #[test]
         fn test_can_register_asset_definition_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -828,13 +925,16 @@ pub mod isi {
 
         #[test]
         fn test_can_register_asset_definition_in_domain_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -843,7 +943,10 @@ pub mod isi {
             };
             let asset = Asset::with_permission(
                 asset_id.clone(),
-                ("register_asset_definition".to_string(), domain_name.clone()),
+                (
+                    "register_asset_definition".to_string(),
+                    domain_name.to_string(),
+                ),
             );
             let mut account = Account::new(
                 &account_id.name,
@@ -879,13 +982,16 @@ pub mod isi {
         #[test]
         fn test_can_register_asset_definition_in_domain_should_fail_with_permission_object_not_found(
         ) {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -934,13 +1040,16 @@ pub mod isi {
         #[test]
         fn test_can_register_asset_definition_without_permission_should_fail_with_permission_not_found(
         ) {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let account = Account::new(
@@ -993,13 +1102,16 @@ pub mod isi {
 
         #[test]
         fn test_can_transfer_asset_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -1051,13 +1163,16 @@ pub mod isi {
 
         #[test]
         fn test_can_transfer_asset_in_domain_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -1109,13 +1224,16 @@ pub mod isi {
 
         #[test]
         fn test_can_transfer_asset_in_domain_should_fail_with_permission_object_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -1164,13 +1282,16 @@ pub mod isi {
 
         #[test]
         fn test_can_transfer_asset_without_permission_should_fail_with_permission_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let account = Account::new(
@@ -1226,13 +1347,16 @@ pub mod isi {
 
         #[test]
         fn test_can_mint_asset_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -1280,13 +1404,16 @@ pub mod isi {
 
         #[test]
         fn test_can_mint_asset_in_domain_should_pass() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -1338,13 +1465,16 @@ pub mod isi {
 
         #[test]
         fn test_can_mint_asset_in_domain_should_fail_with_permission_object_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let asset_id = AssetId {
@@ -1393,13 +1523,16 @@ pub mod isi {
 
         #[test]
         fn test_can_mint_asset_without_permission_should_fail_with_permission_not_found() {
-            let domain_name = "Company".to_string();
+            let domain_name = Name::new("Company");
             let public_key = [0; 32];
             let mut asset_definitions = HashMap::new();
             let asset_definition_id = permission_asset_definition_id();
             asset_definitions.insert(
                 asset_definition_id.clone(),
-                AssetDefinition::new(asset_definition_id.clone()),
+                AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::new("root", "global"),
+                ),
             );
             let account_id = AccountId::new("ROOT", &domain_name);
             let account = Account::new(&account_id.name, &account_id.domain_name, public_key);
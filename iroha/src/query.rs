@@ -1,25 +1,174 @@
 //! This module contains query related Iroha functionality.
 
-use crate::{asset, prelude::*};
+use crate::{account, asset, block, crypto, domain, event, peer, permission, prelude::*};
 use iroha_derive::Io;
 use parity_scale_codec::{Decode, Encode};
 
 /// I/O ready structure to send queries.
-#[derive(Debug, Io, Encode, Decode)]
+#[derive(Clone, Debug, Io, Encode, Decode)]
 pub struct QueryRequest {
     /// Timestamp of the query creation.
     pub timestamp: String,
-    /// Optional query signature.
+    /// Optional query signature made by a signatory of the `account_id` or by a trusted peer if
+    /// the `account_id` is not set.
     pub signature: Option<Signature>,
     /// Query definition.
     pub query: IrohaQuery,
+    /// Optional height of the blockchain to execute the query against the state at, the
+    /// current state is used if not set.
+    pub at_height: Option<u64>,
+    /// Optional account the query is made on behalf of, should be set together with the
+    /// `signature` unless the request is signed by a peer.
+    pub account_id: Option<AccountId>,
+}
+
+impl QueryRequest {
+    /// Execute the query against the state at the given height instead of the current one.
+    pub fn at_height(mut self, height: u64) -> Self {
+        self.at_height = Some(height);
+        self
+    }
+
+    /// Sign the request on behalf of the account.
+    pub fn sign(
+        mut self,
+        account_id: AccountId,
+        public_key: &PublicKey,
+        private_key: &PrivateKey,
+    ) -> Result<Self, String> {
+        self.account_id = Some(account_id);
+        self.signature = Some(Signature::new_with_context(
+            &KeyPair::new(*public_key, *private_key),
+            crypto::context::QUERY,
+            &self.payload(),
+        )?);
+        Ok(self)
+    }
+
+    /// Sign the request with the key of a peer, trusted peers can read the committed blocks to
+    /// synchronize them.
+    pub fn sign_as_peer(mut self, key_pair: &KeyPair) -> Result<Self, String> {
+        self.account_id = None;
+        self.signature = Some(Signature::new_with_context(
+            key_pair,
+            crypto::context::QUERY,
+            &self.payload(),
+        )?);
+        Ok(self)
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        (
+            &self.timestamp,
+            &self.query,
+            &self.at_height,
+            &self.account_id,
+        )
+            .encode()
+    }
+
+    /// Account which signed the request or `None` if the request is not signed.
+    ///
+    /// The signature should satisfy the account's quorum the same way transactions do.
+    /// Returns `Err(String)` if the signature is invalid.
+    pub fn authority(
+        &self,
+        world_state_view: &WorldStateView,
+    ) -> Result<Option<AccountId>, String> {
+        match (&self.signature, &self.account_id) {
+            (None, None) => Ok(None),
+            (Some(signature), Some(account_id)) => {
+                signature
+                    .verify_with_context(crypto::context::QUERY, &self.payload())
+                    .map_err(|e| format!("Failed to verify query signature: {}", e))?;
+                world_state_view
+                    .read_account(account_id)
+                    .ok_or(format!("Failed to find account {}.", account_id))?
+                    .check_quorum(std::slice::from_ref(signature))?;
+                Ok(Some(account_id.clone()))
+            }
+            _ => Err("Query signature and account should be set together.".to_string()),
+        }
+    }
+
+    /// Check that the signer of the request is allowed to make the query.
+    ///
+    /// Returns `Err(String)` with the reason if the request should be rejected.
+    pub fn authorize(&self, world_state_view: &WorldStateView) -> Result<(), String> {
+        if let (Some(signature), None) = (&self.signature, &self.account_id) {
+            return self.authorize_peer(signature, world_state_view);
+        }
+        let authority = self.authority(world_state_view)?;
+        self.query.authorize(authority.as_ref(), world_state_view)
+    }
+
+    /// Trusted peers can read the committed blocks, other queries signed by a peer are
+    /// authorized as unsigned ones.
+    fn authorize_peer(
+        &self,
+        signature: &Signature,
+        world_state_view: &WorldStateView,
+    ) -> Result<(), String> {
+        signature
+            .verify_with_context(crypto::context::QUERY, &self.payload())
+            .map_err(|e| format!("Failed to verify query signature: {}", e))?;
+        if !world_state_view
+            .read_peer()
+            .peers
+            .iter()
+            .any(|peer_id| peer_id.public_key == signature.public_key)
+        {
+            return Err("Query without an account should be signed by a trusted peer.".to_string());
+        }
+        match self.query {
+            IrohaQuery::GetBlock(_) | IrohaQuery::GetBlocks(_) | IrohaQuery::GetEvents(_) => Ok(()),
+            _ => self.query.authorize(None, world_state_view),
+        }
+    }
+
+    /// Execute query on the `WorldStateView` or on the state reconstructed at `at_height`.
+    /// Permissions are not checked, see `authorize`.
+    ///
+    /// Returns Ok(QueryResult) if succeeded and Err(String) if failed.
+    pub fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
+        match self.at_height {
+            Some(height) => self.query.execute(&world_state_view.at_height(height)?),
+            None => self.query.execute(world_state_view),
+        }
+    }
 }
 
 /// Enumeration of all legal Iroha Queries.
-#[derive(Debug, Encode, Decode)]
+#[derive(Clone, Debug, Encode, Decode)]
 pub enum IrohaQuery {
     /// Query all Assets related to the Account.
     GetAccountAssets(asset::query::GetAccountAssets),
+    /// Query asset transfers from or to the Account.
+    GetAccountTransfers(account::query::GetAccountTransfers),
+    /// Query the total supply and the number of holders of the Asset.
+    GetAssetSupply(asset::query::GetAssetSupply),
+    /// Query the number of committed blocks.
+    GetBlockchainHeight(block::query::GetBlockchainHeight),
+    /// Query the committed Block by its height.
+    GetBlock(block::query::GetBlock),
+    /// Query a chunk of committed Blocks starting from the height.
+    GetBlocks(block::query::GetBlocks),
+    /// Query the block building parameters and the number of pending transactions.
+    GetBlockSchedule(block::query::GetBlockSchedule),
+    /// Query whether the peer is building blocks.
+    GetHealth(block::query::GetHealth),
+    /// Query the heights of the known peers.
+    GetNetworkStatus(peer::query::GetNetworkStatus),
+    /// Query the signature schemes accepted by the peer.
+    GetCryptoPolicy(peer::query::GetCryptoPolicy),
+    /// Query events emitted by the committed blocks.
+    GetEvents(event::query::GetEvents),
+    /// Query a page of all Domains.
+    GetAllDomains(domain::query::GetAllDomains),
+    /// Query a page of the Asset Definitions of the Domain.
+    GetDomainAssetDefinitions(domain::query::GetDomainAssetDefinitions),
+    /// Query the number of Accounts and Asset Definitions of the Domain.
+    GetDomainStats(domain::query::GetDomainStats),
 }
 
 /// Result of queries execution.
@@ -27,15 +176,106 @@ pub enum IrohaQuery {
 pub enum QueryResult {
     /// Query all Assets related to the Account result.
     GetAccountAssets(asset::query::GetAccountAssetsResult),
+    /// Query asset transfers from or to the Account result.
+    GetAccountTransfers(account::query::GetAccountTransfersResult),
+    /// Query the total supply and the number of holders of the Asset result.
+    GetAssetSupply(asset::query::GetAssetSupplyResult),
+    /// Query the number of committed blocks result.
+    GetBlockchainHeight(block::query::GetBlockchainHeightResult),
+    /// Query the committed Block by its height result.
+    GetBlock(block::query::GetBlockResult),
+    /// Query a chunk of committed Blocks starting from the height result.
+    GetBlocks(block::query::GetBlocksResult),
+    /// Query the block building parameters and the number of pending transactions result.
+    GetBlockSchedule(block::query::GetBlockScheduleResult),
+    /// Query whether the peer is building blocks result.
+    GetHealth(block::query::GetHealthResult),
+    /// Query the heights of the known peers result.
+    GetNetworkStatus(peer::query::GetNetworkStatusResult),
+    /// Query the signature schemes accepted by the peer result.
+    GetCryptoPolicy(peer::query::GetCryptoPolicyResult),
+    /// Query events emitted by the committed blocks result.
+    GetEvents(event::query::GetEventsResult),
+    /// Query a page of all Domains result.
+    GetAllDomains(domain::query::GetAllDomainsResult),
+    /// Query a page of the Asset Definitions of the Domain result.
+    GetDomainAssetDefinitions(domain::query::GetDomainAssetDefinitionsResult),
+    /// Query the number of Accounts and Asset Definitions of the Domain result.
+    GetDomainStats(domain::query::GetDomainStatsResult),
 }
 
 impl IrohaQuery {
+    /// Check that the `authority` is allowed to make the query. Public queries are allowed
+    /// without an authority.
+    pub fn authorize(
+        &self,
+        authority: Option<&AccountId>,
+        world_state_view: &WorldStateView,
+    ) -> Result<(), String> {
+        match self {
+            IrohaQuery::GetAccountAssets(query) => query.authorize(authority, world_state_view),
+            IrohaQuery::GetAccountTransfers(query) => query.authorize(authority, world_state_view),
+            IrohaQuery::GetBlock(_) | IrohaQuery::GetBlocks(_) | IrohaQuery::GetEvents(_) => {
+                let authority =
+                    authority.ok_or("Query of the committed blocks should be signed.")?;
+                permission::check_can_read_blocks(authority, world_state_view)
+            }
+            IrohaQuery::GetAllDomains(_) => {
+                let authority = authority
+                    .ok_or("Query of all domains should be signed, they contain all accounts.")?;
+                permission::check_can_read_blocks(authority, world_state_view)
+            }
+            IrohaQuery::GetAssetSupply(_)
+            | IrohaQuery::GetBlockchainHeight(_)
+            | IrohaQuery::GetBlockSchedule(_)
+            | IrohaQuery::GetHealth(_)
+            | IrohaQuery::GetNetworkStatus(_)
+            | IrohaQuery::GetCryptoPolicy(_)
+            | IrohaQuery::GetDomainAssetDefinitions(_)
+            | IrohaQuery::GetDomainStats(_) => Ok(()),
+        }
+    }
+
+    /// Whether the result of the query may be reused by clients for a while. Results of queries
+    /// which change with every block, like the blockchain height, should always be refetched.
+    pub fn is_cacheable(&self) -> bool {
+        match self {
+            IrohaQuery::GetBlockchainHeight(_)
+            | IrohaQuery::GetBlockSchedule(_)
+            | IrohaQuery::GetHealth(_)
+            | IrohaQuery::GetNetworkStatus(_)
+            | IrohaQuery::GetEvents(_) => false,
+            IrohaQuery::GetAccountAssets(_)
+            | IrohaQuery::GetAccountTransfers(_)
+            | IrohaQuery::GetAssetSupply(_)
+            | IrohaQuery::GetBlock(_)
+            | IrohaQuery::GetBlocks(_)
+            | IrohaQuery::GetCryptoPolicy(_)
+            | IrohaQuery::GetAllDomains(_)
+            | IrohaQuery::GetDomainAssetDefinitions(_)
+            | IrohaQuery::GetDomainStats(_) => true,
+        }
+    }
+
     /// Execute query on the `WorldStateView`.
     ///
     /// Returns Ok(QueryResult) if succeeded and Err(String) if failed.
     pub fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String> {
         match self {
             IrohaQuery::GetAccountAssets(query) => query.execute(world_state_view),
+            IrohaQuery::GetAccountTransfers(query) => query.execute(world_state_view),
+            IrohaQuery::GetAssetSupply(query) => query.execute(world_state_view),
+            IrohaQuery::GetBlockchainHeight(query) => query.execute(world_state_view),
+            IrohaQuery::GetBlock(query) => query.execute(world_state_view),
+            IrohaQuery::GetBlocks(query) => query.execute(world_state_view),
+            IrohaQuery::GetBlockSchedule(query) => query.execute(world_state_view),
+            IrohaQuery::GetHealth(query) => query.execute(world_state_view),
+            IrohaQuery::GetNetworkStatus(query) => query.execute(world_state_view),
+            IrohaQuery::GetCryptoPolicy(query) => query.execute(world_state_view),
+            IrohaQuery::GetEvents(query) => query.execute(world_state_view),
+            IrohaQuery::GetAllDomains(query) => query.execute(world_state_view),
+            IrohaQuery::GetDomainAssetDefinitions(query) => query.execute(world_state_view),
+            IrohaQuery::GetDomainStats(query) => query.execute(world_state_view),
         }
     }
 }
@@ -47,3 +287,188 @@ pub trait Query {
     /// Returns Ok(QueryResult) if succeeded and Err(String) if failed.
     fn execute(&self, world_state_view: &WorldStateView) -> Result<QueryResult, String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asset::query::GetAccountAssets,
+        block::query::{GetBlock, GetBlockchainHeight},
+        domain::query::GetAllDomains,
+        peer::PeerId,
+        permission::permission_asset_definition_id,
+    };
+    use std::collections::HashMap;
+
+    const DOMAIN_NAME: &str = "wonderland";
+
+    struct Signer {
+        account_id: AccountId,
+        public_key: PublicKey,
+        private_key: PrivateKey,
+    }
+
+    impl Signer {
+        fn new(name: &str) -> Signer {
+            let (public_key, private_key) =
+                crypto::generate_key_pair().expect("Failed to generate key pair.");
+            Signer {
+                account_id: AccountId::new(name, DOMAIN_NAME),
+                public_key,
+                private_key,
+            }
+        }
+
+        fn sign(&self, request: QueryRequest) -> QueryRequest {
+            request
+                .sign(self.account_id.clone(), &self.public_key, &self.private_key)
+                .expect("Failed to sign query.")
+        }
+    }
+
+    /// `bob` can read accounts of `alice`, `dave` can read the blocks, `carol` has no
+    /// permissions.
+    fn world_state_view(signers: &[&Signer]) -> WorldStateView {
+        let mut accounts = HashMap::new();
+        for signer in signers {
+            let mut account = Account::new(
+                &signer.account_id.name,
+                &signer.account_id.domain_name,
+                signer.public_key,
+            );
+            let permission = match signer.account_id.name.as_str() {
+                "bob" => Some((
+                    "read_account".to_string(),
+                    AccountId::new("alice", DOMAIN_NAME).to_string(),
+                )),
+                "dave" => Some(("read_blocks".to_string(), "".to_string())),
+                _ => None,
+            };
+            if let Some(permission) = permission {
+                let permission_asset_id = AssetId {
+                    definition_id: permission_asset_definition_id(),
+                    account_id: signer.account_id.clone(),
+                };
+                account.assets.insert(
+                    permission_asset_id.clone(),
+                    Asset::with_permission(permission_asset_id, permission),
+                );
+            }
+            accounts.insert(signer.account_id.clone(), account);
+        }
+        let mut domains = HashMap::new();
+        domains.insert(
+            Name::new(DOMAIN_NAME),
+            Domain {
+                name: Name::from(DOMAIN_NAME),
+                accounts,
+                asset_definitions: HashMap::new(),
+            },
+        );
+        WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+            domains,
+        ))
+    }
+
+    #[test]
+    fn protected_query_requires_permitted_signer() {
+        let alice = Signer::new("alice");
+        let bob = Signer::new("bob");
+        let carol = Signer::new("carol");
+        let world_state_view = world_state_view(&[&alice, &bob, &carol]);
+        let request = || GetAccountAssets::build_request(alice.account_id.clone());
+        let signed_by_bob = bob.sign(request());
+        assert_eq!(Ok(()), signed_by_bob.authorize(&world_state_view));
+        assert!(signed_by_bob.execute(&world_state_view).is_ok());
+        assert_eq!(Ok(()), alice.sign(request()).authorize(&world_state_view));
+        let error = request()
+            .authorize(&world_state_view)
+            .expect_err("Unsigned query should be rejected.");
+        assert!(error.contains("should be signed"));
+        assert!(carol.sign(request()).authorize(&world_state_view).is_err());
+        let mut impersonating = bob.sign(request());
+        impersonating.account_id = Some(alice.account_id.clone());
+        assert!(impersonating.authorize(&world_state_view).is_err());
+    }
+
+    #[test]
+    fn query_signed_by_foreign_key_is_rejected() {
+        let alice = Signer::new("alice");
+        let world_state_view = world_state_view(&[&alice]);
+        let foreign = Signer {
+            account_id: alice.account_id.clone(),
+            ..Signer::new("mallory")
+        };
+        assert!(foreign
+            .sign(GetAccountAssets::build_request(alice.account_id.clone()))
+            .authorize(&world_state_view)
+            .is_err());
+    }
+
+    #[test]
+    fn block_queries_require_permission_or_trusted_peer() {
+        let alice = Signer::new("alice");
+        let dave = Signer::new("dave");
+        let mut world_state_view = world_state_view(&[&alice, &dave]);
+        let (peer_public_key, peer_private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        world_state_view.peer().peers.insert(PeerId {
+            address: "127.0.0.1:8081".to_string(),
+            public_key: peer_public_key,
+        });
+        let request = || GetBlock::build_request(0);
+        assert_eq!(Ok(()), dave.sign(request()).authorize(&world_state_view));
+        assert!(alice.sign(request()).authorize(&world_state_view).is_err());
+        assert!(request().authorize(&world_state_view).is_err());
+        assert_eq!(
+            Ok(()),
+            request()
+                .sign_as_peer(&KeyPair::new(peer_public_key, peer_private_key))
+                .expect("Failed to sign query.")
+                .authorize(&world_state_view)
+        );
+        assert!(request()
+            .sign_as_peer(&KeyPair::new(alice.public_key, alice.private_key))
+            .expect("Failed to sign query.")
+            .authorize(&world_state_view)
+            .is_err());
+    }
+
+    #[test]
+    fn all_domains_are_not_exposed_to_unpermitted_callers() {
+        let alice = Signer::new("alice");
+        let carol = Signer::new("carol");
+        let dave = Signer::new("dave");
+        let world_state_view = world_state_view(&[&alice, &carol, &dave]);
+        let request = || GetAllDomains::build_request(0, 10);
+        let error = request()
+            .authorize(&world_state_view)
+            .expect_err("Unsigned query of all domains should be rejected.");
+        assert!(error.contains("should be signed"));
+        assert!(carol.sign(request()).authorize(&world_state_view).is_err());
+        let signed_by_dave = dave.sign(request());
+        assert_eq!(Ok(()), signed_by_dave.authorize(&world_state_view));
+        match signed_by_dave
+            .execute(&world_state_view)
+            .expect("Failed to execute query.")
+        {
+            QueryResult::GetAllDomains(result) => {
+                assert!(result.domains[0].accounts.contains_key(&alice.account_id))
+            }
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    #[test]
+    fn public_query_works_unsigned() {
+        let world_state_view = world_state_view(&[]);
+        let request = GetBlockchainHeight::build_request();
+        assert_eq!(Ok(()), request.authorize(&world_state_view));
+        assert!(request.execute(&world_state_view).is_ok());
+    }
+}
@@ -1,34 +1,368 @@
-use crate::prelude::*;
+use crate::{
+    block::DEFAULT_MAX_BLOCK_BYTES, crypto, prelude::*, sumeragi::DEFAULT_MAX_BLOCK_SIGNATURES,
+};
+use parity_scale_codec::Encode;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
-#[derive(Default, Debug)]
+/// Default maximum number of transactions taken from the queue into a single block.
+pub const DEFAULT_MAX_TRANSACTIONS_IN_BLOCK: usize = 10_000;
+/// Default maximum number of transactions waiting in the queue, e.g. while block production is
+/// paused.
+pub const DEFAULT_MAX_PENDING_TRANSACTIONS: usize = 100_000;
+
+/// Pending transactions ordered by the creation time set by the account, the nonce of the
+/// account's transactions, and then by the arrival order.
+type AccountTransactions = BTreeMap<(u128, u64), AcceptedTransaction>;
+
+#[derive(Debug)]
 pub struct Queue {
-    pending_tx: Vec<AcceptedTransaction>,
+    pending_tx: HashMap<AccountId, AccountTransactions>,
+    pending_tx_count: usize,
+    /// Arrival number of the next pushed transaction.
+    next_arrival: u64,
+    max_transactions_in_block: usize,
+    max_pending_transactions: usize,
+    /// Maximum size of the SCALE encoded block built from the taken transactions.
+    max_block_bytes: usize,
+    /// Maximum number of signatures of peers the block collects.
+    max_block_signatures: usize,
+    /// Time after which the pending transactions are sealed into a block.
+    block_time: Duration,
+    /// Number of pending transactions that seals a block before `block_time` elapses.
+    block_trigger_depth: usize,
+    /// When transactions were last taken for a block.
+    last_block_time: Instant,
+    /// Number of pending transactions shared with the `WorldStateView` for queries.
+    depth: Arc<AtomicUsize>,
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Queue::new(
+            DEFAULT_MAX_TRANSACTIONS_IN_BLOCK,
+            Arc::new(AtomicUsize::new(0)),
+        )
+    }
 }
 
 impl Queue {
-    pub fn push_pending_transaction(&mut self, tx: AcceptedTransaction) {
-        self.pending_tx.push(tx);
+    pub fn new(max_transactions_in_block: usize, depth: Arc<AtomicUsize>) -> Self {
+        Queue {
+            pending_tx: HashMap::new(),
+            pending_tx_count: 0,
+            next_arrival: 0,
+            max_transactions_in_block: max_transactions_in_block.max(1),
+            max_pending_transactions: DEFAULT_MAX_PENDING_TRANSACTIONS,
+            max_block_bytes: DEFAULT_MAX_BLOCK_BYTES,
+            max_block_signatures: DEFAULT_MAX_BLOCK_SIGNATURES,
+            block_time: crate::TX_RETRIEVAL_INTERVAL,
+            block_trigger_depth: max_transactions_in_block.max(1),
+            last_block_time: Instant::now(),
+            depth,
+        }
+    }
+
+    /// Set the maximum number of transactions waiting in the queue - will overwrite the
+    /// existing one.
+    pub fn max_pending_transactions(&mut self, max_pending_transactions: usize) {
+        self.max_pending_transactions = max_pending_transactions;
+    }
+
+    /// Set the maximum size of the SCALE encoded blocks, which will collect at most
+    /// `max_block_signatures` signatures of peers - will overwrite the existing one.
+    ///
+    /// Transactions are taken into a block only while it stays under `max_block_bytes` with the
+    /// signature the leader adds to every transaction and all signatures of the block.
+    pub fn max_block_bytes(&mut self, max_block_bytes: usize, max_block_signatures: usize) {
+        self.max_block_bytes = max_block_bytes;
+        self.max_block_signatures = max_block_signatures;
+    }
+
+    /// Set the time after which pending transactions are sealed into a block - will overwrite
+    /// the existing one.
+    pub fn block_time(&mut self, block_time: Duration) {
+        self.block_time = block_time;
+    }
+
+    /// Set the number of pending transactions that seals a block without waiting for the block
+    /// time - will overwrite the existing one.
+    pub fn block_trigger_depth(&mut self, block_trigger_depth: usize) {
+        self.block_trigger_depth = block_trigger_depth.max(1);
     }
 
+    /// Returns `Err(String)` and drops the transaction if the queue is full.
+    pub fn push_pending_transaction(&mut self, tx: AcceptedTransaction) -> Result<(), String> {
+        if self.pending_tx_count >= self.max_pending_transactions {
+            return Err(format!(
+                "Queue is full, {} transactions are pending.",
+                self.pending_tx_count
+            ));
+        }
+        self.pending_tx
+            .entry(tx.account_id().clone())
+            .or_default()
+            .insert((tx.creation_time_ms(), self.next_arrival), tx);
+        self.next_arrival += 1;
+        self.pending_tx_count += 1;
+        self.depth.store(self.pending_tx_count, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Takes at most `max_transactions_in_block` pending transactions, which fit into a block of
+    /// `max_block_bytes`.
+    ///
+    /// Accounts take turns, one transaction each, in the arrival order of their next
+    /// transactions, so a single account can not fill the whole block while others wait.
+    /// Transactions of an account are taken in their creation order.
+    /// A transaction which does not fit even into an empty block is dropped.
     pub fn pop_pending_transactions(&mut self) -> Vec<AcceptedTransaction> {
-        self.pending_tx.drain(..).collect()
+        let count = self.pending_tx_count.min(self.max_transactions_in_block);
+        let (max_block_bytes, max_block_signatures) =
+            (self.max_block_bytes, self.max_block_signatures);
+        let fits_in_block = |transactions_len: usize| {
+            ValidBlock::max_encoded_len(transactions_len, max_block_signatures) <= max_block_bytes
+        };
+        let mut transactions = Vec::with_capacity(count);
+        let mut transactions_len = 0;
+        let mut dropped = 0;
+        let mut block_is_full = false;
+        while transactions.len() < count && !block_is_full && !self.pending_tx.is_empty() {
+            let mut turns: Vec<(u64, AccountId)> = self
+                .pending_tx
+                .iter()
+                .filter_map(|(account_id, account_transactions)| {
+                    account_transactions
+                        .keys()
+                        .next()
+                        .map(|(_, arrival)| (*arrival, account_id.clone()))
+                })
+                .collect();
+            turns.sort();
+            for (_, account_id) in turns.into_iter().take(count - transactions.len()) {
+                if let Some(account_transactions) = self.pending_tx.get_mut(&account_id) {
+                    if let Some((key, transaction)) = account_transactions.iter().next() {
+                        // The leader adds its signature to every transaction of the block.
+                        let transaction_len =
+                            transaction.encode().len() + crypto::MAX_ENCODED_SIGNATURE_LEN;
+                        let key = *key;
+                        if fits_in_block(transactions_len + transaction_len) {
+                            transactions_len += transaction_len;
+                            transactions.extend(account_transactions.remove(&key));
+                        } else if fits_in_block(transaction_len) {
+                            block_is_full = true;
+                        } else {
+                            eprintln!(
+                                "Transaction of {} bytes does not fit into a block of {} bytes and is dropped.",
+                                transaction_len, max_block_bytes
+                            );
+                            account_transactions.remove(&key);
+                            dropped += 1;
+                        }
+                    }
+                    if account_transactions.is_empty() {
+                        self.pending_tx.remove(&account_id);
+                    }
+                }
+                if block_is_full {
+                    break;
+                }
+            }
+        }
+        self.pending_tx_count -= transactions.len() + dropped;
+        self.depth.store(self.pending_tx_count, Ordering::Relaxed);
+        transactions
+    }
+
+    /// Takes the transactions of the next block once the block time has elapsed since the
+    /// previous one or the queue has reached `block_trigger_depth`, returns `None` otherwise.
+    ///
+    /// The returned transactions might be empty if the block time has elapsed on an idle queue.
+    pub fn pop_block_transactions(&mut self) -> Option<Vec<AcceptedTransaction>> {
+        if self.pending_tx_count < self.block_trigger_depth
+            && self.last_block_time.elapsed() < self.block_time
+        {
+            return None;
+        }
+        self.last_block_time = Instant::now();
+        Some(self.pop_pending_transactions())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{thread, time::Duration};
+
+    fn transaction() -> AcceptedTransaction {
+        account_transaction("account")
+    }
+
+    fn account_transaction(account_name: &str) -> AcceptedTransaction {
+        RequestedTransaction::new(
+            Vec::new(),
+            <Account as Identifiable>::Id::new(account_name, "domain"),
+        )
+        .accept()
+        .expect("Failed to create Transaction.")
+    }
 
     #[test]
     fn push_pending_transaction() {
         let mut queue = Queue::default();
-        queue.push_pending_transaction(
-            RequestedTransaction::new(
-                Vec::new(),
-                <Account as Identifiable>::Id::new("account", "domain"),
-            )
-            .accept()
-            .expect("Failed to create Transaction."),
+        queue
+            .push_pending_transaction(transaction())
+            .expect("Failed to push transaction.");
+    }
+
+    #[test]
+    fn push_fails_if_queue_is_full() {
+        let mut queue = Queue::default();
+        queue.max_pending_transactions(2);
+        for _ in 0..2 {
+            queue
+                .push_pending_transaction(transaction())
+                .expect("Failed to push transaction.");
+        }
+        assert!(queue.push_pending_transaction(transaction()).is_err());
+        assert_eq!(2, queue.pop_pending_transactions().len());
+    }
+
+    #[test]
+    fn pop_respects_max_transactions_in_block() {
+        let depth = Arc::new(AtomicUsize::new(0));
+        let mut queue = Queue::new(2, Arc::clone(&depth));
+        for _ in 0..5 {
+            queue
+                .push_pending_transaction(transaction())
+                .expect("Failed to push transaction.");
+        }
+        assert_eq!(5, depth.load(Ordering::Relaxed));
+        assert_eq!(2, queue.pop_pending_transactions().len());
+        assert_eq!(3, depth.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn transactions_are_split_across_blocks_under_max_block_bytes() {
+        let mut queue = Queue::default();
+        let transaction_len = transaction().encode().len() + crypto::MAX_ENCODED_SIGNATURE_LEN;
+        let max_block_bytes = ValidBlock::max_encoded_len(2 * transaction_len, 4);
+        queue.max_block_bytes(max_block_bytes, 4);
+        for _ in 0..5 {
+            queue
+                .push_pending_transaction(transaction())
+                .expect("Failed to push transaction.");
+        }
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let mut block_sizes = Vec::new();
+        loop {
+            let transactions = queue.pop_pending_transactions();
+            if transactions.is_empty() {
+                break;
+            }
+            block_sizes.push(transactions.len());
+            let block = PendingBlock::new(transactions)
+                .chain_first()
+                .sign(&public_key, &private_key)
+                .expect("Failed to sign block.");
+            // Signed block lacks only the version byte of the `ValidBlock` encoding.
+            assert!(Vec::from(&block).len() < max_block_bytes);
+        }
+        assert_eq!(vec![2, 2, 1], block_sizes);
+    }
+
+    #[test]
+    fn transaction_larger_than_block_is_dropped() {
+        let mut queue = Queue::default();
+        queue.max_block_bytes(ValidBlock::max_encoded_len(0, 1), 1);
+        queue
+            .push_pending_transaction(transaction())
+            .expect("Failed to push transaction.");
+        assert!(queue.pop_pending_transactions().is_empty());
+        assert!(queue.pending_tx.is_empty());
+        assert_eq!(0, queue.pending_tx_count);
+    }
+
+    #[test]
+    fn pop_takes_transactions_of_account_in_creation_order() {
+        let mut queue = Queue::default();
+        let first = account_transaction("alice");
+        thread::sleep(Duration::from_millis(2));
+        let second = account_transaction("alice");
+        let (first_hash, second_hash) = (first.hash(), second.hash());
+        for transaction in [second, first] {
+            queue
+                .push_pending_transaction(transaction)
+                .expect("Failed to push transaction.");
+        }
+        let block = PendingBlock::new(queue.pop_pending_transactions()).chain_first();
+        let hashes: Vec<Hash> = block
+            .transactions
+            .iter()
+            .map(AcceptedTransaction::hash)
+            .collect();
+        assert_eq!(vec![first_hash, second_hash], hashes);
+    }
+
+    #[test]
+    fn pop_takes_turns_between_accounts() {
+        let mut queue = Queue::new(2, Arc::new(AtomicUsize::new(0)));
+        for account_name in &["alice", "alice", "alice", "bob"] {
+            queue
+                .push_pending_transaction(account_transaction(account_name))
+                .expect("Failed to push transaction.");
+        }
+        let account_names: Vec<String> = queue
+            .pop_pending_transactions()
+            .iter()
+            .map(|transaction| transaction.account_id().name.to_string())
+            .collect();
+        assert_eq!(vec!["alice", "bob"], account_names);
+        assert_eq!(2, queue.pop_pending_transactions().len());
+        assert!(queue.pop_pending_transactions().is_empty());
+    }
+
+    #[test]
+    fn burst_reaching_trigger_depth_seals_before_block_time() {
+        let mut queue = Queue::default();
+        queue.block_time(Duration::from_secs(60));
+        queue.block_trigger_depth(3);
+        for _ in 0..2 {
+            queue
+                .push_pending_transaction(transaction())
+                .expect("Failed to push transaction.");
+        }
+        assert!(queue.pop_block_transactions().is_none());
+        queue
+            .push_pending_transaction(transaction())
+            .expect("Failed to push transaction.");
+        assert_eq!(
+            Some(3),
+            queue
+                .pop_block_transactions()
+                .map(|transactions| transactions.len())
+        );
+    }
+
+    #[test]
+    fn idle_queue_seals_only_after_block_time() {
+        let mut queue = Queue::default();
+        queue.block_time(Duration::from_millis(50));
+        assert!(queue.pop_block_transactions().is_none());
+        thread::sleep(Duration::from_millis(60));
+        assert_eq!(
+            Some(0),
+            queue
+                .pop_block_transactions()
+                .map(|transactions| transactions.len())
         );
+        assert!(queue.pop_block_transactions().is_none());
     }
 }
@@ -0,0 +1,689 @@
+//! This module contains the scheduler of the block transactions execution.
+//!
+//! Transactions are grouped by the accounts and asset definitions they touch. Groups which do
+//! not conflict are executed in parallel on copies of the touched state, the copies are merged
+//! back afterwards, so the resulting state is identical to the sequential execution.
+
+use crate::{
+    account::isi::AccountInstruction, asset::isi::AssetInstruction, domain::isi::DomainInstruction,
+    isi::AssertInstruction, permission::isi::PermissionInstruction, prelude::*,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    mem,
+    num::NonZeroUsize,
+    thread,
+};
+
+/// Result of the validation of a single transaction.
+pub type ExecutionResult = Result<ValidTransaction, String>;
+
+/// Part of the world state accessed by transactions, the unit of the conflict detection.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StateKey {
+    /// Account together with its assets.
+    Account(<Account as Identifiable>::Id),
+    /// Definition of an asset.
+    AssetDefinition(<AssetDefinition as Identifiable>::Id),
+}
+
+/// Parts of the world state a transaction reads and writes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Footprint {
+    /// Keys which are only read.
+    pub reads: BTreeSet<StateKey>,
+    /// Keys which could be modified.
+    pub writes: BTreeSet<StateKey>,
+    /// Transaction could access the state not covered by the keys, so it conflicts with all
+    /// other transactions.
+    pub global: bool,
+}
+
+impl Footprint {
+    /// Footprint of the transaction validation including the signatures and fee checks.
+    pub fn of(transaction: &SignedTransaction) -> Footprint {
+        let mut footprint = Footprint::default();
+        let authority = transaction.account_id();
+        // Signatories and permissions are read and the fee is debited from the creator.
+        footprint.write(StateKey::Account(authority.clone()));
+        for instruction in transaction.instructions() {
+            footprint.add(instruction);
+        }
+        footprint
+    }
+
+    /// Footprint of the transaction validation in the `world_state_view`, fees are burned so
+    /// with fees enabled every transaction changes the supply of the fee asset.
+    pub fn in_view(
+        transaction: &SignedTransaction,
+        world_state_view: &WorldStateView,
+    ) -> Footprint {
+        let mut footprint = Footprint::of(transaction);
+        if let Some(definition_id) = &world_state_view
+            .transaction_limits()
+            .fee_asset_definition_id
+        {
+            footprint.write(StateKey::AssetDefinition(definition_id.clone()));
+        }
+        footprint
+    }
+
+    fn read(&mut self, key: StateKey) {
+        self.reads.insert(key);
+    }
+
+    fn write(&mut self, key: StateKey) {
+        self.writes.insert(key);
+    }
+
+    fn add(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::Account(AccountInstruction::TransferAsset(
+                source_id,
+                destination_id,
+                _,
+                _,
+            )) => {
+                self.write(StateKey::Account(source_id.clone()));
+                self.write(StateKey::Account(destination_id.clone()));
+            }
+            Instruction::Account(AccountInstruction::AddSignatory(account_id, _))
+            | Instruction::Account(AccountInstruction::RemoveSignatory(account_id, _))
+            | Instruction::Account(AccountInstruction::SetQuorum(account_id, _)) => {
+                self.write(StateKey::Account(account_id.clone()))
+            }
+            Instruction::Asset(AssetInstruction::MintAsset(_, asset_id))
+            | Instruction::Asset(AssetInstruction::MintBigAsset(_, asset_id))
+            | Instruction::Asset(AssetInstruction::BurnAsset(_, asset_id)) => {
+                self.write(StateKey::AssetDefinition(asset_id.definition_id.clone()));
+                self.write(StateKey::Account(asset_id.account_id.clone()));
+            }
+            Instruction::Asset(AssetInstruction::MintAssetToMany(definition_id, recipients)) => {
+                self.write(StateKey::AssetDefinition(definition_id.clone()));
+                for (account_id, _) in recipients {
+                    self.write(StateKey::Account(account_id.clone()));
+                }
+            }
+            Instruction::Asset(AssetInstruction::TransferAssetDefinitionOwnership(
+                definition_id,
+                new_owner,
+            )) => {
+                self.write(StateKey::AssetDefinition(definition_id.clone()));
+                self.read(StateKey::Account(new_owner.clone()));
+            }
+            Instruction::Domain(DomainInstruction::RegisterAccount(domain_name, account))
+                if account.id.domain_name == *domain_name =>
+            {
+                self.write(StateKey::Account(account.id.clone()))
+            }
+            Instruction::Domain(DomainInstruction::RegisterAsset(domain_name, definition))
+                if definition.id.domain_name == *domain_name =>
+            {
+                self.write(StateKey::AssetDefinition(definition.id.clone()))
+            }
+            Instruction::Permission(permission) => {
+                self.read(StateKey::Account(permission_authority(permission).clone()))
+            }
+            Instruction::Compose(left, right) => {
+                self.add(left);
+                self.add(right);
+            }
+            Instruction::If(condition, then, otherwise) => {
+                self.add(condition);
+                self.add(then);
+                if let Some(otherwise) = otherwise {
+                    self.add(otherwise);
+                }
+            }
+            Instruction::Assert(AssertInstruction::AssertAccountExists(account_id)) => {
+                self.read(StateKey::Account(account_id.clone()))
+            }
+            Instruction::Assert(AssertInstruction::AssertAssetBalanceAtLeast(asset_id, _)) => {
+                self.read(StateKey::Account(asset_id.account_id.clone()))
+            }
+            Instruction::Notify(_) => (),
+            _ => self.global = true,
+        }
+    }
+}
+
+/// Part of the world state a transaction could modify, saved before the transaction is applied,
+/// so its changes can be rolled back if it fails.
+pub(crate) enum Checkpoint {
+    /// Previous values of the written accounts and asset definitions, `None` if they did not
+    /// exist.
+    Entries {
+        accounts: Vec<(<Account as Identifiable>::Id, Option<Account>)>,
+        asset_definitions: Vec<(
+            <AssetDefinition as Identifiable>::Id,
+            Option<AssetDefinition>,
+        )>,
+    },
+    /// Copy of the whole state for transactions with a global footprint.
+    Peer(Box<Peer>),
+}
+
+impl Checkpoint {
+    /// Save the entities the `transaction` could write, see `Footprint::in_view`.
+    pub(crate) fn save(
+        transaction: &SignedTransaction,
+        world_state_view: &WorldStateView,
+    ) -> Checkpoint {
+        let footprint = Footprint::in_view(transaction, world_state_view);
+        if footprint.global {
+            return Checkpoint::Peer(Box::new(world_state_view.read_peer().clone()));
+        }
+        let mut accounts = Vec::new();
+        let mut asset_definitions = Vec::new();
+        for key in footprint.writes {
+            match key {
+                StateKey::Account(id) => {
+                    let account = world_state_view.read_account(&id).cloned();
+                    accounts.push((id, account));
+                }
+                StateKey::AssetDefinition(id) => {
+                    let definition = world_state_view.read_asset_definition(&id).cloned();
+                    asset_definitions.push((id, definition));
+                }
+            }
+        }
+        Checkpoint::Entries {
+            accounts,
+            asset_definitions,
+        }
+    }
+
+    /// Put the saved entities back into the view, dropping the ones created since.
+    pub(crate) fn restore(self, world_state_view: &mut WorldStateView) {
+        let (accounts, asset_definitions) = match self {
+            Checkpoint::Peer(peer) => {
+                *world_state_view.peer() = *peer;
+                return;
+            }
+            Checkpoint::Entries {
+                accounts,
+                asset_definitions,
+            } => (accounts, asset_definitions),
+        };
+        for (id, account) in accounts {
+            if let Some(domain) = world_state_view.domain(&id.domain_name) {
+                match account {
+                    Some(account) => domain.accounts.insert(id, account),
+                    None => domain.accounts.remove(&id),
+                };
+            }
+        }
+        for (id, definition) in asset_definitions {
+            if let Some(domain) = world_state_view.domain(&id.domain_name) {
+                match definition {
+                    Some(definition) => domain.asset_definitions.insert(id, definition),
+                    None => domain.asset_definitions.remove(&id),
+                };
+            }
+        }
+    }
+}
+
+fn permission_authority(permission: &PermissionInstruction) -> &<Account as Identifiable>::Id {
+    match permission {
+        PermissionInstruction::CanAnything(authority)
+        | PermissionInstruction::CanAddListener(authority)
+        | PermissionInstruction::CanAddDomain(authority)
+        | PermissionInstruction::CanRegisterAccount(authority, _)
+        | PermissionInstruction::CanRegisterAssetDefinition(authority, _)
+        | PermissionInstruction::CanTransferAsset(authority, _, _)
+        | PermissionInstruction::CanMintAsset(authority, _, _) => authority,
+    }
+}
+
+/// Validate transactions one by one in the given order.
+pub fn execute_sequentially(
+    transactions: Vec<SignedTransaction>,
+    world_state_view: &mut WorldStateView,
+) -> Vec<ExecutionResult> {
+    transactions
+        .into_iter()
+        .map(|transaction| transaction.validate(world_state_view))
+        .collect()
+}
+
+/// Validate transactions executing the non conflicting ones in parallel.
+///
+/// Results are returned in the order of `transactions` and the resulting state is identical to
+/// the one after `execute_sequentially`.
+pub fn execute_in_parallel(
+    transactions: Vec<SignedTransaction>,
+    world_state_view: &mut WorldStateView,
+) -> Vec<ExecutionResult> {
+    let mut results = Vec::with_capacity(transactions.len());
+    let mut batch = Vec::new();
+    for transaction in transactions {
+        let footprint = Footprint::in_view(&transaction, world_state_view);
+        if footprint.global {
+            results.extend(execute_batch(mem::take(&mut batch), world_state_view));
+            results.push(transaction.validate(world_state_view));
+        } else {
+            batch.push((transaction, footprint));
+        }
+    }
+    results.extend(execute_batch(batch, world_state_view));
+    results
+}
+
+/// Transactions of a conflict group with the copy of the state they touch.
+struct Job {
+    transactions: Vec<(usize, SignedTransaction)>,
+    world_state_view: WorldStateView,
+    writes: BTreeSet<StateKey>,
+}
+
+impl Job {
+    fn run(
+        mut self,
+    ) -> (
+        Vec<(usize, ExecutionResult)>,
+        WorldStateView,
+        BTreeSet<StateKey>,
+    ) {
+        let results = mem::take(&mut self.transactions)
+            .into_iter()
+            .map(|(index, transaction)| (index, transaction.validate(&mut self.world_state_view)))
+            .collect();
+        (results, self.world_state_view, self.writes)
+    }
+}
+
+/// Execute transactions which access only the keys of their footprints.
+fn execute_batch(
+    batch: Vec<(SignedTransaction, Footprint)>,
+    world_state_view: &mut WorldStateView,
+) -> Vec<ExecutionResult> {
+    let groups = conflict_groups(
+        &batch
+            .iter()
+            .map(|(_, footprint)| footprint)
+            .collect::<Vec<&Footprint>>(),
+    );
+    if groups.len() <= 1 {
+        let transactions = batch.into_iter().map(|(transaction, _)| transaction);
+        return execute_sequentially(transactions.collect(), world_state_view);
+    }
+    let len = batch.len();
+    let mut batch: Vec<Option<(SignedTransaction, Footprint)>> =
+        batch.into_iter().map(Some).collect();
+    let jobs: Vec<Job> = groups
+        .into_iter()
+        .map(|indices| {
+            let mut keys = BTreeSet::new();
+            let mut writes = BTreeSet::new();
+            let mut transactions = Vec::with_capacity(indices.len());
+            for index in indices {
+                let (transaction, footprint) = batch[index]
+                    .take()
+                    .expect("Transaction belongs to a single group.");
+                keys.extend(footprint.reads);
+                keys.extend(footprint.writes.iter().cloned());
+                writes.extend(footprint.writes);
+                transactions.push((index, transaction));
+            }
+            Job {
+                transactions,
+                world_state_view: sparse_copy(world_state_view, &keys),
+                writes,
+            }
+        })
+        .collect();
+    let workers = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(jobs.len());
+    let mut assigned_jobs: Vec<Vec<Job>> = (0..workers).map(|_| Vec::new()).collect();
+    for (index, job) in jobs.into_iter().enumerate() {
+        assigned_jobs[index % workers].push(job);
+    }
+    let finished_jobs: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = assigned_jobs
+            .into_iter()
+            .map(|jobs| scope.spawn(move || jobs.into_iter().map(Job::run).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .expect("Transaction execution thread panicked.")
+            })
+            .collect()
+    });
+    let mut results: Vec<Option<ExecutionResult>> = (0..len).map(|_| None).collect();
+    for (job_results, copy, writes) in finished_jobs {
+        merge(world_state_view, copy, &writes);
+        for (index, result) in job_results {
+            results[index] = Some(result);
+        }
+    }
+    results
+        .into_iter()
+        .map(|result| result.expect("Every transaction is executed."))
+        .collect()
+}
+
+/// Split transactions into groups, so no transaction writes a key accessed by a transaction
+/// of another group. Groups and transactions inside of them keep the original order.
+fn conflict_groups(footprints: &[&Footprint]) -> Vec<Vec<usize>> {
+    let mut parents: Vec<usize> = (0..footprints.len()).collect();
+    let mut accesses: BTreeMap<&StateKey, (Vec<usize>, bool)> = BTreeMap::new();
+    for (index, footprint) in footprints.iter().enumerate() {
+        for key in &footprint.reads {
+            accesses.entry(key).or_default().0.push(index);
+        }
+        for key in &footprint.writes {
+            let access = accesses.entry(key).or_default();
+            access.0.push(index);
+            access.1 = true;
+        }
+    }
+    for (indices, written) in accesses.values() {
+        if *written {
+            for index in &indices[1..] {
+                let (first, other) = (find(&mut parents, indices[0]), find(&mut parents, *index));
+                parents[first.max(other)] = first.min(other);
+            }
+        }
+    }
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for index in 0..footprints.len() {
+        groups
+            .entry(find(&mut parents, index))
+            .or_default()
+            .push(index);
+    }
+    groups.into_values().collect()
+}
+
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Copy of the view with all domains, but only the accounts and asset definitions of `keys`.
+fn sparse_copy(world_state_view: &WorldStateView, keys: &BTreeSet<StateKey>) -> WorldStateView {
+    let mut domains: HashMap<Name, Domain> = world_state_view
+        .domains_iter()
+        .map(|domain| (domain.name.clone(), Domain::new(domain.name.clone())))
+        .collect();
+    for key in keys {
+        match key {
+            StateKey::Account(id) => {
+                if let (Some(account), Some(domain)) = (
+                    world_state_view.read_account(id),
+                    domains.get_mut(id.domain_name.as_str()),
+                ) {
+                    domain.accounts.insert(id.clone(), account.clone());
+                }
+            }
+            StateKey::AssetDefinition(id) => {
+                if let (Some(definition), Some(domain)) = (
+                    world_state_view.read_asset_definition(id),
+                    domains.get_mut(id.domain_name.as_str()),
+                ) {
+                    domain
+                        .asset_definitions
+                        .insert(id.clone(), definition.clone());
+                }
+            }
+        }
+    }
+    let mut copy = WorldStateView::new(Peer::with_domains(
+        world_state_view.read_peer().id.clone(),
+        &[],
+        domains,
+    ));
+    copy.set_transaction_limits(world_state_view.transaction_limits().clone());
+    copy
+}
+
+/// Move the written entities of the `copy` into the view.
+fn merge(
+    world_state_view: &mut WorldStateView,
+    mut copy: WorldStateView,
+    keys: &BTreeSet<StateKey>,
+) {
+    for key in keys {
+        match key {
+            StateKey::Account(id) => {
+                let account = copy
+                    .domain(&id.domain_name)
+                    .and_then(|domain| domain.accounts.remove(id));
+                if let Some(domain) = world_state_view.domain(&id.domain_name) {
+                    match account {
+                        Some(account) => domain.accounts.insert(id.clone(), account),
+                        None => domain.accounts.remove(id),
+                    };
+                }
+            }
+            StateKey::AssetDefinition(id) => {
+                let definition = copy
+                    .domain(&id.domain_name)
+                    .and_then(|domain| domain.asset_definitions.remove(id));
+                if let Some(domain) = world_state_view.domain(&id.domain_name) {
+                    match definition {
+                        Some(definition) => domain.asset_definitions.insert(id.clone(), definition),
+                        None => domain.asset_definitions.remove(id),
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{peer::PeerId, permission::permission_asset_definition_id};
+
+    const DOMAIN_NAME: &str = "wonderland";
+
+    /// Key pair of the signatory of all accounts.
+    fn key_pair() -> (PublicKey, PrivateKey) {
+        crate::crypto::generate_key_pair_from_seed([7; 32]).expect("Failed to generate key pair.")
+    }
+
+    fn world_state_view_with_accounts(balances: &[u32]) -> WorldStateView {
+        let definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        let mut domain = Domain::new(DOMAIN_NAME.to_string());
+        domain.asset_definitions.insert(
+            definition_id.clone(),
+            AssetDefinition::new(definition_id.clone(), account_id(0)),
+        );
+        for (index, balance) in balances.iter().enumerate() {
+            let id = account_id(index);
+            let mut account = Account::new(&id.name, DOMAIN_NAME, key_pair().0);
+            let asset_id = AssetId {
+                definition_id: definition_id.clone(),
+                account_id: id.clone(),
+            };
+            account
+                .assets
+                .insert(asset_id.clone(), Asset::with_quantity(asset_id, *balance));
+            let permission_asset_id = AssetId {
+                definition_id: permission_asset_definition_id(),
+                account_id: id.clone(),
+            };
+            account.assets.insert(
+                permission_asset_id.clone(),
+                Asset::with_permission(
+                    permission_asset_id,
+                    ("anything".to_string(), "".to_string()),
+                ),
+            );
+            domain.accounts.insert(id, account);
+        }
+        let mut domains = HashMap::new();
+        domains.insert(Name::new(DOMAIN_NAME), domain);
+        WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+            domains,
+        ))
+    }
+
+    fn account_id(index: usize) -> AccountId {
+        AccountId::new(&format!("account{}", index), DOMAIN_NAME)
+    }
+
+    fn transfer(source: usize, destination: usize, quantity: u32) -> SignedTransaction {
+        let (public_key, private_key) = key_pair();
+        let asset_id = AssetId {
+            definition_id: AssetDefinitionId::new("xor", DOMAIN_NAME),
+            account_id: account_id(source),
+        };
+        RequestedTransaction::new(
+            vec![Instruction::Account(AccountInstruction::TransferAsset(
+                account_id(source),
+                account_id(destination),
+                Asset::with_quantity(asset_id, quantity),
+                None,
+            ))],
+            account_id(source),
+        )
+        .accept()
+        .expect("Failed to accept transaction.")
+        .sign(&public_key, &private_key)
+        .expect("Failed to sign transaction.")
+    }
+
+    /// Transferred assets are credited under the id of the source's asset, so all `xor` assets
+    /// of the account are summed up.
+    fn balance(world_state_view: &WorldStateView, index: usize) -> u32 {
+        let definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        world_state_view
+            .read_account(&account_id(index))
+            .expect("Failed to find account.")
+            .assets
+            .values()
+            .filter(|asset| asset.id.definition_id == definition_id)
+            .map(|asset| asset.quantity)
+            .sum()
+    }
+
+    fn hashes(results: &[ExecutionResult]) -> Vec<Option<Hash>> {
+        results
+            .iter()
+            .map(|result| result.as_ref().ok().map(ValidTransaction::hash))
+            .collect()
+    }
+
+    #[test]
+    fn parallel_execution_matches_sequential() {
+        let world_state_view = world_state_view_with_accounts(&[1000; 200]);
+        // Every pair of accounts trades only with each other, every tenth transfer overdraws.
+        let transactions: Vec<SignedTransaction> = (0..1000)
+            .map(|index| {
+                let pair = index % 100;
+                let quantity = if index % 10 == 9 {
+                    5000
+                } else {
+                    index as u32 % 13 + 1
+                };
+                if index % 3 == 0 {
+                    transfer(2 * pair + 1, 2 * pair, quantity)
+                } else {
+                    transfer(2 * pair, 2 * pair + 1, quantity)
+                }
+            })
+            .collect();
+        let footprints: Vec<Footprint> = transactions.iter().map(Footprint::of).collect();
+        assert_eq!(
+            100,
+            conflict_groups(&footprints.iter().collect::<Vec<&Footprint>>()).len()
+        );
+        let mut sequential_world_state_view = world_state_view.clone();
+        let sequential_results =
+            execute_sequentially(transactions.clone(), &mut sequential_world_state_view);
+        let mut parallel_world_state_view = world_state_view;
+        let parallel_results = execute_in_parallel(transactions, &mut parallel_world_state_view);
+        assert_eq!(hashes(&sequential_results), hashes(&parallel_results));
+        assert_eq!(
+            100,
+            sequential_results
+                .iter()
+                .filter(|result| result.is_err())
+                .count()
+        );
+        assert!(sequential_world_state_view
+            .diff(&parallel_world_state_view)
+            .is_empty());
+    }
+
+    #[test]
+    fn conflicting_transfers_are_serialized() {
+        let mut world_state_view = world_state_view_with_accounts(&[100, 0, 0, 50, 0]);
+        let transactions = vec![transfer(0, 1, 60), transfer(3, 4, 50), transfer(0, 2, 60)];
+        let footprints: Vec<Footprint> = transactions.iter().map(Footprint::of).collect();
+        assert_eq!(
+            vec![vec![0, 2], vec![1]],
+            conflict_groups(&footprints.iter().collect::<Vec<&Footprint>>())
+        );
+        let results = execute_in_parallel(transactions, &mut world_state_view);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[2].is_err());
+        assert_eq!(
+            vec![40, 60, 0, 0, 50],
+            (0..5)
+                .map(|index| balance(&world_state_view, index))
+                .collect::<Vec<u32>>()
+        );
+    }
+
+    #[test]
+    fn checkpoint_saves_only_written_entities() {
+        let mut world_state_view = world_state_view_with_accounts(&[1000, 1000]);
+        let new_account_id = AccountId::new("account2", DOMAIN_NAME);
+        let (public_key, private_key) = key_pair();
+        let transaction = RequestedTransaction::new(
+            vec![
+                Instruction::Domain(DomainInstruction::RegisterAccount(
+                    Name::from(DOMAIN_NAME),
+                    Account::new(&new_account_id.name, DOMAIN_NAME, public_key),
+                )),
+                Instruction::Account(AccountInstruction::SetQuorum(account_id(1), 2)),
+            ],
+            account_id(0),
+        )
+        .accept()
+        .expect("Failed to accept transaction.")
+        .sign(&public_key, &private_key)
+        .expect("Failed to sign transaction.");
+        let checkpoint = Checkpoint::save(&transaction, &world_state_view);
+        match &checkpoint {
+            Checkpoint::Entries {
+                accounts,
+                asset_definitions,
+            } => {
+                assert_eq!(
+                    vec![&account_id(0), &account_id(1), &new_account_id],
+                    accounts.iter().map(|(id, _)| id).collect::<Vec<_>>()
+                );
+                assert!(accounts[2].1.is_none());
+                assert!(asset_definitions.is_empty());
+            }
+            Checkpoint::Peer(_) => panic!("Only the written accounts should be saved."),
+        }
+        let state_before = world_state_view.clone();
+        for instruction in transaction.instructions() {
+            instruction
+                .execute(account_id(0), &mut world_state_view)
+                .expect("Failed to execute instruction.");
+        }
+        assert!(!state_before.diff(&world_state_view).is_empty());
+        checkpoint.restore(&mut world_state_view);
+        assert!(state_before.diff(&world_state_view).is_empty());
+    }
+}
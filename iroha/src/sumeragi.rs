@@ -4,14 +4,14 @@
 
 use crate::{
     block::{PendingBlock, SignedBlock},
-    crypto::Hash,
-    peer::PeerId,
+    crypto::{self, Hash, RetainVerified},
+    peer::{self, PeerId},
     prelude::*,
     torii::uri,
 };
 use async_std::sync::RwLock;
 use iroha_derive::*;
-use iroha_network::{Network, Request, Response};
+use iroha_network::{Request, Response};
 use parity_scale_codec::{Decode, Encode};
 use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use std::{
@@ -21,6 +21,10 @@ use std::{
     time::{Duration, SystemTime},
 };
 
+/// Number of signatures the proxy tail collects for a block by default, well above the number
+/// of peers in any realistic network.
+pub const DEFAULT_MAX_BLOCK_SIGNATURES: usize = 1024;
+
 trait Consensus {
     fn round(&mut self, transactions: Vec<AcceptedTransaction>) -> Option<PendingBlock>;
 }
@@ -47,14 +51,14 @@ pub enum Message {
 impl Message {
     #[log]
     async fn send_to(self, peer: &PeerId) -> Result<(), String> {
-        match Network::send_request_to(
-            &peer.address,
+        match peer::send_request_to(
+            peer,
             Request::new(uri::CONSENSUS_URI.to_string(), self.into()),
         )
         .await?
         {
             Response::Ok(_) => Ok(()),
-            Response::InternalError => Err(format!(
+            Response::InternalError | Response::Rejected(_) => Err(format!(
                 "Failed to send message - Internal Error on peer: {:?}",
                 peer
             )),
@@ -88,7 +92,7 @@ impl NoTransactionReceiptReceived {
         } else {
             self.signatures.insert(
                 *public_key,
-                Signature::new(*public_key, &payload, private_key)?,
+                Signature::new(&KeyPair::new(*public_key, *private_key), &payload)?,
             );
             Ok(())
         }
@@ -123,7 +127,7 @@ impl TransactionReceipt {
             received_at: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .expect("Failed to get System Time."),
-            signature: Signature::new(*public_key, &transaction_hash, private_key)?,
+            signature: Signature::new(&KeyPair::new(*public_key, *private_key), &transaction_hash)?,
         })
     }
 }
@@ -153,7 +157,10 @@ impl CommitTimeout {
         } else {
             self.signatures.insert(
                 *public_key,
-                Signature::new(*public_key, &self.voting_block_hash, private_key)?,
+                Signature::new(
+                    &KeyPair::new(*public_key, *private_key),
+                    &self.voting_block_hash,
+                )?,
             );
             Ok(())
         }
@@ -210,6 +217,11 @@ pub struct Sumeragi {
     pending_forwarded_tx_hashes: Arc<RwLock<BTreeSet<Hash>>>,
     commit_time: Duration,
     tx_receipt_time: Duration,
+    max_block_signatures: usize,
+    /// Whether the leader seals blocks without transactions.
+    produce_empty_blocks: bool,
+    /// Height and hash of the last block sent to `Kura`.
+    latest_block: Option<(u64, Hash)>,
 }
 
 impl Sumeragi {
@@ -244,12 +256,26 @@ impl Sumeragi {
                 commit_time: Duration::from_millis(commit_time_ms),
                 transaction_sender,
                 tx_receipt_time: Duration::from_millis(tx_receipt_time_ms),
+                max_block_signatures: DEFAULT_MAX_BLOCK_SIGNATURES,
+                produce_empty_blocks: false,
+                latest_block: None,
             })
         } else {
             Err(format!("Not enough peers to be Byzantine fault tolerant. Expected a least {} peers, got {}", 3 * max_faults + 1, peers.len()))
         }
     }
 
+    /// Set the maximum number of signatures the proxy tail collects for a block, signatures
+    /// gossiped above it are dropped.
+    pub fn max_block_signatures(&mut self, max_block_signatures: usize) {
+        self.max_block_signatures = max_block_signatures;
+    }
+
+    /// Set whether the leader seals a block in a round that has no transactions, disabled by default.
+    pub fn produce_empty_blocks(&mut self, produce_empty_blocks: bool) {
+        self.produce_empty_blocks = produce_empty_blocks;
+    }
+
     /// Returns `true` if some block is in discussion, `false` otherwise.
     pub async fn voting_in_progress(&self) -> bool {
         self.voting_block.write().await.is_some()
@@ -257,18 +283,35 @@ impl Sumeragi {
 
     /// the leader of each round just uses the transactions they have at hand to create a block
     pub async fn round(&mut self, transactions: Vec<AcceptedTransaction>) -> Result<(), String> {
-        if transactions.is_empty() {
+        if transactions.is_empty() && !self.produce_empty_blocks {
             return Ok(());
         }
         if let Role::Leader = self.role() {
             let block = PendingBlock::new(transactions)
-                //TODO: actually chain block?
-                .chain_first()
-                .sign(&self.public_key, &self.private_key)?;
+                .without_invalid_transactions()
+                .without_transactions_invalid_in(
+                    &*self.world_state_view.read().await,
+                    &self.public_key,
+                    &self.private_key,
+                )?;
+            if block.transactions.is_empty() && !self.produce_empty_blocks {
+                return Ok(());
+            }
+            let block = match self.top_block().await {
+                Some((height, hash)) => block.chain(
+                    height.checked_add(1).ok_or(format!(
+                        "Top block is at the maximum height {}, no more blocks can be built.",
+                        height
+                    ))?,
+                    hash,
+                ),
+                None => block.chain_first(),
+            }
+            .sign(&self.public_key, &self.private_key)?;
             let minimum_quorum_of_peers = 2;
             if self.sorted_peers.len() < minimum_quorum_of_peers {
                 let block = block.validate(&*self.world_state_view.read().await)?;
-                self.blocks_sender.write().await.send(block).await;
+                self.send_to_kura(block).await;
                 Ok(())
             } else {
                 *self.voting_block.write().await = Some(VotingBlock::new(block.clone()));
@@ -546,20 +589,29 @@ impl Sumeragi {
     #[log]
     async fn handle_block_signed(&mut self, block: SignedBlock) -> Result<(), String> {
         if let Role::ProxyTail = self.role() {
-            let voting_block = self.voting_block.write().await.clone();
-            match voting_block {
-                Some(voting_block) => {
-                    // TODO: verify signatures
-                    let mut voting_block = voting_block.clone();
-                    for signature in block.signatures {
-                        if !voting_block.block.signatures.contains(&signature) {
-                            voting_block.block.signatures.push(signature)
-                        }
-                    }
-                    *self.voting_block.write().await = Some(voting_block);
-                }
-                None => *self.voting_block.write().await = Some(VotingBlock::new(block)),
-            };
+            let mut block = block;
+            let mut signatures = std::mem::take(&mut block.signatures);
+            let dropped =
+                signatures.retain_verified_with_context(crypto::context::BLOCK, &block.hash());
+            if dropped > 0 {
+                eprintln!(
+                    "Dropped {} invalid signatures of the signed block.",
+                    dropped
+                );
+            }
+            let mut voting_block = self
+                .voting_block
+                .write()
+                .await
+                .clone()
+                .unwrap_or_else(|| VotingBlock::new(block));
+            if let Err(e) = voting_block
+                .block
+                .append_signatures(signatures, self.max_block_signatures)
+            {
+                eprintln!("Failed to add signatures to the voting block: {}", e);
+            }
+            *self.voting_block.write().await = Some(voting_block);
             let voting_block = self.voting_block.write().await.clone();
             if let Some(VotingBlock { block, .. }) = voting_block {
                 if block.signatures.len() >= 2 * self.max_faults {
@@ -582,7 +634,7 @@ impl Sumeragi {
                         });
                     let block = block.validate(&*self.world_state_view.read().await)?;
                     let hash = block.hash();
-                    self.blocks_sender.write().await.send(block).await;
+                    self.send_to_kura(block).await;
                     self.next_round(hash).await;
                 }
             }
@@ -595,11 +647,28 @@ impl Sumeragi {
         //TODO: check if the block is the same as pending
         let block = block.validate(&*self.world_state_view.read().await)?;
         let hash = block.hash();
-        self.blocks_sender.write().await.send(block).await;
+        self.send_to_kura(block).await;
         self.next_round(hash).await;
         Ok(())
     }
 
+    /// Height and hash of the block a new block should be chained to. Blocks sent to `Kura`
+    /// reach the `WorldStateView` a bit later, while synchronized blocks do not pass through
+    /// `Sumeragi`, so the highest of both is used.
+    async fn top_block(&self) -> Option<(u64, Hash)> {
+        let world_state_view = self.world_state_view.read().await;
+        let world_state_view_top_block = world_state_view
+            .height()
+            .checked_sub(1)
+            .and_then(|height| Some((height, world_state_view.read_block(height)?.hash())));
+        world_state_view_top_block.max(self.latest_block)
+    }
+
+    async fn send_to_kura(&mut self, block: ValidBlock) {
+        self.latest_block = Some((block.header.height, block.hash()));
+        self.blocks_sender.write().await.send(block).await;
+    }
+
     #[log]
     async fn handle_commit_timeout(&mut self, commit_timeout: CommitTimeout) -> Result<(), String> {
         let current_time = SystemTime::now()
@@ -744,6 +813,47 @@ mod tests {
         .expect("Failed to create Sumeragi.");
     }
 
+    #[async_std::test]
+    async fn empty_rounds_seal_blocks_only_if_enabled() {
+        let (blocks_sender, mut blocks_receiver) = sync::channel(100);
+        let (transaction_sender, _transaction_receiver) = sync::channel(100);
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let this_peer = PeerId {
+            address: "127.0.0.1:7880".to_string(),
+            public_key,
+        };
+        let mut sumeragi = Sumeragi::new(
+            private_key,
+            &[this_peer.clone()],
+            this_peer.clone(),
+            0,
+            Arc::new(RwLock::new(blocks_sender)),
+            Arc::new(RwLock::new(WorldStateView::new(Peer::new(
+                this_peer.clone(),
+                &[this_peer],
+            )))),
+            transaction_sender,
+            config::DEFAULT_COMMIT_TIME_MS,
+            config::DEFAULT_TX_RECEIPT_TIME_MS,
+        )
+        .expect("Failed to create Sumeragi.");
+        for _ in 0..3 {
+            sumeragi.round(Vec::new()).await.expect("Round failed.");
+        }
+        assert!(blocks_receiver.is_empty());
+        sumeragi.produce_empty_blocks(true);
+        for height in 0..3 {
+            sumeragi.round(Vec::new()).await.expect("Round failed.");
+            let block = blocks_receiver
+                .next()
+                .await
+                .expect("Failed to receive block.");
+            assert_eq!(height, block.header.height);
+            assert!(block.transactions.is_empty());
+        }
+    }
+
     #[test]
     fn different_order() {
         let mut peers1 = vec![
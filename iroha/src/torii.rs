@@ -1,14 +1,39 @@
 //! This module contains incoming requests handling logic of Iroha.
 //! `Torii` is used to receive, accept and route incoming instructions, queries and messages.
 
-use crate::{prelude::*, sumeragi::Message, MessageSender};
+use crate::{
+    domain::isi::DomainInstruction, prelude::*, queue::DEFAULT_MAX_PENDING_TRANSACTIONS,
+    sumeragi::Message, MessageSender,
+};
 use async_std::{sync::RwLock, task};
 use iroha_derive::*;
 #[cfg(feature = "mock")]
 use iroha_network::mock::prelude::*;
 #[cfg(not(feature = "mock"))]
 use iroha_network::prelude::*;
-use std::{convert::TryFrom, sync::Arc};
+#[cfg(feature = "tls")]
+use iroha_network::tls;
+use iroha_network::DEFAULT_MAX_PAYLOAD_BYTES;
+use parity_scale_codec::{Decode, Encode};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// Default number of transactions per second an account can submit.
+pub const DEFAULT_TRANSACTION_RATE: u32 = 1000;
+/// Default number of transactions an account can submit at once above the rate.
+pub const DEFAULT_TRANSACTION_BURST: u32 = 10_000;
+/// Number of the rate limiter buckets above which the buckets of idle accounts are evicted.
+const RATE_LIMITER_EVICTION_THRESHOLD: usize = 10_000;
+/// Default maximum size of an encoded transaction accepted by `Torii`.
+pub const DEFAULT_MAX_TRANSACTION_BYTES: usize = 1024 * 1024;
 
 /// Main network handler and the only entrypoint of the Iroha.
 pub struct Torii {
@@ -16,6 +41,12 @@ pub struct Torii {
     world_state_view: Arc<RwLock<WorldStateView>>,
     transaction_sender: Arc<RwLock<TransactionSender>>,
     message_sender: Arc<RwLock<MessageSender>>,
+    rate_limiter: RateLimiter,
+    max_transaction_bytes: usize,
+    max_pending_transactions: usize,
+    invalid_signature_rejections: Arc<AtomicU64>,
+    #[cfg(feature = "tls")]
+    tls_acceptor: Option<tls::Acceptor>,
 }
 
 impl Torii {
@@ -31,9 +62,48 @@ impl Torii {
             world_state_view,
             transaction_sender: Arc::new(RwLock::new(transaction_sender)),
             message_sender: Arc::new(RwLock::new(message_sender)),
+            rate_limiter: RateLimiter::new(DEFAULT_TRANSACTION_RATE, DEFAULT_TRANSACTION_BURST),
+            max_transaction_bytes: DEFAULT_MAX_TRANSACTION_BYTES,
+            max_pending_transactions: DEFAULT_MAX_PENDING_TRANSACTIONS,
+            invalid_signature_rejections: Arc::new(AtomicU64::new(0)),
+            #[cfg(feature = "tls")]
+            tls_acceptor: None,
         }
     }
 
+    /// Number of transactions rejected because they had no valid signature.
+    pub fn invalid_signature_rejections(&self) -> u64 {
+        self.invalid_signature_rejections.load(Ordering::Relaxed)
+    }
+
+    /// Limit transactions submitted by every account to `rate` per second with up to `burst`
+    /// transactions at once - will overwrite the existing limits.
+    pub fn transaction_rate_limit(&mut self, rate: u32, burst: u32) {
+        self.rate_limiter = RateLimiter::new(rate, burst);
+    }
+
+    /// Refuse transactions larger than `max_transaction_bytes` before they are read from the
+    /// connection - will overwrite the existing limit.
+    pub fn max_transaction_bytes(&mut self, max_transaction_bytes: usize) {
+        self.max_transaction_bytes = max_transaction_bytes;
+    }
+
+    /// Refuse transactions while `max_pending_transactions` are waiting in the queue - will
+    /// overwrite the existing limit. Should match the capacity of the queue.
+    pub fn max_pending_transactions(&mut self, max_pending_transactions: usize) {
+        self.max_pending_transactions = max_pending_transactions;
+    }
+
+    /// Serve requests over TLS with a self-signed certificate of the peer's key, so clients and
+    /// other peers can pin it to the public key of the peer.
+    #[cfg(feature = "tls")]
+    pub fn enable_tls(&mut self, private_key: &PrivateKey) -> Result<(), String> {
+        let mut secret_key = [0; 32];
+        secret_key.copy_from_slice(&private_key[..32]);
+        self.tls_acceptor = Some(tls::Acceptor::new(&secret_key)?);
+        Ok(())
+    }
+
     /// To handle incoming requests `Torii` should be started first.
     pub async fn start(&mut self) -> Result<(), String> {
         let url = &self.url.clone();
@@ -44,8 +114,19 @@ impl Torii {
             world_state_view,
             transaction_sender,
             message_sender,
+            rate_limiter: self.rate_limiter.clone(),
+            max_transaction_bytes: self.max_transaction_bytes,
+            max_pending_transactions: self.max_pending_transactions,
+            invalid_signature_rejections: Arc::clone(&self.invalid_signature_rejections),
         };
-        Network::listen(Arc::new(RwLock::new(state)), url, handle_connection).await?;
+        let state = Arc::new(RwLock::new(state));
+        #[cfg(feature = "tls")]
+        {
+            if let Some(acceptor) = &self.tls_acceptor {
+                return Network::listen_tls(state, url, acceptor, handle_connection).await;
+            }
+        }
+        Network::listen(state, url, handle_connection).await?;
         Ok(())
     }
 }
@@ -55,6 +136,190 @@ struct ToriiState {
     world_state_view: Arc<RwLock<WorldStateView>>,
     transaction_sender: Arc<RwLock<TransactionSender>>,
     message_sender: Arc<RwLock<MessageSender>>,
+    rate_limiter: RateLimiter,
+    max_transaction_bytes: usize,
+    max_pending_transactions: usize,
+    invalid_signature_rejections: Arc<AtomicU64>,
+}
+
+/// Reason of a transaction refused by `Torii` before it reaches the queue.
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
+pub enum TransactionRejection {
+    /// The submitting account exceeded its transactions rate limit.
+    RateLimited {
+        /// Time in milliseconds after which the transaction will be accepted.
+        retry_after_ms: u64,
+    },
+    /// The transaction is not signed or one of its signatures does not match the payload.
+    SignatureInvalid {
+        /// Description of the failed check.
+        reason: String,
+    },
+    /// The queue of pending transactions is full, e.g. while block production is paused.
+    QueueFull {
+        /// Number of transactions waiting in the queue.
+        pending_transactions: u64,
+    },
+}
+
+impl Display for TransactionRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionRejection::RateLimited { retry_after_ms } => write!(
+                f,
+                "Transactions rate limit exceeded, retry after {} ms.",
+                retry_after_ms
+            ),
+            TransactionRejection::SignatureInvalid { reason } => {
+                write!(f, "Transaction signature is invalid: {}", reason)
+            }
+            TransactionRejection::QueueFull {
+                pending_transactions,
+            } => write!(
+                f,
+                "Transaction queue is full, {} transactions are pending.",
+                pending_transactions
+            ),
+        }
+    }
+}
+
+/// Reason of a query refused by `Torii` because its signer is not allowed to make it.
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
+pub struct QueryRejection {
+    /// Description of the failed check.
+    pub reason: String,
+}
+
+impl Display for QueryRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Query was rejected: {}", self.reason)
+    }
+}
+
+/// Kind of the token bucket of an account a transaction spends from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Bucket {
+    Transactions,
+    /// Registrations of new accounts are limited separately, so accounts can be created at the
+    /// network bootstrap regardless of the other transactions of the creator.
+    Registrations,
+}
+
+/// Token buckets per account - every transaction takes a token, tokens are refilled at `rate`
+/// per second up to `burst`.
+///
+/// Full buckets are the same as missing ones, so they are evicted once there are too many
+/// buckets.
+#[derive(Clone, Debug)]
+struct RateLimiter {
+    rate: u32,
+    burst: u32,
+    buckets: HashMap<(<Account as Identifiable>::Id, Bucket), (f64, Instant)>,
+    eviction_threshold: usize,
+}
+
+impl RateLimiter {
+    fn new(rate: u32, burst: u32) -> Self {
+        RateLimiter {
+            rate,
+            burst,
+            buckets: HashMap::new(),
+            eviction_threshold: RATE_LIMITER_EVICTION_THRESHOLD,
+        }
+    }
+
+    fn bucket(transaction: &AcceptedTransaction) -> Bucket {
+        let is_registration = !transaction.instructions().is_empty()
+            && transaction.instructions().iter().all(|instruction| {
+                matches!(
+                    instruction,
+                    Instruction::Domain(DomainInstruction::RegisterAccount(_, _))
+                )
+            });
+        if is_registration {
+            Bucket::Registrations
+        } else {
+            Bucket::Transactions
+        }
+    }
+
+    /// Drop the buckets which were refilled up to `burst` by the `now`.
+    ///
+    /// The threshold grows with the number of the active buckets, so the eviction takes
+    /// amortized constant time per transaction.
+    fn evict_idle_buckets(&mut self, now: Instant) {
+        let (rate, burst) = (f64::from(self.rate), f64::from(self.burst.max(1)));
+        self.buckets.retain(|_, (tokens, last_refill)| {
+            *tokens + now.duration_since(*last_refill).as_secs_f64() * rate < burst
+        });
+        self.eviction_threshold = RATE_LIMITER_EVICTION_THRESHOLD.max(2 * self.buckets.len());
+    }
+
+    /// Take a token from the bucket of the transaction's creator.
+    fn acquire(&mut self, transaction: &AcceptedTransaction) -> Result<(), TransactionRejection> {
+        let now = Instant::now();
+        if self.buckets.len() >= self.eviction_threshold {
+            self.evict_idle_buckets(now);
+        }
+        let (rate, burst) = (f64::from(self.rate), f64::from(self.burst.max(1)));
+        let (tokens, last_refill) = self
+            .buckets
+            .entry((
+                transaction.account_id().clone(),
+                RateLimiter::bucket(transaction),
+            ))
+            .or_insert((burst, now));
+        *tokens = (*tokens + now.duration_since(*last_refill).as_secs_f64() * rate).min(burst);
+        *last_refill = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(TransactionRejection::RateLimited {
+                retry_after_ms: ((1.0 - *tokens) / rate * 1000.0).ceil() as u64,
+            })
+        }
+    }
+}
+
+/// Stateless check of the transaction signatures, done before the transaction is rate limited
+/// and queued for the execution.
+///
+/// Signatures are verified off the executor and without the lock of the `Torii` state, so
+/// other requests are served meanwhile.
+async fn verify_signatures(
+    transaction: RequestedTransaction,
+) -> Result<AcceptedTransaction, TransactionRejection> {
+    let transaction = transaction
+        .accept_async()
+        .await
+        .map_err(|reason| TransactionRejection::SignatureInvalid { reason })?;
+    if !transaction.is_signed() {
+        return Err(TransactionRejection::SignatureInvalid {
+            reason: "Transaction is not signed.".to_string(),
+        });
+    }
+    Ok(transaction)
+}
+
+/// Check that the transaction is signed by the quorum of its creator's signatories, so the
+/// rate limit of the creator can not be spent by the transactions signed with other keys.
+fn verify_signatories(
+    transaction: AcceptedTransaction,
+    world_state_view: &WorldStateView,
+) -> Result<AcceptedTransaction, TransactionRejection> {
+    world_state_view
+        .read_account(transaction.account_id())
+        .ok_or_else(|| {
+            format!(
+                "Failed to find account {} of the transaction.",
+                transaction.account_id()
+            )
+        })
+        .and_then(|account| account.check_quorum(transaction.signatures()))
+        .map_err(|reason| TransactionRejection::SignatureInvalid { reason })?;
+    Ok(transaction)
 }
 
 async fn handle_connection(
@@ -62,8 +327,19 @@ async fn handle_connection(
     stream: Box<dyn AsyncStream>,
 ) -> Result<(), String> {
     let state_arc = Arc::clone(&state);
-    task::spawn(async {
-        if let Err(e) = Network::handle_message_async(state_arc, stream, handle_request).await {
+    let max_transaction_bytes = state.read().await.max_transaction_bytes;
+    task::spawn(async move {
+        if let Err(e) = Network::handle_message_with_limit_async(
+            state_arc,
+            stream,
+            |uri_path| match uri_path {
+                uri::INSTRUCTIONS_URI => max_transaction_bytes,
+                _ => DEFAULT_MAX_PAYLOAD_BYTES,
+            },
+            handle_request,
+        )
+        .await
+        {
             eprintln!("Failed to handle message: {}", e);
         }
     })
@@ -76,15 +352,43 @@ async fn handle_request(state: State<ToriiState>, request: Request) -> Result<Re
     match request.url() {
         uri::INSTRUCTIONS_URI => match RequestedTransaction::try_from(request.payload().to_vec()) {
             Ok(transaction) => {
+                let verified = verify_signatures(transaction).await;
+                let mut state = state.write().await;
+                let verified = match verified {
+                    Ok(transaction) => {
+                        verify_signatories(transaction, &*state.world_state_view.read().await)
+                    }
+                    Err(rejection) => Err(rejection),
+                };
+                let transaction = match verified {
+                    Ok(transaction) => transaction,
+                    Err(rejection) => {
+                        state
+                            .invalid_signature_rejections
+                            .fetch_add(1, Ordering::Relaxed);
+                        eprintln!("{}", rejection);
+                        return Ok(Response::Rejected(rejection.into()));
+                    }
+                };
+                let pending_transactions =
+                    state.world_state_view.read().await.pending_transactions();
+                if pending_transactions >= state.max_pending_transactions {
+                    let rejection = TransactionRejection::QueueFull {
+                        pending_transactions: pending_transactions as u64,
+                    };
+                    return Ok(Response::Rejected(rejection.into()));
+                }
+                if let Err(rejection) = state.rate_limiter.acquire(&transaction) {
+                    return Ok(Response::Rejected(rejection.into()));
+                }
+                let cost = transaction.cost();
                 state
-                    .write()
-                    .await
                     .transaction_sender
                     .write()
                     .await
-                    .send(transaction.accept()?)
+                    .send(transaction)
                     .await;
-                Ok(Response::empty_ok())
+                Ok(Response::Ok(cost.encode()))
             }
             Err(e) => {
                 eprintln!("Failed to decode transaction: {}", e);
@@ -92,19 +396,25 @@ async fn handle_request(state: State<ToriiState>, request: Request) -> Result<Re
             }
         },
         uri::QUERY_URI => match QueryRequest::try_from(request.payload().to_vec()) {
-            Ok(request) => match request
-                .query
-                .execute(&*state.read().await.world_state_view.read().await)
-            {
-                Ok(result) => {
-                    let result = &result;
-                    Ok(Response::Ok(result.into()))
+            Ok(request) => {
+                let state = state.read().await;
+                let world_state_view = state.world_state_view.read().await;
+                if let Err(reason) = request.authorize(&world_state_view) {
+                    let rejection = QueryRejection { reason };
+                    eprintln!("{}", rejection);
+                    return Ok(Response::Rejected(rejection.into()));
                 }
-                Err(e) => {
-                    eprintln!("Failed to execute Query: {}", e);
-                    Ok(Response::InternalError)
+                match request.execute(&world_state_view) {
+                    Ok(result) => {
+                        let result = &result;
+                        Ok(Response::Ok(result.into()))
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to execute Query: {}", e);
+                        Ok(Response::InternalError)
+                    }
                 }
-            },
+            }
             Err(e) => {
                 eprintln!("Failed to decode transaction: {}", e);
                 Ok(Response::InternalError)
@@ -135,7 +445,8 @@ async fn handle_request(state: State<ToriiState>, request: Request) -> Result<Re
 pub mod uri {
     /// Query URI is used to handle incoming Query requests.
     pub const QUERY_URI: &str = "/query";
-    /// Instructions URI is used to handle incoming ISI requests.
+    /// Instructions URI is used to handle incoming ISI requests, an accepted transaction is
+    /// answered with its encoded `u64` cost.
     pub const INSTRUCTIONS_URI: &str = "/instruction";
     /// Block URI is used to handle incoming Block requests.
     pub const CONSENSUS_URI: &str = "/consensus";
@@ -144,12 +455,24 @@ pub mod uri {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Configuration;
+    use crate::{config::Configuration, isi::Register};
     use async_std::{sync, task};
     use std::time::Duration;
 
     const CONFIGURATION_PATH: &str = "tests/test_config.json";
 
+    fn world_state_view_with_root(config: &Configuration) -> WorldStateView {
+        let mut world_state_view =
+            WorldStateView::new(Peer::new(config.peer_id.clone(), &Vec::new()));
+        let mut global = Domain::new("global".to_string());
+        global.accounts.insert(
+            AccountId::new("root", "global"),
+            Account::new("root", "global", config.public_key),
+        );
+        world_state_view.add_domain(global);
+        world_state_view
+    }
+
     #[async_std::test]
     async fn create_and_start_torii() {
         let config =
@@ -173,4 +496,254 @@ mod tests {
         });
         std::thread::sleep(Duration::from_millis(50));
     }
+
+    #[async_std::test]
+    async fn transactions_over_burst_are_rate_limited() {
+        let config =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let (public_key, private_key) = config.key_pair();
+        let (transaction_sender, _transaction_receiver) = sync::channel(100);
+        let (message_sender, _) = sync::channel(100);
+        let state = Arc::new(RwLock::new(ToriiState {
+            world_state_view: Arc::new(RwLock::new(world_state_view_with_root(&config))),
+            transaction_sender: Arc::new(RwLock::new(transaction_sender)),
+            message_sender: Arc::new(RwLock::new(message_sender)),
+            rate_limiter: RateLimiter::new(10, 3),
+            max_transaction_bytes: DEFAULT_MAX_TRANSACTION_BYTES,
+            max_pending_transactions: DEFAULT_MAX_PENDING_TRANSACTIONS,
+            invalid_signature_rejections: Arc::new(AtomicU64::new(0)),
+        }));
+        let submit = |instructions: Vec<Instruction>| {
+            let transaction: RequestedTransaction =
+                RequestedTransaction::new(instructions, AccountId::new("root", "global"))
+                    .accept()
+                    .expect("Failed to accept transaction.")
+                    .sign(&public_key, &private_key)
+                    .expect("Failed to sign transaction.")
+                    .into();
+            handle_request(
+                Arc::clone(&state),
+                Request::new(uri::INSTRUCTIONS_URI.to_string(), transaction.into()),
+            )
+        };
+        for _ in 0..3 {
+            assert_eq!(
+                Ok(Response::Ok(1u64.encode())),
+                submit(vec![Instruction::Notify("Test".to_string())]).await
+            );
+        }
+        let retry_after_ms = match submit(vec![Instruction::Notify("Test".to_string())]).await {
+            Ok(Response::Rejected(payload)) => match TransactionRejection::try_from(payload)
+                .expect("Failed to decode rejection.")
+            {
+                TransactionRejection::RateLimited { retry_after_ms } => retry_after_ms,
+                rejection => panic!("Unexpected rejection: {}", rejection),
+            },
+            response => panic!("Transaction should be rejected, but got: {:?}", response),
+        };
+        assert!(retry_after_ms > 0 && retry_after_ms <= 100);
+        match submit(Vec::new()).await {
+            Ok(Response::Rejected(payload)) => assert!(matches!(
+                TransactionRejection::try_from(payload).expect("Failed to decode rejection."),
+                TransactionRejection::RateLimited { .. }
+            )),
+            response => panic!(
+                "Empty transaction should be rejected, but got: {:?}",
+                response
+            ),
+        }
+        let register = || {
+            submit(vec![Register::<Domain, Account> {
+                object: Account::new("alice", "wonderland", public_key),
+                destination_id: Name::from("wonderland"),
+            }
+            .into()])
+        };
+        for _ in 0..3 {
+            assert_eq!(Ok(Response::Ok(5u64.encode())), register().await);
+        }
+        match register().await {
+            Ok(Response::Rejected(payload)) => assert!(matches!(
+                TransactionRejection::try_from(payload).expect("Failed to decode rejection."),
+                TransactionRejection::RateLimited { .. }
+            )),
+            response => panic!(
+                "Registration over the burst should be rejected, but got: {:?}",
+                response
+            ),
+        }
+        task::sleep(Duration::from_millis(retry_after_ms)).await;
+        assert_eq!(
+            Ok(Response::Ok(1u64.encode())),
+            submit(vec![Instruction::Notify("Test".to_string())]).await
+        );
+    }
+
+    #[async_std::test]
+    async fn transactions_over_queue_capacity_are_rejected() {
+        let config =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let (public_key, private_key) = config.key_pair();
+        let (transaction_sender, _transaction_receiver) = sync::channel(100);
+        let (message_sender, _) = sync::channel(100);
+        let world_state_view = world_state_view_with_root(&config);
+        let pending_transactions = world_state_view.pending_transactions_counter();
+        let state = Arc::new(RwLock::new(ToriiState {
+            world_state_view: Arc::new(RwLock::new(world_state_view)),
+            transaction_sender: Arc::new(RwLock::new(transaction_sender)),
+            message_sender: Arc::new(RwLock::new(message_sender)),
+            rate_limiter: RateLimiter::new(10, 3),
+            max_transaction_bytes: DEFAULT_MAX_TRANSACTION_BYTES,
+            max_pending_transactions: 2,
+            invalid_signature_rejections: Arc::new(AtomicU64::new(0)),
+        }));
+        let submit = || {
+            let transaction: RequestedTransaction = RequestedTransaction::new(
+                vec![Instruction::Notify("Test".to_string())],
+                AccountId::new("root", "global"),
+            )
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.")
+            .into();
+            handle_request(
+                Arc::clone(&state),
+                Request::new(uri::INSTRUCTIONS_URI.to_string(), transaction.into()),
+            )
+        };
+        pending_transactions.store(1, Ordering::Relaxed);
+        assert_eq!(Ok(Response::Ok(1u64.encode())), submit().await);
+        pending_transactions.store(2, Ordering::Relaxed);
+        match submit().await {
+            Ok(Response::Rejected(payload)) => assert_eq!(
+                TransactionRejection::QueueFull {
+                    pending_transactions: 2
+                },
+                TransactionRejection::try_from(payload).expect("Failed to decode rejection.")
+            ),
+            response => panic!("Transaction should be rejected, but got: {:?}", response),
+        }
+    }
+
+    #[async_std::test]
+    async fn transaction_with_corrupted_signature_is_not_queued() {
+        let config =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let (public_key, private_key) = config.key_pair();
+        let (transaction_sender, transaction_receiver) = sync::channel(100);
+        let (message_sender, _) = sync::channel(100);
+        let invalid_signature_rejections = Arc::new(AtomicU64::new(0));
+        let state = Arc::new(RwLock::new(ToriiState {
+            world_state_view: Arc::new(RwLock::new(world_state_view_with_root(&config))),
+            transaction_sender: Arc::new(RwLock::new(transaction_sender)),
+            message_sender: Arc::new(RwLock::new(message_sender)),
+            rate_limiter: RateLimiter::new(10, 3),
+            max_transaction_bytes: DEFAULT_MAX_TRANSACTION_BYTES,
+            max_pending_transactions: DEFAULT_MAX_PENDING_TRANSACTIONS,
+            invalid_signature_rejections: Arc::clone(&invalid_signature_rejections),
+        }));
+        let transaction: RequestedTransaction = RequestedTransaction::new(
+            vec![Instruction::Notify("Test".to_string())],
+            AccountId::new("root", "global"),
+        )
+        .accept()
+        .expect("Failed to accept transaction.")
+        .sign(&public_key, &private_key)
+        .expect("Failed to sign transaction.")
+        .into();
+        let mut payload: Vec<u8> = transaction.into();
+        let position = payload
+            .windows(4)
+            .position(|window| window == b"Test")
+            .expect("Failed to find the instruction in the payload.");
+        payload[position] = b'B';
+        match handle_request(
+            Arc::clone(&state),
+            Request::new(uri::INSTRUCTIONS_URI.to_string(), payload),
+        )
+        .await
+        {
+            Ok(Response::Rejected(payload)) => assert!(matches!(
+                TransactionRejection::try_from(payload).expect("Failed to decode rejection."),
+                TransactionRejection::SignatureInvalid { .. }
+            )),
+            response => panic!("Transaction should be rejected, but got: {:?}", response),
+        }
+        assert_eq!(1, invalid_signature_rejections.load(Ordering::Relaxed));
+        assert!(transaction_receiver.is_empty());
+    }
+
+    #[async_std::test]
+    async fn transaction_signed_by_foreign_key_does_not_spend_rate_limit() {
+        let config =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let (public_key, private_key) = config.key_pair();
+        let (foreign_public_key, foreign_private_key) =
+            crate::crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let (transaction_sender, _transaction_receiver) = sync::channel(100);
+        let (message_sender, _) = sync::channel(100);
+        let state = Arc::new(RwLock::new(ToriiState {
+            world_state_view: Arc::new(RwLock::new(world_state_view_with_root(&config))),
+            transaction_sender: Arc::new(RwLock::new(transaction_sender)),
+            message_sender: Arc::new(RwLock::new(message_sender)),
+            rate_limiter: RateLimiter::new(10, 1),
+            max_transaction_bytes: DEFAULT_MAX_TRANSACTION_BYTES,
+            max_pending_transactions: DEFAULT_MAX_PENDING_TRANSACTIONS,
+            invalid_signature_rejections: Arc::new(AtomicU64::new(0)),
+        }));
+        let submit = |public_key, private_key| {
+            let transaction: RequestedTransaction = RequestedTransaction::new(
+                vec![Instruction::Notify("Test".to_string())],
+                AccountId::new("root", "global"),
+            )
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.")
+            .into();
+            handle_request(
+                Arc::clone(&state),
+                Request::new(uri::INSTRUCTIONS_URI.to_string(), transaction.into()),
+            )
+        };
+        match submit(foreign_public_key, foreign_private_key).await {
+            Ok(Response::Rejected(payload)) => assert!(matches!(
+                TransactionRejection::try_from(payload).expect("Failed to decode rejection."),
+                TransactionRejection::SignatureInvalid { .. }
+            )),
+            response => panic!("Transaction should be rejected, but got: {:?}", response),
+        }
+        assert_eq!(
+            Ok(Response::Ok(1u64.encode())),
+            submit(public_key, private_key).await
+        );
+    }
+
+    #[test]
+    fn refilled_buckets_are_evicted() {
+        let mut rate_limiter = RateLimiter::new(10, 3);
+        let transaction = |account_name| {
+            RequestedTransaction::new(
+                vec![Instruction::Notify("Test".to_string())],
+                AccountId::new(account_name, "wonderland"),
+            )
+            .accept()
+            .expect("Failed to accept transaction.")
+        };
+        rate_limiter
+            .acquire(&transaction("alice"))
+            .expect("Failed to acquire token.");
+        rate_limiter
+            .acquire(&transaction("bob"))
+            .expect("Failed to acquire token.");
+        rate_limiter
+            .acquire(&transaction("bob"))
+            .expect("Failed to acquire token.");
+        rate_limiter.evict_idle_buckets(Instant::now() + Duration::from_millis(150));
+        assert_eq!(
+            vec![&(AccountId::new("bob", "wonderland"), Bucket::Transactions)],
+            rate_limiter.buckets.keys().collect::<Vec<_>>()
+        );
+    }
 }
@@ -2,10 +2,15 @@
 //!
 //! `RequestedTransaction` is the start of the Transaction lifecycle.
 
-use crate::prelude::*;
+use crate::{asset::isi::decrease_total_supply, crypto, prelude::*, scheduler::Checkpoint};
+use async_std::task;
 use iroha_derive::Io;
 use parity_scale_codec::{Decode, Encode};
-use std::time::SystemTime;
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    time::SystemTime,
+};
 
 /// This structure represents transaction in non-trusted form.
 ///
@@ -18,7 +23,7 @@ pub struct RequestedTransaction {
     signatures: Vec<Signature>,
 }
 
-#[derive(Clone, Debug, Io, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
 struct Payload {
     /// Account ID of transaction creator.
     account_id: <Account as Identifiable>::Id,
@@ -28,6 +33,49 @@ struct Payload {
     creation_time: String,
 }
 
+impl Payload {
+    /// Total cost of the instructions.
+    fn cost(&self) -> u64 {
+        Instruction::total_cost(&self.instructions)
+    }
+
+    /// Debit the fee equal to the cost from the creator's asset if fees are enabled.
+    fn charge_fee(&self, world_state_view: &mut WorldStateView) -> Result<(), String> {
+        let definition_id = match &world_state_view
+            .transaction_limits()
+            .fee_asset_definition_id
+        {
+            Some(definition_id) => definition_id.clone(),
+            None => return Ok(()),
+        };
+        let fee = self.cost();
+        let asset_id = AssetId {
+            definition_id,
+            account_id: self.account_id.clone(),
+        };
+        let asset = world_state_view
+            .asset(&asset_id)
+            .ok_or(format!("Failed to find fee asset {}.", asset_id))?;
+        asset.quantity = u32::try_from(fee)
+            .ok()
+            .and_then(|fee| asset.quantity.checked_sub(fee))
+            .ok_or(format!(
+                "Insufficient balance of {} to pay the fee of {}.",
+                asset_id, fee
+            ))?;
+        decrease_total_supply(&asset_id.definition_id, u128::from(fee), world_state_view)
+    }
+}
+
+/// Limits on resources a single transaction may consume.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionLimits {
+    /// Maximum total cost of the transaction instructions, unlimited if not set.
+    pub max_cost: Option<u64>,
+    /// Definition of the asset the fee is debited from, fees are disabled if not set.
+    pub fee_asset_definition_id: Option<AssetDefinitionId>,
+}
+
 impl RequestedTransaction {
     /// Default `RequestedTransaction` constructor.
     pub fn new(
@@ -53,8 +101,13 @@ impl RequestedTransaction {
     ///
     /// Returns `Ok(AcceptedTransaction)` if succeeded and `Err(String)` if failed.
     pub fn accept(self) -> Result<AcceptedTransaction, String> {
+        let payload = Vec::from(&self.payload);
         for signature in &self.signatures {
-            if let Err(e) = signature.verify(&Vec::from(&self.payload)) {
+            //TODO: remove the fallback to signatures without context in the next release.
+            if let Err(e) = signature
+                .verify_with_context(crypto::context::TRANSACTION, &payload)
+                .or_else(|_| signature.verify(&payload))
+            {
                 return Err(format!("Failed to verify signatures: {}", e));
             }
         }
@@ -63,6 +116,34 @@ impl RequestedTransaction {
             signatures: self.signatures,
         })
     }
+
+    /// Accept the transaction like `accept`, but verify the signatures on a thread of the
+    /// blocking pool, so the executor keeps serving other tasks meanwhile.
+    pub async fn accept_async(self) -> Result<AcceptedTransaction, String> {
+        task::spawn_blocking(move || self.accept()).await
+    }
+
+    /// Calculate transaction `Hash`.
+    ///
+    /// Only the payload is hashed, so the result is equal to the hash of the
+    /// `ValidTransaction` this transaction will be committed as.
+    pub fn hash(&self) -> Hash {
+        crate::crypto::hash(self.payload.clone().into())
+    }
+
+    /// Decode a transaction signed offline by `SignedTransaction::to_signed_bytes`.
+    ///
+    /// Returns `Err(String)` if the bytes are not a SCALE-encoded transaction, it carries no
+    /// signatures or any of its signatures is invalid.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<RequestedTransaction, String> {
+        let transaction = RequestedTransaction::decode(&mut &bytes[..])
+            .map_err(|e| format!("Failed to decode signed transaction: {}", e))?;
+        let accepted = transaction.accept()?;
+        if !accepted.is_signed() {
+            return Err("Transaction is not signed.".to_string());
+        }
+        Ok((&accepted).into())
+    }
 }
 
 /// An ordered set of instructions, which is applied to the ledger atomically.
@@ -77,7 +158,106 @@ pub struct AcceptedTransaction {
     signatures: Vec<Signature>,
 }
 
+/// Reason of a transaction rejection by the stateless checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// Signature made by the key does not match the transaction payload.
+    InvalidSignature(PublicKey),
+    /// The key signed the transaction more than once.
+    DuplicateSignature(PublicKey),
+    /// Creation time is not a unix time in milliseconds.
+    MalformedCreationTime(String),
+    /// Instruction at the index failed `Instruction::validate`.
+    InvalidInstruction(usize, String),
+}
+
+impl Display for RejectionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::InvalidSignature(public_key) => {
+                write!(f, "Invalid signature of the key {:?}.", public_key)
+            }
+            RejectionReason::DuplicateSignature(public_key) => {
+                write!(f, "Duplicate signature of the key {:?}.", public_key)
+            }
+            RejectionReason::MalformedCreationTime(creation_time) => {
+                write!(f, "Malformed creation time: {}.", creation_time)
+            }
+            RejectionReason::InvalidInstruction(index, reason) => {
+                write!(f, "Invalid instruction {}: {}", index, reason)
+            }
+        }
+    }
+}
+
 impl AcceptedTransaction {
+    /// Total cost of the instructions, see `Instruction::cost`.
+    pub fn cost(&self) -> u64 {
+        self.payload.cost()
+    }
+
+    /// Check the transaction without the world state: every signature matches the payload,
+    /// no key signed it twice, the creation time is well formed and every instruction passes
+    /// `Instruction::validate`.
+    ///
+    /// Transactions forwarded by other peers are decoded as accepted without `accept`, so these
+    /// checks are repeated before the transactions are put into a block.
+    pub fn validate_stateless(&self) -> Result<(), RejectionReason> {
+        if self.payload.creation_time.parse::<u128>().is_err() {
+            return Err(RejectionReason::MalformedCreationTime(
+                self.payload.creation_time.clone(),
+            ));
+        }
+        for (index, instruction) in self.payload.instructions.iter().enumerate() {
+            instruction
+                .validate()
+                .map_err(|reason| RejectionReason::InvalidInstruction(index, reason))?;
+        }
+        let payload = Vec::from(&self.payload);
+        let mut public_keys = Vec::with_capacity(self.signatures.len());
+        for signature in &self.signatures {
+            if public_keys.contains(&signature.public_key) {
+                return Err(RejectionReason::DuplicateSignature(signature.public_key));
+            }
+            public_keys.push(signature.public_key);
+            //TODO: remove the fallback to signatures without context in the next release.
+            if signature
+                .verify_with_context(crypto::context::TRANSACTION, &payload)
+                .or_else(|_| signature.verify(&payload))
+                .is_err()
+            {
+                return Err(RejectionReason::InvalidSignature(signature.public_key));
+            }
+        }
+        Ok(())
+    }
+
+    /// Id of the account which created the transaction.
+    pub fn account_id(&self) -> &<Account as Identifiable>::Id {
+        &self.payload.account_id
+    }
+
+    /// Time of creation set by the creator (unix time, in milliseconds), zero if it is
+    /// malformed, see `validate_stateless`.
+    pub fn creation_time_ms(&self) -> u128 {
+        self.payload.creation_time.parse().unwrap_or(0)
+    }
+
+    /// Instructions of the transaction.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.payload.instructions
+    }
+
+    /// Whether the transaction carries at least one signature.
+    pub fn is_signed(&self) -> bool {
+        !self.signatures.is_empty()
+    }
+
+    /// Signatures of the transaction.
+    pub fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
     /// Sign transaction with the provided key pair.
     ///
     /// Returns `Ok(SignedTransaction)` if succeeded and `Err(String)` if failed.
@@ -86,11 +266,11 @@ impl AcceptedTransaction {
         public_key: &PublicKey,
         private_key: &PrivateKey,
     ) -> Result<SignedTransaction, String> {
-        let mut signatures = self.signatures.clone();
-        signatures.push(Signature::new(
-            *public_key,
+        let mut signatures = self.signatures;
+        signatures.push(Signature::new_with_context(
+            &KeyPair::new(*public_key, *private_key),
+            crypto::context::TRANSACTION,
             &Vec::from(&self.payload),
-            private_key,
         )?);
         Ok(SignedTransaction {
             payload: self.payload,
@@ -100,18 +280,7 @@ impl AcceptedTransaction {
 
     /// Calculate transaction `Hash`.
     pub fn hash(&self) -> Hash {
-        use ursa::blake2::{
-            digest::{Input, VariableOutput},
-            VarBlake2b,
-        };
-        let bytes: Vec<u8> = self.payload.clone().into();
-        let vec_hash = VarBlake2b::new(32)
-            .expect("Failed to initialize variable size hash")
-            .chain(bytes)
-            .vec_result();
-        let mut hash = [0; 32];
-        hash.copy_from_slice(&vec_hash);
-        hash
+        crypto::hash(self.payload.clone().into())
     }
 }
 
@@ -123,6 +292,22 @@ pub struct SignedTransaction {
 }
 
 impl SignedTransaction {
+    /// Id of the account which created the transaction.
+    pub fn account_id(&self) -> &<Account as Identifiable>::Id {
+        &self.payload.account_id
+    }
+
+    /// Instructions of the transaction.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.payload.instructions
+    }
+
+    /// Encode the transaction the way it is sent to peers, so it can be signed on an offline
+    /// device and submitted later without the private key.
+    pub fn to_signed_bytes(&self) -> Vec<u8> {
+        Vec::from(&RequestedTransaction::from(self))
+    }
+
     /// Add additional Signatures.
     pub fn sign(self, signatures: Vec<Signature>) -> Result<SignedTransaction, String> {
         Ok(SignedTransaction {
@@ -142,8 +327,43 @@ impl SignedTransaction {
         self,
         world_state_view: &mut WorldStateView,
     ) -> Result<ValidTransaction, String> {
-        for instruction in &self.payload.instructions {
-            instruction.execute(self.payload.account_id.clone(), world_state_view)?;
+        // Signatories are taken from the current state, so changes made by the previous
+        // transactions of the same block are taken into account.
+        let account = world_state_view
+            .read_account(&self.payload.account_id)
+            .ok_or(format!(
+                "Failed to find account {} of the transaction.",
+                self.payload.account_id
+            ))?;
+        let payload = Vec::from(&self.payload);
+        // Only signatures of the account's signatories count towards the quorum, a valid
+        // signature made by an unrelated key does not authorize the transaction.
+        let verified_signatures = self
+            .signatures
+            .iter()
+            .filter(|signature| account.signatories().contains(&signature.public_key))
+            .filter(|signature| {
+                signature
+                    .verify_with_context(crypto::context::TRANSACTION, &payload)
+                    .or_else(|_| signature.verify(&payload))
+                    .is_ok()
+            });
+        account.check_quorum(verified_signatures)?;
+        let cost = self.payload.cost();
+        if let Some(max_cost) = world_state_view.transaction_limits().max_cost {
+            if cost > max_cost {
+                return Err(format!(
+                    "Transaction cost {} exceeds the limit of {}.",
+                    cost, max_cost
+                ));
+            }
+        }
+        // Instructions are applied atomically, changes made before the first failure are
+        // rolled back so they are not seen by the following transactions.
+        let checkpoint = Checkpoint::save(&self, world_state_view);
+        if let Err(e) = self.execute(world_state_view) {
+            checkpoint.restore(world_state_view);
+            return Err(e);
         }
         Ok(ValidTransaction {
             payload: self.payload,
@@ -151,33 +371,46 @@ impl SignedTransaction {
         })
     }
 
+    fn execute(&self, world_state_view: &mut WorldStateView) -> Result<(), String> {
+        self.payload.charge_fee(world_state_view)?;
+        for instruction in &self.payload.instructions {
+            instruction.execute(self.payload.account_id.clone(), world_state_view)?;
+        }
+        Ok(())
+    }
+
     /// Calculate transaction `Hash`.
     pub fn hash(&self) -> Hash {
-        use ursa::blake2::{
-            digest::{Input, VariableOutput},
-            VarBlake2b,
-        };
-        let bytes: Vec<u8> = self.into();
-        let vec_hash = VarBlake2b::new(32)
-            .expect("Failed to initialize variable size hash")
-            .chain(bytes)
-            .vec_result();
-        let mut hash = [0; 32];
-        hash.copy_from_slice(&vec_hash);
-        hash
+        crypto::hash(self.into())
     }
 }
 
 /// `ValidTransaction` represents trustfull Transaction state.
-#[derive(Clone, Debug, Io, Encode, Decode)]
+#[derive(Clone, Debug, PartialEq, Eq, Io, Encode, Decode)]
 pub struct ValidTransaction {
     payload: Payload,
     signatures: Vec<Signature>,
 }
 
 impl ValidTransaction {
-    /// Apply instructions to the `WorldStateView`.
+    /// Calculate transaction `Hash`.
+    ///
+    /// Only the payload is hashed, so the result is equal to the hash of the
+    /// `AcceptedTransaction` this transaction originates from.
+    pub fn hash(&self) -> Hash {
+        crate::crypto::hash(self.payload.clone().into())
+    }
+
+    /// Instructions of the transaction in the order they are applied.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.payload.instructions
+    }
+
+    /// Charge the fee and apply instructions to the `WorldStateView`.
     pub fn proceed(&self, world_state_view: &mut WorldStateView) -> Result<(), String> {
+        if let Err(e) = self.payload.charge_fee(world_state_view) {
+            eprintln!("Failed to charge transaction fee on WSV: {}", e);
+        }
         for instruction in &self.payload.instructions {
             if let Err(e) = instruction.execute(self.payload.account_id.clone(), world_state_view) {
                 eprintln!("Failed to invoke instruction on WSV: {}", e);
@@ -213,6 +446,15 @@ impl From<SignedTransaction> for RequestedTransaction {
     }
 }
 
+impl From<ValidTransaction> for SignedTransaction {
+    fn from(transaction: ValidTransaction) -> SignedTransaction {
+        SignedTransaction {
+            payload: transaction.payload,
+            signatures: transaction.signatures,
+        }
+    }
+}
+
 impl From<&ValidTransaction> for RequestedTransaction {
     fn from(transaction: &ValidTransaction) -> RequestedTransaction {
         let transaction = transaction.clone();
@@ -222,3 +464,440 @@ impl From<&ValidTransaction> for RequestedTransaction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::PeerId;
+    use crate::{
+        account::isi::AccountInstruction,
+        isi::{Mint, RenameDomain, Transfer},
+    };
+    use std::collections::HashMap;
+
+    const DOMAIN_NAME: &str = "wonderland";
+
+    fn world_state_view_with_account(
+        account_id: &AccountId,
+        public_key: PublicKey,
+        fee_asset: Asset,
+        transaction_limits: TransactionLimits,
+    ) -> WorldStateView {
+        let mut fee_asset_definition =
+            AssetDefinition::new(fee_asset.id.definition_id.clone(), account_id.clone());
+        fee_asset_definition.total_supply = u128::from(fee_asset.quantity);
+        let mut asset_definitions = HashMap::new();
+        asset_definitions.insert(fee_asset_definition.id.clone(), fee_asset_definition);
+        let mut account = Account::new(&account_id.name, &account_id.domain_name, public_key);
+        account.assets.insert(fee_asset.id.clone(), fee_asset);
+        let mut accounts = HashMap::new();
+        accounts.insert(account_id.clone(), account);
+        let mut domains = HashMap::new();
+        domains.insert(
+            Name::new(DOMAIN_NAME),
+            Domain {
+                name: Name::from(DOMAIN_NAME),
+                accounts,
+                asset_definitions,
+            },
+        );
+        let mut world_state_view = WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key,
+            },
+            &Vec::new(),
+            domains,
+        ));
+        world_state_view.set_transaction_limits(transaction_limits);
+        world_state_view
+    }
+
+    fn fee_asset_definition_id() -> AssetDefinitionId {
+        AssetDefinitionId::new("xor", DOMAIN_NAME)
+    }
+
+    fn notifications(count: usize) -> Vec<Instruction> {
+        (0..count)
+            .map(|index| Instruction::Notify(index.to_string()))
+            .collect()
+    }
+
+    fn validate(
+        instructions: Vec<Instruction>,
+        transaction_limits: TransactionLimits,
+    ) -> (Result<ValidTransaction, String>, WorldStateView, AssetId) {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let fee_asset_id = AssetId {
+            definition_id: fee_asset_definition_id(),
+            account_id: account_id.clone(),
+        };
+        let mut world_state_view = world_state_view_with_account(
+            &account_id,
+            public_key,
+            Asset::with_quantity(fee_asset_id.clone(), 100),
+            transaction_limits,
+        );
+        let result = RequestedTransaction::new(instructions, account_id)
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.")
+            .validate(&mut world_state_view);
+        (result, world_state_view, fee_asset_id)
+    }
+
+    #[test]
+    fn statically_invalid_instructions_are_rejected_before_execution() {
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let asset_id = AssetId {
+            definition_id: fee_asset_definition_id(),
+            account_id: account_id.clone(),
+        };
+        let invalid_instructions: Vec<Instruction> = vec![
+            Transfer::new(
+                account_id.clone(),
+                Asset::with_quantity(asset_id.clone(), 10),
+                account_id.clone(),
+            )
+            .into(),
+            Transfer::new(
+                account_id.clone(),
+                Asset::with_quantity(asset_id.clone(), 0),
+                AccountId::new("bob", DOMAIN_NAME),
+            )
+            .into(),
+            Mint::new(0u32, asset_id.clone()).into(),
+            RenameDomain::new("", "looking-glass").into(),
+            Instruction::Compose(
+                Box::new(Instruction::Notify("valid".to_string())),
+                Box::new(Mint::new(0u32, asset_id.clone()).into()),
+            ),
+        ];
+        for instruction in invalid_instructions {
+            let transaction = RequestedTransaction::new(
+                vec![Instruction::Notify("valid".to_string()), instruction],
+                account_id.clone(),
+            )
+            .accept()
+            .expect("Failed to accept transaction.");
+            match transaction.validate_stateless() {
+                Err(RejectionReason::InvalidInstruction(1, _)) => (),
+                result => panic!("Unexpected validation result: {:?}", result),
+            }
+            assert!(PendingBlock::new(vec![transaction])
+                .without_invalid_transactions()
+                .transactions
+                .is_empty());
+        }
+        let valid_transaction =
+            RequestedTransaction::new(vec![Mint::new(10u32, asset_id).into()], account_id)
+                .accept()
+                .expect("Failed to accept transaction.");
+        assert_eq!(Ok(()), valid_transaction.validate_stateless());
+    }
+
+    #[test]
+    fn failed_instruction_rolls_back_the_whole_transaction() {
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_account(
+            &account_id,
+            [0; 32],
+            Asset::with_quantity(
+                AssetId {
+                    definition_id: fee_asset_definition_id(),
+                    account_id: account_id.clone(),
+                },
+                100,
+            ),
+            TransactionLimits {
+                fee_asset_definition_id: Some(fee_asset_definition_id()),
+                ..TransactionLimits::default()
+            },
+        );
+        let instructions = vec![
+            Instruction::Account(AccountInstruction::AddSignatory(
+                account_id.clone(),
+                [1; 32],
+            )),
+            Instruction::Account(AccountInstruction::SetQuorum(account_id.clone(), 2)),
+            Instruction::Account(AccountInstruction::SetQuorum(
+                AccountId::new("bob", DOMAIN_NAME),
+                2,
+            )),
+        ];
+        let transaction = RequestedTransaction::new(instructions, account_id.clone())
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&[0; 32], &[0; 64])
+            .expect("Failed to sign transaction.");
+        assert!(transaction.validate(&mut world_state_view).is_err());
+        let account = world_state_view
+            .read_account(&account_id)
+            .expect("Failed to find account.");
+        assert_eq!(&[[0; 32]], account.signatories());
+        assert_eq!(1, account.quorum);
+        assert_eq!(
+            100,
+            account.assets[&AssetId {
+                definition_id: fee_asset_definition_id(),
+                account_id: account_id.clone(),
+            }]
+                .quantity
+        );
+    }
+
+    #[test]
+    fn tampering_with_transfer_memo_invalidates_signature() {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let asset = Asset::with_quantity(
+            AssetId {
+                definition_id: fee_asset_definition_id(),
+                account_id: account_id.clone(),
+            },
+            10,
+        );
+        let transfer = |memo: &str| -> Instruction {
+            Transfer::<Account, Asset, Account>::new(
+                account_id.clone(),
+                asset.clone(),
+                AccountId::new("bob", DOMAIN_NAME),
+            )
+            .with_memo(memo)
+            .into()
+        };
+        let mut transaction =
+            RequestedTransaction::new(vec![transfer("invoice 1")], account_id.clone())
+                .accept()
+                .expect("Failed to accept transaction.")
+                .sign(&public_key, &private_key)
+                .expect("Failed to sign transaction.");
+        assert!(transaction.signatures[0]
+            .verify_with_context(
+                crypto::context::TRANSACTION,
+                &Vec::from(&transaction.payload)
+            )
+            .is_ok());
+        transaction.payload.instructions = vec![transfer("invoice 2")];
+        assert!(transaction.signatures[0]
+            .verify_with_context(
+                crypto::context::TRANSACTION,
+                &Vec::from(&transaction.payload)
+            )
+            .is_err());
+        let mut world_state_view = world_state_view_with_account(
+            &account_id,
+            public_key,
+            asset.clone(),
+            TransactionLimits::default(),
+        );
+        world_state_view
+            .account(&account_id)
+            .expect("Failed to find account.")
+            .quorum = 1;
+        assert!(transaction.validate(&mut world_state_view).is_err());
+    }
+
+    #[test]
+    fn only_signed_transactions_are_decoded_from_signed_bytes() {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let transaction = RequestedTransaction::new(notifications(1), account_id)
+            .accept()
+            .expect("Failed to accept transaction.");
+        let unsigned = Vec::from(&RequestedTransaction::from(&transaction));
+        assert!(RequestedTransaction::from_signed_bytes(&unsigned).is_err());
+        let signed = transaction
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.");
+        let bytes = signed.to_signed_bytes();
+        let mut decoded =
+            RequestedTransaction::from_signed_bytes(&bytes).expect("Failed to decode transaction.");
+        assert_eq!(RequestedTransaction::from(&signed).hash(), decoded.hash());
+        assert_eq!(bytes, Vec::from(&decoded));
+        decoded.payload.instructions = notifications(2);
+        assert!(RequestedTransaction::from_signed_bytes(&Vec::from(&decoded)).is_err());
+    }
+
+    #[test]
+    fn signature_of_non_signatory_does_not_count_towards_quorum() {
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let (other_public_key, other_private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_account(
+            &account_id,
+            public_key,
+            Asset::with_quantity(
+                AssetId {
+                    definition_id: fee_asset_definition_id(),
+                    account_id: account_id.clone(),
+                },
+                100,
+            ),
+            TransactionLimits::default(),
+        );
+        let transaction = RequestedTransaction::new(notifications(1), account_id)
+            .accept()
+            .expect("Failed to accept transaction.");
+        let signed_by_other_key = transaction
+            .clone()
+            .sign(&other_public_key, &other_private_key)
+            .expect("Failed to sign transaction.");
+        assert!(signed_by_other_key.signatures[0]
+            .verify_with_context(
+                crypto::context::TRANSACTION,
+                &Vec::from(&signed_by_other_key.payload)
+            )
+            .is_ok());
+        assert!(signed_by_other_key
+            .clone()
+            .validate(&mut world_state_view)
+            .is_err());
+        assert!(signed_by_other_key
+            .sign(vec![Signature::new_with_context(
+                &KeyPair::new(public_key, private_key),
+                crypto::context::TRANSACTION,
+                &Vec::from(&transaction.payload)
+            )
+            .expect("Failed to sign transaction.")])
+            .expect("Failed to add signature.")
+            .validate(&mut world_state_view)
+            .is_ok());
+    }
+
+    #[test]
+    fn default_account_does_not_accept_signature_of_foreign_key() {
+        let (public_key, _) = crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let (foreign_public_key, foreign_private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let account_id = AccountId::new("alice", DOMAIN_NAME);
+        let mut world_state_view = world_state_view_with_account(
+            &account_id,
+            public_key,
+            Asset::with_quantity(
+                AssetId {
+                    definition_id: fee_asset_definition_id(),
+                    account_id: account_id.clone(),
+                },
+                100,
+            ),
+            TransactionLimits::default(),
+        );
+        let result = RequestedTransaction::new(notifications(1), account_id)
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&foreign_public_key, &foreign_private_key)
+            .expect("Failed to sign transaction.")
+            .validate(&mut world_state_view);
+        assert_eq!(
+            Some("Account alice@wonderland requires 1 signatures of its signatories, but got 0."),
+            result.err().as_deref()
+        );
+    }
+
+    #[test]
+    fn transaction_within_cost_limit_is_valid() {
+        let (result, _, _) = validate(
+            notifications(3),
+            TransactionLimits {
+                max_cost: Some(5),
+                fee_asset_definition_id: None,
+            },
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn transaction_exceeding_cost_limit_is_rejected() {
+        let (result, _, _) = validate(
+            vec![Instruction::Compose(
+                Box::new(Instruction::Notify("first".to_string())),
+                Box::new(Instruction::If(
+                    Box::new(Instruction::Notify("condition".to_string())),
+                    Box::new(Instruction::Notify("then".to_string())),
+                    None,
+                )),
+            )]
+            .into_iter()
+            .chain(notifications(3))
+            .collect(),
+            TransactionLimits {
+                max_cost: Some(5),
+                fee_asset_definition_id: None,
+            },
+        );
+        assert_eq!(
+            Err("Transaction cost 6 exceeds the limit of 5.".to_string()),
+            result.map(|_| ())
+        );
+    }
+
+    #[test]
+    fn mint_to_many_accounts_costs_per_recipient() {
+        let definition_id = AssetDefinitionId::new("xor", DOMAIN_NAME);
+        let recipients = ["alice", "bob", "carol"]
+            .iter()
+            .map(|name| (AccountId::new(name, DOMAIN_NAME), 1))
+            .collect();
+        let (result, _, _) = validate(
+            vec![Instruction::Asset(
+                crate::asset::isi::AssetInstruction::MintAssetToMany(definition_id, recipients),
+            )],
+            TransactionLimits {
+                max_cost: Some(5),
+                fee_asset_definition_id: None,
+            },
+        );
+        assert_eq!(
+            Err("Transaction cost 6 exceeds the limit of 5.".to_string()),
+            result.map(|_| ())
+        );
+    }
+
+    #[test]
+    fn fee_is_debited_from_fee_asset() {
+        let instructions = notifications(3);
+        let cost: u64 = instructions.iter().map(Instruction::cost).sum();
+        let (result, mut world_state_view, fee_asset_id) = validate(
+            instructions,
+            TransactionLimits {
+                max_cost: None,
+                fee_asset_definition_id: Some(fee_asset_definition_id()),
+            },
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            100 - cost as u32,
+            world_state_view
+                .asset(&fee_asset_id)
+                .expect("Failed to find fee asset.")
+                .quantity
+        );
+        assert_eq!(
+            100 - u128::from(cost),
+            world_state_view
+                .read_asset_definition(&fee_asset_id.definition_id)
+                .expect("Failed to find fee asset definition.")
+                .total_supply
+        );
+    }
+
+    #[test]
+    fn transaction_without_funds_for_fee_is_rejected() {
+        let (result, _, _) = validate(
+            notifications(101),
+            TransactionLimits {
+                max_cost: None,
+                fee_asset_definition_id: Some(fee_asset_definition_id()),
+            },
+        );
+        assert!(result.is_err());
+    }
+}
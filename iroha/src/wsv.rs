@@ -1,22 +1,88 @@
 //! This module provides `WorldStateView` - in-memory representations of the current blockchain
 //! state.
 
-use crate::prelude::*;
+use crate::{
+    block::BlockSchedule,
+    crypto,
+    event::DataEvent,
+    peer::{self, PeerId, PeerStatus},
+    prelude::*,
+    tx::TransactionLimits,
+};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Maximum number of the latest events kept for subscribers.
+const MAX_EVENTS: usize = 10_000;
+/// Number of blocks between snapshots of the state used to reconstruct historical states.
+const STATE_SNAPSHOT_INTERVAL: u64 = 100;
+/// Maximum number of the latest snapshots kept, older states can not be reconstructed.
+const MAX_STATE_SNAPSHOTS: usize = 10;
 
 /// Current state of the blockchain alligned with `Iroha` module.
 #[derive(Debug, Clone)]
 pub struct WorldStateView {
     peer: Peer,
     blocks: Vec<CommittedBlock>,
+    events: VecDeque<DataEvent>,
+    /// Index of the first event in `events`, older events were dropped.
+    events_offset: u64,
+    transaction_limits: TransactionLimits,
+    block_schedule: BlockSchedule,
+    /// Number of transactions waiting in the queue of the peer, updated by the queue itself.
+    pending_transactions: Arc<AtomicUsize>,
+    /// Whether the peer stopped building new blocks, set by `Iroha::pause_block_production`.
+    block_production_paused: Arc<AtomicBool>,
+    /// Statuses of the other known peers, updated by `Iroha` polling them.
+    peer_statuses: Arc<Mutex<HashMap<PeerId, PeerStatus>>>,
+    /// Heights and states after every `STATE_SNAPSHOT_INTERVAL` blocks, only the latest
+    /// `MAX_STATE_SNAPSHOTS` are kept and shared between the clones of the view.
+    snapshots: VecDeque<(u64, Arc<Peer>)>,
 }
 
 impl WorldStateView {
     /// Default `WorldStateView` constructor.
     pub fn new(peer: Peer) -> Self {
         WorldStateView {
+            snapshots: vec![(0, Arc::new(peer.clone()))].into(),
             peer,
             blocks: Vec::new(),
+            events: VecDeque::new(),
+            events_offset: 0,
+            transaction_limits: TransactionLimits::default(),
+            block_schedule: BlockSchedule::default(),
+            pending_transactions: Arc::new(AtomicUsize::new(0)),
+            block_production_paused: Arc::new(AtomicBool::new(false)),
+            peer_statuses: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// `WorldStateView` constructor which checks that the bootstrap peer is well formed and
+    /// that all the initial accounts have valid public keys, so they can sign transactions.
+    pub fn try_new(peer: Peer) -> Result<Self, String> {
+        peer.id
+            .validate()
+            .map_err(|e| format!("Invalid bootstrap peer: {}", e))?;
+        for account in peer
+            .domains
+            .values()
+            .flat_map(|domain| domain.accounts.values())
+        {
+            for public_key in account.signatories() {
+                crypto::validate_public_key(public_key).map_err(|e| {
+                    format!(
+                        "Invalid public key of the initial account {}: {}",
+                        account.id, e
+                    )
+                })?;
+            }
         }
+        Ok(WorldStateView::new(peer))
     }
 
     /// Put `ValidBlock` of information with changes in form of **Iroha Special Instructions**
@@ -28,6 +94,18 @@ impl WorldStateView {
             }
         }
         self.blocks.push(block.clone());
+        self.execute_listeners();
+        let last_snapshot_height = self.snapshots.back().map_or(0, |(height, _)| *height);
+        if self.height() == last_snapshot_height + STATE_SNAPSHOT_INTERVAL {
+            if self.snapshots.len() >= MAX_STATE_SNAPSHOTS {
+                self.snapshots.pop_front();
+            }
+            self.snapshots
+                .push_back((self.height(), Arc::new(self.peer.clone())));
+        }
+    }
+
+    fn execute_listeners(&mut self) {
         for listener in self.peer.listeners.clone() {
             if let Err(e) = listener.execute(self.peer.authority(), self) {
                 eprintln!("Failed to execute listener on WSV: {}", e);
@@ -35,6 +113,147 @@ impl WorldStateView {
         }
     }
 
+    /// Reconstruct the state right after the first `height` blocks were put into the world.
+    ///
+    /// Blocks are replayed starting from the nearest snapshot below the `height`, so only the
+    /// states above the oldest kept snapshot can be reconstructed. The reconstructed view
+    /// contains no events.
+    pub fn at_height(&self, height: u64) -> Result<WorldStateView, String> {
+        if height > self.height() {
+            return Err(format!(
+                "State at height {} is not known yet, current height is {}.",
+                height,
+                self.height()
+            ));
+        }
+        let (snapshot_height, snapshot) = self
+            .snapshots
+            .iter()
+            .rev()
+            .find(|(snapshot_height, _)| *snapshot_height <= height)
+            .ok_or(format!("No state snapshot found below height {}.", height))?;
+        let snapshot_height = *snapshot_height as usize;
+        let mut world_state_view = WorldStateView::new(Peer::clone(snapshot));
+        world_state_view.snapshots = self
+            .snapshots
+            .iter()
+            .take_while(|(height, _)| *height as usize <= snapshot_height)
+            .cloned()
+            .collect();
+        world_state_view.transaction_limits = self.transaction_limits.clone();
+        world_state_view.blocks = self.blocks[..snapshot_height].to_vec();
+        for block in &self.blocks[snapshot_height..height as usize] {
+            for transaction in &block.transactions {
+                if let Err(e) = transaction.proceed(&mut world_state_view) {
+                    eprintln!("Failed to procced transaction on WSV: {}", e);
+                }
+            }
+            world_state_view.blocks.push(block.clone());
+            world_state_view.execute_listeners();
+        }
+        world_state_view.events.clear();
+        Ok(world_state_view)
+    }
+
+    /// Get `CommittedBlock` at the given height without an ability to modify it.
+    pub fn read_block(&self, height: u64) -> Option<&CommittedBlock> {
+        self.blocks.get(height as usize)
+    }
+
+    /// Events emitted starting from the `from_index` and the index of the next event.
+    ///
+    /// Only the latest events are kept, so events older than them are skipped.
+    pub fn read_events(&self, from_index: u64) -> (impl Iterator<Item = &DataEvent>, u64) {
+        let skip = from_index.saturating_sub(self.events_offset) as usize;
+        (
+            self.events.iter().skip(skip),
+            self.events_offset + self.events.len() as u64,
+        )
+    }
+
+    /// Emit the `event` to the subscribers, the oldest event is dropped if there are too many.
+    pub(crate) fn push_event(&mut self, event: DataEvent) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.pop_front();
+            self.events_offset += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// Get `TransactionLimits` applied during transactions validation.
+    pub fn transaction_limits(&self) -> &TransactionLimits {
+        &self.transaction_limits
+    }
+
+    /// Set `TransactionLimits` - will overwrite the existing ones.
+    pub fn set_transaction_limits(&mut self, transaction_limits: TransactionLimits) {
+        self.transaction_limits = transaction_limits;
+    }
+
+    /// Get `BlockSchedule` the peer builds blocks with.
+    pub fn block_schedule(&self) -> &BlockSchedule {
+        &self.block_schedule
+    }
+
+    /// Set `BlockSchedule` - will overwrite the existing one.
+    pub fn set_block_schedule(&mut self, block_schedule: BlockSchedule) {
+        self.block_schedule = block_schedule;
+    }
+
+    /// Number of transactions waiting in the queue to be put into a block.
+    pub fn pending_transactions(&self) -> usize {
+        self.pending_transactions.load(Ordering::Relaxed)
+    }
+
+    /// Counter of the pending transactions which should be updated by the queue.
+    pub(crate) fn pending_transactions_counter(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.pending_transactions)
+    }
+
+    /// Whether the peer stopped building new blocks.
+    pub fn block_production_paused(&self) -> bool {
+        self.block_production_paused.load(Ordering::Relaxed)
+    }
+
+    /// Flag of the paused block production which should be updated by `Iroha`.
+    pub(crate) fn block_production_paused_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.block_production_paused)
+    }
+
+    /// Status of the current peer followed by the statuses of the other known peers ordered by
+    /// address. Peers which were not polled yet are reported with unknown height.
+    pub fn network_status(&self) -> Vec<PeerStatus> {
+        let peer_statuses = self
+            .peer_statuses
+            .lock()
+            .expect("Failed to lock peer statuses.");
+        let mut peers: Vec<&PeerId> = self.peer.peers.iter().collect();
+        peers.sort_by(|left, right| left.address.cmp(&right.address));
+        let mut network_status = vec![PeerStatus {
+            peer_id: self.peer.id.clone(),
+            height: Some(self.height()),
+            last_seen_ms: Some(peer::now_ms()),
+            reachable: true,
+        }];
+        network_status.extend(peers.into_iter().map(|peer_id| {
+            peer_statuses
+                .get(peer_id)
+                .cloned()
+                .unwrap_or_else(|| PeerStatus::unknown(peer_id.clone()))
+        }));
+        network_status
+    }
+
+    /// Statuses of the other known peers which should be updated by `Iroha`.
+    pub(crate) fn peer_statuses(&self) -> Arc<Mutex<HashMap<PeerId, PeerStatus>>> {
+        Arc::clone(&self.peer_statuses)
+    }
+
+    /// Number of blocks which were put into the `WorldStateView`.
+    pub fn height(&self) -> u64 {
+        self.blocks.len() as u64
+    }
+
     /// Get `Peer` without an ability to modify it.
     pub fn read_peer(&self) -> &Peer {
         &self.peer
@@ -60,6 +279,23 @@ impl WorldStateView {
         self.peer.domains.get_mut(name)
     }
 
+    /// Iterate over all `Domain`s without cloning them.
+    pub fn domains_iter(&self) -> impl Iterator<Item = &Domain> {
+        self.peer.domains.values()
+    }
+
+    /// Iterate over `Account`s of all domains without cloning them.
+    pub fn accounts_iter(&self) -> impl Iterator<Item = &Account> {
+        self.domains_iter()
+            .flat_map(|domain| domain.accounts.values())
+    }
+
+    /// Iterate over `Asset`s of all accounts without cloning them.
+    pub fn assets_iter(&self) -> impl Iterator<Item = &Asset> {
+        self.accounts_iter()
+            .flat_map(|account| account.assets.values())
+    }
+
     /// Get `Account` without an ability to modify it.
     pub fn read_account(&self, id: &<Account as Identifiable>::Id) -> Option<&Account> {
         self.read_domain(&id.domain_name)?.accounts.get(id)
@@ -71,8 +307,8 @@ impl WorldStateView {
     }
 
     /// Get `Asset` without an ability to modify it.
-    pub fn read_asset(&mut self, id: &<Asset as Identifiable>::Id) -> Option<&mut Asset> {
-        self.account(&id.account_id)?.assets.get_mut(id)
+    pub fn read_asset(&self, id: &<Asset as Identifiable>::Id) -> Option<&Asset> {
+        self.read_account(&id.account_id)?.assets.get(id)
     }
 
     /// Get `Asset` with an ability to modify it.
@@ -103,6 +339,169 @@ impl WorldStateView {
     ) -> Option<&mut AssetDefinition> {
         self.domain(&id.domain_name)?.asset_definitions.get_mut(id)
     }
+
+    /// Compute changes of domains, accounts and assets which turn this view into the `other`.
+    ///
+    /// Entities nested into an added or removed entity are reported as added or removed too.
+    pub fn diff(&self, other: &WorldStateView) -> StateDiff {
+        let mut changes = Vec::new();
+        for (name, (before, after)) in pair_by_key(&self.peer.domains, &other.peer.domains) {
+            let empty_domain = Domain::new(name.clone());
+            match (before, after) {
+                (None, Some(after)) => {
+                    changes.push(StateChange::DomainAdded(name.to_string()));
+                    diff_domains(&empty_domain, after, &mut changes);
+                }
+                (Some(before), None) => {
+                    diff_domains(before, &empty_domain, &mut changes);
+                    changes.push(StateChange::DomainRemoved(name.to_string()));
+                }
+                (Some(before), Some(after)) => diff_domains(before, after, &mut changes),
+                (None, None) => (),
+            }
+        }
+        StateDiff { changes }
+    }
+}
+
+/// Change of a single entity between two `WorldStateView`s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateChange {
+    /// `Domain` with the name was added.
+    DomainAdded(String),
+    /// `Domain` with the name was removed.
+    DomainRemoved(String),
+    /// `AssetDefinition` with the id was registered.
+    AssetDefinitionAdded(AssetDefinitionId),
+    /// `AssetDefinition` with the id was removed.
+    AssetDefinitionRemoved(AssetDefinitionId),
+    /// `AssetDefinition` with the id was changed, e.g. its owner.
+    AssetDefinitionModified(AssetDefinitionId),
+    /// `Account` with the id was registered.
+    AccountAdded(AccountId),
+    /// `Account` with the id was removed.
+    AccountRemoved(AccountId),
+    /// `Account` with the id was changed, e.g. its signatories. Changes of the account's assets
+    /// are reported separately.
+    AccountModified(AccountId),
+    /// `Asset` was added to the account.
+    AssetAdded {
+        /// Account which holds the asset.
+        account_id: AccountId,
+        /// Added asset.
+        asset: Asset,
+    },
+    /// `Asset` was removed from the account.
+    AssetRemoved {
+        /// Account which held the asset.
+        account_id: AccountId,
+        /// Removed asset.
+        asset: Asset,
+    },
+    /// `Asset` of the account was changed, e.g. its quantity.
+    AssetModified {
+        /// Account which holds the asset.
+        account_id: AccountId,
+        /// Asset before the change.
+        before: Asset,
+        /// Asset after the change.
+        after: Asset,
+    },
+}
+
+/// Result of `WorldStateView::diff` - changes ordered by domain, then by entity id.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Changes of the entities.
+    pub changes: Vec<StateChange>,
+}
+
+impl StateDiff {
+    /// Check whether views were equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Pair values of the two maps with the same keys, ordered by key.
+fn pair_by_key<'a, K, V, B, A>(
+    before: B,
+    after: A,
+) -> BTreeMap<&'a K, (Option<&'a V>, Option<&'a V>)>
+where
+    K: Ord + 'a,
+    V: 'a,
+    B: IntoIterator<Item = (&'a K, &'a V)>,
+    A: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    let mut pairs = BTreeMap::new();
+    for (key, value) in before {
+        pairs.entry(key).or_insert((None, None)).0 = Some(value);
+    }
+    for (key, value) in after {
+        pairs.entry(key).or_insert((None, None)).1 = Some(value);
+    }
+    pairs
+}
+
+fn diff_domains(before: &Domain, after: &Domain, changes: &mut Vec<StateChange>) {
+    for (id, pair) in pair_by_key(&before.asset_definitions, &after.asset_definitions) {
+        match pair {
+            (None, Some(_)) => changes.push(StateChange::AssetDefinitionAdded(id.clone())),
+            (Some(_), None) => changes.push(StateChange::AssetDefinitionRemoved(id.clone())),
+            (Some(before), Some(after)) if before != after => {
+                changes.push(StateChange::AssetDefinitionModified(id.clone()))
+            }
+            _ => (),
+        }
+    }
+    for (id, pair) in pair_by_key(&before.accounts, &after.accounts) {
+        let empty_account = Account::new(&id.name, &id.domain_name, [0; 32]);
+        match pair {
+            (None, Some(after)) => {
+                changes.push(StateChange::AccountAdded(id.clone()));
+                diff_assets(&empty_account, after, changes);
+            }
+            (Some(before), None) => {
+                diff_assets(before, &empty_account, changes);
+                changes.push(StateChange::AccountRemoved(id.clone()));
+            }
+            (Some(before), Some(after)) => {
+                if before.signatories() != after.signatories()
+                    || before.quorum != after.quorum
+                    || before.metadata != after.metadata
+                {
+                    changes.push(StateChange::AccountModified(id.clone()));
+                }
+                diff_assets(before, after, changes);
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+fn diff_assets(before: &Account, after: &Account, changes: &mut Vec<StateChange>) {
+    for (_, pair) in pair_by_key(&before.assets, &after.assets) {
+        let account_id = after.id.clone();
+        match pair {
+            (None, Some(asset)) => changes.push(StateChange::AssetAdded {
+                account_id,
+                asset: asset.clone(),
+            }),
+            (Some(asset), None) => changes.push(StateChange::AssetRemoved {
+                account_id,
+                asset: asset.clone(),
+            }),
+            (Some(before), Some(after)) if before != after => {
+                changes.push(StateChange::AssetModified {
+                    account_id,
+                    before: before.clone(),
+                    after: after.clone(),
+                })
+            }
+            _ => (),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -110,10 +509,145 @@ mod tests {
     use super::*;
     use crate::{
         block::BlockHeader,
+        isi::{Burn, Mint, Register, Transfer},
         peer::{Peer, PeerId},
     };
     use std::collections::HashMap;
 
+    #[test]
+    fn bootstrap_peer_is_validated() {
+        let (public_key, _) = crate::crypto::generate_key_pair().expect("Failed to generate key.");
+        let peer_id =
+            PeerId::try_new("127.0.0.1:1337", &public_key).expect("Failed to create peer id.");
+        assert!(WorldStateView::try_new(Peer::new(peer_id.clone(), &[])).is_ok());
+        let host_peer_id =
+            PeerId::try_new("localhost:1337", &public_key).expect("Failed to create peer id.");
+        assert!(WorldStateView::try_new(Peer::new(host_peer_id, &[])).is_ok());
+        for address in &[
+            "127.0.0.1",
+            "127.0.0.1:port",
+            ":1337",
+            "127.0.0.1:70000",
+            "",
+        ] {
+            assert!(PeerId::try_new(address, &public_key).is_err());
+            let malformed_peer_id = PeerId {
+                address: address.to_string(),
+                ..peer_id.clone()
+            };
+            assert!(WorldStateView::try_new(Peer::new(malformed_peer_id, &[])).is_err());
+        }
+        assert!(PeerId::try_new("127.0.0.1:1337", &public_key[..31]).is_err());
+        assert!(PeerId::try_new("127.0.0.1:1337", &[0; 33]).is_err());
+    }
+
+    #[test]
+    fn initial_accounts_with_malformed_keys_are_rejected() {
+        let (public_key, _) = crate::crypto::generate_key_pair().expect("Failed to generate key.");
+        let peer_id =
+            PeerId::try_new("127.0.0.1:1337", &public_key).expect("Failed to create peer id.");
+        let peer_with_account = |account_public_key| {
+            let mut domain = Domain::new("wonderland".to_string());
+            domain.accounts.insert(
+                AccountId::new("alice", "wonderland"),
+                Account::new("alice", "wonderland", account_public_key),
+            );
+            let mut domains = HashMap::new();
+            domains.insert(domain.name.clone(), domain);
+            Peer::with_domains(peer_id.clone(), &[], domains)
+        };
+        assert!(WorldStateView::try_new(peer_with_account(public_key)).is_ok());
+        let off_curve_key = (0..=u8::MAX)
+            .map(|byte| [byte; 32])
+            .find(|key| {
+                curve25519_dalek::edwards::CompressedEdwardsY(*key)
+                    .decompress()
+                    .is_none()
+            })
+            .expect("Failed to find a key off the curve.");
+        for malformed_key in &[off_curve_key, [0; 32]] {
+            let error = WorldStateView::try_new(peer_with_account(*malformed_key))
+                .expect_err("Account with malformed key should be rejected.");
+            assert!(error.contains("alice@wonderland"));
+        }
+    }
+
+    #[test]
+    fn accounts_and_assets_are_looked_up_across_domains() {
+        let peer_id = PeerId {
+            address: "127.0.0.1:1337".to_string(),
+            public_key: [0; 32],
+        };
+        let mut world_state_view = WorldStateView::new(Peer::new(peer_id, &[]));
+        let alice_id = AccountId::new("alice", "wonderland");
+        let bob_id = AccountId::new("bob", "garden");
+        let xor_id = AssetDefinitionId::new("xor", "wonderland");
+        for account_id in &[&alice_id, &bob_id] {
+            let mut domain = Domain::new(account_id.domain_name.to_string());
+            domain.accounts.insert(
+                (*account_id).clone(),
+                Account::new(&account_id.name, &account_id.domain_name, [0; 32]),
+            );
+            world_state_view.add_domain(domain);
+        }
+        let bob_xor_id = AssetId {
+            definition_id: xor_id.clone(),
+            account_id: bob_id.clone(),
+        };
+        world_state_view.add_asset(Asset::with_quantity(bob_xor_id.clone(), 10));
+        assert_eq!(
+            Some(&alice_id),
+            world_state_view
+                .read_account(&alice_id)
+                .map(|account| &account.id)
+        );
+        assert_eq!(
+            Some(&bob_id),
+            world_state_view.account(&bob_id).map(|account| &account.id)
+        );
+        for absent_account_id in &[
+            AccountId::new("alice", "garden"),
+            AccountId::new("carol", "wonderland"),
+            AccountId::new("alice", "looking-glass"),
+        ] {
+            assert!(world_state_view.read_account(absent_account_id).is_none());
+            assert!(world_state_view.account(absent_account_id).is_none());
+        }
+        assert_eq!(
+            Some(10),
+            world_state_view
+                .read_asset(&bob_xor_id)
+                .map(|asset| asset.quantity)
+        );
+        world_state_view
+            .asset(&bob_xor_id)
+            .expect("Failed to find asset.")
+            .quantity = 20;
+        assert_eq!(
+            Some(20),
+            world_state_view
+                .read_asset(&bob_xor_id)
+                .map(|asset| asset.quantity)
+        );
+        for absent_asset_id in &[
+            AssetId {
+                definition_id: xor_id.clone(),
+                account_id: alice_id.clone(),
+            },
+            AssetId {
+                definition_id: AssetDefinitionId::new("rose", "garden"),
+                account_id: bob_id.clone(),
+            },
+            AssetId {
+                definition_id: xor_id,
+                account_id: AccountId::new("bob", "wonderland"),
+            },
+        ] {
+            assert!(world_state_view.read_asset(absent_asset_id).is_none());
+            assert!(world_state_view.asset(absent_asset_id).is_none());
+        }
+    }
+
     #[async_std::test]
     async fn test_listeners() {
         let block = CommittedBlock {
@@ -126,12 +660,15 @@ mod tests {
             transactions: Vec::new(),
             signatures: Vec::new(),
         };
-        let domain_name = "global".to_string();
+        let domain_name = Name::new("global");
         let mut asset_definitions = HashMap::new();
         let asset_definition_id = crate::permission::permission_asset_definition_id();
         asset_definitions.insert(
             asset_definition_id.clone(),
-            AssetDefinition::new(asset_definition_id.clone()),
+            AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::new("root", "global"),
+            ),
         );
         let public_key = [0; 32];
         let account_id = AccountId::new("root", &domain_name);
@@ -170,11 +707,299 @@ mod tests {
             Box::new(peer.add_domain(Domain::new("Test".to_string())).into()),
             None,
         ));
-        let mut world_state_view = WorldStateView {
-            peer,
-            blocks: Vec::new(),
-        };
+        let mut world_state_view = WorldStateView::new(peer);
         world_state_view.put(&block).await;
         assert!(world_state_view.domain("Test").is_some());
     }
+
+    fn world_state_view_with_account_asset(
+        account_id: &AccountId,
+        asset_id: &AssetId,
+        quantity: u32,
+    ) -> WorldStateView {
+        let public_key = [0; 32];
+        let root_id = AccountId::new("root", "global");
+        let permission_asset_id = AssetId {
+            definition_id: crate::permission::permission_asset_definition_id(),
+            account_id: root_id.clone(),
+        };
+        let mut root = Account::new(&root_id.name, &root_id.domain_name, public_key);
+        root.assets.insert(
+            permission_asset_id.clone(),
+            Asset::with_permission(
+                permission_asset_id,
+                ("anything".to_string(), "".to_string()),
+            ),
+        );
+        let mut account = Account::new(&account_id.name, &account_id.domain_name, public_key);
+        account.assets.insert(
+            asset_id.clone(),
+            Asset::with_quantity(asset_id.clone(), quantity),
+        );
+        let mut world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key,
+            },
+            &Vec::new(),
+        ));
+        let mut global = Domain::new("global".to_string());
+        global.accounts.insert(root_id, root);
+        world_state_view.add_domain(global);
+        let mut domain = Domain::new(account_id.domain_name.to_string());
+        domain.accounts.insert(account_id.clone(), account);
+        world_state_view.add_domain(domain);
+        world_state_view
+    }
+
+    #[test]
+    fn diff_after_account_registration() {
+        let alice_id = AccountId::new("alice", "wonderland");
+        let asset_id = AssetId {
+            definition_id: AssetDefinitionId::new("xor", "wonderland"),
+            account_id: alice_id.clone(),
+        };
+        let world_state_view = world_state_view_with_account_asset(&alice_id, &asset_id, 100);
+        let mut changed_world_state_view = world_state_view.clone();
+        Instruction::from(Register::<Domain, Account> {
+            object: Account::new("bob", "wonderland", [0; 32]),
+            destination_id: Name::from("wonderland"),
+        })
+        .execute(
+            AccountId::new("root", "global"),
+            &mut changed_world_state_view,
+        )
+        .expect("Failed to register account.");
+        assert!(world_state_view.diff(&world_state_view).is_empty());
+        assert_eq!(
+            vec![StateChange::AccountAdded(AccountId::new(
+                "bob",
+                "wonderland"
+            ))],
+            world_state_view.diff(&changed_world_state_view).changes
+        );
+        assert_eq!(
+            vec![StateChange::AccountRemoved(AccountId::new(
+                "bob",
+                "wonderland"
+            ))],
+            changed_world_state_view.diff(&world_state_view).changes
+        );
+    }
+
+    #[test]
+    fn diff_after_transfer() {
+        let alice_id = AccountId::new("alice", "wonderland");
+        let bob_id = AccountId::new("bob", "wonderland");
+        let asset_id = AssetId {
+            definition_id: AssetDefinitionId::new("xor", "wonderland"),
+            account_id: alice_id.clone(),
+        };
+        let mut world_state_view = world_state_view_with_account_asset(&alice_id, &asset_id, 100);
+        world_state_view
+            .domain("wonderland")
+            .expect("Failed to find domain.")
+            .accounts
+            .insert(bob_id.clone(), Account::new("bob", "wonderland", [0; 32]));
+        let mut changed_world_state_view = world_state_view.clone();
+        let transferred = Asset::with_quantity(asset_id.clone(), 20);
+        Instruction::from(Transfer::<Account, Asset, Account>::new(
+            alice_id.clone(),
+            transferred.clone(),
+            bob_id.clone(),
+        ))
+        .execute(
+            AccountId::new("root", "global"),
+            &mut changed_world_state_view,
+        )
+        .expect("Failed to transfer asset.");
+        assert_eq!(
+            vec![
+                StateChange::AssetModified {
+                    account_id: alice_id,
+                    before: Asset::with_quantity(asset_id.clone(), 100),
+                    after: Asset::with_quantity(asset_id, 80),
+                },
+                StateChange::AssetAdded {
+                    account_id: bob_id,
+                    asset: transferred,
+                },
+            ],
+            world_state_view.diff(&changed_world_state_view).changes
+        );
+    }
+
+    #[async_std::test]
+    async fn state_at_height_is_replayed_from_snapshot() {
+        let (public_key, private_key) =
+            crate::crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let alice_id = AccountId::new("alice", "wonderland");
+        let asset_definition_id = AssetDefinitionId::new("xor", "wonderland");
+        let asset_id = AssetId {
+            definition_id: asset_definition_id.clone(),
+            account_id: alice_id.clone(),
+        };
+        let mut domain = Domain::new("wonderland".to_string());
+        domain.accounts.insert(
+            alice_id.clone(),
+            Account::new(&alice_id.name, &alice_id.domain_name, public_key),
+        );
+        domain.asset_definitions.insert(
+            asset_definition_id.clone(),
+            AssetDefinition::new(asset_definition_id, alice_id.clone()),
+        );
+        let mut domains = HashMap::new();
+        domains.insert(domain.name.clone(), domain);
+        let mut world_state_view = WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key,
+            },
+            &Vec::new(),
+            domains,
+        ));
+        for height in 0..150 {
+            let transaction = RequestedTransaction::new(
+                vec![Mint::<Asset, u32>::new(1, asset_id.clone()).into()],
+                alice_id.clone(),
+            )
+            .accept()
+            .expect("Failed to accept transaction.")
+            .sign(&public_key, &private_key)
+            .expect("Failed to sign transaction.")
+            .validate(&mut world_state_view.clone())
+            .expect("Failed to validate transaction.");
+            world_state_view
+                .put(&CommittedBlock {
+                    header: BlockHeader {
+                        timestamp: 0,
+                        height,
+                        previous_block_hash: [0; 32],
+                        merkle_root_hash: [0; 32],
+                    },
+                    transactions: vec![transaction],
+                    signatures: Vec::new(),
+                })
+                .await;
+        }
+        for height in &[0, 50, 100, 120, 150] {
+            let mut state = world_state_view
+                .at_height(*height)
+                .expect("Failed to reconstruct state.");
+            assert_eq!(*height, state.height());
+            assert_eq!(
+                *height as u32,
+                state.asset(&asset_id).map_or(0, |asset| asset.quantity)
+            );
+        }
+        assert!(world_state_view.at_height(151).is_err());
+    }
+
+    #[async_std::test]
+    async fn only_latest_state_snapshots_are_kept() {
+        let mut world_state_view = WorldStateView::new(Peer::new(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key: [0; 32],
+            },
+            &Vec::new(),
+        ));
+        let blocks_count = STATE_SNAPSHOT_INTERVAL * MAX_STATE_SNAPSHOTS as u64 + 1;
+        for height in 0..blocks_count {
+            world_state_view
+                .put(&CommittedBlock {
+                    header: BlockHeader {
+                        timestamp: 0,
+                        height,
+                        previous_block_hash: [0; 32],
+                        merkle_root_hash: [0; 32],
+                    },
+                    transactions: Vec::new(),
+                    signatures: Vec::new(),
+                })
+                .await;
+        }
+        assert_eq!(MAX_STATE_SNAPSHOTS, world_state_view.snapshots.len());
+        assert!(world_state_view
+            .at_height(STATE_SNAPSHOT_INTERVAL - 1)
+            .is_err());
+        assert_eq!(
+            blocks_count,
+            world_state_view
+                .at_height(blocks_count)
+                .expect("Failed to reconstruct state.")
+                .height()
+        );
+    }
+
+    #[async_std::test]
+    async fn events_are_emitted_by_executed_instructions() {
+        let (public_key, private_key) =
+            crate::crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let alice_id = AccountId::new("alice", "wonderland");
+        let asset_definition_id = AssetDefinitionId::new("xor", "wonderland");
+        let asset_id = AssetId {
+            definition_id: asset_definition_id.clone(),
+            account_id: alice_id.clone(),
+        };
+        let mut domain = Domain::new("wonderland".to_string());
+        domain.accounts.insert(
+            alice_id.clone(),
+            Account::new(&alice_id.name, &alice_id.domain_name, public_key),
+        );
+        domain.asset_definitions.insert(
+            asset_definition_id.clone(),
+            AssetDefinition::new(asset_definition_id, alice_id.clone()),
+        );
+        let mut domains = HashMap::new();
+        domains.insert(domain.name.clone(), domain);
+        let mut world_state_view = WorldStateView::new(Peer::with_domains(
+            PeerId {
+                address: "127.0.0.1:8080".to_string(),
+                public_key,
+            },
+            &Vec::new(),
+            domains,
+        ));
+        let transaction = RequestedTransaction::new(
+            vec![
+                Mint::<Asset, u32>::new(10, asset_id.clone()).into(),
+                Burn::<Asset, u32>::new(10, asset_id.clone()).into(),
+            ],
+            alice_id,
+        )
+        .accept()
+        .expect("Failed to accept transaction.")
+        .sign(&public_key, &private_key)
+        .expect("Failed to sign transaction.")
+        .validate(&mut world_state_view.clone())
+        .expect("Failed to validate transaction.");
+        world_state_view
+            .put(&CommittedBlock {
+                header: BlockHeader {
+                    timestamp: 0,
+                    height: 0,
+                    previous_block_hash: [0; 32],
+                    merkle_root_hash: [0; 32],
+                },
+                transactions: vec![transaction],
+                signatures: Vec::new(),
+            })
+            .await;
+        let (events, next_index) = world_state_view.read_events(0);
+        assert_eq!(
+            vec![
+                DataEvent::AssetMinted {
+                    asset_id: asset_id.clone(),
+                    quantity: 10,
+                },
+                DataEvent::AssetBurned {
+                    asset_id,
+                    quantity: 10,
+                },
+            ],
+            events.cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(2, next_index);
+    }
 }
@@ -20,16 +20,21 @@ fn query_requests(criterion: &mut Criterion) {
         destination_id: configuration.peer_id.clone(),
     };
     let account_name = "account";
-    let account_id = AccountId::new(account_name, domain_name);
+    let account_id =
+        AccountId::try_new(account_name, domain_name).expect("Failed to create account id.");
     let (public_key, _) = configuration.key_pair();
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, public_key),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, public_key)
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let quantity: u32 = 200;
     let mint_asset = isi::Mint {
@@ -58,7 +63,10 @@ fn query_requests(criterion: &mut Criterion) {
         b.iter(
             || match executor::block_on(iroha_client.request(&request)) {
                 Ok(query_result) => {
-                    let QueryResult::GetAccountAssets(result) = query_result;
+                    let result = match query_result {
+                        QueryResult::GetAccountAssets(result) => result,
+                        _ => panic!("Wrong Query Result Type."),
+                    };
                     assert!(!result.assets.is_empty());
                     success_count += 1;
                 }
@@ -88,16 +96,21 @@ fn instruction_submits(criterion: &mut Criterion) {
         destination_id: configuration.peer_id.clone(),
     };
     let account_name = "account";
-    let account_id = AccountId::new(account_name, domain_name);
+    let account_id =
+        AccountId::try_new(account_name, domain_name).expect("Failed to create account id.");
     let (public_key, _) = configuration.key_pair();
     let create_account = isi::Register {
-        object: Account::new(account_name, domain_name, public_key),
-        destination_id: String::from(domain_name),
+        object: Account::try_new(account_name, domain_name, public_key)
+            .expect("Failed to create account."),
+        destination_id: Name::from(domain_name),
     };
     let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
     let create_asset = isi::Register {
-        object: AssetDefinition::new(asset_definition_id.clone()),
-        destination_id: domain_name.to_string(),
+        object: AssetDefinition::new(
+            asset_definition_id.clone(),
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        ),
+        destination_id: Name::from(domain_name),
     };
     let mut iroha_client = Client::new(
         &Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration."),
@@ -1,115 +1,761 @@
-use iroha::{crypto, prelude::*, torii::uri};
+use async_std::task;
+use futures::channel::mpsc::{self, UnboundedReceiver};
+use iroha::{
+    crypto::{self, Algorithm},
+    event::{query::GetEvents, DataEvent, EventFilter},
+    isi::{Add, Mint, Register},
+    peer::PeerId,
+    prelude::*,
+    torii::{uri, QueryRejection, TransactionRejection},
+};
 use iroha_derive::log;
 use iroha_network::{prelude::*, Network};
+use parity_scale_codec::{Decode, Encode};
 use std::{
+    collections::HashMap,
     convert::{TryFrom, TryInto},
     fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
 };
 
+const POLL_PERIOD: Duration = Duration::from_millis(50);
+/// Longest pause between the blockchain height polls of `Client::wait_for_height`.
+const MAX_POLL_PERIOD: Duration = Duration::from_secs(1);
+/// Time `Client::create_account` waits for the registration of the account to be committed.
+const CREATE_ACCOUNT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Number of blocks below the current height `Client::await_commit` searches for a transaction
+/// committed before it was first called.
+const COMMIT_SEARCH_WINDOW: u64 = 10;
+
 pub struct Client {
-    torii_url: String,
+    peer_id: PeerId,
+    torii: Torii,
     public_key: PublicKey,
     private_key: PrivateKey,
+    query_cache: Option<QueryCache>,
 }
 
 impl Debug for Client {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("Client")
             .field("public_key", &self.public_key)
-            .field("torii_url", &self.torii_url)
+            .field("torii_urls", &self.torii.urls)
             .finish()
     }
 }
 
 /// Representation of `Iroha` client.
 impl Client {
+    /// Creates a client which signs transactions and queries as `root@global` with the key pair
+    /// of the `config`.
     pub fn new(config: &Configuration) -> Self {
-        let (public_key, private_key) =
-            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let (public_key, private_key) = config.key_pair();
         Client {
-            torii_url: config.peer_id.address.clone(),
+            peer_id: config.peer_id.clone(),
+            torii: Torii::new(config),
             public_key: public_key[..]
                 .try_into()
                 .expect("Public key should be [u8;32]"),
             private_key,
+            query_cache: None,
+        }
+    }
+
+    /// Creates a client and checks the algorithm of the configured key pair against the
+    /// signature schemes accepted by the peer, so a mismatch fails here instead of rejecting the
+    /// transactions later.
+    pub async fn connect(config: &Configuration) -> Result<Self, String> {
+        let client = Client::new(config);
+        client.check_crypto_policy(config.key_algorithm).await?;
+        Ok(client)
+    }
+
+    /// Checks that the peer accepts keys of the `key_algorithm`.
+    pub async fn check_crypto_policy(&self, key_algorithm: Algorithm) -> Result<(), String> {
+        match query(&self.torii, &peers::crypto_policy()).await? {
+            QueryResult::GetCryptoPolicy(result) => {
+                key_algorithm.check_allowed(&result.allowed_algorithms)
+            }
+            result => Err(format!("Unexpected query result: {:?}", result)),
+        }
+    }
+
+    /// Enables caching of query results for `ttl`, at most `max_size` results are kept.
+    /// Queries which are not cacheable, like the blockchain height, are always sent to the peer.
+    pub fn query_cache(&mut self, ttl: Duration, max_size: usize) {
+        self.query_cache = Some(QueryCache::new(ttl, max_size));
+    }
+
+    /// Drops all cached query results, the following queries are sent to the peer.
+    pub fn clear_query_cache(&mut self) {
+        if let Some(cache) = &mut self.query_cache {
+            cache.clear();
         }
     }
 
     /// Contract API entry point. Submits contract to `Iroha` peers.
+    ///
+    /// Returns `Ok` with the receipt of the transaction if it was accepted by the peer, see
+    /// `Client::await_commit` to track its inclusion into a block.
     #[log]
-    pub async fn submit(&mut self, command: Instruction) -> Result<(), String> {
-        let network = Network::new(&self.torii_url);
-        let transaction: RequestedTransaction =
-            RequestedTransaction::new(vec![command], iroha::account::Id::new("root", "global"))
-                .accept()?
-                .sign(&self.public_key, &self.private_key)?
-                .into();
-        if let Response::InternalError = network
-            .send_request(Request::new(
-                uri::INSTRUCTIONS_URI.to_string(),
-                Vec::from(&transaction),
-            ))
-            .await
-            .map_err(|e| {
-                format!(
-                    "Error: {}, Failed to write a transaction request: {:?}",
-                    e, &transaction
-                )
-            })?
-        {
-            return Err("Server error.".to_string());
-        }
-        Ok(())
+    pub async fn submit(&mut self, command: Instruction) -> Result<TransactionReceipt, String> {
+        let transaction = self.build_transaction(vec![command])?;
+        self.submit_transaction(&transaction).await
     }
 
     /// Contract API entry point. Submits contracts to `Iroha` peers.
-    pub async fn submit_all(&mut self, commands: Vec<Instruction>) -> Result<(), String> {
-        let network = Network::new(&self.torii_url);
-        let transaction: RequestedTransaction =
-            RequestedTransaction::new(commands, iroha::account::Id::new("root", "global"))
+    ///
+    /// All instructions are put into a single transaction which is applied atomically: if any
+    /// of them fails, none are applied. Instructions submitted in separate transactions are
+    /// applied independently of each other.
+    pub async fn submit_all(
+        &mut self,
+        commands: Vec<Instruction>,
+    ) -> Result<TransactionReceipt, String> {
+        let transaction = self.build_transaction(commands)?;
+        self.submit_transaction(&transaction).await
+    }
+
+    /// Estimate the cost of a transaction with the `instructions` without submitting it, the
+    /// peer computes the same cost with the same weights, see `Instruction::cost`.
+    pub fn estimate_cost(instructions: &[Instruction]) -> u64 {
+        Instruction::total_cost(instructions)
+    }
+
+    /// Submits a transaction signed offline, e.g. by a cold wallet, with
+    /// `SignedTransaction::to_signed_bytes`.
+    ///
+    /// The client's own key pair is not used, the transaction is sent as is and accepted by the
+    /// peer the same way as one signed by `submit`.
+    pub async fn submit_signed(
+        &mut self,
+        signed_tx_bytes: Vec<u8>,
+    ) -> Result<TransactionReceipt, String> {
+        let transaction = RequestedTransaction::from_signed_bytes(&signed_tx_bytes)?;
+        self.submit_transaction(&transaction).await
+    }
+
+    /// Submit an instruction adding the domain to the peer.
+    ///
+    /// Returns `Ok` with the id of the domain if the transaction was accepted by the peer.
+    pub async fn create_domain(
+        &mut self,
+        name: &str,
+    ) -> Result<<Domain as Identifiable>::Id, String> {
+        let domain = Domain::new(name);
+        let domain_name = domain.name.clone();
+        let create_domain = Add {
+            object: domain,
+            destination_id: self.peer_id.clone(),
+        };
+        self.submit(create_domain.into()).await?;
+        Ok(domain_name)
+    }
+
+    /// Submit an instruction registering the account with a single signatory in the domain.
+    ///
+    /// Returns `Ok` with the id of the account if the transaction was accepted by the peer.
+    pub async fn register_account(
+        &mut self,
+        domain_name: &str,
+        account_name: &str,
+        public_key: PublicKey,
+    ) -> Result<<Account as Identifiable>::Id, String> {
+        let account = Account::try_new(account_name, domain_name, public_key)?;
+        let account_id = account.id.clone();
+        let register_account = Register {
+            object: account,
+            destination_id: domain_name.into(),
+        };
+        self.submit(register_account.into()).await?;
+        Ok(account_id)
+    }
+
+    /// Generate a key pair of the `algorithm` and register the account with its public key as
+    /// the single signatory in the domain, waiting until the registration is committed.
+    ///
+    /// Returns `Ok` with the id of the account and the generated key pair. The caller is
+    /// responsible for storing the private key securely, it can not be recovered.
+    ///
+    /// # Errors
+    /// Fails if accounts can not have keys of the `algorithm`, if the transaction is rejected or
+    /// is not committed in time.
+    pub async fn create_account(
+        &mut self,
+        domain_name: &str,
+        account_name: &str,
+        algorithm: Algorithm,
+    ) -> Result<(<Account as Identifiable>::Id, KeyPair), String> {
+        if algorithm != Algorithm::Ed25519 {
+            return Err(format!(
+                "Accounts can only have {} keys, got {}.",
+                Algorithm::Ed25519,
+                algorithm
+            ));
+        }
+        let (public_key, private_key) = crypto::generate_key_pair()?;
+        let account = Account::try_new(account_name, domain_name, public_key)?;
+        let account_id = account.id.clone();
+        let register_account = Register {
+            object: account,
+            destination_id: domain_name.into(),
+        };
+        let mut receipt = self.submit(register_account.into()).await?;
+        self.await_commit(&mut receipt, CREATE_ACCOUNT_TIMEOUT)
+            .await?;
+        Ok((account_id, KeyPair::new(public_key, private_key)))
+    }
+
+    /// Submit an instruction registering the asset definition in the domain, owned by the
+    /// account the client submits transactions from.
+    ///
+    /// Returns `Ok` with the id of the asset definition if the transaction was accepted by the
+    /// peer.
+    pub async fn register_asset(
+        &mut self,
+        domain_name: &str,
+        asset_name: &str,
+    ) -> Result<<AssetDefinition as Identifiable>::Id, String> {
+        let definition_id: AssetDefinitionId = format!("{}#{}", asset_name, domain_name).parse()?;
+        let register_asset = Register {
+            object: AssetDefinition::new(
+                definition_id.clone(),
+                iroha::account::Id::try_new("root", "global")?,
+            ),
+            destination_id: domain_name.into(),
+        };
+        self.submit(register_asset.into()).await?;
+        Ok(definition_id)
+    }
+
+    /// Submit an instruction minting `quantity` of the asset.
+    ///
+    /// Returns `Ok` with the id of the asset if the transaction was accepted by the peer.
+    pub async fn mint(
+        &mut self,
+        asset_id: <Asset as Identifiable>::Id,
+        quantity: u32,
+    ) -> Result<<Asset as Identifiable>::Id, String> {
+        let mint_asset = Mint {
+            object: quantity,
+            destination_id: asset_id.clone(),
+        };
+        self.submit(mint_asset.into()).await?;
+        Ok(asset_id)
+    }
+
+    /// Contract API entry point. Submits contract to `Iroha` peers and waits until the
+    /// transaction is committed.
+    ///
+    /// Returns `Ok(CommittedBlock)` with the block containing the transaction if it was committed
+    /// before `timeout` expired and `Err(String)` otherwise.
+    pub async fn submit_isi_and_await_block(
+        &mut self,
+        command: Instruction,
+        timeout: Duration,
+    ) -> Result<CommittedBlock, String> {
+        let mut receipt = self.submit(command).await?;
+        self.await_commit(&mut receipt, timeout).await
+    }
+
+    /// Waits until the transaction of the `receipt` is committed and fills the receipt's block
+    /// height and index of the transaction in the block.
+    ///
+    /// The first call starts the search `COMMIT_SEARCH_WINDOW` blocks below the current height,
+    /// so it should be made soon after the submission.
+    ///
+    /// Returns `Ok(CommittedBlock)` with the block containing the transaction if it was committed
+    /// before `timeout` expired and `Err(String)` otherwise.
+    pub async fn await_commit(
+        &mut self,
+        receipt: &mut TransactionReceipt,
+        timeout: Duration,
+    ) -> Result<CommittedBlock, String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let current_height = self.blockchain_height().await?;
+            let mut height = receipt
+                .search_from_height
+                .unwrap_or_else(|| current_height.saturating_sub(COMMIT_SEARCH_WINDOW));
+            while height < current_height {
+                let block = self.block(height).await?;
+                if let Some(index) = block
+                    .transactions
+                    .iter()
+                    .position(|transaction| transaction.hash() == receipt.hash)
+                {
+                    receipt.block_height = Some(block.header.height);
+                    receipt.index_in_block = Some(index);
+                    return Ok(block);
+                }
+                height += 1;
+            }
+            receipt.search_from_height = Some(height);
+            if Instant::now() >= deadline {
+                return Err(format!(
+                    "Transaction {:?} was not committed in {:?}.",
+                    receipt.hash, timeout
+                ));
+            }
+            task::sleep(POLL_PERIOD).await;
+        }
+    }
+
+    /// Waits until the blockchain reaches the `target` height, polling the peer with
+    /// an exponential backoff.
+    ///
+    /// Returns `Err(String)` if the height is still lower after the `timeout`.
+    pub async fn wait_for_height(&self, target: u64, timeout: Duration) -> Result<(), String> {
+        let deadline = Instant::now() + timeout;
+        let mut poll_period = POLL_PERIOD;
+        loop {
+            let height = match query(&self.torii, &blocks::height()).await? {
+                QueryResult::GetBlockchainHeight(result) => result.height,
+                result => return Err(format!("Unexpected query result: {:?}", result)),
+            };
+            if height >= target {
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(format!(
+                    "Blockchain height is {} instead of {} after {:?}.",
+                    height, target, timeout
+                ));
+            }
+            task::sleep(poll_period.min(deadline - now)).await;
+            poll_period = (poll_period * 2).min(MAX_POLL_PERIOD);
+        }
+    }
+
+    /// Subscribe to the events emitted by the blocks committed after the subscription which
+    /// match the `filter`.
+    ///
+    /// Events are polled in the background until the returned receiver is dropped.
+    pub async fn subscribe_events(
+        &self,
+        filter: EventFilter,
+    ) -> Result<UnboundedReceiver<DataEvent>, String> {
+        let mut next_index = match query(
+            &self.torii,
+            &self.sign_query(GetEvents::build_request(u64::MAX, filter.clone()))?,
+        )
+        .await?
+        {
+            QueryResult::GetEvents(result) => result.next_index,
+            result => return Err(format!("Unexpected query result: {:?}", result)),
+        };
+        let (sender, receiver) = mpsc::unbounded();
+        let torii = self.torii.clone();
+        let (public_key, private_key) = (self.public_key, self.private_key);
+        task::spawn(async move {
+            while !sender.is_closed() {
+                let request = match sign_query(
+                    GetEvents::build_request(next_index, filter.clone()),
+                    &public_key,
+                    &private_key,
+                ) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        eprintln!("Failed to sign events query: {}", e);
+                        return;
+                    }
+                };
+                match query(&torii, &request).await {
+                    Ok(QueryResult::GetEvents(result)) => {
+                        next_index = result.next_index;
+                        for event in result.events {
+                            if sender.unbounded_send(event).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Ok(result) => eprintln!("Unexpected query result: {:?}", result),
+                    Err(e) => eprintln!("Failed to poll events: {}", e),
+                }
+                task::sleep(POLL_PERIOD).await;
+            }
+        });
+        Ok(receiver)
+    }
+
+    fn build_transaction(
+        &self,
+        commands: Vec<Instruction>,
+    ) -> Result<RequestedTransaction, String> {
+        Ok(
+            RequestedTransaction::new(commands, iroha::account::Id::try_new("root", "global")?)
                 .accept()?
                 .sign(&self.public_key, &self.private_key)?
-                .into();
-        if let Response::InternalError = network
+                .into(),
+        )
+    }
+
+    async fn submit_transaction(
+        &mut self,
+        transaction: &RequestedTransaction,
+    ) -> Result<TransactionReceipt, String> {
+        match self
+            .torii
             .send_request(Request::new(
                 uri::INSTRUCTIONS_URI.to_string(),
-                Vec::from(&transaction),
+                Vec::from(transaction),
             ))
             .await
             .map_err(|e| {
                 format!(
                     "Error: {}, Failed to write a transaction request: {:?}",
-                    e, &transaction
+                    e, transaction
                 )
-            })?
-        {
-            return Err("Server error.".to_string());
+            })? {
+            Response::Ok(payload) => Ok(TransactionReceipt {
+                hash: transaction.hash(),
+                cost: u64::decode(&mut payload.as_slice()).ok(),
+                accepted_at_ms: SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map_err(|e| format!("Failed to get the current time: {}", e))?
+                    .as_millis(),
+                block_height: None,
+                index_in_block: None,
+                search_from_height: None,
+            }),
+            Response::InternalError => Err("Server error.".to_string()),
+            Response::Rejected(payload) => Err(format!(
+                "Transaction rejected: {}",
+                TransactionRejection::try_from(payload)
+                    .map_err(|e| format!("Failed to decode rejection: {}", e))?
+            )),
+        }
+    }
+
+    async fn blockchain_height(&mut self) -> Result<u64, String> {
+        match self.request(&blocks::height()).await? {
+            QueryResult::GetBlockchainHeight(result) => Ok(result.height),
+            result => Err(format!("Unexpected query result: {:?}", result)),
+        }
+    }
+
+    async fn block(&mut self, height: u64) -> Result<CommittedBlock, String> {
+        match self.request(&blocks::by_height(height)).await? {
+            QueryResult::GetBlock(result) => Ok(result.block),
+            result => Err(format!("Unexpected query result: {:?}", result)),
         }
-        Ok(())
     }
 
     /// Query API entry point. Requests queries from `Iroha` peers.
     #[log]
     pub async fn request(&mut self, request: &QueryRequest) -> Result<QueryResult, String> {
-        let network = Network::new(&self.torii_url);
-        match network
-            .send_request(Request::new(uri::QUERY_URI.to_string(), request.into()))
-            .await
-            .map_err(|e| format!("Failed to write a get request: {}", e))?
+        let key = match &self.query_cache {
+            Some(_) if request.query.is_cacheable() => Some(QueryCache::key(request)),
+            _ => None,
+        };
+        if let (Some(cache), Some(key)) = (&mut self.query_cache, &key) {
+            if let Some(result) = cache.get(key) {
+                return Ok(result);
+            }
+        }
+        let result = query(&self.torii, &self.sign_query(request.clone())?).await?;
+        if let (Some(cache), Some(key)) = (&mut self.query_cache, key) {
+            cache.insert(key, &result);
+        }
+        Ok(result)
+    }
+
+    /// Estimate the time in milliseconds until a transaction submitted now is put into a block,
+    /// based on the block building parameters and the queue depth reported by the peer.
+    ///
+    /// The estimate is advisory and does not include the consensus round.
+    pub async fn estimate_inclusion_ms(&self) -> Result<u64, String> {
+        match query(&self.torii, &blocks::schedule()).await? {
+            QueryResult::GetBlockSchedule(result) => Ok(result.estimate_inclusion_ms()),
+            result => Err(format!("Unexpected query result: {:?}", result)),
+        }
+    }
+
+    /// Requests the query to be executed against the state right after the first `height`
+    /// blocks were committed.
+    pub async fn query_at_height(
+        &mut self,
+        request: QueryRequest,
+        height: u64,
+    ) -> Result<QueryResult, String> {
+        self.request(&request.at_height(height)).await
+    }
+
+    fn sign_query(&self, request: QueryRequest) -> Result<QueryRequest, String> {
+        sign_query(request, &self.public_key, &self.private_key)
+    }
+}
+
+/// Queries are made on behalf of the same account transactions are submitted from.
+fn sign_query(
+    request: QueryRequest,
+    public_key: &PublicKey,
+    private_key: &PrivateKey,
+) -> Result<QueryRequest, String> {
+    request.sign(
+        iroha::account::Id::try_new("root", "global")?,
+        public_key,
+        private_key,
+    )
+}
+
+/// Lifecycle of a transaction submitted by the `Client`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionReceipt {
+    /// Hash of the transaction, known as soon as it is submitted.
+    pub hash: Hash,
+    /// Time in milliseconds since the Unix epoch when the peer accepted the transaction.
+    pub accepted_at_ms: u128,
+    /// Cost of the transaction computed by the peer, `None` if the peer did not report it.
+    pub cost: Option<u64>,
+    /// Height of the block with the transaction, `None` until `Client::await_commit` finds it.
+    pub block_height: Option<u64>,
+    /// Index of the transaction in the block, `None` until `Client::await_commit` finds it.
+    pub index_in_block: Option<usize>,
+    /// Height of the next block `Client::await_commit` searches, set on its first call.
+    search_from_height: Option<u64>,
+}
+
+/// Results of the queries keyed by the encoded query and height, the timestamp and the
+/// signature of the request do not affect the result.
+struct QueryCache {
+    ttl: Duration,
+    max_size: usize,
+    entries: HashMap<Vec<u8>, (Instant, Vec<u8>)>,
+}
+
+impl QueryCache {
+    fn new(ttl: Duration, max_size: usize) -> Self {
+        QueryCache {
+            ttl,
+            max_size,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn key(request: &QueryRequest) -> Vec<u8> {
+        (&request.query, &request.at_height).encode()
+    }
+
+    fn get(&mut self, key: &[u8]) -> Option<QueryResult> {
+        match self.entries.get(key) {
+            Some((cached_at, result)) if cached_at.elapsed() < self.ttl => {
+                QueryResult::try_from(result.clone()).ok()
+            }
+            Some(_) => {
+                self.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Expired results are evicted first when the cache is full, then the oldest one.
+    fn insert(&mut self, key: Vec<u8>, result: &QueryResult) {
+        if self.max_size == 0 {
+            return;
+        }
+        if self.entries.len() >= self.max_size && !self.entries.contains_key(&key) {
+            let ttl = self.ttl;
+            self.entries
+                .retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+            if self.entries.len() >= self.max_size {
+                if let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (cached_at, _))| *cached_at)
+                    .map(|(key, _)| key.clone())
+                {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+        self.entries.insert(key, (Instant::now(), result.into()));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Torii addresses of the peers with the time of the last failed attempt to reach each of them.
+#[derive(Clone)]
+struct Torii {
+    urls: Vec<String>,
+    failed_at: Arc<Mutex<HashMap<String, Instant>>>,
+    /// Public keys of the peers by their addresses, the certificates of the peers are pinned to
+    /// them if TLS is enabled.
+    public_keys: Option<HashMap<String, PublicKey>>,
+}
+
+impl Torii {
+    fn new(config: &Configuration) -> Self {
+        let urls = if config.torii_urls.is_empty() {
+            vec![config.peer_id.address.clone()]
+        } else {
+            config.torii_urls.clone()
+        };
+        let public_keys = if config.tls {
+            Some(
+                config
+                    .trusted_peers
+                    .iter()
+                    .chain(std::iter::once(&config.peer_id))
+                    .map(|peer_id| (peer_id.address.clone(), peer_id.public_key))
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        Torii {
+            urls,
+            failed_at: Arc::new(Mutex::new(HashMap::new())),
+            public_keys,
+        }
+    }
+
+    /// Reachable peers are tried first in the configured order, then the failed ones starting
+    /// with the one which failed the longest time ago.
+    fn ordered_urls(&self) -> Vec<String> {
+        let failed_at = self.failed_at.lock().expect("Failed to lock failures.");
+        let (mut failed, mut urls): (Vec<&String>, Vec<&String>) = self
+            .urls
+            .iter()
+            .partition(|url| failed_at.contains_key(*url));
+        failed.sort_by_key(|url| failed_at[*url]);
+        urls.append(&mut failed);
+        urls.into_iter().cloned().collect()
+    }
+
+    /// Sends the request to the first peer which can be reached.
+    async fn send_request(&self, request: Request) -> Result<Response, String> {
+        let mut errors = Vec::new();
+        for url in self.ordered_urls() {
+            match self.send_request_to(&url, request.clone()).await {
+                Ok(response) => {
+                    self.failed_at
+                        .lock()
+                        .expect("Failed to lock failures.")
+                        .remove(&url);
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.failed_at
+                        .lock()
+                        .expect("Failed to lock failures.")
+                        .insert(url.clone(), Instant::now());
+                    errors.push(format!("{}: {}", url, e));
+                }
+            }
+        }
+        Err(format!("Failed to reach any peer: {}", errors.join(", ")))
+    }
+
+    async fn send_request_to(&self, url: &str, request: Request) -> Result<Response, String> {
+        let public_keys = match &self.public_keys {
+            Some(public_keys) => public_keys,
+            None => return Network::new(url).send_request(request).await,
+        };
+        let public_key = public_keys
+            .get(url)
+            .ok_or_else(|| format!("Public key of the peer is unknown, TLS needs it: {}", url))?;
+        #[cfg(feature = "tls")]
+        return Network::send_request_over_tls(url, public_key, request).await;
+        #[cfg(not(feature = "tls"))]
         {
-            Response::Ok(payload) => Ok(
-                QueryResult::try_from(payload).expect("Failed to try Query Result from vector.")
-            ),
-            Response::InternalError => Err("Server error.".to_string()),
+            let _ = public_key;
+            Err("TLS requires Iroha client built with the `tls` feature.".to_string())
         }
     }
 }
 
+async fn query(torii: &Torii, request: &QueryRequest) -> Result<QueryResult, String> {
+    match torii
+        .send_request(Request::new(uri::QUERY_URI.to_string(), request.into()))
+        .await
+        .map_err(|e| format!("Failed to write a get request: {}", e))?
+    {
+        Response::Ok(payload) => {
+            Ok(QueryResult::try_from(payload).expect("Failed to try Query Result from vector."))
+        }
+        Response::Rejected(payload) => Err(QueryRejection::try_from(payload)
+            .map_err(|e| format!("Failed to decode query rejection: {}", e))?
+            .to_string()),
+        Response::InternalError => Err("Server error.".to_string()),
+    }
+}
+
 pub mod assets {
     use super::*;
-    use iroha::asset::query::GetAccountAssets;
+    use iroha::asset::query::{GetAccountAssets, GetAssetSupply};
 
     pub fn by_account_id(account_id: <Account as Identifiable>::Id) -> QueryRequest {
         GetAccountAssets::build_request(account_id)
     }
+
+    pub fn supply(definition_id: <AssetDefinition as Identifiable>::Id) -> QueryRequest {
+        GetAssetSupply::build_request(definition_id)
+    }
+}
+
+pub mod accounts {
+    use super::*;
+    use iroha::account::query::GetAccountTransfers;
+
+    pub fn transfers(account_id: <Account as Identifiable>::Id) -> QueryRequest {
+        GetAccountTransfers::build_request(account_id)
+    }
+}
+
+pub mod domains {
+    use super::*;
+    use iroha::domain::query::{GetAllDomains, GetDomainAssetDefinitions, GetDomainStats};
+
+    pub fn all(offset: u64, limit: u64) -> QueryRequest {
+        GetAllDomains::build_request(offset, limit)
+    }
+
+    pub fn asset_definitions(domain_name: &str, offset: u64, limit: u64) -> QueryRequest {
+        GetDomainAssetDefinitions::build_request(domain_name.into(), offset, limit)
+    }
+
+    pub fn stats(domain_name: &str) -> QueryRequest {
+        GetDomainStats::build_request(domain_name.into())
+    }
+}
+
+pub mod blocks {
+    use super::*;
+    use iroha::block::query::{GetBlock, GetBlockSchedule, GetBlockchainHeight, GetHealth};
+
+    pub fn height() -> QueryRequest {
+        GetBlockchainHeight::build_request()
+    }
+
+    pub fn by_height(height: u64) -> QueryRequest {
+        GetBlock::build_request(height)
+    }
+
+    pub fn schedule() -> QueryRequest {
+        GetBlockSchedule::build_request()
+    }
+
+    pub fn health() -> QueryRequest {
+        GetHealth::build_request()
+    }
+}
+
+pub mod peers {
+    use super::*;
+    use iroha::peer::query::{GetCryptoPolicy, GetNetworkStatus};
+
+    pub fn network_status() -> QueryRequest {
+        GetNetworkStatus::build_request()
+    }
+
+    pub fn crypto_policy() -> QueryRequest {
+        GetCryptoPolicy::build_request()
+    }
 }
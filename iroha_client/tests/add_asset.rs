@@ -18,11 +18,15 @@ mod tests {
             Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
         let domain_name = "global";
         let account_name = "root";
-        let account_id = AccountId::new(account_name, domain_name);
+        let account_id =
+            AccountId::try_new(account_name, domain_name).expect("Failed to create account id.");
         let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
         let create_asset = isi::Register {
-            object: AssetDefinition::new(asset_definition_id.clone()),
-            destination_id: domain_name.to_string(),
+            object: AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
         };
         let mut iroha_client = Client::new(&configuration);
         iroha_client
@@ -56,7 +60,10 @@ mod tests {
             .request(&request)
             .await
             .expect("Failed to execute request.");
-        let QueryResult::GetAccountAssets(result) = query_result;
+        let result = match query_result {
+            QueryResult::GetAccountAssets(result) => result,
+            _ => panic!("Wrong Query Result Type."),
+        };
         assert!(!result.assets.is_empty());
         assert_eq!(
             quantity,
@@ -25,16 +25,21 @@ mod tests {
         };
         configuration.peer_id(peers.first().expect("Failed to get first peer.").clone());
         let account_name = "account";
-        let account_id = AccountId::new(account_name, domain_name);
+        let account_id =
+            AccountId::try_new(account_name, domain_name).expect("Failed to create account id.");
         let (public_key, _) = configuration.key_pair();
         let create_account = isi::Register {
-            object: Account::new(account_name, domain_name, public_key),
-            destination_id: String::from(domain_name),
+            object: Account::try_new(account_name, domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
         };
         let asset_id = AssetDefinitionId::new("xor", domain_name);
         let create_asset = isi::Register {
-            object: AssetDefinition::new(asset_id.clone()),
-            destination_id: domain_name.to_string(),
+            object: AssetDefinition::new(
+                asset_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
         };
         let mut iroha_client = Client::new(&configuration);
         iroha_client
@@ -76,7 +81,10 @@ mod tests {
             .request(&request)
             .await
             .expect("Failed to execute request.");
-        let QueryResult::GetAccountAssets(result) = query_result;
+        let result = match query_result {
+            QueryResult::GetAccountAssets(result) => result,
+            _ => panic!("Wrong Query Result Type."),
+        };
         assert!(!result.assets.is_empty());
         assert_eq!(
             quantity,
@@ -105,6 +113,7 @@ mod tests {
                     .expect("Failed to load configuration.");
                 configuration.kura_block_store_path(temp_dir.path());
                 configuration.peer_id(peer_id.clone());
+                configuration.root_public_key = configuration.public_key;
                 configuration.public_key = public_key;
                 configuration.private_key = private_key;
                 configuration.trusted_peers(peer_ids.clone());
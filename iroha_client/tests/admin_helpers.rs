@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::thread;
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn client_helpers_prepare_state_for_transfer() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let (public_key, _) = configuration.key_pair();
+        let domain_name = iroha_client
+            .create_domain("domain")
+            .await
+            .expect("Failed to create domain.");
+        wait_for_block(&configuration);
+        let account1_id = iroha_client
+            .register_account(&domain_name, "account1", public_key)
+            .await
+            .expect("Failed to register account.");
+        let account2_id = iroha_client
+            .register_account(&domain_name, "account2", public_key)
+            .await
+            .expect("Failed to register account.");
+        let asset_definition_id = iroha_client
+            .register_asset(&domain_name, "xor")
+            .await
+            .expect("Failed to register asset.");
+        wait_for_block(&configuration);
+        let asset_id = iroha_client
+            .mint(
+                AssetId {
+                    definition_id: asset_definition_id.clone(),
+                    account_id: account1_id.clone(),
+                },
+                200,
+            )
+            .await
+            .expect("Failed to mint asset.");
+        wait_for_block(&configuration);
+        assert_eq!(
+            AccountId::try_new("account1", "domain").expect("Failed to create account id."),
+            account1_id
+        );
+        assert_eq!(AssetDefinitionId::new("xor", "domain"), asset_definition_id);
+        assert!(iroha_client
+            .register_account(&domain_name, "account 3", public_key)
+            .await
+            .is_err());
+        //When
+        let transfer_asset = isi::Transfer {
+            source_id: account1_id.clone(),
+            destination_id: account2_id.clone(),
+            memo: None,
+            object: Asset::with_quantity(asset_id, 20),
+        };
+        iroha_client
+            .submit(transfer_asset.into())
+            .await
+            .expect("Failed to submit command.");
+        wait_for_block(&configuration);
+        //Then
+        assert_eq!(20, quantity(&mut iroha_client, account2_id).await);
+        assert_eq!(180, quantity(&mut iroha_client, account1_id).await);
+    }
+
+    fn wait_for_block(configuration: &Configuration) {
+        thread::sleep(std::time::Duration::from_millis(
+            configuration.block_build_step_ms * 2,
+        ));
+    }
+
+    async fn quantity(iroha_client: &mut Client, account_id: AccountId) -> u32 {
+        match iroha_client
+            .request(&client::assets::by_account_id(account_id))
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetAccountAssets(result) => {
+                result.assets.first().expect("Asset should exist.").quantity
+            }
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn transfer_is_not_applied_if_balance_precondition_fails() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let domain_name = "domain";
+        let create_domain = isi::Add {
+            object: Domain::new(domain_name.to_string()),
+            destination_id: configuration.peer_id.clone(),
+        };
+        let account1_name = "account1";
+        let account2_name = "account2";
+        let account1_id =
+            AccountId::try_new(account1_name, domain_name).expect("Failed to create account id.");
+        let account2_id =
+            AccountId::try_new(account2_name, domain_name).expect("Failed to create account id.");
+        let (public_key, _) = configuration.key_pair();
+        let create_account1 = isi::Register {
+            object: Account::try_new(account1_name, domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let create_account2 = isi::Register {
+            object: Account::try_new(account2_name, domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
+        let asset_id = AssetId {
+            definition_id: asset_definition_id.clone(),
+            account_id: account1_id.clone(),
+        };
+        let create_asset = isi::Register {
+            object: AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
+        };
+        let mint_asset = isi::Mint {
+            object: 200_u32,
+            destination_id: asset_id.clone(),
+        };
+        let mut receipt = iroha_client
+            .submit_all(vec![
+                create_domain.into(),
+                create_account1.into(),
+                create_account2.into(),
+                create_asset.into(),
+                mint_asset.into(),
+            ])
+            .await
+            .expect("Failed to prepare state.");
+        iroha_client
+            .await_commit(&mut receipt, timeout)
+            .await
+            .expect("Failed to await commit.");
+        let transfer = |quantity: u32| -> Instruction {
+            isi::Transfer {
+                source_id: account1_id.clone(),
+                destination_id: account2_id.clone(),
+                memo: None,
+                object: Asset::with_quantity(asset_id.clone(), quantity),
+            }
+            .into()
+        };
+        //When
+        iroha_client
+            .submit_all(vec![
+                isi::AssertInstruction::AssertAssetBalanceAtLeast(asset_id.clone(), 300).into(),
+                transfer(50),
+            ])
+            .await
+            .expect("Failed to submit transaction.");
+        let mut receipt = iroha_client
+            .submit_all(vec![
+                isi::AssertInstruction::AssertAccountExists(account2_id.clone()).into(),
+                isi::AssertInstruction::AssertAssetBalanceAtLeast(asset_id.clone(), 20).into(),
+                transfer(20),
+            ])
+            .await
+            .expect("Failed to submit transaction.");
+        iroha_client
+            .await_commit(&mut receipt, timeout)
+            .await
+            .expect("Failed to await commit.");
+        //Then
+        let query_result = iroha_client
+            .request(&client::assets::by_account_id(account2_id))
+            .await
+            .expect("Failed to execute request.");
+        let result = match query_result {
+            QueryResult::GetAccountAssets(result) => result,
+            _ => panic!("Wrong Query Result Type."),
+        };
+        assert_eq!(
+            20,
+            result.assets.first().expect("Asset should exist.").quantity,
+        );
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{crypto, isi, peer::PeerId, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn empty_peer_should_sync_blocks_from_another_peer() {
+        // Given
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let first_peer = configuration.peer_id.clone();
+        start_iroha_peer(
+            first_peer.clone(),
+            (configuration.public_key, configuration.private_key),
+            vec![first_peer.clone()],
+        );
+        task::sleep(Duration::from_millis(100)).await;
+        let mut iroha_client = Client::new(&configuration);
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let domain_name = "domain";
+        let create_domain = isi::Add {
+            object: Domain::new(domain_name.to_string()),
+            destination_id: first_peer.clone(),
+        };
+        iroha_client
+            .submit_isi_and_await_block(create_domain.into(), timeout)
+            .await
+            .expect("Failed to await block.");
+        for account_name in &["account1", "account2", "account3"] {
+            let create_account = isi::Register {
+                object: Account::try_new(account_name, domain_name, configuration.public_key)
+                    .expect("Failed to create account."),
+                destination_id: Name::from(domain_name),
+            };
+            iroha_client
+                .submit_isi_and_await_block(create_account.into(), timeout)
+                .await
+                .expect("Failed to await block.");
+        }
+        //When
+        let key_pair = crypto::generate_key_pair().expect("Failed to generate key pair.");
+        let second_peer = PeerId {
+            address: "127.0.0.1:1339".to_string(),
+            public_key: key_pair.0,
+        };
+        iroha_client
+            .submit_isi_and_await_block(
+                isi::Add {
+                    object: second_peer.clone(),
+                    destination_id: first_peer.clone(),
+                }
+                .into(),
+                timeout,
+            )
+            .await
+            .expect("Failed to await block.");
+        let height = blockchain_height(&mut iroha_client).await;
+        start_iroha_peer(
+            second_peer.clone(),
+            key_pair,
+            vec![first_peer.clone(), second_peer.clone()],
+        );
+        task::sleep(Duration::from_millis(
+            configuration.block_build_step_ms * 20,
+        ))
+        .await;
+        //Then
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.peer_id(second_peer);
+        let mut second_peer_client = Client::new(&configuration);
+        assert!(height >= 4);
+        assert_eq!(height, blockchain_height(&mut second_peer_client).await);
+        assert_eq!(
+            top_block(&mut iroha_client, height).await.hash(),
+            top_block(&mut second_peer_client, height).await.hash()
+        );
+    }
+
+    async fn blockchain_height(iroha_client: &mut Client) -> u64 {
+        match iroha_client
+            .request(&client::blocks::height())
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetBlockchainHeight(result) => result.height,
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    async fn top_block(iroha_client: &mut Client, height: u64) -> CommittedBlock {
+        match iroha_client
+            .request(&client::blocks::by_height(height - 1))
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetBlock(result) => result.block,
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    fn start_iroha_peer(
+        peer_id: PeerId,
+        (public_key, private_key): (PublicKey, PrivateKey),
+        trusted_peers: Vec<PeerId>,
+    ) {
+        task::spawn(async move {
+            let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+            let mut configuration = Configuration::from_path(CONFIGURATION_PATH)
+                .expect("Failed to load configuration.");
+            configuration.kura_block_store_path(temp_dir.path());
+            configuration.peer_id(peer_id);
+            configuration.root_public_key = configuration.public_key;
+            configuration.public_key = public_key;
+            configuration.private_key = private_key;
+            configuration.trusted_peers(trusted_peers);
+            let iroha = Iroha::new(configuration);
+            iroha.start().await.expect("Failed to start Iroha.");
+            //Prevents temp_dir from clean up untill the end of the tests.
+            loop {}
+        });
+    }
+}
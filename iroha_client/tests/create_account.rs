@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{crypto::Algorithm, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::thread;
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn created_account_has_the_returned_public_key() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let domain_name = iroha_client
+            .create_domain("domain")
+            .await
+            .expect("Failed to create domain.");
+        thread::sleep(std::time::Duration::from_millis(
+            configuration.block_build_step_ms * 2,
+        ));
+        // When
+        let (account_id, key_pair) = iroha_client
+            .create_account(&domain_name, "account", Algorithm::Ed25519)
+            .await
+            .expect("Failed to create account.");
+        // Then
+        assert_eq!(
+            AccountId::try_new("account", "domain").expect("Failed to create account id."),
+            account_id
+        );
+        let domains = match iroha_client
+            .request(&client::domains::all(0, 10))
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetAllDomains(result) => result.domains,
+            _ => panic!("Wrong Query Result Type."),
+        };
+        let account = domains
+            .iter()
+            .find(|domain| domain.name == domain_name)
+            .and_then(|domain| domain.accounts.get(&account_id))
+            .expect("Failed to find created account.");
+        assert_eq!(&[key_pair.public_key], account.signatories());
+        assert!(iroha_client
+            .create_account(&domain_name, "other", Algorithm::Secp256k1)
+            .await
+            .is_err());
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {}
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::Client;
+    use std::thread;
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn client_estimate_equals_cost_computed_by_peer() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let domain_name = "domain";
+        let account_id =
+            AccountId::try_new("account", domain_name).expect("Failed to create account id.");
+        let (public_key, _) = configuration.key_pair();
+        let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
+        let instructions: Vec<Instruction> = vec![
+            isi::Add {
+                object: Domain::new(domain_name.to_string()),
+                destination_id: configuration.peer_id.clone(),
+            }
+            .into(),
+            isi::Register {
+                object: Account::try_new("account", domain_name, public_key)
+                    .expect("Failed to create account."),
+                destination_id: Name::from(domain_name),
+            }
+            .into(),
+            isi::Register {
+                object: AssetDefinition::new(
+                    asset_definition_id.clone(),
+                    AccountId::try_new("root", "global").expect("Failed to create account id."),
+                ),
+                destination_id: Name::from(domain_name),
+            }
+            .into(),
+            isi::Mint {
+                object: 100,
+                destination_id: AssetId {
+                    definition_id: asset_definition_id,
+                    account_id,
+                },
+            }
+            .into(),
+            Instruction::Notify("Prepared.".to_string()),
+        ];
+        let estimate = Client::estimate_cost(&instructions);
+        // When
+        let receipt = iroha_client
+            .submit_all(instructions)
+            .await
+            .expect("Failed to submit instructions.");
+        // Then
+        assert_eq!(Some(estimate), receipt.cost);
+        assert_eq!(10 + 5 + 5 + 2 + 1, estimate);
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {}
+    }
+}
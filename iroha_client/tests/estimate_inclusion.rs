@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use async_std::{sync::RwLock, task};
+    use iroha::{block::query::GetBlockScheduleResult, prelude::*, torii::uri};
+    use iroha_client::client::Client;
+    use iroha_network::prelude::*;
+    use std::{sync::Arc, time::Duration};
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+    const STUB_PEER_URL: &str = "127.0.0.1:1341";
+
+    #[async_std::test]
+    async fn inclusion_estimate_accounts_for_queue_depth() {
+        // Given
+        task::spawn(Network::listen(
+            Arc::new(RwLock::new(())),
+            STUB_PEER_URL,
+            handle_connection,
+        ));
+        task::sleep(Duration::from_millis(100)).await;
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.peer_id.address = STUB_PEER_URL.to_string();
+        let iroha_client = Client::new(&configuration);
+        //When
+        let estimate = iroha_client
+            .estimate_inclusion_ms()
+            .await
+            .expect("Failed to estimate inclusion time.");
+        //Then
+        // 250 pending transactions fill two blocks of 100, so the new one gets into the third.
+        assert_eq!(3 * 200, estimate);
+    }
+
+    async fn handle_connection(
+        state: State<()>,
+        stream: Box<dyn AsyncStream>,
+    ) -> Result<(), String> {
+        Network::handle_message_async(state, stream, handle_request).await
+    }
+
+    async fn handle_request(_state: State<()>, request: Request) -> Result<Response, String> {
+        assert_eq!(uri::QUERY_URI, request.url());
+        Ok(Response::Ok(
+            QueryResult::GetBlockSchedule(GetBlockScheduleResult {
+                block_build_step_ms: 200,
+                max_transactions_in_block: 100,
+                pending_transactions: 250,
+            })
+            .into(),
+        ))
+    }
+}
@@ -23,22 +23,29 @@ mod tests {
         };
         let account1_name = "account1";
         let account2_name = "account2";
-        let account1_id = AccountId::new(account1_name, domain_name);
-        let account2_id = AccountId::new(account2_name, domain_name);
+        let account1_id =
+            AccountId::try_new(account1_name, domain_name).expect("Failed to create account id.");
+        let account2_id =
+            AccountId::try_new(account2_name, domain_name).expect("Failed to create account id.");
         let (public_key, _) = configuration.key_pair();
         let create_account1 = isi::Register {
-            object: Account::new(account1_name, domain_name, public_key),
-            destination_id: String::from(domain_name),
+            object: Account::try_new(account1_name, domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
         };
         let create_account2 = isi::Register {
-            object: Account::new(account2_name, domain_name, public_key),
-            destination_id: String::from(domain_name),
+            object: Account::try_new(account2_name, domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
         };
         let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
         let quantity: u32 = 200;
         let create_asset = isi::Register {
-            object: AssetDefinition::new(asset_definition_id.clone()),
-            destination_id: domain_name.to_string(),
+            object: AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
         };
         let mint_asset = isi::Mint {
             object: quantity,
@@ -66,6 +73,7 @@ mod tests {
             let transfer_asset = isi::Transfer {
                 source_id: account1_id.clone(),
                 destination_id: account2_id.clone(),
+                memo: None,
                 object: Asset::with_quantity(
                     AssetId {
                         definition_id: asset_definition_id.clone(),
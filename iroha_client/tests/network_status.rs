@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use async_std::{sync::RwLock, task};
+    use iroha::{
+        block::query::{GetBlockchainHeightResult, GetHealthResult},
+        peer::PeerId,
+        prelude::*,
+        query::IrohaQuery,
+    };
+    use iroha_client::client::{self, Client};
+    use iroha_network::prelude::*;
+    use std::{convert::TryFrom, sync::Arc, thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+    const STUB_PEER_URL: &str = "127.0.0.1:1344";
+    // Sorted after the other peers, so the tested peer stays the leader.
+    const UNREACHABLE_PEER_URL: &str = "127.0.0.1:2";
+    const STUB_PEER_HEIGHT: u64 = 42;
+
+    #[async_std::test]
+    async fn network_status_reports_heights_of_known_peers() {
+        // Given
+        task::spawn(Network::listen(
+            Arc::new(RwLock::new(())),
+            STUB_PEER_URL,
+            handle_connection,
+        ));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let stub_peer_id = PeerId {
+            address: STUB_PEER_URL.to_string(),
+            public_key: [1; 32],
+        };
+        let unreachable_peer_id = PeerId {
+            address: UNREACHABLE_PEER_URL.to_string(),
+            public_key: [2; 32],
+        };
+        let trusted_peers = vec![
+            configuration.peer_id.clone(),
+            stub_peer_id.clone(),
+            unreachable_peer_id.clone(),
+        ];
+        thread::spawn(move || create_and_start_iroha(trusted_peers));
+        let mut iroha_client = Client::new(&configuration);
+        //When
+        let mut peers = Vec::new();
+        for _ in 0..100 {
+            task::sleep(Duration::from_millis(100)).await;
+            peers = match iroha_client.request(&client::peers::network_status()).await {
+                Ok(QueryResult::GetNetworkStatus(result)) => result.peers,
+                Ok(_) => panic!("Wrong Query Result Type."),
+                Err(_) => continue,
+            };
+            if peers
+                .iter()
+                .all(|peer| peer.last_seen_ms.is_some() || peer.peer_id == unreachable_peer_id)
+            {
+                break;
+            }
+        }
+        //Then
+        assert_eq!(3, peers.len());
+        assert_eq!(configuration.peer_id, peers[0].peer_id);
+        assert_eq!(Some(0), peers[0].height);
+        assert!(peers[0].reachable);
+        let stub_peer = &peers[1];
+        assert_eq!(stub_peer_id, stub_peer.peer_id);
+        assert_eq!(Some(STUB_PEER_HEIGHT), stub_peer.height);
+        assert!(stub_peer.reachable);
+        assert!(stub_peer.last_seen_ms.is_some());
+        let unreachable_peer = &peers[2];
+        assert_eq!(unreachable_peer_id, unreachable_peer.peer_id);
+        assert_eq!(None, unreachable_peer.height);
+        assert!(!unreachable_peer.reachable);
+    }
+
+    async fn handle_connection(
+        state: State<()>,
+        stream: Box<dyn AsyncStream>,
+    ) -> Result<(), String> {
+        Network::handle_message_async(state, stream, handle_request).await
+    }
+
+    /// Responds as a peer which has `STUB_PEER_HEIGHT` blocks, but does not share them.
+    async fn handle_request(_state: State<()>, request: Request) -> Result<Response, String> {
+        let result = match QueryRequest::try_from(request.payload().to_vec())?.query {
+            IrohaQuery::GetHealth(_) => QueryResult::GetHealth(GetHealthResult {
+                height: STUB_PEER_HEIGHT,
+                pending_transactions: 0,
+                block_production_paused: false,
+            }),
+            IrohaQuery::GetBlockchainHeight(_) => {
+                QueryResult::GetBlockchainHeight(GetBlockchainHeightResult { height: 0 })
+            }
+            _ => return Ok(Response::InternalError),
+        };
+        Ok(Response::Ok(result.into()))
+    }
+
+    fn create_and_start_iroha(trusted_peers: Vec<PeerId>) {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        configuration.trusted_peers = trusted_peers;
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{block::query::GetHealthResult, isi, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::{sync::Arc, thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn blocks_are_not_built_while_production_is_paused() {
+        // Given
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Arc::new(Iroha::new(configuration));
+        let started_iroha = Arc::clone(&iroha);
+        thread::spawn(move || {
+            task::block_on(started_iroha.start()).expect("Failed to start Iroha.");
+        });
+        thread::sleep(Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let block_build_step = Duration::from_millis(configuration.block_build_step_ms);
+        //When
+        iroha.pause_block_production();
+        let height = health(&mut iroha_client).await.height;
+        let mut receipts = Vec::new();
+        for domain_name in &["paused1", "paused2"] {
+            let create_domain = isi::Add {
+                object: Domain::new(domain_name.to_string()),
+                destination_id: configuration.peer_id.clone(),
+            };
+            receipts.push(
+                iroha_client
+                    .submit(create_domain.into())
+                    .await
+                    .expect("Failed to submit transaction."),
+            );
+        }
+        task::sleep(block_build_step * 3).await;
+        //Then
+        let health_while_paused = health(&mut iroha_client).await;
+        assert!(health_while_paused.block_production_paused);
+        assert_eq!(height, health_while_paused.height);
+        assert_eq!(2, health_while_paused.pending_transactions);
+        //When
+        iroha.resume_block_production();
+        //Then
+        for receipt in &mut receipts {
+            iroha_client
+                .await_commit(receipt, block_build_step * 20)
+                .await
+                .expect("Failed to await commit.");
+            assert!(receipt.block_height.expect("Block height should be set.") >= height);
+        }
+        let health_after_resume = health(&mut iroha_client).await;
+        assert!(!health_after_resume.block_production_paused);
+        assert!(health_after_resume.height > height);
+    }
+
+    async fn health(iroha_client: &mut Client) -> GetHealthResult {
+        match iroha_client
+            .request(&client::blocks::health())
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetHealth(result) => result,
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn client_query_at_height_should_return_historical_balance() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let domain_name = "domain";
+        let create_domain = isi::Add {
+            object: Domain::new(domain_name.to_string()),
+            destination_id: configuration.peer_id.clone(),
+        };
+        let account1_id =
+            AccountId::try_new("account1", domain_name).expect("Failed to create account id.");
+        let account2_id =
+            AccountId::try_new("account2", domain_name).expect("Failed to create account id.");
+        let create_account1 = isi::Register {
+            object: Account::try_new("account1", domain_name, configuration.public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let create_account2 = isi::Register {
+            object: Account::try_new("account2", domain_name, configuration.public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
+        let create_asset = isi::Register {
+            object: AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
+        };
+        let asset_id = AssetId {
+            definition_id: asset_definition_id,
+            account_id: account1_id.clone(),
+        };
+        iroha_client
+            .submit_isi_and_await_block(
+                Instruction::Compose(
+                    Box::new(create_domain.into()),
+                    Box::new(Instruction::Compose(
+                        Box::new(create_account1.into()),
+                        Box::new(Instruction::Compose(
+                            Box::new(create_account2.into()),
+                            Box::new(create_asset.into()),
+                        )),
+                    )),
+                ),
+                timeout,
+            )
+            .await
+            .expect("Failed to prepare state.");
+        let mint_asset = isi::Mint {
+            object: 200,
+            destination_id: asset_id.clone(),
+        };
+        let mint_block = iroha_client
+            .submit_isi_and_await_block(mint_asset.into(), timeout)
+            .await
+            .expect("Failed to mint asset.");
+        iroha_client
+            .submit_isi_and_await_block(Instruction::Notify("Between".to_string()), timeout)
+            .await
+            .expect("Failed to submit notification.");
+        let transfer_asset = isi::Transfer {
+            source_id: account1_id.clone(),
+            destination_id: account2_id,
+            memo: None,
+            object: Asset::with_quantity(asset_id.clone(), 20),
+        };
+        let transfer_block = iroha_client
+            .submit_isi_and_await_block(transfer_asset.into(), timeout)
+            .await
+            .expect("Failed to transfer asset.");
+        //When
+        let balance_after_mint = balance(
+            &mut iroha_client,
+            &account1_id,
+            &asset_id,
+            mint_block.header.height + 1,
+        )
+        .await;
+        let balance_after_transfer = balance(
+            &mut iroha_client,
+            &account1_id,
+            &asset_id,
+            transfer_block.header.height + 1,
+        )
+        .await;
+        let balance_before_mint = balance(
+            &mut iroha_client,
+            &account1_id,
+            &asset_id,
+            mint_block.header.height,
+        )
+        .await;
+        //Then
+        assert!(transfer_block.header.height > mint_block.header.height + 1);
+        assert_eq!(0, balance_before_mint);
+        assert_eq!(200, balance_after_mint);
+        assert_eq!(180, balance_after_transfer);
+    }
+
+    async fn balance(
+        iroha_client: &mut Client,
+        account_id: &AccountId,
+        asset_id: &AssetId,
+        height: u64,
+    ) -> u32 {
+        match iroha_client
+            .query_at_height(client::assets::by_account_id(account_id.clone()), height)
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetAccountAssets(result) => result
+                .assets
+                .iter()
+                .find(|asset| asset.id == *asset_id)
+                .map_or(0, |asset| asset.quantity),
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {}
+    }
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use async_std::{sync::RwLock, task};
+    use iroha::{
+        block::query::GetBlockchainHeightResult, domain::query::GetAllDomainsResult, prelude::*,
+        query::IrohaQuery, torii::uri,
+    };
+    use iroha_client::client::{self, Client};
+    use iroha_network::prelude::*;
+    use parity_scale_codec::Decode;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+    const STUB_PEER_URL: &str = "127.0.0.1:1342";
+    const TTL: Duration = Duration::from_millis(500);
+
+    static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+    #[async_std::test]
+    async fn cached_query_results_are_reused_until_expired() {
+        // Given
+        task::spawn(Network::listen(
+            Arc::new(RwLock::new(())),
+            STUB_PEER_URL,
+            handle_connection,
+        ));
+        task::sleep(Duration::from_millis(100)).await;
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.peer_id.address = STUB_PEER_URL.to_string();
+        let mut iroha_client = Client::new(&configuration);
+        iroha_client.query_cache(TTL, 10);
+        //When
+        request_domains(&mut iroha_client).await;
+        request_domains(&mut iroha_client).await;
+        //Then
+        assert_eq!(1, REQUESTS.load(Ordering::SeqCst));
+        //When
+        task::sleep(TTL).await;
+        request_domains(&mut iroha_client).await;
+        //Then
+        assert_eq!(2, REQUESTS.load(Ordering::SeqCst));
+        //When
+        iroha_client.clear_query_cache();
+        request_domains(&mut iroha_client).await;
+        //Then
+        assert_eq!(3, REQUESTS.load(Ordering::SeqCst));
+        //When
+        for _ in 0..2 {
+            iroha_client
+                .request(&client::blocks::height())
+                .await
+                .expect("Failed to execute request.");
+        }
+        //Then
+        assert_eq!(5, REQUESTS.load(Ordering::SeqCst));
+    }
+
+    async fn request_domains(iroha_client: &mut Client) {
+        match iroha_client
+            .request(&client::domains::all(0, 10))
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetAllDomains(result) => assert_eq!(1, result.total),
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    async fn handle_connection(
+        state: State<()>,
+        stream: Box<dyn AsyncStream>,
+    ) -> Result<(), String> {
+        Network::handle_message_async(state, stream, handle_request).await
+    }
+
+    async fn handle_request(_state: State<()>, request: Request) -> Result<Response, String> {
+        assert_eq!(uri::QUERY_URI, request.url());
+        REQUESTS.fetch_add(1, Ordering::SeqCst);
+        let request = QueryRequest::decode(&mut request.payload())
+            .map_err(|e| format!("Failed to decode query request: {}", e))?;
+        let result = match request.query {
+            IrohaQuery::GetBlockchainHeight(_) => {
+                QueryResult::GetBlockchainHeight(GetBlockchainHeightResult { height: 1 })
+            }
+            _ => QueryResult::GetAllDomains(GetAllDomainsResult {
+                domains: vec![Domain::new("global".to_string())],
+                total: 1,
+            }),
+        };
+        Ok(Response::Ok(result.into()))
+    }
+}
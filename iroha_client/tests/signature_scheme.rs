@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{crypto::Algorithm, prelude::*};
+    use iroha_client::client::Client;
+    use std::thread;
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn client_with_disallowed_key_algorithm_fails_to_connect() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(300));
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        Client::connect(&configuration)
+            .await
+            .expect("Ed25519 client should connect.");
+        //When
+        configuration.key_algorithm = Algorithm::Secp256k1;
+        let result = Client::connect(&configuration).await;
+        //Then
+        let error = result.expect_err("Secp256k1 client should not connect.");
+        assert_eq!(
+            "Key algorithm secp256k1 is not allowed by the peer, allowed algorithms: ed25519.",
+            error
+        );
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn client_submit_isi_and_await_block_should_return_committing_block() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let domain_name = "domain";
+        let create_domain = isi::Add {
+            object: Domain::new(domain_name.to_string()),
+            destination_id: configuration.peer_id.clone(),
+        };
+        let account1_id =
+            AccountId::try_new("account1", domain_name).expect("Failed to create account id.");
+        let account2_id =
+            AccountId::try_new("account2", domain_name).expect("Failed to create account id.");
+        let (public_key, _) = configuration.key_pair();
+        let create_account1 = isi::Register {
+            object: Account::try_new("account1", domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let create_account2 = isi::Register {
+            object: Account::try_new("account2", domain_name, public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
+        let create_asset = isi::Register {
+            object: AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
+        };
+        let mint_asset = isi::Mint {
+            object: 200,
+            destination_id: AssetId {
+                definition_id: asset_definition_id.clone(),
+                account_id: account1_id.clone(),
+            },
+        };
+        iroha_client
+            .submit_all(vec![
+                create_domain.into(),
+                create_account1.into(),
+                create_account2.into(),
+                create_asset.into(),
+                mint_asset.into(),
+            ])
+            .await
+            .expect("Failed to prepare state.");
+        std::thread::sleep(std::time::Duration::from_millis(
+            &configuration.block_build_step_ms * 2,
+        ));
+        let height = match iroha_client
+            .request(&client::blocks::height())
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetBlockchainHeight(result) => result.height,
+            _ => panic!("Wrong Query Result Type."),
+        };
+        //When
+        let transfer_asset = isi::Transfer {
+            source_id: account1_id.clone(),
+            destination_id: account2_id.clone(),
+            memo: None,
+            object: Asset::with_quantity(
+                AssetId {
+                    definition_id: asset_definition_id.clone(),
+                    account_id: account1_id.clone(),
+                },
+                20,
+            ),
+        };
+        let block = iroha_client
+            .submit_isi_and_await_block(transfer_asset.into(), timeout)
+            .await
+            .expect("Failed to await block.");
+        //Then
+        assert!(block.header.height >= height);
+        assert_eq!(1, block.transactions.len());
+        let committed_block = match iroha_client
+            .request(&client::blocks::by_height(block.header.height))
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetBlock(result) => result.block,
+            _ => panic!("Wrong Query Result Type."),
+        };
+        assert_eq!(committed_block.hash(), block.hash());
+        assert_eq!(
+            committed_block.transactions[0].hash(),
+            block.transactions[0].hash()
+        );
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {}
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{account::isi::AccountInstruction, crypto, isi, prelude::*};
+    use iroha_client::client::Client;
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn transaction_signed_offline_is_committed() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let create_domain = isi::Add {
+            object: Domain::new("cold_wallet".to_string()),
+            destination_id: configuration.peer_id.clone(),
+        };
+        let (public_key, private_key) = configuration.key_pair();
+        let signed_tx_bytes = RequestedTransaction::new(
+            vec![create_domain.into()],
+            AccountId::try_new("root", "global").expect("Failed to create account id."),
+        )
+        .accept()
+        .expect("Failed to accept transaction.")
+        .sign(&public_key, &private_key)
+        .expect("Failed to sign transaction.")
+        .to_signed_bytes();
+        // The online side does not know the private key of the root account.
+        let mut online_configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let (public_key, private_key) =
+            crypto::generate_key_pair().expect("Failed to generate key pair.");
+        online_configuration.public_key = public_key;
+        online_configuration.private_key = private_key;
+        // It only holds a key added as a signatory of the root account to query the peer.
+        Client::new(&configuration)
+            .submit_isi_and_await_block(
+                Instruction::Account(AccountInstruction::AddSignatory(
+                    AccountId::try_new("root", "global").expect("Failed to create account id."),
+                    public_key,
+                )),
+                timeout,
+            )
+            .await
+            .expect("Failed to add signatory.");
+        let mut iroha_client = Client::new(&online_configuration);
+        //When
+        let mut receipt = iroha_client
+            .submit_signed(signed_tx_bytes)
+            .await
+            .expect("Failed to submit transaction.");
+        //Then
+        let block = iroha_client
+            .await_commit(&mut receipt, timeout)
+            .await
+            .expect("Failed to await commit.");
+        let index = receipt
+            .index_in_block
+            .expect("Index should be set after commit.");
+        assert_eq!(receipt.hash, block.transactions[index].hash());
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
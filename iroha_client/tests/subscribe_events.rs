@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use async_std::{future, task};
+    use futures::StreamExt;
+    use iroha::{
+        event::{DataEvent, EventFilter, EventKind},
+        isi,
+        prelude::*,
+    };
+    use iroha_client::client::Client;
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn client_subscribed_to_transfers_should_receive_transfer_event() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let domain_name = "domain";
+        let create_domain = isi::Add {
+            object: Domain::new(domain_name.to_string()),
+            destination_id: configuration.peer_id.clone(),
+        };
+        let account1_id =
+            AccountId::try_new("account1", domain_name).expect("Failed to create account id.");
+        let account2_id =
+            AccountId::try_new("account2", domain_name).expect("Failed to create account id.");
+        let create_account1 = isi::Register {
+            object: Account::try_new("account1", domain_name, configuration.public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let create_account2 = isi::Register {
+            object: Account::try_new("account2", domain_name, configuration.public_key)
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
+        };
+        let asset_definition_id = AssetDefinitionId::new("xor", domain_name);
+        let create_asset = isi::Register {
+            object: AssetDefinition::new(
+                asset_definition_id.clone(),
+                AccountId::try_new("root", "global").expect("Failed to create account id."),
+            ),
+            destination_id: Name::from(domain_name),
+        };
+        let asset_id = AssetId {
+            definition_id: asset_definition_id.clone(),
+            account_id: account1_id.clone(),
+        };
+        let mint_asset = isi::Mint {
+            object: 200,
+            destination_id: asset_id.clone(),
+        };
+        iroha_client
+            .submit_isi_and_await_block(
+                Instruction::Compose(
+                    Box::new(create_domain.into()),
+                    Box::new(Instruction::Compose(
+                        Box::new(create_account1.into()),
+                        Box::new(Instruction::Compose(
+                            Box::new(create_account2.into()),
+                            Box::new(create_asset.into()),
+                        )),
+                    )),
+                ),
+                timeout,
+            )
+            .await
+            .expect("Failed to prepare state.");
+        let mut events = iroha_client
+            .subscribe_events(EventFilter::default().kind(EventKind::AssetTransferred))
+            .await
+            .expect("Failed to subscribe to events.");
+        //When
+        iroha_client
+            .submit_isi_and_await_block(mint_asset.into(), timeout)
+            .await
+            .expect("Failed to mint asset.");
+        let transfer_asset = isi::Transfer {
+            source_id: account1_id.clone(),
+            destination_id: account2_id.clone(),
+            memo: None,
+            object: Asset::with_quantity(asset_id, 20),
+        };
+        iroha_client
+            .submit_isi_and_await_block(transfer_asset.into(), timeout)
+            .await
+            .expect("Failed to transfer asset.");
+        //Then
+        let event = future::timeout(timeout, events.next())
+            .await
+            .expect("Failed to receive event in time.")
+            .expect("Events stream was closed.");
+        assert_eq!(
+            DataEvent::AssetTransferred {
+                source_id: account1_id,
+                destination_id: account2_id,
+                asset_definition_id,
+                quantity: 20,
+            },
+            event
+        );
+        task::sleep(Duration::from_millis(configuration.block_build_step_ms * 2)).await;
+        assert!(events.try_recv().is_err());
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {}
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(all(test, feature = "tls"))]
+mod tests {
+    use async_std::task;
+    use iroha::prelude::*;
+    use iroha_client::client::{self, Client};
+    use std::{sync::Once, thread};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+    const TLS_PEER_URL: &str = "127.0.0.1:1345";
+
+    static START_IROHA: Once = Once::new();
+
+    #[async_std::test]
+    async fn query_over_tls_succeeds() {
+        // Given
+        start_iroha();
+        let mut iroha_client = Client::new(&tls_configuration());
+        // When
+        let result = iroha_client
+            .request(&client::blocks::height())
+            .await
+            .expect("Failed to execute request.");
+        // Then
+        match result {
+            QueryResult::GetBlockchainHeight(_) => (),
+            _ => panic!("Wrong Query Result Type."),
+        }
+    }
+
+    #[async_std::test]
+    async fn peer_with_another_key_is_rejected() {
+        // Given
+        start_iroha();
+        let mut configuration = tls_configuration();
+        configuration.peer_id.public_key = [7; 32];
+        configuration.trusted_peers.clear();
+        let mut iroha_client = Client::new(&configuration);
+        // When
+        let error = iroha_client
+            .request(&client::blocks::height())
+            .await
+            .expect_err("Peer with another key should be rejected.");
+        // Then
+        assert!(error.contains("TLS handshake failed"), "{}", error);
+    }
+
+    fn tls_configuration() -> Configuration {
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.tls = true;
+        configuration.peer_id.address = TLS_PEER_URL.to_string();
+        for peer_id in &mut configuration.trusted_peers {
+            peer_id.address = TLS_PEER_URL.to_string();
+        }
+        configuration
+    }
+
+    fn start_iroha() {
+        START_IROHA.call_once(|| {
+            thread::spawn(create_and_start_iroha);
+            thread::sleep(std::time::Duration::from_millis(300));
+        });
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration = tls_configuration();
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use async_std::{sync::RwLock, task};
+    use iroha::{block::query::GetBlockchainHeightResult, prelude::*, torii::uri};
+    use iroha_client::client::{self, Client};
+    use iroha_network::prelude::*;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+    const UNREACHABLE_PEER_URL: &str = "127.0.0.1:1";
+    const STUB_PEER_URL: &str = "127.0.0.1:1343";
+
+    static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+    #[async_std::test]
+    async fn requests_fail_over_to_reachable_peer() {
+        // Given
+        task::spawn(Network::listen(
+            Arc::new(RwLock::new(())),
+            STUB_PEER_URL,
+            handle_connection,
+        ));
+        task::sleep(Duration::from_millis(100)).await;
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.torii_urls =
+            vec![UNREACHABLE_PEER_URL.to_string(), STUB_PEER_URL.to_string()];
+        let mut iroha_client = Client::new(&configuration);
+        //When
+        for _ in 0..3 {
+            match iroha_client
+                .request(&client::blocks::height())
+                .await
+                .expect("Failed to execute request.")
+            {
+                QueryResult::GetBlockchainHeight(result) => assert_eq!(1, result.height),
+                _ => panic!("Wrong Query Result Type."),
+            }
+        }
+        //Then
+        assert_eq!(3, REQUESTS.load(Ordering::SeqCst));
+    }
+
+    #[async_std::test]
+    async fn request_fails_if_no_peer_is_reachable() {
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.torii_urls = vec![UNREACHABLE_PEER_URL.to_string()];
+        let mut iroha_client = Client::new(&configuration);
+        let error = iroha_client
+            .request(&client::blocks::height())
+            .await
+            .expect_err("Request should fail.");
+        assert!(error.contains("Failed to reach any peer"));
+    }
+
+    async fn handle_connection(
+        state: State<()>,
+        stream: Box<dyn AsyncStream>,
+    ) -> Result<(), String> {
+        Network::handle_message_async(state, stream, handle_request).await
+    }
+
+    async fn handle_request(_state: State<()>, request: Request) -> Result<Response, String> {
+        assert_eq!(uri::QUERY_URI, request.url());
+        REQUESTS.fetch_add(1, Ordering::SeqCst);
+        Ok(Response::Ok(
+            QueryResult::GetBlockchainHeight(GetBlockchainHeightResult { height: 1 }).into(),
+        ))
+    }
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::Client;
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn receipt_is_filled_after_commit() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let timeout = Duration::from_millis(configuration.block_build_step_ms * 20);
+        let create_domain = isi::Add {
+            object: Domain::new("receipts".to_string()),
+            destination_id: configuration.peer_id.clone(),
+        };
+        //When
+        let mut receipt = iroha_client
+            .submit(create_domain.into())
+            .await
+            .expect("Failed to submit transaction.");
+        //Then
+        assert!(receipt.accepted_at_ms > 0);
+        assert_eq!(None, receipt.block_height);
+        assert_eq!(None, receipt.index_in_block);
+        //When
+        let block = iroha_client
+            .await_commit(&mut receipt, timeout)
+            .await
+            .expect("Failed to await commit.");
+        //Then
+        assert_eq!(Some(block.header.height), receipt.block_height);
+        let index = receipt
+            .index_in_block
+            .expect("Index should be set after commit.");
+        assert_eq!(receipt.hash, block.transactions[index].hash());
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {
+            thread::park();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use async_std::task;
+    use iroha::{isi, prelude::*};
+    use iroha_client::client::{self, Client};
+    use std::{thread, time::Duration};
+    use tempfile::TempDir;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    #[async_std::test]
+    async fn wait_for_height_returns_once_blocks_are_committed() {
+        // Given
+        thread::spawn(create_and_start_iroha);
+        thread::sleep(std::time::Duration::from_millis(100));
+        let configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let mut iroha_client = Client::new(&configuration);
+        let block_time = Duration::from_millis(configuration.block_build_step_ms);
+        let initial_height = match iroha_client
+            .request(&client::blocks::height())
+            .await
+            .expect("Failed to execute request.")
+        {
+            QueryResult::GetBlockchainHeight(result) => result.height,
+            _ => panic!("Wrong Query Result Type."),
+        };
+        let blocks = 3;
+        // When
+        for i in 0..blocks {
+            let create_domain = isi::Add {
+                object: Domain::new(format!("domain{}", i)),
+                destination_id: configuration.peer_id.clone(),
+            };
+            iroha_client
+                .submit(create_domain.into())
+                .await
+                .expect("Failed to create domain.");
+            iroha_client
+                .wait_for_height(initial_height + i + 1, block_time * 20)
+                .await
+                .expect("Failed to wait for the block of the transaction.");
+        }
+        // Then
+        iroha_client
+            .wait_for_height(initial_height + blocks, block_time)
+            .await
+            .expect("Reached height should be returned at once.");
+        assert!(iroha_client
+            .wait_for_height(initial_height + blocks + 100, block_time * 2)
+            .await
+            .is_err());
+    }
+
+    fn create_and_start_iroha() {
+        let temp_dir = TempDir::new().expect("Failed to create TempDir.");
+        let mut configuration =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        configuration.kura_block_store_path(temp_dir.path());
+        let iroha = Iroha::new(configuration);
+        task::block_on(iroha.start()).expect("Failed to start Iroha.");
+        //Prevents temp_dir from clean up untill the end of the tests.
+        loop {}
+    }
+}
@@ -152,8 +152,9 @@ mod account {
 
     fn create_account(account_name: &str, domain_name: &str, _public_key: &str) {
         let create_account = isi::Register {
-            object: Account::new(account_name, domain_name, [0; 32]),
-            destination_id: String::from(domain_name),
+            object: Account::try_new(account_name, domain_name, [0; 32])
+                .expect("Failed to create account."),
+            destination_id: Name::from(domain_name),
         };
         let mut iroha_client = Client::new(
             &Configuration::from_path("config.json").expect("Failed to load configuration."),
@@ -169,6 +170,7 @@ mod asset {
     use futures::executor;
     use iroha::{isi, prelude::*};
     use iroha_client::client::{self, Client};
+    use std::convert::TryFrom;
 
     const REGISTER: &str = "register";
     const MINT: &str = "mint";
@@ -264,8 +266,11 @@ App::new(GET)
         executor::block_on(
             iroha_client.submit(
                 isi::Register {
-                    object: AssetDefinition::new(AssetDefinitionId::new(asset_name, domain_name)),
-                    destination_id: domain_name.to_string(),
+                    object: AssetDefinition::new(
+                        AssetDefinitionId::new(asset_name, domain_name),
+                        AccountId::try_new("root", "global").expect("Failed to create account id."),
+                    ),
+                    destination_id: Name::from(domain_name),
                 }
                 .into(),
             ),
@@ -279,7 +284,7 @@ App::new(GET)
             object: quantity,
             destination_id: AssetId {
                 definition_id: AssetDefinitionId::from(asset_definition_id),
-                account_id: AccountId::from(account_id),
+                account_id: AccountId::try_from(account_id).expect("Failed to parse account id."),
             },
         };
         let mut iroha_client = Client::new(
@@ -293,11 +298,17 @@ App::new(GET)
         let mut iroha_client = Client::new(
             &Configuration::from_path("config.json").expect("Failed to load configuration."),
         );
-        let query_result = executor::block_on(iroha_client.request(
-            &client::assets::by_account_id(<Account as Identifiable>::Id::from(account_id)),
-        ))
+        let query_result = executor::block_on(
+            iroha_client.request(&client::assets::by_account_id(
+                <Account as Identifiable>::Id::try_from(account_id)
+                    .expect("Failed to parse account id."),
+            )),
+        )
         .expect("Failed to get asset.");
-        let QueryResult::GetAccountAssets(result) = query_result;
+        let result = match query_result {
+            QueryResult::GetAccountAssets(result) => result,
+            _ => panic!("Wrong Query Result Type."),
+        };
         println!("Get Asset result: {:?}", result);
     }
 }
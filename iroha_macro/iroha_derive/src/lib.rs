@@ -7,13 +7,12 @@ use syn::{
     PatTuple, PatTupleStruct, PatType, Signature,
 };
 
+/// Logs arguments and result of the function with the level set as an attribute argument
+/// (`#[log(info)]`), `debug` if not set. Records are filtered by the `iroha_macro::log`
+/// configuration at runtime.
 #[proc_macro_attribute]
-pub fn log(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    if cfg!(debug_assertions) {
-        impl_log(item)
-    } else {
-        item
-    }
+pub fn log(attr: TokenStream, item: TokenStream) -> TokenStream {
+    impl_log(attr, item)
 }
 
 #[proc_macro_derive(Io)]
@@ -34,8 +33,32 @@ pub fn into_query_derive(input: TokenStream) -> TokenStream {
     impl_into_query(&ast)
 }
 
-fn impl_log(item: TokenStream) -> TokenStream {
+fn impl_log(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let level = if attr.is_empty() {
+        Ident::new("Debug", proc_macro2::Span::call_site())
+    } else {
+        let level: Ident = syn::parse_macro_input!(attr as Ident);
+        let level_name = level.to_string().to_lowercase();
+        let variant = match level_name.as_str() {
+            "error" => "Error",
+            "warn" => "Warn",
+            "info" => "Info",
+            "debug" => "Debug",
+            "trace" => "Trace",
+            _ => {
+                return syn::Error::new(
+                    level.span(),
+                    "Expected one of: error, warn, info, debug, trace.",
+                )
+                .to_compile_error()
+                .into()
+            }
+        };
+        Ident::new(variant, level.span())
+    };
     let input: ItemFn = syn::parse_macro_input!(item as ItemFn);
+    let message = Ident::new("message", proc_macro2::Span::mixed_site());
+    let log_enabled = Ident::new("log_enabled", proc_macro2::Span::mixed_site());
     let ItemFn {
         attrs,
         vis,
@@ -66,7 +89,7 @@ fn impl_log(item: TokenStream) -> TokenStream {
             FnArg::Typed(PatType { pat, .. }) => param_names(*pat),
             FnArg::Receiver(_) => Box::new(std::iter::once(Ident::new("self", param.span()))),
         })
-        .map(|item| quote!(print!("{} = {:?}, ", stringify!(#item), &#item);))
+        .map(|item| quote!(#message.push_str(&format!("{} = {:?}, ", stringify!(#item), &#item));))
         .collect();
     let arguments = quote!(#(#param_names)*);
     let ident_str = ident.to_string();
@@ -75,22 +98,23 @@ fn impl_log(item: TokenStream) -> TokenStream {
         #vis #constness #unsafety #asyncness #abi fn #ident<#gen_params>(#params) #return_type
         #where_clause
         {
-            print!("{} {}[start]: ",
-                std::time::SystemTime::now().duration_since(
-                    std::time::SystemTime::UNIX_EPOCH
-                    ).expect("SystemTime befor UNIX EPOCH.").as_millis(),
-                #ident_str,
+            let #log_enabled = iroha_macro::log::enabled(
+                iroha_macro::log::Level::#level,
+                module_path!(),
             );
-            #arguments
-            println!("");
+            if #log_enabled {
+                let mut #message = format!("{}[start]: ", #ident_str);
+                #arguments
+                iroha_macro::log::log(iroha_macro::log::Level::#level, module_path!(), &#message);
+            }
             let result = #block;
-            println!("{} {}[end]: {:?}",
-                std::time::SystemTime::now().duration_since(
-                    std::time::SystemTime::UNIX_EPOCH
-                    ).expect("SystemTime befor UNIX EPOCH.").as_millis(),
-                #ident_str,
-                &result
-            );
+            if #log_enabled {
+                iroha_macro::log::log(
+                    iroha_macro::log::Level::#level,
+                    module_path!(),
+                    &format!("{}[end]: {:?}", #ident_str, &result),
+                );
+            }
             result
         }
     )
@@ -2,6 +2,11 @@
 mod tests {
 
     use iroha_derive::*;
+    use iroha_macro::log::{self, Configuration};
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
 
     #[log]
     fn func_to_log(arg1: String) -> Result<String, String> {
@@ -13,6 +18,29 @@ mod tests {
         Ok(arg1)
     }
 
+    #[log(info)]
+    fn func_to_log_info(arg1: String) -> String {
+        arg1
+    }
+
+    #[log(warn)]
+    fn func_to_log_warn(arg1: String) -> String {
+        arg1
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("Failed to lock output.").write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
     /// ```
     /// fn func_after_log(arg1: String) -> Result<String, String> {
     ///     println!("DATE_TIME func_to_log[start]: arg1 = {:?}", arg1);
@@ -39,4 +67,21 @@ mod tests {
                 .expect("Failed to execute function.")
         );
     }
+
+    #[test]
+    fn test_records_below_configured_level_are_suppressed() {
+        let output = SharedOutput::default();
+        log::set_output(Box::new(output.clone()));
+        log::init(Configuration::parse("warn", "text").expect("Failed to parse configuration."));
+        func_to_log("debug_value".to_string()).expect("Failed to execute function.");
+        func_to_log_info("info_value".to_string());
+        func_to_log_warn("warn_value".to_string());
+        let output = String::from_utf8(output.0.lock().expect("Failed to lock output.").clone())
+            .expect("Failed to read output.");
+        assert!(!output.contains("debug_value"));
+        assert!(!output.contains("info_value"));
+        assert!(output.contains("WARN"));
+        assert!(output.contains("func_to_log_warn[start]: arg1 = \"warn_value\""));
+        assert!(output.contains("func_to_log_warn[end]: \"warn_value\""));
+    }
 }
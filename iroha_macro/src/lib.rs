@@ -1,3 +1,5 @@
+pub mod log;
+
 pub trait Io: parity_scale_codec::Encode + parity_scale_codec::Decode {}
 pub trait IntoContract {}
 pub trait IntoQuery {}
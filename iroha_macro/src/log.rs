@@ -0,0 +1,233 @@
+//! Runtime configuration of the logs produced by the `#[log]` attribute.
+
+use std::{
+    io::{self, Write},
+    str::FromStr,
+    sync::{Mutex, RwLock},
+    time::SystemTime,
+};
+
+static CONFIGURATION: RwLock<Option<Configuration>> = RwLock::new(None);
+static OUTPUT: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+/// Verbosity of the log record, ordered from the least to the most verbose.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Errors only.
+    Error,
+    /// Warnings and errors.
+    Warn,
+    /// Information about the node work.
+    Info,
+    /// Details useful for debugging, e.g. arguments and results of functions.
+    Debug,
+    /// The most verbose level.
+    Trace,
+}
+
+impl Level {
+    fn as_str(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string.trim().to_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            other => Err(format!("Unknown log level: {}", other)),
+        }
+    }
+}
+
+/// Format of the log records.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable `timestamp level target: message` lines.
+    Text,
+    /// One JSON object per line for log aggregation.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string.trim().to_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            other => Err(format!("Unknown log format: {}", other)),
+        }
+    }
+}
+
+/// Logging configuration parameters container.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Configuration {
+    /// Level used for modules without their own level.
+    pub level: Level,
+    /// Levels of the specific modules (and their submodules), e.g. `iroha::kura`.
+    pub module_levels: Vec<(String, Level)>,
+    /// Format of the log records.
+    pub format: Format,
+}
+
+impl Configuration {
+    /// Build `Configuration` from the comma separated list of levels, where a level could be
+    /// prefixed with the module path, e.g. `warn,iroha::kura=debug`, and the format name.
+    pub fn parse(levels: &str, format: &str) -> Result<Configuration, String> {
+        let mut configuration = Configuration {
+            format: format.parse()?,
+            ..Configuration::default()
+        };
+        for directive in levels
+            .split(',')
+            .filter(|directive| !directive.trim().is_empty())
+        {
+            match directive.find('=') {
+                Some(index) => configuration.module_levels.push((
+                    directive[..index].trim().to_string(),
+                    directive[index + 1..].parse()?,
+                )),
+                None => configuration.level = directive.parse()?,
+            }
+        }
+        Ok(configuration)
+    }
+
+    fn level(&self, module_path: &str) -> Level {
+        self.module_levels
+            .iter()
+            .filter(|(module, _)| {
+                module_path == module || module_path.starts_with(&format!("{}::", module))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.level)
+    }
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Configuration {
+            level: Level::Info,
+            module_levels: Vec::new(),
+            format: Format::Text,
+        }
+    }
+}
+
+/// Set the logging configuration used by all subsequent log records.
+pub fn init(configuration: Configuration) {
+    *CONFIGURATION
+        .write()
+        .expect("Failed to lock log configuration.") = Some(configuration);
+}
+
+/// Redirect log records from the standard output to the `output`.
+pub fn set_output(output: Box<dyn Write + Send>) {
+    *OUTPUT.lock().expect("Failed to lock log output.") = Some(output);
+}
+
+/// Check whether records of the `level` from the module with `module_path` should be logged.
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    match &*CONFIGURATION
+        .read()
+        .expect("Failed to lock log configuration.")
+    {
+        Some(configuration) => level <= configuration.level(module_path),
+        None => level <= Configuration::default().level,
+    }
+}
+
+/// Write the `message` as a record of the `level` if it is enabled for the module.
+pub fn log(level: Level, module_path: &str, message: &str) {
+    if !enabled(level, module_path) {
+        return;
+    }
+    let format = CONFIGURATION
+        .read()
+        .expect("Failed to lock log configuration.")
+        .as_ref()
+        .map_or(Format::Text, |configuration| configuration.format);
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("SystemTime before UNIX EPOCH.")
+        .as_millis();
+    let record = match format {
+        Format::Text => format!(
+            "{} {} {}: {}",
+            timestamp,
+            level.as_str().to_uppercase(),
+            module_path,
+            message
+        ),
+        Format::Json => format!(
+            r#"{{"timestamp":{},"level":"{}","target":"{}","message":"{}"}}"#,
+            timestamp,
+            level.as_str(),
+            escape_json(module_path),
+            escape_json(message)
+        ),
+    };
+    let mut output = OUTPUT.lock().expect("Failed to lock log output.");
+    let result = match output.as_mut() {
+        Some(output) => writeln!(output, "{}", record),
+        None => writeln!(io::stdout(), "{}", record),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to write log record: {}", e);
+    }
+}
+
+fn escape_json(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for character in string.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_levels_per_module() {
+        let configuration = Configuration::parse("warn, iroha::kura=debug", "json")
+            .expect("Failed to parse configuration.");
+        assert_eq!(Format::Json, configuration.format);
+        assert_eq!(Level::Warn, configuration.level("iroha::sumeragi"));
+        assert_eq!(Level::Debug, configuration.level("iroha::kura"));
+        assert_eq!(Level::Debug, configuration.level("iroha::kura::tests"));
+        assert_eq!(Level::Warn, configuration.level("iroha::kurator"));
+        assert!(Configuration::parse("loud", "text").is_err());
+    }
+
+    #[test]
+    fn escape_json_special_characters() {
+        assert_eq!(r#"a \"b\" \\ c\n"#, escape_json("a \"b\" \\ c\n"));
+    }
+}
@@ -1,21 +1,40 @@
 #[cfg(feature = "mock")]
 pub mod mock;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 use async_std::{
+    future,
     net::{TcpListener, TcpStream},
     prelude::*,
     sync::RwLock,
 };
+#[cfg(feature = "tls")]
+use async_std::{sync, task};
 use iroha_derive::{log, Io};
 use parity_scale_codec::{Decode, Encode};
 use std::{
     convert::{TryFrom, TryInto},
     error::Error,
+    fmt::{self, Display, Formatter},
     future::Future,
+    pin::Pin,
     sync::Arc,
 };
 
-const BUFFER_SIZE: usize = 2048;
+/// Maximum size of a request payload accepted by `handle_message_async`.
+pub const DEFAULT_MAX_PAYLOAD_BYTES: usize = 16 * 1024 * 1024;
+/// Maximum size of a response payload accepted by `send_request_to`, larger than the request
+/// limit because a response can carry a whole batch of blocks.
+pub const MAX_RESPONSE_BYTES: usize = 64 * 1024 * 1024;
+/// Maximum length of the URI path line of a request.
+const MAX_URI_BYTES: usize = 1024;
+/// Number of connections which completed the TLS handshake and wait to be handled.
+#[cfg(feature = "tls")]
+const TLS_HANDSHAKES_BACKLOG: usize = 100;
+/// Size of the little endian payload length prefix following the URI path line of a request and
+/// preceding a response.
+const LENGTH_PREFIX_BYTES: usize = 4;
 
 pub type State<T> = Arc<RwLock<T>>;
 pub trait AsyncStream: async_std::io::Read + async_std::io::Write + Send + Unpin {}
@@ -53,18 +72,61 @@ impl Network {
     /// Establishes connection to server on `server_url`, sends `request` closes connection and returns `Response`.
     #[log]
     pub async fn send_request_to(server_url: &str, request: Request) -> Result<Response, String> {
-        let mut stream = TcpStream::connect(server_url)
+        let stream = TcpStream::connect(server_url)
             .await
             .map_err(|e| e.to_string())?;
+        Network::exchange(stream, request).await
+    }
+
+    /// Same as `send_request_to`, but over TLS, the server should present the certificate of the
+    /// Ed25519 `public_key`, see `tls`.
+    #[cfg(feature = "tls")]
+    #[log]
+    pub async fn send_request_over_tls(
+        server_url: &str,
+        public_key: &[u8; 32],
+        request: Request,
+    ) -> Result<Response, String> {
+        let stream = TcpStream::connect(server_url)
+            .await
+            .map_err(|e| e.to_string())?;
+        Network::exchange(tls::connect(stream, public_key).await?, request).await
+    }
+
+    async fn exchange<S: AsyncStream>(mut stream: S, request: Request) -> Result<Response, String> {
         let payload: Vec<u8> = request.into();
         stream
             .write_all(&payload)
             .await
             .map_err(|e| e.to_string())?;
         stream.flush().await.map_err(|e| e.to_string())?;
-        let mut buffer = vec![0u8; BUFFER_SIZE];
-        let read_size = stream.read(&mut buffer).await.map_err(|e| e.to_string())?;
-        Ok(Response::try_from(buffer[..read_size].to_vec())?)
+        Network::read_response(&mut stream, MAX_RESPONSE_BYTES).await
+    }
+
+    /// Reads the response length prefix first, so the response buffer is allocated only if the
+    /// length is within `max_response_bytes`.
+    async fn read_response<S: AsyncStream>(
+        stream: &mut S,
+        max_response_bytes: usize,
+    ) -> Result<Response, String> {
+        let mut length = [0u8; LENGTH_PREFIX_BYTES];
+        stream
+            .read_exact(&mut length)
+            .await
+            .map_err(|e| e.to_string())?;
+        let size = u32::from_le_bytes(length) as usize;
+        if size > max_response_bytes {
+            return Err(format!(
+                "Response of {} bytes exceeds the limit of {} bytes.",
+                size, max_response_bytes
+            ));
+        }
+        let mut buffer = vec![0u8; size];
+        stream
+            .read_exact(&mut buffer)
+            .await
+            .map_err(|e| e.to_string())?;
+        Response::try_from(buffer)
     }
 
     /// Listens on the specified `server_url`.
@@ -96,37 +158,195 @@ impl Network {
         Ok(())
     }
 
+    /// Same as `listen`, but the connections are accepted over TLS with the certificate of the
+    /// `acceptor`, connections which fail the handshake are dropped.
+    ///
+    /// Handshakes run in their own tasks, so a connection stalling the handshake does not hold
+    /// up the others, established connections are passed to `handler` in the order they complete.
+    #[cfg(feature = "tls")]
+    pub async fn listen_tls<H, F, S>(
+        state: State<S>,
+        server_url: &str,
+        acceptor: &tls::Acceptor,
+        mut handler: H,
+    ) -> Result<(), String>
+    where
+        H: FnMut(State<S>, Box<dyn AsyncStream>) -> F,
+        F: Future<Output = Result<(), String>>,
+    {
+        let listener = TcpListener::bind(server_url)
+            .await
+            .map_err(|e| e.to_string())?;
+        let (sender, receiver) = sync::channel(TLS_HANDSHAKES_BACKLOG);
+        let accept = async {
+            let mut incoming = listener.incoming();
+            while let Some(stream) = incoming.next().await {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        eprintln!("Failed to accept connection: {}", e);
+                        continue;
+                    }
+                };
+                let acceptor = acceptor.clone();
+                let sender = sender.clone();
+                task::spawn(async move {
+                    match acceptor.accept(stream).await {
+                        Ok(stream) => sender.send(stream).await,
+                        Err(e) => eprintln!("Failed to accept connection: {}", e),
+                    }
+                });
+            }
+            Ok(())
+        };
+        let handle = async {
+            while let Some(stream) = receiver.recv().await {
+                handler(Arc::clone(&state), Box::new(stream)).await?;
+            }
+            Ok(())
+        };
+        accept.race(handle).await
+    }
+
     /// Helper function to call inside `listen_async` `handler` function to parse and send response.
     /// The `handler` specified here will need to generate `Response` from `Request`.
     /// See `listen_async` for the description of the `state`.
     pub async fn handle_message_async<H, F, S>(
+        state: State<S>,
+        stream: Box<dyn AsyncStream>,
+        handler: H,
+    ) -> Result<(), String>
+    where
+        H: FnMut(State<S>, Request) -> F,
+        F: Future<Output = Result<Response, String>>,
+    {
+        Network::handle_message_with_limit_async(
+            state,
+            stream,
+            |_| DEFAULT_MAX_PAYLOAD_BYTES,
+            handler,
+        )
+        .await
+    }
+
+    /// Same as `handle_message_async`, but payloads of requests to the URI path `uri_path` larger
+    /// than `max_payload_bytes(uri_path)` are refused before they are read, the connection is
+    /// closed without a response.
+    pub async fn handle_message_with_limit_async<L, H, F, S>(
         state: State<S>,
         mut stream: Box<dyn AsyncStream>,
+        max_payload_bytes: L,
         mut handler: H,
     ) -> Result<(), String>
     where
+        L: Fn(&str) -> usize,
         H: FnMut(State<S>, Request) -> F,
         F: Future<Output = Result<Response, String>>,
     {
-        let mut buffer = [0u8; BUFFER_SIZE];
-        let read_size = stream
-            .read(&mut buffer)
+        let request = Network::read_request(&mut stream, max_payload_bytes)
             .await
-            .expect("Request read failed.");
-        let bytes: Vec<u8> = buffer[..read_size].to_vec();
-        let request: Request = bytes
-            .try_into()
-            .map_err(|e: Box<dyn Error>| e.to_string())?;
+            .map_err(|e| e.to_string())?;
         let response: Vec<u8> = handler(state, request).await?.into();
+        stream
+            .write_all(&(response.len() as u32).to_le_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
         stream
             .write_all(&response)
             .await
             .map_err(|e| e.to_string())?;
         stream.flush().await.map_err(|e| e.to_string())?;
+        // TLS streams have to be closed explicitly to send the close notification.
+        future::poll_fn(|context| Pin::new(&mut stream).poll_close(context))
+            .await
+            .map_err(|e| e.to_string())?;
         Ok(())
     }
+
+    /// Reads the URI path line and the payload length prefix first, so the payload buffer is
+    /// allocated only if the length is within `max_payload_bytes(uri_path)`.
+    pub async fn read_request<L>(
+        stream: &mut Box<dyn AsyncStream>,
+        max_payload_bytes: L,
+    ) -> Result<Request, RequestError>
+    where
+        L: Fn(&str) -> usize,
+    {
+        let mut uri_path = Vec::new();
+        let mut byte = [0u8; 1];
+        while !uri_path.ends_with(b"\r\n") {
+            if uri_path.len() >= MAX_URI_BYTES {
+                return Err(RequestError::Malformed(
+                    "URI path line is too long.".to_string(),
+                ));
+            }
+            stream
+                .read_exact(&mut byte)
+                .await
+                .map_err(|e| RequestError::Malformed(e.to_string()))?;
+            uri_path.push(byte[0]);
+        }
+        uri_path.truncate(uri_path.len() - 2);
+        let uri_path =
+            String::from_utf8(uri_path).map_err(|e| RequestError::Malformed(e.to_string()))?;
+        let mut length = [0u8; LENGTH_PREFIX_BYTES];
+        stream
+            .read_exact(&mut length)
+            .await
+            .map_err(|e| RequestError::Malformed(e.to_string()))?;
+        let size = u32::from_le_bytes(length) as usize;
+        let max_size = max_payload_bytes(&uri_path);
+        if size > max_size {
+            return Err(RequestError::TooLarge {
+                uri_path,
+                size,
+                max_size,
+            });
+        }
+        let mut payload = vec![0u8; size];
+        stream
+            .read_exact(&mut payload)
+            .await
+            .map_err(|e| RequestError::Malformed(e.to_string()))?;
+        Ok(Request { uri_path, payload })
+    }
+}
+
+/// Reason an incoming request was not read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestError {
+    /// The payload length prefix exceeds the limit of the URI path, the payload was not read.
+    TooLarge {
+        /// URI path of the request.
+        uri_path: String,
+        /// Payload size declared by the length prefix.
+        size: usize,
+        /// Maximum payload size accepted for the URI path.
+        max_size: usize,
+    },
+    /// The stream ended before the request was read or the request is not properly framed.
+    Malformed(String),
 }
 
+impl Display for RequestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::TooLarge {
+                uri_path,
+                size,
+                max_size,
+            } => write!(
+                f,
+                "Request payload to {} of {} bytes exceeds the limit of {} bytes.",
+                uri_path, size, max_size
+            ),
+            RequestError::Malformed(reason) => write!(f, "Malformed request: {}", reason),
+        }
+    }
+}
+
+impl Error for RequestError {}
+
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct Request {
     pub uri_path: String,
@@ -167,6 +387,7 @@ impl From<Request> for Vec<u8> {
         let mut bytes = Vec::new();
         bytes.extend_from_slice(request.uri_path.as_bytes());
         bytes.extend_from_slice(b"\r\n");
+        bytes.extend_from_slice(&(request.payload.len() as u32).to_le_bytes());
         bytes.extend(request.payload.into_iter());
         bytes
     }
@@ -182,7 +403,19 @@ impl TryFrom<Vec<u8>> for Request {
             .position(|byte| *byte == b"\n"[0])
             .expect("Request should contain \\r\\n sequence.")
             + 1;
-        let payload: Vec<u8> = bytes.drain(n..).collect();
+        if bytes.len() < n + LENGTH_PREFIX_BYTES {
+            return Err(Box::new(RequestError::Malformed(
+                "Request should contain payload length.".to_string(),
+            )));
+        }
+        let mut payload: Vec<u8> = bytes.drain(n..).collect();
+        let length: [u8; LENGTH_PREFIX_BYTES] = payload[..LENGTH_PREFIX_BYTES].try_into()?;
+        payload.drain(..LENGTH_PREFIX_BYTES);
+        if payload.len() != u32::from_le_bytes(length) as usize {
+            return Err(Box::new(RequestError::Malformed(
+                "Payload length does not match the length prefix.".to_string(),
+            )));
+        }
         Ok(Request {
             uri_path: String::from_utf8(bytes[..(bytes.len() - 2)].to_vec())?,
             payload,
@@ -194,6 +427,8 @@ impl TryFrom<Vec<u8>> for Request {
 pub enum Response {
     Ok(Vec<u8>),
     InternalError,
+    /// Request was valid, but refused by the server, the payload describes the reason.
+    Rejected(Vec<u8>),
 }
 
 impl Response {
@@ -206,7 +441,7 @@ pub mod prelude {
     //! Re-exports important traits and types. Meant to be glob imported when using `iroha_network`.
 
     #[doc(inline)]
-    pub use crate::{AsyncStream, Network, Request, Response, State};
+    pub use crate::{AsyncStream, Network, Request, RequestError, Response, State};
 }
 
 #[cfg(test)]
@@ -229,7 +464,7 @@ mod tests {
             payload: b"some_command".to_vec(),
         };
         let bytes: Vec<u8> = request.into();
-        assert_eq!(b"/commands\r\nsome_command".to_vec(), bytes)
+        assert_eq!(b"/commands\r\n\x0c\0\0\0some_command".to_vec(), bytes)
     }
 
     #[test]
@@ -239,11 +474,83 @@ mod tests {
             payload: b"some_command".to_vec(),
         };
         assert_eq!(
-            Request::try_from(b"/commands\r\nsome_command".to_vec()).unwrap(),
+            Request::try_from(b"/commands\r\n\x0c\0\0\0some_command".to_vec()).unwrap(),
             request
         )
     }
 
+    #[async_std::test]
+    async fn oversized_payload_is_not_read() {
+        let mut bytes = b"/commands\r\n".to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        let mut stream: Box<dyn AsyncStream> = Box::new(async_std::io::Cursor::new(bytes));
+        assert_eq!(
+            Err(RequestError::TooLarge {
+                uri_path: "/commands".to_string(),
+                size: u32::MAX as usize,
+                max_size: 1024,
+            }),
+            Network::read_request(&mut stream, |_| 1024).await
+        );
+    }
+
+    #[async_std::test]
+    async fn oversized_response_is_not_read() {
+        let mut bytes = u32::MAX.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&Response::empty_ok().encode());
+        let mut stream = async_std::io::Cursor::new(bytes);
+        assert_eq!(
+            Err(format!(
+                "Response of {} bytes exceeds the limit of 1024 bytes.",
+                u32::MAX
+            )),
+            Network::read_response(&mut stream, 1024).await
+        );
+    }
+
+    #[async_std::test]
+    async fn connection_with_oversized_payload_is_closed() {
+        async fn handle_request<S>(
+            _state: State<S>,
+            _request: Request,
+        ) -> Result<Response, String> {
+            panic!("Oversized request should not be handled.")
+        }
+
+        async fn handle_connection<S>(
+            state: State<S>,
+            stream: Box<dyn AsyncStream>,
+        ) -> Result<(), String> {
+            let result =
+                Network::handle_message_with_limit_async(state, stream, |_| 1024, handle_request)
+                    .await;
+            assert!(result
+                .expect_err("Oversized request should be refused.")
+                .contains("exceeds the limit"));
+            Ok(())
+        }
+
+        task::spawn(async move {
+            Network::listen(get_empty_state(), "127.0.0.1:7871", handle_connection).await
+        });
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let mut stream = async_std::net::TcpStream::connect("127.0.0.1:7871")
+            .await
+            .expect("Failed to connect.");
+        let mut bytes = b"/commands\r\n".to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        stream
+            .write_all(&bytes)
+            .await
+            .expect("Failed to write request.");
+        let mut response = Vec::new();
+        stream
+            .read_to_end(&mut response)
+            .await
+            .expect("Failed to read response.");
+        assert!(response.is_empty());
+    }
+
     #[async_std::test]
     async fn single_threaded_async() {
         async fn handle_request<S>(
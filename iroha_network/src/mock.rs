@@ -238,6 +238,8 @@ impl TryFrom<Vec<u8>> for Request {
 pub enum Response {
     Ok(Vec<u8>),
     InternalError,
+    /// Request was valid, but refused by the server, the payload describes the reason.
+    Rejected(Vec<u8>),
 }
 
 impl Response {
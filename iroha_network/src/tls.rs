@@ -0,0 +1,294 @@
+//! TLS transport between peers and clients.
+//!
+//! Every peer presents a self-signed certificate of its Ed25519 key, the connecting side pins it
+//! to the public key of the peer it expects, so no certificate authority is involved and the TLS
+//! identity of a peer is the same as its consensus identity.
+
+use async_std::{future, net::TcpStream};
+use async_tls::{client, server, TlsAcceptor, TlsConnector};
+use rustls::{
+    Certificate, ClientConfig, NoClientAuth, PrivateKey, RootCertStore, ServerCertVerified,
+    ServerCertVerifier, ServerConfig, TLSError,
+};
+use std::{sync::Arc, time::Duration};
+
+/// Time a connecting side has to complete the handshake before the connection is dropped.
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Certificates are pinned to the keys, so the name the connecting side expects is fixed.
+const SERVER_NAME: &str = "iroha";
+/// DER tag of a `SEQUENCE`.
+const SEQUENCE_TAG: u8 = 0x30;
+/// DER tag of the explicit `[0]` version field of a certificate.
+const VERSION_TAG: u8 = 0xa0;
+/// PKCS#8 v1 private key info of an Ed25519 key without the 32 bytes of the secret key, see
+/// RFC 8410.
+const ED25519_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+/// Contents of the subject public key info of an Ed25519 key without the 32 bytes of the public
+/// key, see RFC 8410.
+const ED25519_SPKI_PREFIX: [u8; 10] = [0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+/// Server side of the TLS transport, presents the certificate of the peer's key.
+#[derive(Clone)]
+pub struct Acceptor {
+    acceptor: TlsAcceptor,
+}
+
+impl Acceptor {
+    /// Creates an acceptor with a self-signed certificate of the Ed25519 `secret_key`.
+    pub fn new(secret_key: &[u8; 32]) -> Result<Acceptor, String> {
+        let mut pkcs8 = ED25519_PKCS8_PREFIX.to_vec();
+        pkcs8.extend_from_slice(secret_key);
+        let key_pair = rcgen::KeyPair::from_der(&pkcs8)
+            .map_err(|e| format!("Failed to load TLS key: {}", e))?;
+        let mut params = rcgen::CertificateParams::new(vec![SERVER_NAME.to_string()]);
+        params.alg = &rcgen::PKCS_ED25519;
+        params.key_pair = Some(key_pair);
+        let certificate = rcgen::Certificate::from_params(params)
+            .and_then(|certificate| certificate.serialize_der())
+            .map_err(|e| format!("Failed to create TLS certificate: {}", e))?;
+        let mut config = ServerConfig::new(NoClientAuth::new());
+        config
+            .set_single_cert(vec![Certificate(certificate)], PrivateKey(pkcs8))
+            .map_err(|e| format!("Failed to set TLS certificate: {}", e))?;
+        Ok(Acceptor {
+            acceptor: TlsAcceptor::from(config),
+        })
+    }
+
+    /// Performs the server side of the TLS handshake over the accepted `stream`, fails if it is
+    /// not completed within `HANDSHAKE_TIMEOUT`.
+    pub async fn accept(&self, stream: TcpStream) -> Result<server::TlsStream<TcpStream>, String> {
+        future::timeout(HANDSHAKE_TIMEOUT, self.acceptor.accept(stream))
+            .await
+            .map_err(|_| "TLS handshake timed out.".to_string())?
+            .map_err(|e| format!("TLS handshake failed: {}", e))
+    }
+}
+
+/// Performs the client side of the TLS handshake over the `stream`, the server should present
+/// the certificate of the Ed25519 `public_key`.
+pub async fn connect(
+    stream: TcpStream,
+    public_key: &[u8; 32],
+) -> Result<client::TlsStream<TcpStream>, String> {
+    let mut config = ClientConfig::new();
+    config
+        .dangerous()
+        .set_certificate_verifier(Arc::new(PinnedKeyVerifier {
+            public_key: *public_key,
+        }));
+    TlsConnector::from(config)
+        .connect(SERVER_NAME, stream)
+        .await
+        .map_err(|e| format!("TLS handshake failed: {}", e))
+}
+
+/// Accepts only the certificate of the pinned key.
+///
+/// The handshake signature is verified with the key of the certificate by `rustls`, so the server
+/// proves that it holds the pinned key.
+struct PinnedKeyVerifier {
+    public_key: [u8; 32],
+}
+
+impl ServerCertVerifier for PinnedKeyVerifier {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        presented_certs: &[Certificate],
+        _dns_name: webpki::DNSNameRef<'_>,
+        _ocsp_response: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        let certificate = presented_certs
+            .first()
+            .ok_or(TLSError::NoCertificatesPresented)?;
+        if ed25519_public_key(&certificate.0) == Some(self.public_key) {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(TLSError::General(
+                "Certificate does not match the public key of the peer.".to_string(),
+            ))
+        }
+    }
+}
+
+/// Ed25519 key of the subject public key info of the DER encoded `certificate`.
+///
+/// The certificate is walked field by field instead of searching for the key, so bytes of other
+/// fields (e.g. the subject name) can not be taken for the key.
+fn ed25519_public_key(certificate: &[u8]) -> Option<[u8; 32]> {
+    let (certificate, _) = der_field(certificate, SEQUENCE_TAG)?;
+    let (mut fields, _) = der_field(certificate, SEQUENCE_TAG)?;
+    if fields.first() == Some(&VERSION_TAG) {
+        fields = der_tlv(fields)?.2;
+    }
+    // Serial number, signature algorithm, issuer, validity and subject.
+    for _ in 0..5 {
+        fields = der_tlv(fields)?.2;
+    }
+    let (subject_public_key_info, _) = der_field(fields, SEQUENCE_TAG)?;
+    if subject_public_key_info.len() != ED25519_SPKI_PREFIX.len() + 32
+        || !subject_public_key_info.starts_with(&ED25519_SPKI_PREFIX)
+    {
+        return None;
+    }
+    let mut public_key = [0; 32];
+    public_key.copy_from_slice(&subject_public_key_info[ED25519_SPKI_PREFIX.len()..]);
+    Some(public_key)
+}
+
+/// Contents of the DER field with the `tag` at the start of `der` and the bytes following it.
+fn der_field(der: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    match der_tlv(der)? {
+        (field_tag, contents, rest) if field_tag == tag => Some((contents, rest)),
+        _ => None,
+    }
+}
+
+/// Splits the DER field at the start of `der` into its tag, its contents and the bytes following
+/// it.
+fn der_tlv(der: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, der) = der.split_first()?;
+    let (&length, der) = der.split_first()?;
+    let (length, der) = if length < 0x80 {
+        (length as usize, der)
+    } else {
+        let length_bytes = (length & 0x7f) as usize;
+        if length_bytes == 0 || length_bytes > 4 || der.len() < length_bytes {
+            return None;
+        }
+        let (length, der) = der.split_at(length_bytes);
+        (
+            length
+                .iter()
+                .fold(0, |length, byte| (length << 8) | *byte as usize),
+            der,
+        )
+    };
+    if der.len() < length {
+        return None;
+    }
+    let (contents, rest) = der.split_at(length);
+    Some((tag, contents, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsyncStream, Network, Request, Response, State};
+    use async_std::{sync::RwLock, task};
+
+    fn public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+        let mut pkcs8 = ED25519_PKCS8_PREFIX.to_vec();
+        pkcs8.extend_from_slice(secret_key);
+        let mut public_key = [0; 32];
+        public_key.copy_from_slice(
+            rcgen::KeyPair::from_der(&pkcs8)
+                .expect("Failed to load key.")
+                .public_key_raw(),
+        );
+        public_key
+    }
+
+    async fn handle_request(_state: State<()>, _request: Request) -> Result<Response, String> {
+        Ok(Response::Ok(b"pong".to_vec()))
+    }
+
+    async fn handle_connection(
+        state: State<()>,
+        stream: Box<dyn AsyncStream>,
+    ) -> Result<(), String> {
+        Network::handle_message_async(state, stream, handle_request).await
+    }
+
+    fn start_server(server_url: &'static str, secret_key: [u8; 32]) {
+        let acceptor = Acceptor::new(&secret_key).expect("Failed to create acceptor.");
+        task::spawn(async move {
+            Network::listen_tls(
+                Arc::new(RwLock::new(())),
+                server_url,
+                &acceptor,
+                handle_connection,
+            )
+            .await
+        });
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    #[async_std::test]
+    async fn request_to_server_with_pinned_key_succeeds() {
+        let secret_key = [1; 32];
+        start_server("127.0.0.1:7872", secret_key);
+        match Network::send_request_over_tls(
+            "127.0.0.1:7872",
+            &public_key(&secret_key),
+            Request::new("/ping".to_string(), Vec::new()),
+        )
+        .await
+        .expect("Failed to send request.")
+        {
+            Response::Ok(payload) => assert_eq!(b"pong".to_vec(), payload),
+            response => panic!("Response should be ok, but got: {:?}", response),
+        }
+    }
+
+    #[async_std::test]
+    async fn server_with_certificate_of_another_key_is_rejected() {
+        start_server("127.0.0.1:7873", [2; 32]);
+        let error = Network::send_request_over_tls(
+            "127.0.0.1:7873",
+            &public_key(&[3; 32]),
+            Request::new("/ping".to_string(), Vec::new()),
+        )
+        .await
+        .expect_err("Server with another key should be rejected.");
+        assert!(error.contains("TLS handshake failed"), "{}", error);
+    }
+
+    #[async_std::test]
+    async fn stalled_handshake_does_not_block_other_connections() {
+        let secret_key = [4; 32];
+        start_server("127.0.0.1:7874", secret_key);
+        let _stalled = TcpStream::connect("127.0.0.1:7874")
+            .await
+            .expect("Failed to connect.");
+        let response = future::timeout(
+            Duration::from_secs(5),
+            Network::send_request_over_tls(
+                "127.0.0.1:7874",
+                &public_key(&secret_key),
+                Request::new("/ping".to_string(), Vec::new()),
+            ),
+        )
+        .await
+        .expect("Request should not wait for the stalled handshake.")
+        .expect("Failed to send request.");
+        assert_eq!(Response::Ok(b"pong".to_vec()), response);
+    }
+
+    #[test]
+    fn key_in_subject_name_is_not_taken_for_the_public_key() {
+        let own_key = [5; 32];
+        let mut pkcs8 = ED25519_PKCS8_PREFIX.to_vec();
+        pkcs8.extend_from_slice(&own_key);
+        // Both the prefix and the forged key are ASCII, so the name keeps them byte for byte.
+        let mut forged_key_info = ED25519_SPKI_PREFIX.to_vec();
+        forged_key_info.extend_from_slice(&[b'A'; 32]);
+        let mut params = rcgen::CertificateParams::new(vec![SERVER_NAME.to_string()]);
+        params.alg = &rcgen::PKCS_ED25519;
+        params.key_pair = Some(rcgen::KeyPair::from_der(&pkcs8).expect("Failed to load key."));
+        params.distinguished_name.push(
+            rcgen::DnType::CommonName,
+            String::from_utf8(forged_key_info.clone()).expect("Failed to build name."),
+        );
+        let certificate = rcgen::Certificate::from_params(params)
+            .and_then(|certificate| certificate.serialize_der())
+            .expect("Failed to create certificate.");
+        assert!(certificate
+            .windows(forged_key_info.len())
+            .any(|window| window == forged_key_info.as_slice()));
+        assert_eq!(Some(public_key(&own_key)), ed25519_public_key(&certificate));
+    }
+}